@@ -5,6 +5,11 @@ use crate::models::{
 use crate::models::frontmatter::StatePhase;
 use crate::orchestrator::{GeminiOrchestrator, CodexOrchestrator, UsageMetrics};
 use crate::parser::parse_archive_review_verdict;
+use crate::services::dependency_service::{self, DependencyGate};
+use crate::services::merge_service::{self, FileMerge};
+use crate::services::{
+    history_service, scope_drift_service, spec_diff_service, spec_history_service,
+};
 use crate::state::StateManager;
 use crate::validator::{SemanticValidator, SpecFormatValidator};
 use crate::Result;
@@ -45,7 +50,12 @@ fn record_usage(
 
 pub struct ArchiveCommand;
 
-pub async fn run(change_id: &str) -> Result<()> {
+/// Run the archive workflow
+///
+/// With `dry_run`, prints the diff each main spec would receive before the
+/// validation and scope-drift gates (so the preview is shown even when they
+/// would block), then stops; nothing is written and no LLM is called.
+pub async fn run(change_id: &str, dry_run: bool) -> Result<()> {
     let project_root = env::current_dir()?;
     let config = AgentdConfig::load(&project_root)?;
 
@@ -59,6 +69,20 @@ pub async fn run(change_id: &str) -> Result<()> {
         anyhow::bail!("Change '{}' not found", change_id);
     }
 
+    // Prerequisite changes must be merged first (a preview is still allowed)
+    if let Err(e) = dependency_service::ensure_ready(change_id, DependencyGate::Merge, &project_root) {
+        if !dry_run {
+            return Err(e);
+        }
+        println!("{}", format!("⚠️  {}", e).yellow());
+        println!();
+    }
+
+    if dry_run {
+        print_merge_preview(&merge_service::plan_change_merge(change_id, &project_root)?);
+        println!();
+    }
+
     // Step 1: Validate spec files (zero token cost)
     println!("{}", "🔍 [1/7] Validating spec files...".cyan());
     let specs_dir = change_dir.join("specs");
@@ -77,6 +101,46 @@ pub async fn run(change_id: &str) -> Result<()> {
 
     println!("   {} All specs valid", "✅".green());
 
+    // Scope drift gate: implementation must match tasks.md closely enough
+    match scope_drift_service::check_scope_drift(change_id, None, None, &project_root) {
+        Ok(drift) if drift.exceeds(config.workflow.scope_drift_threshold) => {
+            println!();
+            println!(
+                "{}",
+                format!(
+                    "❌ Scope drift: {} finding(s), threshold is {}",
+                    drift.drift_count(),
+                    config.workflow.scope_drift_threshold
+                )
+                .red()
+                .bold()
+            );
+            for line in drift.to_markdown().lines().filter(|l| l.starts_with("- ")) {
+                println!("   {}", line);
+            }
+            println!();
+            println!(
+                "{}",
+                "🛑 Archive blocked. Update tasks.md or the implementation, or raise workflow.scope_drift_threshold."
+                    .yellow()
+            );
+            return Ok(());
+        }
+        Ok(drift) if !drift.is_clean() => {
+            println!(
+                "   {} Scope drift within threshold ({} finding(s))",
+                "⚠️".yellow(),
+                drift.drift_count()
+            );
+        }
+        Ok(_) => {}
+        Err(e) => println!("   {} Scope drift not checked: {}", "⚠️".yellow(), e),
+    }
+
+    // Other active changes touching the same specs or files will need a rebase
+    crate::cli::conflicts::warn_conflicts(change_id, &project_root);
+    crate::cli::rebase::warn_main_spec_drift(change_id, &change_dir);
+
     // Step 2: Compute metrics and decide strategy (zero token cost)
     println!();
     println!("{}", "📊 [2/7] Analyzing delta metrics...".cyan());
@@ -109,23 +173,64 @@ pub async fn run(change_id: &str) -> Result<()> {
         merge_strategies.push((relative_path.to_path_buf(), decision.strategy.clone()));
     }
 
+    if dry_run {
+        return Ok(());
+    }
+
+    let file_merges = merge_service::plan_change_merge(change_id, &project_root)?;
+
     // Step 3: Backup original specs (for potential rollback)
     println!();
     println!("{}", "💾 [3/7] Backing up original specs...".cyan());
     backup_original_specs(&project_root)?;
     println!("   {} Backup created", "✅".green());
 
-    // Step 4: Merge specs with Gemini
+    // Step 4: Merge specs structurally, falling back to Gemini on conflicts
     println!();
-    println!("{}", "🔄 [4/7] Merging spec deltas with Gemini...".cyan());
+    println!("{}", "🔄 [4/7] Merging spec deltas...".cyan());
 
-    for (relative_path, strategy) in &merge_strategies {
+    for file_merge in &file_merges {
+        let relative_path = &file_merge.relative_path;
         println!();
         println!("   Merging: {}", relative_path.display());
 
+        let merge = &file_merge.merge;
+        if merge.is_clean() {
+            if merge.is_noop() {
+                println!("   {} Already up to date", "✅".green());
+                continue;
+            }
+            if let Some(parent) = file_merge.main_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&file_merge.main_path, &merge.content)?;
+            for operation in &merge.operations {
+                println!("      • {}", operation.describe());
+            }
+            println!(
+                "   {} Merged to agentd/specs/{}",
+                "✅".green(),
+                relative_path.display()
+            );
+            continue;
+        }
+
+        println!(
+            "   {} Structural merge has conflicts, falling back to Gemini:",
+            "⚠️".yellow()
+        );
+        for conflict in &merge.conflicts {
+            println!("      • {}", conflict);
+        }
+        let strategy = merge_strategies
+            .iter()
+            .find(|(path, _)| path == relative_path)
+            .map(|(_, strategy)| *strategy)
+            .unwrap_or(MergingStrategy::Hybrid);
+
         merge_spec_with_gemini(
             change_id,
-            strategy,
+            &strategy,
             relative_path.to_str().unwrap(),
             &project_root,
             &config,
@@ -261,6 +366,19 @@ pub async fn run(change_id: &str) -> Result<()> {
     state_manager.set_last_action("archive");
     state_manager.save()?;
 
+    // Record which main specs this merge altered (for history and unmerge)
+    let backup_dir = project_root.join(".agentd-backup");
+    match spec_history_service::record_merge(change_id, &backup_dir, &archive_path, &project_root) {
+        Ok(Some(snapshot)) => println!(
+            "   {} Spec snapshot saved ({} file(s)), revert with: agentd unmerge {}",
+            "✅".green(),
+            snapshot.files.len(),
+            change_id
+        ),
+        Ok(None) => {}
+        Err(e) => println!("   {} Could not save spec snapshot: {}", "⚠️".yellow(), e),
+    }
+
     // Clean up backup after successful archive
     cleanup_backup(&project_root)?;
 
@@ -275,6 +393,50 @@ pub async fn run(change_id: &str) -> Result<()> {
     Ok(())
 }
 
+/// Print what the merge would do to each main spec, as unified diffs
+fn print_merge_preview(file_merges: &[FileMerge]) {
+    println!();
+    println!("{}", "🔎 Dry run: spec merge preview".cyan().bold());
+
+    for file_merge in file_merges {
+        let relative_path = file_merge.relative_path.display().to_string();
+        let merge = &file_merge.merge;
+        println!();
+        println!("   File: {}", relative_path);
+
+        if !merge.is_clean() {
+            println!(
+                "   {} Structural merge has conflicts; merge-change would fall back to Gemini:",
+                "⚠️".yellow()
+            );
+            for conflict in &merge.conflicts {
+                println!("      • {}", conflict);
+            }
+            continue;
+        }
+        if merge.is_noop() {
+            println!("   {} Already up to date", "✅".green());
+            continue;
+        }
+        for operation in &merge.operations {
+            println!("      • {}", operation.describe());
+        }
+        println!();
+        print!(
+            "{}",
+            history_service::unified_diff(
+                file_merge.original.as_deref().unwrap_or(""),
+                &merge.content,
+                &format!("a/agentd/specs/{}", relative_path),
+                &format!("b/agentd/specs/{}", relative_path),
+            )
+        );
+    }
+
+    println!();
+    println!("{}", "No files were changed (dry run).".bright_black());
+}
+
 /// Validate all spec files in the directory
 fn validate_specs(specs_dir: &Path, rules: &ValidationRules) -> Result<crate::models::ValidationResult> {
     let format_validator = SpecFormatValidator::new(rules.clone());
@@ -324,80 +486,21 @@ fn collect_spec_files(specs_dir: &Path) -> Result<Vec<PathBuf>> {
     Ok(files)
 }
 
-/// Compute delta metrics for a spec file
+/// Compute delta metrics for a spec file from its semantic diff
 fn compute_delta_metrics(main_spec_path: &Path, delta_spec_path: &Path) -> Result<DeltaMetrics> {
-    let mut metrics = DeltaMetrics::new();
-
-    // Get file sizes
-    if main_spec_path.exists() {
-        let main_content = std::fs::read_to_string(main_spec_path)?;
-        metrics.existing_spec_size = main_content.len();
-        metrics.existing_req_count = count_requirements(&main_content);
-    }
-
-    let delta_content = std::fs::read_to_string(delta_spec_path)?;
-    metrics.delta_spec_size = delta_content.len();
-
-    // Analyze deltas (simplified - could be more sophisticated)
-    // For now, we'll use simple heuristics
-    let delta_req_count = count_requirements(&delta_content);
-
-    if metrics.existing_spec_size == 0 {
-        // New file - all additions
-        metrics.added_count = delta_req_count;
+    let main_content = if main_spec_path.exists() {
+        Some(std::fs::read_to_string(main_spec_path)?)
     } else {
-        // Estimate changes based on size difference
-        let main_content = std::fs::read_to_string(main_spec_path)?;
-        let main_req_count = metrics.existing_req_count;
-
-        if delta_req_count > main_req_count {
-            metrics.added_count = delta_req_count - main_req_count;
-        }
-
-        if delta_req_count < main_req_count {
-            metrics.removed_count = main_req_count - delta_req_count;
-        }
-
-        // Detect structural changes
-        metrics.has_new_sections = detect_new_sections(&main_content, &delta_content);
-        metrics.has_schema_changes = delta_content.contains("## Data Schema")
-            || delta_content.contains("## Database Schema");
-        metrics.has_api_changes =
-            delta_content.contains("## API Endpoints") || delta_content.contains("### API:");
-
-        // Affected requirements (simplified: all non-added requirements)
-        metrics.affected_req_count = delta_req_count.saturating_sub(metrics.added_count);
-        if metrics.affected_req_count == 0 && metrics.added_count == 0 && delta_req_count > 0 {
-            metrics.affected_req_count = delta_req_count; // Modifications
-            metrics.modified_count = delta_req_count;
-        }
-    }
-
-    metrics.calculate_ratios();
-    Ok(metrics)
-}
-
-/// Count requirements in markdown content
-fn count_requirements(content: &str) -> usize {
-    content
-        .lines()
-        .filter(|line| line.starts_with("### R") && line.contains(':'))
-        .count()
-}
-
-/// Detect if new top-level sections were added
-fn detect_new_sections(main_content: &str, delta_content: &str) -> bool {
-    let main_sections: Vec<_> = main_content
-        .lines()
-        .filter(|line| line.starts_with("## "))
-        .collect();
-
-    let delta_sections: Vec<_> = delta_content
-        .lines()
-        .filter(|line| line.starts_with("## "))
-        .collect();
+        None
+    };
+    let delta_content = std::fs::read_to_string(delta_spec_path)?;
 
-    delta_sections.len() > main_sections.len()
+    let diff = spec_diff_service::diff_spec(
+        &delta_spec_path.display().to_string(),
+        main_content.as_deref(),
+        &delta_content,
+    );
+    Ok(diff.metrics)
 }
 
 /// Merge spec delta with Gemini
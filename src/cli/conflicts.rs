@@ -0,0 +1,97 @@
+//! Conflicts CLI command
+//!
+//! Reports overlaps between active changes and suggests a merge order.
+
+use crate::services::conflict_service::{self, ChangeConflict, ConflictSeverity};
+use crate::Result;
+use colored::Colorize;
+use std::env;
+use std::path::Path;
+
+/// Print conflicts among active changes, optionally only those involving
+/// `change_id`
+///
+/// `format` is one of markdown or json.
+pub fn run(change_id: Option<&str>, format: &str) -> Result<()> {
+    let project_root = env::current_dir()?;
+    let mut report = conflict_service::detect_conflicts(&project_root)?;
+
+    if let Some(id) = change_id {
+        if !report.changes.iter().any(|c| c == id) {
+            anyhow::bail!("Change '{}' not found in agentd/changes", id);
+        }
+        report.conflicts.retain(|c| c.changes.iter().any(|x| x == id));
+        report
+            .suggested_order
+            .retain(|x| x == id || report.conflicts.iter().any(|c| c.changes.contains(x)));
+    }
+
+    match format {
+        "markdown" | "md" => println!("{}", report.to_markdown()),
+        "json" => println!("{}", serde_json::to_string_pretty(&report)?),
+        other => anyhow::bail!("Unknown format '{}': expected markdown or json", other),
+    }
+    Ok(())
+}
+
+/// Warn about conflicts between `change_id` and other active changes
+///
+/// Used by plan-change and merge-change; never fails the calling command.
+pub fn warn_conflicts(change_id: &str, project_root: &Path) {
+    let report = match conflict_service::detect_conflicts(project_root) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!(
+                "{}",
+                format!("⚠️  Could not check for conflicting changes: {}", e).yellow()
+            );
+            return;
+        }
+    };
+    let conflicts = report.involving(change_id);
+    if conflicts.is_empty() {
+        return;
+    }
+
+    println!();
+    println!(
+        "{}",
+        format!(
+            "⚠️  {} overlap(s) with other active changes:",
+            conflicts.len()
+        )
+        .yellow()
+        .bold()
+    );
+    for conflict in &conflicts {
+        println!("   {}", describe(conflict, change_id));
+    }
+    if !report.suggested_order.is_empty() {
+        println!(
+            "   Suggested merge order: {}",
+            report.suggested_order.join(" → ").cyan()
+        );
+    }
+    println!("   Run {} for details", "agentd conflicts".cyan());
+}
+
+fn describe(conflict: &ChangeConflict, change_id: &str) -> String {
+    let other = conflict
+        .changes
+        .iter()
+        .find(|id| *id != change_id)
+        .map(String::as_str)
+        .unwrap_or(change_id);
+    let severity = match conflict.severity {
+        ConflictSeverity::High => conflict.severity.label().red().bold(),
+        ConflictSeverity::Medium => conflict.severity.label().yellow(),
+        ConflictSeverity::Low => conflict.severity.label().dimmed(),
+    };
+    format!(
+        "[{}] {} with {}: {}",
+        severity,
+        conflict.subject.describe(),
+        other.cyan(),
+        conflict.detail
+    )
+}
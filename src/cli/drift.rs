@@ -0,0 +1,88 @@
+//! Drift CLI command
+//!
+//! Compares the main specs with the code and reports whether they diverged.
+//! The binary exits with [`DRIFT_EXIT_CODE`] on drift, for use in CI; errors
+//! exit with 1 like every command.
+
+use crate::fillback::code::{CodeStrategy, CodeStrategyConfig};
+use crate::models::AgentdConfig;
+use crate::services::spec_drift_service;
+use crate::Result;
+use colored::Colorize;
+use std::env;
+use std::path::PathBuf;
+
+/// Exit status when drift is found, distinct from the status 1 of errors
+pub const DRIFT_EXIT_CODE: i32 = 2;
+
+/// Result of a drift check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriftOutcome {
+    /// Specs match the code
+    Clean,
+    /// Specs diverged from the code
+    Drifted,
+}
+
+/// Re-run the AST analysis and report drift against agentd/specs/
+///
+/// `format` is markdown or json. Returns [`DriftOutcome::Drifted`] when drift
+/// is found.
+pub fn run(
+    path: Option<&str>,
+    package: Option<&str>,
+    format: &str,
+    no_cache: bool,
+) -> Result<DriftOutcome> {
+    if !matches!(format, "markdown" | "md" | "json") {
+        anyhow::bail!("Unknown format '{}': expected markdown or json", format);
+    }
+
+    let project_root = env::current_dir()?;
+    let fillback_config = AgentdConfig::load(&project_root)?.fillback;
+
+    let source_path = path
+        .map(PathBuf::from)
+        .unwrap_or_else(|| project_root.clone());
+    if !source_path.is_dir() {
+        anyhow::bail!("Source path must be a directory: {}", source_path.display());
+    }
+
+    // Progress goes to stderr so `--format json` stays parseable
+    let strategy = CodeStrategy::with_config(CodeStrategyConfig {
+        cache_dir: (fillback_config.cache && !no_cache).then(|| project_root.join(".agentd/cache")),
+        stderr_progress: true,
+        ..CodeStrategyConfig::default().with_limits(&fillback_config)
+    });
+    let report = spec_drift_service::check_drift(
+        &strategy,
+        &source_path,
+        package,
+        &project_root.join("agentd/specs"),
+        &project_root,
+    )?;
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("{}", report.to_markdown());
+    }
+
+    if !report.is_clean() {
+        if format != "json" {
+            eprintln!(
+                "{}",
+                format!(
+                    "⚠️  Specs drifted from code: {} removed, {} renamed, {} unspecified",
+                    report.removed.len(),
+                    report.renamed.len(),
+                    report.unspecified.len()
+                )
+                .yellow()
+            );
+        }
+        return Ok(DriftOutcome::Drifted);
+    }
+
+    Ok(DriftOutcome::Clean)
+}
@@ -0,0 +1,45 @@
+//! Export CLI command
+//!
+//! Writes main specs (or one change's specs) in OpenSpec or Speckit format.
+
+use crate::fillback::{export_specs, ExportFormat};
+use crate::Result;
+use colored::Colorize;
+use std::env;
+use std::path::PathBuf;
+
+/// Export specs to `output` (default: `export/<format>`)
+pub fn run(format: &str, change_id: Option<&str>, output: Option<&str>) -> Result<()> {
+    let project_root = env::current_dir()?;
+    let format = ExportFormat::parse(format)?;
+    let output = output
+        .map(PathBuf::from)
+        .unwrap_or_else(|| project_root.join("export").join(format.name()));
+
+    let files = export_specs(change_id, format, &output, &project_root)?;
+    if files.is_empty() {
+        println!("{}", "No specs with requirements to export".yellow());
+        return Ok(());
+    }
+
+    let source = match change_id {
+        Some(id) => format!("change '{}'", id),
+        None => "agentd/specs".to_string(),
+    };
+    println!(
+        "{}",
+        format!(
+            "📤 Exported {} spec(s) from {} as {}",
+            files.len(),
+            source,
+            format.name()
+        )
+        .green()
+        .bold()
+    );
+    for file in &files {
+        let shown = file.strip_prefix(&project_root).unwrap_or(file);
+        println!("   {}", shown.display());
+    }
+    Ok(())
+}
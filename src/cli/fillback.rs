@@ -1,10 +1,13 @@
 //! Fillback CLI Command
 //!
 //! Analyzes existing codebase using AST parsing and generates
-//! language-agnostic specifications in agentd/specs/.
+//! language-agnostic specifications in agentd/specs/, or imports
+//! OpenSpec/Speckit spec trees as a validated change.
 
-use crate::fillback::code::{CodeStrategy, CodeStrategyConfig};
-use crate::fillback::ImportStrategy;
+use crate::cli::validate_proposal::validate_proposal;
+use crate::fillback::code::CodeStrategyConfig;
+use crate::fillback::StrategyFactory;
+use crate::models::{AgentdConfig, ValidationOptions};
 use crate::Result;
 use colored::Colorize;
 use std::env;
@@ -12,19 +15,26 @@ use std::path::PathBuf;
 
 /// Run the fillback command to analyze codebase and generate specs
 ///
+/// `jobs` and `no_cache` override the `[fillback]` settings in agentd/config.toml.
+///
 /// # Workflow
-/// 1. Parse source files using tree-sitter AST analysis
-/// 2. Build dependency graph from module relationships
-/// 3. Display analysis summary and dependency graph
-/// 4. Run interactive clarification to refine understanding
-/// 5. Check for existing specs and confirm overwrites
-/// 6. Generate specifications in agentd/specs/
+/// 1. Pick a strategy through `StrategyFactory` (`auto` detects it from the source)
+/// 2. `code`: parse sources (in parallel, reusing cached results for unchanged
+///    files), build the dependency graph, clarify and write specs to agentd/specs/;
+///    workspaces get one spec folder per package unless `package` selects one
+/// 3. `openspec`/`speckit`: import the spec tree into agentd/changes/<change>/
+///    and run proposal validation on the result
 pub async fn run(
     path: Option<&str>,
-    module: Option<&str>,
+    package: Option<&str>,
     force: bool,
+    strategy: &str,
+    change_id: Option<&str>,
+    jobs: Option<usize>,
+    no_cache: bool,
 ) -> Result<()> {
     let project_root = env::current_dir()?;
+    let fillback_config = AgentdConfig::load(&project_root)?.fillback;
 
     println!("{}", "Agentd Fillback".cyan().bold());
     println!(
@@ -45,7 +55,7 @@ pub async fn run(
         anyhow::bail!("Source path does not exist: {}", source_path.display());
     }
 
-    if !source_path.is_dir() {
+    if strategy == "code" && !source_path.is_dir() {
         anyhow::bail!("Source path must be a directory: {}", source_path.display());
     }
 
@@ -54,11 +64,8 @@ pub async fn run(
         format!("Source: {}", source_path.display()).bright_black()
     );
 
-    if let Some(m) = module {
-        println!(
-            "{}",
-            format!("Module filter: {}", m).bright_black()
-        );
+    if let Some(package) = package {
+        println!("{}", format!("Package: {}", package).bright_black());
     }
 
     if force {
@@ -73,25 +80,78 @@ pub async fn run(
     // Create strategy with configuration
     let config = CodeStrategyConfig {
         path: path.map(String::from),
-        module: module.map(String::from),
+        package: package.map(String::from),
         force,
         output_dir: Some(project_root.join("agentd/specs").to_string_lossy().to_string()),
+        jobs: jobs.unwrap_or(fillback_config.jobs),
+        cache_dir: (fillback_config.cache && !no_cache).then(|| project_root.join(".agentd/cache")),
+        ..CodeStrategyConfig::default().with_limits(&fillback_config)
     };
 
-    let strategy = CodeStrategy::with_config(config);
+    let strategy = StrategyFactory::create_with_config(strategy, &source_path, config)?;
+
+    if strategy.name() == "code" {
+        if change_id.is_some() {
+            println!(
+                "{}",
+                "ℹ️  --change only applies to openspec/speckit imports; writing to agentd/specs/".blue()
+            );
+        }
+
+        // Execute the strategy (it handles all the steps internally)
+        // The change_id parameter is not used by the code strategy
+        strategy.execute(&source_path, "fillback").await?;
+
+        println!();
+        println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_black());
+        println!("{}", "Fillback completed!".green().bold());
+        println!();
+        println!("{}", "Next steps:".bright_black());
+        println!("  1. Review generated specs in agentd/specs/");
+        println!("  2. Edit and enhance specifications as needed");
+        println!("  3. Use specs as reference for future changes");
+        return Ok(());
+    }
+
+    let Some(change_id) = change_id else {
+        anyhow::bail!(
+            "--change <id> is required when importing with the {} strategy",
+            strategy.name()
+        );
+    };
 
-    // Execute the strategy (it handles all the steps internally)
-    // The change_id parameter is no longer used but kept for trait compatibility
-    strategy.execute(&source_path, "fillback").await?;
+    strategy.execute(&source_path, change_id).await?;
+
+    // The imported change must be ready for the normal workflow
+    let options = ValidationOptions::new().with_json(true);
+    let summary = validate_proposal(change_id, &project_root, &options)?;
 
     println!();
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_black());
-    println!("{}", "Fillback completed!".green().bold());
+    if !summary.is_valid() {
+        println!("{}", "❌ Imported change failed validation:".red().bold());
+        for error in &summary.errors {
+            println!("   • {}", error);
+        }
+        anyhow::bail!(
+            "Imported change '{}' has {} HIGH severity validation error(s)",
+            change_id,
+            summary.high_count
+        );
+    }
+
+    println!("{}", "Import completed!".green().bold());
+    if summary.has_warnings() {
+        println!(
+            "   {} validation warning(s); run 'agentd validate-proposal {}' for details",
+            summary.medium_count + summary.low_count,
+            change_id
+        );
+    }
     println!();
     println!("{}", "Next steps:".bright_black());
-    println!("  1. Review generated specs in agentd/specs/");
-    println!("  2. Edit and enhance specifications as needed");
-    println!("  3. Use specs as reference for future changes");
+    println!("  1. Review agentd/changes/{}/", change_id);
+    println!("  2. agentd challenge {}", change_id);
 
     Ok(())
 }
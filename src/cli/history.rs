@@ -0,0 +1,123 @@
+//! History CLI commands
+//!
+//! Lists saved versions of change documents and restores a single document
+//! to an earlier version.
+
+use crate::services::history_service::{self, DiffStats};
+use crate::Result;
+use colored::Colorize;
+use std::env;
+
+/// List saved versions of a document with per-version line changes
+pub fn run(change_id: &str, file: &str, show_diff: bool) -> Result<()> {
+    let project_root = env::current_dir()?;
+    let change_dir = project_root.join("agentd/changes").join(change_id);
+    if !change_dir.exists() {
+        anyhow::bail!("Change '{}' not found.", change_id);
+    }
+
+    let key = history_service::document_key(&change_dir, file)?;
+    let versions = history_service::list_versions(&change_dir, &key)?;
+
+    println!(
+        "{}",
+        format!("📜 History for {}.md (change: {})", key, change_id)
+            .cyan()
+            .bold()
+    );
+    println!();
+
+    if versions.is_empty() {
+        println!("{}", "   No saved versions yet.".yellow());
+        return Ok(());
+    }
+
+    let mut previous: Option<(String, String)> = None;
+    for entry in &versions {
+        let content = std::fs::read_to_string(&entry.path)?;
+        let label = format!("v{}", entry.version);
+        let stats = previous
+            .as_ref()
+            .map(|(_, old)| history_service::diff_stats(old, &content));
+        println!(
+            "   {:<8} {}  {}",
+            label.bold(),
+            entry.saved_at.format("%Y-%m-%d %H:%M:%S"),
+            format_stats(stats)
+        );
+        if show_diff {
+            print_diff(previous.as_ref(), &label, &content);
+        }
+        previous = Some((label, content));
+    }
+
+    let doc_path = history_service::document_path(&change_dir, &key);
+    if doc_path.exists() {
+        let content = std::fs::read_to_string(&doc_path)?;
+        let stats = previous
+            .as_ref()
+            .map(|(_, old)| history_service::diff_stats(old, &content));
+        println!(
+            "   {:<8} {}  {}",
+            "current".green().bold(),
+            " ".repeat(19),
+            format_stats(stats)
+        );
+        if show_diff {
+            print_diff(previous.as_ref(), "current", &content);
+        }
+    }
+
+    println!();
+    println!(
+        "{}",
+        format!(
+            "   Restore with: agentd restore {} {} <version>",
+            change_id, file
+        )
+        .bright_black()
+    );
+
+    Ok(())
+}
+
+/// Roll a single document back to a saved version
+pub fn run_restore(change_id: &str, file: &str, version: u32) -> Result<()> {
+    let project_root = env::current_dir()?;
+    let result = history_service::restore_version(change_id, file, version, &project_root)?;
+    println!("{}", format!("✅ {}", result).green());
+    Ok(())
+}
+
+fn format_stats(stats: Option<DiffStats>) -> String {
+    match stats {
+        Some(s) => format!(
+            "{} {}",
+            format!("+{}", s.added).green(),
+            format!("-{}", s.removed).red()
+        ),
+        None => "(initial)".bright_black().to_string(),
+    }
+}
+
+fn print_diff(previous: Option<&(String, String)>, label: &str, content: &str) {
+    let Some((old_label, old)) = previous else {
+        return;
+    };
+    let diff = history_service::unified_diff(old, content, old_label, label);
+    for line in diff.lines() {
+        let colored_line = if line.starts_with("+++") || line.starts_with("---") {
+            line.bold()
+        } else if line.starts_with('+') {
+            line.green()
+        } else if line.starts_with('-') {
+            line.red()
+        } else if line.starts_with("@@") {
+            line.cyan()
+        } else {
+            line.normal()
+        };
+        println!("      {}", colored_line);
+    }
+    println!();
+}
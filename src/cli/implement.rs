@@ -1,7 +1,10 @@
 use crate::models::frontmatter::StatePhase;
-use crate::models::{SpecGroup, TaskGraph};
+use crate::models::{SpecGroup, TaskGraph, TaskStatus};
+use crate::cli::rebase;
 use crate::orchestrator::{ClaudeOrchestrator, CodexOrchestrator, UsageMetrics};
 use crate::parser::parse_review_verdict;
+use crate::services::dependency_service::{self, DependencyGate};
+use crate::services::{diff_service, scope_drift_service, tasks_service};
 use crate::state::StateManager;
 use crate::{
     models::{Change, Complexity, ReviewVerdict, AgentdConfig},
@@ -10,7 +13,7 @@ use crate::{
 use colored::Colorize;
 use std::collections::HashSet;
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Result from running the implementation workflow
 pub struct ImplementEngineResult {
@@ -51,6 +54,65 @@ async fn run_spec_implementation(
     Ok(())
 }
 
+/// Update tasks.md status for every task in a spec group
+///
+/// Progress tracking is best-effort: a failure is reported but never aborts
+/// the implementation workflow.
+fn mark_spec_tasks(
+    change_id: &str,
+    spec_group: &SpecGroup,
+    status: TaskStatus,
+    project_root: &Path,
+) {
+    let task_ids: Vec<String> = spec_group.tasks.iter().map(|t| t.id.clone()).collect();
+    if task_ids.is_empty() {
+        return;
+    }
+    if let Err(e) = tasks_service::update_task_status(change_id, &task_ids, status, project_root) {
+        println!(
+            "{}",
+            format!("   ⚠️  Could not update task status: {}", e).yellow()
+        );
+    }
+}
+
+/// Print scope drift for the tasks implemented so far
+///
+/// Informational only: drift blocks merge-change, not implementation.
+fn report_scope_drift(change_id: &str, done_tasks: &HashSet<String>, project_root: &Path) {
+    match scope_drift_service::check_scope_drift(change_id, None, Some(done_tasks), project_root) {
+        Ok(drift) if drift.is_clean() => println!("   ✅ No scope drift"),
+        Ok(drift) => {
+            println!(
+                "{}",
+                format!("   ⚠️  Scope drift: {} finding(s)", drift.drift_count()).yellow()
+            );
+            for line in drift.to_markdown().lines().filter(|l| l.starts_with("- ")) {
+                println!("      {}", line);
+            }
+        }
+        Err(e) => println!(
+            "{}",
+            format!("   ⚠️  Could not check scope drift: {}", e).yellow()
+        ),
+    }
+}
+
+/// Record HEAD as the change's baseline commit the first time implementation
+/// starts, so later diffs cover exactly the implementation's changes
+fn record_baseline_commit(state_manager: &mut StateManager, project_root: &Path) {
+    if state_manager.baseline_commit().is_some() {
+        return;
+    }
+    match diff_service::head_commit(project_root) {
+        Ok(commit) => state_manager.set_baseline_commit(commit),
+        Err(e) => println!(
+            "{}",
+            format!("⚠️  Could not record baseline commit: {}", e).yellow()
+        ),
+    }
+}
+
 /// Run Codex review for a single spec
 async fn run_spec_review(
     change_id: &str,
@@ -184,7 +246,9 @@ pub async fn run_sequential(change_id: &str) -> Result<ImplementEngineResult> {
     // Update STATE to Implementing phase
     let mut state_manager = StateManager::load(&change_dir)?;
     state_manager.set_phase(StatePhase::Implementing);
+    record_baseline_commit(&mut state_manager, &project_root);
     state_manager.save()?;
+    rebase::warn_main_spec_drift(change_id, &change_dir);
 
     println!("{}", "🎨 Agentd Spec-by-Spec Implementation".cyan().bold());
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_black());
@@ -199,8 +263,9 @@ pub async fn run_sequential(change_id: &str) -> Result<ImplementEngineResult> {
     println!("   Found {} layers, {} specs", task_graph.layers.len(), total_specs);
     println!();
 
-    // 2. Track completed specs
+    // 2. Track completed specs (and their tasks, for drift checks)
     let mut completed = HashSet::new();
+    let mut done_tasks = HashSet::new();
 
     // 3. Execute spec by spec
     for (idx, spec_group) in execution_order.iter().enumerate() {
@@ -217,6 +282,7 @@ pub async fn run_sequential(change_id: &str) -> Result<ImplementEngineResult> {
         }
 
         // Implement this spec's tasks
+        mark_spec_tasks(change_id, spec_group, TaskStatus::InProgress, &project_root);
         run_spec_implementation(change_id, spec_group, &project_root, &config).await?;
 
         // Codex review for this spec
@@ -237,7 +303,15 @@ pub async fn run_sequential(change_id: &str) -> Result<ImplementEngineResult> {
         }
 
         // Mark complete
+        let task_status = if spec_verdict == ReviewVerdict::MajorIssues {
+            TaskStatus::Blocked
+        } else {
+            TaskStatus::Completed
+        };
+        mark_spec_tasks(change_id, spec_group, task_status, &project_root);
         completed.insert(spec_group.spec_id.clone());
+        done_tasks.extend(spec_group.tasks.iter().map(|t| t.id.clone()));
+        report_scope_drift(change_id, &done_tasks, &project_root);
         println!();
     }
 
@@ -355,6 +429,9 @@ pub async fn run(change_id: &str, tasks: Option<&str>) -> Result<ImplementEngine
     let config = AgentdConfig::load(&project_root)?;
     let change_dir = project_root.join("agentd/changes").join(change_id);
 
+    // Prerequisite changes must reach their required phase first
+    dependency_service::ensure_ready(change_id, DependencyGate::Implement, &project_root)?;
+
     // Load current state
     let state_manager = StateManager::load(&change_dir)?;
     let phase = state_manager.phase();
@@ -446,7 +523,9 @@ async fn run_full_workflow(change_id: &str, tasks: Option<&str>) -> Result<Imple
     // Update STATE to Implementing phase
     let mut state_manager = StateManager::load(&change_dir)?;
     state_manager.set_phase(StatePhase::Implementing);
+    record_baseline_commit(&mut state_manager, &project_root);
     state_manager.save()?;
+    rebase::warn_main_spec_drift(change_id, &change_dir);
 
     println!("{}", "🎨 Agentd Implementation Workflow".cyan().bold());
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_black());
@@ -18,9 +18,10 @@ pub enum ImplementationCommands {
         /// Change ID
         change_id: String,
 
-        /// Base branch to compare against (default: main)
-        #[arg(long, default_value = "main")]
-        base_branch: String,
+        /// Base ref to compare against (default: the change's baseline
+        /// commit, else main)
+        #[arg(long)]
+        base_branch: Option<String>,
 
         /// Optional filter pattern (simple string match)
         #[arg(long)]
@@ -43,7 +44,7 @@ pub fn run(cmd: ImplementationCommands) -> Result<()> {
         } => {
             let result = implementation_service::list_changed_files(
                 &change_id,
-                Some(&base_branch),
+                base_branch.as_deref(),
                 filter.as_deref(),
                 &project_root,
             )?;
@@ -1,5 +1,6 @@
 use crate::Result;
 use crate::parser::markdown::extract_heading_section;
+use crate::services::dependency_service;
 use colored::Colorize;
 use std::env;
 use std::fs;
@@ -19,13 +20,28 @@ pub fn run(archived: bool) -> Result<()> {
     // List active changes
     if !archived {
         println!("\n{}", "Active changes:".green().bold());
-        for entry in std::fs::read_dir(&changes_dir)? {
-            let entry = entry?;
-            let path = entry.path();
-            if path.is_dir() {
-                if let Some(name) = path.file_name() {
-                    println!("   • {}", name.to_string_lossy());
-                }
+        let graph = dependency_service::dependency_graph(&project_root)?;
+        for change in &graph.changes {
+            let blocked = change.blocked_by();
+            if blocked.is_empty() {
+                println!("   • {} ({})", change.id, change.phase.as_str());
+            } else {
+                println!(
+                    "   • {} ({}) {}",
+                    change.id,
+                    change.phase.as_str(),
+                    format!("⛔ waiting on {}", blocked.join(", ")).yellow()
+                );
+            }
+        }
+
+        if graph.has_edges() {
+            println!("\n{}", "Dependency graph:".green().bold());
+            for line in graph.render_tree().lines() {
+                println!("   {}", line);
+            }
+            for cycle in graph.cycles() {
+                println!("   {}", format!("⚠️  Dependency cycle: {}", cycle.join(" → ")).red());
             }
         }
     } else {
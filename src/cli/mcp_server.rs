@@ -4,6 +4,7 @@
 //! The server communicates via JSON-RPC 2.0 over stdio.
 
 use crate::mcp::McpServer;
+use crate::models::AgentdConfig;
 use crate::Result;
 
 /// Run the MCP server
@@ -11,7 +12,21 @@ use crate::Result;
 /// # Arguments
 ///
 /// * `tools` - Optional workflow stage to filter tools (plan, challenge, implement, review, archive)
+///
+/// Upstream MCP servers from `[[mcp.upstream]]` in agentd/config.toml are
+/// connected before serving, and their tools are proxied for the same stage.
+/// Custom command tools from `[[mcp.custom_tools]]` are registered likewise.
 pub async fn run(tools: Option<&str>) -> Result<()> {
-    let server = McpServer::new_for_stage(tools)?;
+    let mut server = McpServer::new_for_stage(tools)?;
+
+    let project_root = std::env::current_dir()?;
+    let config = AgentdConfig::load(&project_root)?;
+    if !config.mcp.upstream.is_empty() {
+        server
+            .connect_upstreams(&config.mcp.upstream, &project_root)
+            .await;
+    }
+    server.register_custom_tools(&config.mcp.custom_tools, &project_root);
+
     server.run().await
 }
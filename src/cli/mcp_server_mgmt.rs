@@ -5,14 +5,27 @@
 //! - stop: Unregister a project
 //! - list: List all registered projects
 //! - shutdown: Stop the entire server
+//!
+//! Without a subcommand, serves MCP over stdio (see `cli::mcp_server`).
 
 use crate::mcp::Registry;
 use crate::Result;
-use clap::Subcommand;
+use clap::{Args, Subcommand};
 use colored::Colorize;
 use std::env;
 use std::process::{Command, Stdio};
 
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct McpServerArgs {
+    #[command(subcommand)]
+    pub command: Option<McpServerCommands>,
+
+    /// Serve over stdio with the tools of one workflow stage
+    #[arg(long)]
+    pub tools: Option<String>,
+}
+
 #[derive(Subcommand)]
 pub enum McpServerCommands {
     /// Start HTTP MCP server and register current project
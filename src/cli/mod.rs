@@ -1,7 +1,11 @@
 pub mod archive;
 pub mod clarifications;
+pub mod conflicts;
+pub mod drift;
+pub mod export;
 pub mod file;
 pub mod fillback;
+pub mod history;
 pub mod implement;
 pub mod implementation;
 pub mod init;
@@ -13,12 +17,15 @@ pub mod migrate_xml;
 pub mod plan;
 pub mod proposal;
 pub mod proposal_engine;
+pub mod rebase;
 pub mod refine;
 pub mod revise;
 pub mod server; // Unified server commands (R1)
 pub mod spec;
 pub mod status;
 pub mod tasks;
+pub mod trace;
+pub mod unmerge;
 pub mod update;
 pub mod validate_challenge;
 pub mod validate_proposal;
@@ -1,4 +1,4 @@
-use crate::cli::proposal_engine;
+use crate::cli::{conflicts, proposal_engine};
 use crate::models::AgentdConfig;
 use crate::Result;
 use colored::Colorize;
@@ -85,10 +85,13 @@ pub async fn run(change_id: &str, description: Option<String>, skip_clarify: boo
         change_id: change_id.to_string(),
         description,
         skip_clarify,
-        project_root,
+        project_root: project_root.clone(),
         config,
     };
 
     proposal_engine::run_plan_change(engine_config).await?;
+
+    // Overlaps with other active changes are reported, not blocking
+    conflicts::warn_conflicts(change_id, &project_root);
     Ok(())
 }
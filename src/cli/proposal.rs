@@ -128,6 +128,16 @@ pub fn run(cmd: ProposalCommands) -> Result<()> {
                 .and_then(|v| v.as_str())
                 .map(String::from);
 
+            let depends_on_changes: Vec<String> = json
+                .get("depends_on_changes")
+                .and_then(|v| v.as_array())
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|v| v.as_str().map(String::from))
+                        .collect()
+                })
+                .unwrap_or_default();
+
             // Create input struct
             let input = CreateProposalInput {
                 change_id,
@@ -142,6 +152,7 @@ pub fn run(cmd: ProposalCommands) -> Result<()> {
                     affected_code,
                     breaking_changes,
                 },
+                depends_on_changes,
             };
 
             // Create proposal
@@ -237,7 +237,8 @@ pub async fn run_plan_change(config: ProposalEngineConfig) -> Result<ProposalEng
         .filter(|spec| !change_dir.join("specs").join(format!("{}.md", spec.id)).exists())
         .collect();
 
-    if !missing_specs.is_empty() {
+    let specs_generated = !missing_specs.is_empty();
+    if specs_generated {
         println!();
         println!("{}", format!("📝 Phase 2: Generating {} missing specs...", missing_specs.len()).cyan().bold());
 
@@ -315,6 +316,16 @@ pub async fn run_plan_change(config: ProposalEngineConfig) -> Result<ProposalEng
     println!("{}", "✨ Plan workflow completed!".green().bold());
     println!("{}", format!("   Location: agentd/changes/{}", change_id).cyan());
 
+    // Remember the main specs the deltas were written against, so merges of
+    // other changes show up as drift (see `agentd rebase-change`)
+    if let Ok(mut state) = StateManager::load(&change_dir) {
+        if specs_generated || !state.has_main_spec_checksums() {
+            if let Err(e) = state.record_main_spec_checksums().and_then(|_| state.save()) {
+                println!("{}", format!("⚠️  Could not record main spec checksums: {}", e).yellow());
+            }
+        }
+    }
+
     // Update change phase to challenged
     let mut change = Change::new(&change_id, &description);
     change.update_phase(ChangePhase::Proposed);
@@ -0,0 +1,380 @@
+//! Rebase-change command
+//!
+//! When another change is merged while this one is in progress, its delta
+//! specs were written against outdated main specs. Rebasing re-runs spec
+//! review (and revision where the review asks for it) against the current
+//! `agentd/specs`, then records the new main-spec checksums.
+
+use crate::models::{AgentdConfig, Change, Complexity};
+use crate::orchestrator::{CodexOrchestrator, GeminiOrchestrator, UsageMetrics};
+use crate::parser::{parse_latest_review, ReviewBlock};
+use crate::services::merge_service;
+use crate::state::StateManager;
+use crate::Result;
+use colored::Colorize;
+use std::env;
+use std::path::Path;
+
+/// Outcome of one spec review during a rebase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SpecReview {
+    Approved,
+    NeedsRevision,
+    Rejected,
+    /// The review run recorded no verdict
+    Unknown,
+}
+
+/// Re-review a change's delta specs against moved main specs
+///
+/// Only specs whose main spec changed since planning are reviewed, unless
+/// `force` is set or no checksums were recorded (then all are).
+pub async fn run(change_id: &str, force: bool) -> Result<()> {
+    let project_root = env::current_dir()?;
+    let config = AgentdConfig::load(&project_root)?;
+    let change_dir = project_root.join("agentd/changes").join(change_id);
+    if !change_dir.exists() {
+        anyhow::bail!("Change '{}' not found", change_id);
+    }
+
+    let state = StateManager::load(&change_dir)?;
+    if state.phase().is_merge_phase() {
+        anyhow::bail!(
+            "Change '{}' is already being merged or archived; nothing to rebase",
+            change_id
+        );
+    }
+
+    let recorded = state.has_main_spec_checksums();
+    let drifted = state.drifted_main_specs()?;
+    if recorded && drifted.is_empty() && !force {
+        println!(
+            "{}",
+            format!("✅ Main specs unchanged since '{}' was planned", change_id).green()
+        );
+        return Ok(());
+    }
+
+    println!("{}", "🔀 Agentd Rebase-Change".cyan().bold());
+    println!(
+        "{}",
+        "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_black()
+    );
+    if !recorded {
+        println!(
+            "{}",
+            "ℹ️  No main spec checksums recorded at planning time; reviewing every spec".blue()
+        );
+    } else if !drifted.is_empty() {
+        println!("Main specs changed since planning:");
+        for spec in &drifted {
+            println!("   • agentd/specs/{}", spec);
+        }
+    }
+
+    let delta_specs = list_delta_specs(&change_dir.join("specs"));
+    let to_review: Vec<String> = delta_specs
+        .into_iter()
+        .filter(|spec| force || !recorded || drifted.contains(spec))
+        .collect();
+
+    let complexity = Change::new(change_id, "").assess_complexity(&project_root);
+    let codex = CodexOrchestrator::new(&config, &project_root);
+    let gemini = GeminiOrchestrator::new(&config, &project_root);
+    let max_iterations = config.workflow.planning_iterations.max(1);
+
+    let mut unresolved = Vec::new();
+    for spec in &to_review {
+        let spec_id = spec.trim_end_matches(".md");
+        println!();
+        println!(
+            "{}",
+            format!("📄 Reviewing {} against current main spec...", spec_id).cyan()
+        );
+
+        let mut review = SpecReview::Unknown;
+        for iteration in 1..=max_iterations {
+            // Number past the reviews already in proposal.md, so the verdict
+            // read back is the one this run recorded
+            let review_iteration = latest_review(&change_dir)?.map_or(0, |r| r.iteration) + 1;
+            let (_output, usage) = codex
+                .run_review_spec_mcp(change_id, spec_id, review_iteration, complexity)
+                .await?;
+            let model = config.codex.select_model(complexity).model.clone();
+            record_usage(
+                change_id,
+                &project_root,
+                &format!("rebase-review-{}", spec_id),
+                &model,
+                &usage,
+                &config,
+                complexity,
+                "codex",
+            );
+
+            review = parse_spec_review(latest_review(&change_dir)?.as_ref(), review_iteration);
+            match review {
+                SpecReview::Approved => {
+                    println!("   {} Review {}: APPROVED", "✓".green(), iteration);
+                    break;
+                }
+                SpecReview::Rejected => {
+                    println!("   {} Review {}: REJECTED", "✗".red(), iteration);
+                    break;
+                }
+                SpecReview::NeedsRevision => {
+                    println!("   {} Review {}: NEEDS_REVISION", "⚠".yellow(), iteration);
+                }
+                SpecReview::Unknown => {
+                    println!(
+                        "   {} Review {}: no verdict recorded with append_review",
+                        "❓".yellow(),
+                        iteration
+                    );
+                    break;
+                }
+            }
+            if iteration == max_iterations {
+                break;
+            }
+
+            println!("   📝 Revising {}...", spec_id);
+            let (_output, usage) = gemini
+                .run_revise_spec_mcp(change_id, spec_id, complexity)
+                .await?;
+            let model = config.gemini.select_model(complexity).model.clone();
+            record_usage(
+                change_id,
+                &project_root,
+                &format!("rebase-revise-{}", spec_id),
+                &model,
+                &usage,
+                &config,
+                complexity,
+                "gemini",
+            );
+        }
+
+        if review != SpecReview::Approved {
+            unresolved.push(spec_id.to_string());
+        }
+    }
+
+    // The rebased deltas should now apply structurally to the new main specs
+    let merges = merge_service::plan_change_merge(change_id, &project_root)?;
+    let conflicting: Vec<_> = merges.iter().filter(|m| !m.merge.is_clean()).collect();
+    if !conflicting.is_empty() {
+        println!();
+        println!(
+            "{}",
+            "⚠️  Structural merge conflicts against current main specs:".yellow()
+        );
+        for file in &conflicting {
+            for conflict in &file.merge.conflicts {
+                println!("   • {}: {}", file.relative_path.display(), conflict);
+            }
+        }
+    }
+
+    println!();
+    if unresolved.is_empty() {
+        let mut state = StateManager::load(&change_dir)?;
+        state.record_main_spec_checksums()?;
+        state.set_last_action("rebase-change");
+        state.save()?;
+        println!(
+            "{}",
+            format!("✅ '{}' rebased onto current main specs", change_id)
+                .green()
+                .bold()
+        );
+    } else {
+        // Checksums stay as they were so the drift keeps being reported
+        println!(
+            "{}",
+            format!(
+                "⚠️  Review did not approve: {}. Fix the specs and run 'agentd rebase-change {}' again.",
+                unresolved.join(", "),
+                change_id
+            )
+            .yellow()
+        );
+    }
+
+    Ok(())
+}
+
+/// Warn when main specs moved since the change was planned
+///
+/// Used by impl-change and merge-change; never fails the calling command.
+pub fn warn_main_spec_drift(change_id: &str, change_dir: &Path) {
+    let Ok(state) = StateManager::load(change_dir) else {
+        return;
+    };
+    match state.drifted_main_specs() {
+        Ok(drifted) if !drifted.is_empty() => {
+            println!(
+                "{}",
+                format!(
+                    "⚠️  Main specs changed since planning: {}",
+                    drifted.join(", ")
+                )
+                .yellow()
+            );
+            println!(
+                "   Run {} to re-review the delta specs",
+                format!("agentd rebase-change {}", change_id).cyan()
+            );
+        }
+        Ok(_) => {}
+        Err(e) => println!(
+            "{}",
+            format!("⚠️  Could not check main spec drift: {}", e).yellow()
+        ),
+    }
+}
+
+/// Delta spec paths relative to the change's specs directory
+fn list_delta_specs(specs_dir: &Path) -> Vec<String> {
+    if !specs_dir.exists() {
+        return Vec::new();
+    }
+    let mut specs: Vec<String> = walkdir::WalkDir::new(specs_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "md"))
+        .filter_map(|e| {
+            e.path()
+                .strip_prefix(specs_dir)
+                .ok()
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+        })
+        .collect();
+    specs.sort();
+    specs
+}
+
+/// Latest review recorded in the change's proposal.md
+fn latest_review(change_dir: &Path) -> Result<Option<ReviewBlock>> {
+    let proposal_path = change_dir.join("proposal.md");
+    if !proposal_path.exists() {
+        return Ok(None);
+    }
+    parse_latest_review(&std::fs::read_to_string(proposal_path)?)
+}
+
+/// Verdict a review_spec run recorded as review `iteration`
+///
+/// A missing review, one from another iteration, or an unknown status counts
+/// as no verdict.
+fn parse_spec_review(review: Option<&ReviewBlock>, iteration: u32) -> SpecReview {
+    match review {
+        Some(review) if review.iteration == iteration => {
+            match review.status.to_lowercase().as_str() {
+                "approved" => SpecReview::Approved,
+                "needs_revision" => SpecReview::NeedsRevision,
+                "rejected" => SpecReview::Rejected,
+                _ => SpecReview::Unknown,
+            }
+        }
+        _ => SpecReview::Unknown,
+    }
+}
+
+/// Record LLM usage to StateManager
+#[allow(clippy::too_many_arguments)]
+fn record_usage(
+    change_id: &str,
+    project_root: &Path,
+    step: &str,
+    model: &str,
+    usage: &UsageMetrics,
+    config: &AgentdConfig,
+    complexity: Complexity,
+    provider: &str,
+) {
+    let change_dir = project_root.join("agentd/changes").join(change_id);
+
+    if let Ok(mut manager) = StateManager::load(&change_dir) {
+        let (cost_in, cost_out) = match provider {
+            "codex" => {
+                let m = config.codex.select_model(complexity);
+                (m.cost_per_1m_input, m.cost_per_1m_output)
+            }
+            _ => {
+                let m = config.gemini.select_model(complexity);
+                (m.cost_per_1m_input, m.cost_per_1m_output)
+            }
+        };
+        manager.record_llm_call(
+            step,
+            Some(model.to_string()),
+            usage.tokens_in,
+            usage.tokens_out,
+            usage.duration_ms,
+            cost_in,
+            cost_out,
+        );
+        let _ = manager.save();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn review(status: &str, iteration: u32) -> ReviewBlock {
+        ReviewBlock {
+            status: status.to_string(),
+            iteration,
+            reviewer: "codex".to_string(),
+            content: "The spec cannot be approved as written.".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_spec_review() {
+        assert_eq!(
+            parse_spec_review(Some(&review("approved", 3)), 3),
+            SpecReview::Approved
+        );
+        assert_eq!(
+            parse_spec_review(Some(&review("needs_revision", 3)), 3),
+            SpecReview::NeedsRevision
+        );
+        assert_eq!(
+            parse_spec_review(Some(&review("REJECTED", 3)), 3),
+            SpecReview::Rejected
+        );
+
+        // Unknown statuses, stale reviews and missing reviews are unresolved
+        assert_eq!(
+            parse_spec_review(Some(&review("not approved", 3)), 3),
+            SpecReview::Unknown
+        );
+        assert_eq!(
+            parse_spec_review(Some(&review("approved", 2)), 3),
+            SpecReview::Unknown
+        );
+        assert_eq!(parse_spec_review(None, 1), SpecReview::Unknown);
+    }
+
+    #[test]
+    fn test_latest_review_reads_proposal() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let change_dir = temp_dir.path();
+        assert!(latest_review(change_dir).unwrap().is_none());
+
+        std::fs::write(
+            change_dir.join("proposal.md"),
+            "# Change\n\n<review status=\"approved\" iteration=\"1\" reviewer=\"codex\">\nOK\n</review>\n\n<review status=\"needs_revision\" iteration=\"2\" reviewer=\"codex\">\nNot approved yet\n</review>\n",
+        )
+        .unwrap();
+        let latest = latest_review(change_dir).unwrap().unwrap();
+        assert_eq!(latest.iteration, 2);
+        assert_eq!(
+            parse_spec_review(Some(&latest), 2),
+            SpecReview::NeedsRevision
+        );
+    }
+}
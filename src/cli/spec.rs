@@ -1,9 +1,11 @@
 //! Spec CLI commands
 
 use crate::services::file_service;
+use crate::services::{history_service, spec_diff_service, spec_history_service};
 use crate::services::spec_service::{self, CreateSpecInput, RequirementData, ScenarioData};
 use crate::Result;
 use clap::Subcommand;
+use colored::Colorize;
 use std::env;
 use std::path::PathBuf;
 
@@ -27,6 +29,30 @@ pub enum SpecCommands {
         #[arg(long)]
         json_file: PathBuf,
     },
+
+    /// Show how a change's specs differ from the main specs, requirement by
+    /// requirement
+    Diff {
+        /// Change ID
+        change_id: String,
+
+        /// Only diff this spec (e.g. "auth" or "auth.md")
+        spec: Option<String>,
+
+        /// Output format: text or json
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
+
+    /// List the merged changes that altered a main spec
+    History {
+        /// Spec path under agentd/specs (e.g. "auth" or "auth.md")
+        spec: String,
+
+        /// Show the diff each merge applied
+        #[arg(long)]
+        diff: bool,
+    },
 }
 
 pub fn run(cmd: SpecCommands) -> Result<()> {
@@ -117,6 +143,57 @@ pub fn run(cmd: SpecCommands) -> Result<()> {
             let result = spec_service::create_spec(input, &project_root)?;
             println!("{}", result);
         }
+
+        SpecCommands::Diff {
+            change_id,
+            spec,
+            format,
+        } => {
+            let diffs = spec_diff_service::diff_change(&change_id, spec.as_deref(), &project_root)?;
+            match format.as_str() {
+                "text" => print!("{}", spec_diff_service::render_text(&diffs, true)),
+                "json" => println!("{}", serde_json::to_string_pretty(&diffs)?),
+                other => anyhow::bail!("Unknown format '{}': use text or json", other),
+            }
+        }
+
+        SpecCommands::History { spec, diff } => {
+            let entries = spec_history_service::spec_history(&spec, &project_root)?;
+            println!("{}", format!("📜 Merge history for agentd/specs/{}", spec).cyan().bold());
+            println!();
+            if entries.is_empty() {
+                println!("{}", "   No recorded merges altered this spec.".yellow());
+                return Ok(());
+            }
+            for entry in &entries {
+                let snapshot = &entry.snapshot;
+                println!(
+                    "   {}  {:<24} {:<8} +{} -{}  ({})",
+                    snapshot.merged_at.format("%Y-%m-%d %H:%M"),
+                    snapshot.change_id.bold(),
+                    entry.file.change(),
+                    entry.stats.added,
+                    entry.stats.removed,
+                    snapshot.archive_path
+                );
+                if diff {
+                    let path = &entry.file.path;
+                    let before = spec_history_service::read_version(&snapshot.change_id, path, false, &project_root)?;
+                    let after = spec_history_service::read_version(&snapshot.change_id, path, true, &project_root)?;
+                    println!();
+                    print!(
+                        "{}",
+                        history_service::unified_diff(
+                            before.as_deref().unwrap_or(""),
+                            after.as_deref().unwrap_or(""),
+                            &format!("{} (before {})", path, snapshot.change_id),
+                            &format!("{} (after {})", path, snapshot.change_id),
+                        )
+                    );
+                    println!();
+                }
+            }
+        }
     }
     Ok(())
 }
@@ -3,6 +3,7 @@
 use crate::services::tasks_service::{self, CreateTasksInput, FileActionData, TaskData};
 use crate::Result;
 use clap::Subcommand;
+use colored::Colorize;
 use std::env;
 use std::path::PathBuf;
 
@@ -17,6 +18,16 @@ pub enum TasksCommands {
         #[arg(long)]
         json_file: PathBuf,
     },
+
+    /// Show task progress per layer
+    Status {
+        /// Change ID
+        change_id: String,
+
+        /// Output in JSON format
+        #[arg(short, long)]
+        json: bool,
+    },
 }
 
 pub fn run(cmd: TasksCommands) -> Result<()> {
@@ -69,7 +80,75 @@ pub fn run(cmd: TasksCommands) -> Result<()> {
             let result = tasks_service::create_tasks(input, &project_root)?;
             println!("{}", result);
         }
+
+        TasksCommands::Status { change_id, json } => {
+            let progress = tasks_service::task_progress(&change_id, &project_root)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&progress)?);
+            } else {
+                print_progress(&change_id, &progress);
+            }
+        }
     }
 
     Ok(())
 }
+
+/// Print a per-layer progress table
+fn print_progress(change_id: &str, progress: &[tasks_service::LayerProgress]) {
+    println!("{}", format!("📑 Task progress for: {}", change_id).cyan().bold());
+    println!();
+    println!(
+        "   {}",
+        "Layer          Total   Done   Active  Blocked  Pending   Progress".bold()
+    );
+    println!("   {}", "─".repeat(72).bright_black());
+
+    let mut total = 0;
+    let mut completed = 0;
+    for layer in progress {
+        total += layer.total;
+        completed += layer.completed;
+        let percent = percent(layer.completed, layer.total);
+        let bar = progress_bar(percent);
+        let bar = if layer.blocked > 0 {
+            bar.red()
+        } else if layer.completed == layer.total {
+            bar.green()
+        } else {
+            bar.yellow()
+        };
+        println!(
+            "   {:<14} {:>5} {:>6} {:>8} {:>8} {:>8}   {} {:>3}%",
+            format!("{}. {}", layer.order, layer.layer),
+            layer.total,
+            layer.completed,
+            layer.in_progress,
+            layer.blocked,
+            layer.pending,
+            bar,
+            percent
+        );
+    }
+
+    println!("   {}", "─".repeat(72).bright_black());
+    println!(
+        "   {}",
+        format!(
+            "Overall: {}/{} tasks completed ({}%)",
+            completed,
+            total,
+            percent(completed, total)
+        )
+        .bold()
+    );
+}
+
+fn percent(done: u32, total: u32) -> u32 {
+    (done * 100).checked_div(total).unwrap_or(0)
+}
+
+fn progress_bar(percent: u32) -> String {
+    let filled = (percent / 10) as usize;
+    format!("{}{}", "█".repeat(filled), "░".repeat(10 - filled))
+}
@@ -0,0 +1,43 @@
+//! Trace CLI command
+//!
+//! Prints the requirement → task → file → test matrix for a change.
+
+use crate::services::trace_service;
+use crate::Result;
+use colored::Colorize;
+use std::env;
+
+/// Build and print the traceability matrix
+///
+/// `format` is one of markdown, json or mermaid.
+pub fn run(change_id: &str, format: &str, base: Option<&str>) -> Result<()> {
+    let project_root = env::current_dir()?;
+    let matrix = trace_service::build_trace(change_id, base, &project_root)?;
+
+    match format {
+        "markdown" | "md" => println!("{}", trace_service::render_markdown(&matrix)),
+        "json" => println!("{}", serde_json::to_string_pretty(&matrix)?),
+        "mermaid" => println!("{}", trace_service::render_mermaid(&matrix)?),
+        other => anyhow::bail!(
+            "Unknown format '{}': expected markdown, json or mermaid",
+            other
+        ),
+    }
+
+    if format != "json" {
+        let uncovered = matrix.uncovered().len();
+        if uncovered > 0 || !matrix.orphan_tasks.is_empty() {
+            eprintln!(
+                "{}",
+                format!(
+                    "⚠️  {} requirement(s) not fully covered, {} orphan task(s)",
+                    uncovered,
+                    matrix.orphan_tasks.len()
+                )
+                .yellow()
+            );
+        }
+    }
+
+    Ok(())
+}
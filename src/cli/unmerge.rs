@@ -0,0 +1,43 @@
+//! Unmerge CLI command
+//!
+//! Reverts a merged change's edits to agentd/specs and moves the change from
+//! the archive back to agentd/changes.
+
+use crate::services::spec_history_service;
+use crate::Result;
+use colored::Colorize;
+use std::env;
+
+/// Revert a merged change
+pub fn run(change_id: &str, force: bool) -> Result<()> {
+    let project_root = env::current_dir()?;
+    let report = spec_history_service::unmerge(change_id, force, &project_root)?;
+
+    println!(
+        "{}",
+        format!("↩️  Unmerged change: {}", change_id).cyan().bold()
+    );
+    println!();
+    for file in &report.restored {
+        let action = match file.change() {
+            "added" => "removed",
+            _ => "restored",
+        };
+        println!("   {} {} agentd/specs/{}", "✅".green(), action, file.path);
+    }
+    if report.changelog_reverted {
+        println!(
+            "   {} removed entry from agentd/specs/CHANGELOG.md",
+            "✅".green()
+        );
+    } else {
+        println!(
+            "   {} CHANGELOG entry not removed (none recorded or edited since); check agentd/specs/CHANGELOG.md",
+            "⚠️".yellow()
+        );
+    }
+    println!();
+    println!("   Change moved back to: {}", report.change_dir.display());
+    println!("   Re-merge when ready: agentd merge-change {}", change_id);
+    Ok(())
+}
@@ -1,9 +1,10 @@
 use crate::models::{
-    Change, AgentdConfig, JsonValidationError, Severity, ValidationCounts,
+    Change, AgentdConfig, ErrorCategory, JsonValidationError, Severity, ValidationCounts,
     ValidationError, ValidationJsonOutput, ValidationOptions, ValidationResult, ValidationRules,
     frontmatter::ValidationMode,
 };
 use crate::parser::has_frontmatter;
+use crate::services::dependency_service;
 use crate::state::StateManager;
 use crate::validator::{AutoFixer, ConsistencyValidator, SchemaValidator, SpecFormatValidator, SemanticValidator};
 use crate::Result;
@@ -352,7 +353,24 @@ pub fn validate_proposal(
         let result = prd_format_validator.validate(&proposal_path);
         acc.process_result(&result, "proposal.md", "      ", options);
 
-        if result.is_valid() && schema_valid && !options.json {
+        // Prerequisite changes must exist and must not form a cycle
+        let dependency_messages = dependency_service::validate_dependencies(change_id, project_root)
+            .unwrap_or_else(|e| vec![e.to_string()]);
+        let dependency_errors: Vec<ValidationError> = dependency_messages
+            .into_iter()
+            .map(|message| {
+                ValidationError::new(
+                    message,
+                    &proposal_path,
+                    None,
+                    Severity::High,
+                    ErrorCategory::BrokenReference,
+                )
+            })
+            .collect();
+        acc.process_errors_slice(&dependency_errors, "proposal.md", "      ", options);
+
+        if result.is_valid() && schema_valid && dependency_errors.is_empty() && !options.json {
             println!("      {}", "✓ OK".green());
         }
     }
@@ -513,6 +531,15 @@ pub fn validate_proposal(
         }
     }
 
+    if staleness.has_main_spec_drift() && !options.json {
+        println!();
+        println!("{}", "⚠️  Main specs changed since planning:".yellow());
+        for spec in &staleness.drifted_main_specs {
+            println!("   • agentd/specs/{}", spec);
+        }
+        println!("   Run 'agentd rebase-change {}' to re-review the deltas", change_id);
+    }
+
     // Record validation result
     let validation_mode = if options.strict {
         ValidationMode::Strict
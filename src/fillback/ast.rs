@@ -3,6 +3,8 @@
 //! Provides tree-sitter based parsing for supported languages.
 //! Extracts modules, functions, structs, and imports from source files.
 
+use crate::fillback::datamodel::{detect_entities, Entity};
+use crate::fillback::interfaces::{detect_interfaces, Interface};
 use crate::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -18,6 +20,12 @@ pub enum SupportedLanguage {
     JavaScript,
     TypeScript,
     Go,
+    Java,
+    CSharp,
+    C,
+    Cpp,
+    Ruby,
+    Kotlin,
 }
 
 impl SupportedLanguage {
@@ -29,6 +37,12 @@ impl SupportedLanguage {
             "js" | "jsx" | "mjs" | "cjs" => Some(Self::JavaScript),
             "ts" | "tsx" => Some(Self::TypeScript),
             "go" => Some(Self::Go),
+            "java" => Some(Self::Java),
+            "cs" => Some(Self::CSharp),
+            "c" | "h" => Some(Self::C),
+            "cc" | "cpp" | "cxx" | "hh" | "hpp" | "hxx" => Some(Self::Cpp),
+            "rb" => Some(Self::Ruby),
+            "kt" | "kts" => Some(Self::Kotlin),
             _ => None,
         }
     }
@@ -41,6 +55,12 @@ impl SupportedLanguage {
             Self::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
             Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
             Self::Go => tree_sitter_go::LANGUAGE.into(),
+            Self::Java => tree_sitter_java::LANGUAGE.into(),
+            Self::CSharp => tree_sitter_c_sharp::LANGUAGE.into(),
+            Self::C => tree_sitter_c::LANGUAGE.into(),
+            Self::Cpp => tree_sitter_cpp::LANGUAGE.into(),
+            Self::Ruby => tree_sitter_ruby::LANGUAGE.into(),
+            Self::Kotlin => tree_sitter_kotlin_ng::LANGUAGE.into(),
         }
     }
 
@@ -52,6 +72,12 @@ impl SupportedLanguage {
             Self::JavaScript => "JavaScript",
             Self::TypeScript => "TypeScript",
             Self::Go => "Go",
+            Self::Java => "Java",
+            Self::CSharp => "C#",
+            Self::C => "C",
+            Self::Cpp => "C++",
+            Self::Ruby => "Ruby",
+            Self::Kotlin => "Kotlin",
         }
     }
 }
@@ -104,6 +130,31 @@ pub struct Import {
     pub is_external: bool,
 }
 
+/// A call from a function to another function, resolved by name only
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallSite {
+    /// Enclosing function making the call
+    pub caller: String,
+    /// Called function name (last path segment)
+    pub callee: String,
+    /// Qualifier before the callee (`utils` in `utils::fmt()`, `self` in `self.run()`)
+    pub receiver: Option<String>,
+    pub line: usize,
+}
+
+/// A test function or `it`/`test` block, mined into spec scenarios
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestCase {
+    /// Test function name, or the description of a Jest/Vitest block
+    pub name: String,
+    /// Enclosing `describe` blocks or pytest class
+    pub suite: Option<String>,
+    pub doc: Option<String>,
+    /// Functions called from the test body, in call order
+    pub calls: Vec<String>,
+    pub line: usize,
+}
+
 /// Parsed module information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModuleInfo {
@@ -112,6 +163,16 @@ pub struct ModuleInfo {
     pub language: SupportedLanguage,
     pub symbols: Vec<Symbol>,
     pub imports: Vec<Import>,
+    #[serde(default)]
+    pub calls: Vec<CallSite>,
+    /// HTTP routes and CLI commands declared in the module
+    #[serde(default)]
+    pub interfaces: Vec<Interface>,
+    /// ORM models and database tables declared in the module
+    #[serde(default)]
+    pub entities: Vec<Entity>,
+    #[serde(default)]
+    pub tests: Vec<TestCase>,
 }
 
 /// Parse error information
@@ -127,6 +188,9 @@ pub struct AnalysisContext {
     pub modules: Vec<ModuleInfo>,
     pub skipped_files: Vec<String>,
     pub language_counts: HashMap<String, usize>,
+    /// Entities read from SQL migrations and Prisma schemas
+    #[serde(default)]
+    pub entities: Vec<Entity>,
 }
 
 impl AnalysisContext {
@@ -135,6 +199,7 @@ impl AnalysisContext {
             modules: Vec::new(),
             skipped_files: Vec::new(),
             language_counts: HashMap::new(),
+            entities: Vec::new(),
         }
     }
 
@@ -180,6 +245,12 @@ impl AstAnalyzer {
             SupportedLanguage::JavaScript,
             SupportedLanguage::TypeScript,
             SupportedLanguage::Go,
+            SupportedLanguage::Java,
+            SupportedLanguage::CSharp,
+            SupportedLanguage::C,
+            SupportedLanguage::Cpp,
+            SupportedLanguage::Ruby,
+            SupportedLanguage::Kotlin,
         ] {
             let mut parser = Parser::new();
             parser.set_language(&lang.tree_sitter_language())?;
@@ -222,6 +293,12 @@ impl AstAnalyzer {
             .to_string();
 
         let (symbols, imports) = self.extract_symbols_and_imports(&tree, content, language);
+        let mut calls = Vec::new();
+        self.collect_calls(&tree.root_node(), content, language, None, &mut calls);
+        let interfaces = detect_interfaces(&tree.root_node(), content, language, &imports);
+        let entities = detect_entities(&tree.root_node(), content, language, &imports);
+        let mut tests = Vec::new();
+        self.collect_tests(&tree.root_node(), content, language, None, &mut tests);
 
         Ok(ModuleInfo {
             name: module_name,
@@ -229,6 +306,10 @@ impl AstAnalyzer {
             language,
             symbols,
             imports,
+            calls,
+            interfaces,
+            entities,
+            tests,
         })
     }
 
@@ -260,6 +341,21 @@ impl AstAnalyzer {
                 SupportedLanguage::Go => {
                     self.extract_go_node(&node, source, &mut symbols, &mut imports);
                 }
+                SupportedLanguage::Java => {
+                    self.extract_java_node(&node, source, &mut symbols, &mut imports);
+                }
+                SupportedLanguage::CSharp => {
+                    self.extract_csharp_node(&node, source, &mut symbols, &mut imports);
+                }
+                SupportedLanguage::C | SupportedLanguage::Cpp => {
+                    self.extract_c_node(&node, source, true, &mut symbols, &mut imports);
+                }
+                SupportedLanguage::Ruby => {
+                    self.extract_ruby_node(&node, source, &mut symbols, &mut imports);
+                }
+                SupportedLanguage::Kotlin => {
+                    self.extract_kotlin_node(&node, source, &mut symbols, &mut imports);
+                }
             }
         }
 
@@ -1094,180 +1190,1607 @@ impl AstAnalyzer {
         imports
     }
 
-    /// Get text content of a node
-    fn node_text(&self, node: &tree_sitter::Node, source: &str) -> String {
-        let start = node.start_byte();
-        let end = node.end_byte();
-        source[start..end].to_string()
-    }
-}
-
-impl Default for AstAnalyzer {
-    fn default() -> Self {
-        Self::new().expect("Failed to initialize AST analyzer")
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::path::PathBuf;
+    /// Extract symbols and imports from Java AST nodes
+    fn extract_java_node(
+        &self,
+        node: &tree_sitter::Node,
+        source: &str,
+        symbols: &mut Vec<Symbol>,
+        imports: &mut Vec<Import>,
+    ) {
+        match node.kind() {
+            "import_declaration" => {
+                let text = self.node_text(node, source);
+                let path = text
+                    .trim()
+                    .trim_start_matches("import")
+                    .trim()
+                    .trim_start_matches("static ")
+                    .trim_end_matches(';')
+                    .trim()
+                    .to_string();
+                let is_external = self.is_external_package(node, source, &path);
 
-    #[test]
-    fn test_language_from_extension() {
-        assert_eq!(
-            SupportedLanguage::from_extension("rs"),
-            Some(SupportedLanguage::Rust)
-        );
-        assert_eq!(
-            SupportedLanguage::from_extension("py"),
-            Some(SupportedLanguage::Python)
-        );
-        assert_eq!(
-            SupportedLanguage::from_extension("js"),
-            Some(SupportedLanguage::JavaScript)
-        );
-        assert_eq!(
-            SupportedLanguage::from_extension("ts"),
-            Some(SupportedLanguage::TypeScript)
-        );
-        assert_eq!(
-            SupportedLanguage::from_extension("go"),
-            Some(SupportedLanguage::Go)
-        );
-        assert_eq!(SupportedLanguage::from_extension("txt"), None);
+                imports.push(Import {
+                    path,
+                    items: vec![],
+                    is_external,
+                });
+            }
+            "class_declaration"
+            | "record_declaration"
+            | "interface_declaration"
+            | "annotation_type_declaration"
+            | "enum_declaration" => {
+                self.extract_java_type(node, source, false, symbols);
+            }
+            _ => {}
+        }
     }
 
-    #[test]
-    fn test_parse_rust_file() {
-        let mut analyzer = AstAnalyzer::new().unwrap();
-        let content = r#"
-use std::path::Path;
-use crate::models::Foo;
-
-/// A test function
-pub fn test_function(x: i32) -> String {
-    x.to_string()
-}
-
-struct TestStruct {
-    field: String,
-}
-
-pub enum TestEnum {
-    A,
-    B,
-}
-"#;
-
-        let result = analyzer.parse_file(&PathBuf::from("test.rs"), content);
-        assert!(result.is_ok());
+    /// Extract a Java type declaration and its methods and nested types
+    fn extract_java_type(
+        &self,
+        node: &tree_sitter::Node,
+        source: &str,
+        in_interface: bool,
+        symbols: &mut Vec<Symbol>,
+    ) {
+        let Some(name_node) = node.child_by_field_name("name") else {
+            return;
+        };
 
-        let module = result.unwrap();
-        assert_eq!(module.name, "test");
-        assert_eq!(module.language, SupportedLanguage::Rust);
+        let kind = match node.kind() {
+            "interface_declaration" | "annotation_type_declaration" => SymbolKind::Interface,
+            "enum_declaration" => SymbolKind::Enum,
+            _ => SymbolKind::Class,
+        };
+        let is_interface = kind == SymbolKind::Interface;
+
+        symbols.push(Symbol {
+            name: self.node_text(&name_node, source),
+            kind,
+            signature: None,
+            doc: self.extract_c_style_doc(node, source),
+            line: node.start_position().row + 1,
+            is_public: in_interface || self.has_modifier(node, source, "public"),
+        });
 
-        // Check symbols
-        let function = module
-            .symbols
-            .iter()
-            .find(|s| s.name == "test_function")
-            .expect("Should find test_function");
-        assert_eq!(function.kind, SymbolKind::Function);
-        assert!(function.is_public);
-        assert!(function.signature.as_ref().unwrap().contains("i32"));
+        let Some(body) = node.child_by_field_name("body") else {
+            return;
+        };
 
-        let struct_symbol = module
-            .symbols
-            .iter()
-            .find(|s| s.name == "TestStruct")
-            .expect("Should find TestStruct");
-        assert_eq!(struct_symbol.kind, SymbolKind::Struct);
+        // Enum methods live in a nested enum_body_declarations node
+        let mut members = Vec::new();
+        let mut cursor = body.walk();
+        for child in body.children(&mut cursor) {
+            if child.kind() == "enum_body_declarations" {
+                let mut inner_cursor = child.walk();
+                members.extend(child.children(&mut inner_cursor));
+            } else {
+                members.push(child);
+            }
+        }
 
-        let enum_symbol = module
-            .symbols
-            .iter()
-            .find(|s| s.name == "TestEnum")
-            .expect("Should find TestEnum");
-        assert_eq!(enum_symbol.kind, SymbolKind::Enum);
+        for member in members {
+            match member.kind() {
+                "method_declaration" | "constructor_declaration" => {
+                    if let Some(name_node) = member.child_by_field_name("name") {
+                        // Interface members are implicitly public
+                        let is_public = if is_interface {
+                            !self.has_modifier(&member, source, "private")
+                        } else {
+                            self.has_modifier(&member, source, "public")
+                        };
 
-        // Check imports
-        assert!(module.imports.len() >= 2);
-        let external_import = module.imports.iter().find(|i| i.path.contains("std"));
-        assert!(external_import.is_some());
+                        symbols.push(Symbol {
+                            name: self.node_text(&name_node, source),
+                            kind: SymbolKind::Function,
+                            signature: Some(self.extract_declaration_signature(&member, source)),
+                            doc: self.extract_c_style_doc(&member, source),
+                            line: member.start_position().row + 1,
+                            is_public,
+                        });
+                    }
+                }
+                "class_declaration"
+                | "record_declaration"
+                | "interface_declaration"
+                | "annotation_type_declaration"
+                | "enum_declaration" => {
+                    self.extract_java_type(&member, source, is_interface, symbols);
+                }
+                _ => {}
+            }
+        }
     }
 
-    #[test]
-    fn test_parse_python_file() {
-        let mut analyzer = AstAnalyzer::new().unwrap();
-        let content = r#"
-import os
-from typing import List
-
-def public_function(x: int) -> str:
-    """This is a docstring."""
-    return str(x)
+    /// Extract symbols and imports from C# AST nodes
+    fn extract_csharp_node(
+        &self,
+        node: &tree_sitter::Node,
+        source: &str,
+        symbols: &mut Vec<Symbol>,
+        imports: &mut Vec<Import>,
+    ) {
+        match node.kind() {
+            "using_directive" => {
+                let text = self.node_text(node, source);
+                let mut path = text
+                    .trim()
+                    .trim_start_matches("global ")
+                    .trim_start_matches("using ")
+                    .trim_start_matches("static ")
+                    .trim_end_matches(';')
+                    .trim();
+
+                // Alias directives: using Json = System.Text.Json;
+                if let Some((_, target)) = path.split_once('=') {
+                    path = target.trim();
+                }
 
-def _private_function():
-    pass
+                let path = path.to_string();
+                let is_external = self.is_external_package(node, source, &path);
 
-class TestClass:
-    """A test class."""
-    def method(self):
-        pass
-"#;
+                imports.push(Import {
+                    path,
+                    items: vec![],
+                    is_external,
+                });
+            }
+            "namespace_declaration" | "file_scoped_namespace_declaration" | "declaration_list" => {
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    self.extract_csharp_node(&child, source, symbols, imports);
+                }
+            }
+            "class_declaration"
+            | "struct_declaration"
+            | "interface_declaration"
+            | "enum_declaration"
+            | "record_declaration"
+            | "record_struct_declaration" => {
+                self.extract_csharp_type(node, source, false, symbols);
+            }
+            _ => {}
+        }
+    }
 
-        let result = analyzer.parse_file(&PathBuf::from("test.py"), content);
-        assert!(result.is_ok());
+    /// Extract a C# type declaration and its methods and nested types
+    fn extract_csharp_type(
+        &self,
+        node: &tree_sitter::Node,
+        source: &str,
+        in_interface: bool,
+        symbols: &mut Vec<Symbol>,
+    ) {
+        let Some(name_node) = node.child_by_field_name("name") else {
+            return;
+        };
 
-        let module = result.unwrap();
-        assert_eq!(module.language, SupportedLanguage::Python);
+        let kind = match node.kind() {
+            "interface_declaration" => SymbolKind::Interface,
+            "struct_declaration" | "record_struct_declaration" => SymbolKind::Struct,
+            "enum_declaration" => SymbolKind::Enum,
+            _ => SymbolKind::Class,
+        };
+        let is_interface = kind == SymbolKind::Interface;
+
+        symbols.push(Symbol {
+            name: self.node_text(&name_node, source),
+            kind,
+            signature: None,
+            doc: self.extract_csharp_doc(node, source),
+            line: node.start_position().row + 1,
+            is_public: in_interface || self.has_modifier(node, source, "public"),
+        });
 
-        let public_fn = module
-            .symbols
-            .iter()
-            .find(|s| s.name == "public_function")
-            .expect("Should find public_function");
-        assert!(public_fn.is_public);
-        assert!(public_fn.doc.is_some());
+        let Some(body) = node.child_by_field_name("body") else {
+            return;
+        };
 
-        let private_fn = module
-            .symbols
-            .iter()
-            .find(|s| s.name == "_private_function")
-            .expect("Should find _private_function");
-        assert!(!private_fn.is_public);
+        let mut cursor = body.walk();
+        for member in body.children(&mut cursor) {
+            match member.kind() {
+                "method_declaration" | "constructor_declaration" => {
+                    if let Some(name_node) = member.child_by_field_name("name") {
+                        let is_public = if is_interface {
+                            !self.has_modifier(&member, source, "private")
+                        } else {
+                            self.has_modifier(&member, source, "public")
+                        };
 
-        let class = module
-            .symbols
-            .iter()
-            .find(|s| s.name == "TestClass")
-            .expect("Should find TestClass");
-        assert_eq!(class.kind, SymbolKind::Class);
+                        symbols.push(Symbol {
+                            name: self.node_text(&name_node, source),
+                            kind: SymbolKind::Function,
+                            signature: Some(self.extract_declaration_signature(&member, source)),
+                            doc: self.extract_csharp_doc(&member, source),
+                            line: member.start_position().row + 1,
+                            is_public,
+                        });
+                    }
+                }
+                "class_declaration"
+                | "struct_declaration"
+                | "interface_declaration"
+                | "enum_declaration"
+                | "record_declaration"
+                | "record_struct_declaration" => {
+                    self.extract_csharp_type(&member, source, is_interface, symbols);
+                }
+                _ => {}
+            }
+        }
     }
 
-    #[test]
-    fn test_parse_javascript_file() {
-        let mut analyzer = AstAnalyzer::new().unwrap();
-        let content = r#"
-import { something } from './module';
-import external from 'external-package';
-
-function regularFunction(x) {
-    return x;
-}
-
-const arrowFunction = (y) => y * 2;
+    /// Extract C# XML doc comment, dropping the markup tags
+    fn extract_csharp_doc(&self, node: &tree_sitter::Node, source: &str) -> Option<String> {
+        let doc = self.extract_c_style_doc(node, source)?;
+
+        let mut text = String::new();
+        let mut in_tag = false;
+        for c in doc.chars() {
+            match c {
+                '<' => in_tag = true,
+                '>' if in_tag => in_tag = false,
+                _ if !in_tag => text.push(c),
+                _ => {}
+            }
+        }
 
-class TestClass {
-    constructor() {}
-}
-"#;
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.is_empty() {
+            None
+        } else {
+            Some(text)
+        }
+    }
 
-        let result = analyzer.parse_file(&PathBuf::from("test.js"), content);
-        assert!(result.is_ok());
+    /// Extract symbols and imports from C and C++ AST nodes
+    ///
+    /// `is_public` carries the access level of the enclosing scope, so
+    /// members of a C++ class inherit the current access specifier.
+    fn extract_c_node(
+        &self,
+        node: &tree_sitter::Node,
+        source: &str,
+        is_public: bool,
+        symbols: &mut Vec<Symbol>,
+        imports: &mut Vec<Import>,
+    ) {
+        match node.kind() {
+            "preproc_include" => {
+                if let Some(path_node) = node.child_by_field_name("path") {
+                    let text = self.node_text(&path_node, source);
+
+                    // <header.h> comes from the system or a dependency
+                    let is_external = path_node.kind() == "system_lib_string";
+                    let path = text
+                        .trim_matches(|c| c == '<' || c == '>' || c == '"')
+                        .to_string();
 
-        let module = result.unwrap();
+                    imports.push(Import {
+                        path,
+                        items: vec![],
+                        is_external,
+                    });
+                }
+            }
+            "preproc_def" | "preproc_function_def" => {
+                if let Some(name_node) = node.child_by_field_name("name") {
+                    symbols.push(Symbol {
+                        name: self.node_text(&name_node, source),
+                        kind: SymbolKind::Constant,
+                        signature: None,
+                        doc: self.extract_c_style_doc(node, source),
+                        line: node.start_position().row + 1,
+                        is_public,
+                    });
+                }
+            }
+            // Include guards and conditional compilation wrap whole headers
+            "preproc_ifdef"
+            | "preproc_if"
+            | "preproc_else"
+            | "preproc_elif"
+            | "declaration_list"
+            | "template_declaration" => {
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    self.extract_c_node(&child, source, is_public, symbols, imports);
+                }
+            }
+            "linkage_specification" => {
+                if let Some(body) = node.child_by_field_name("body") {
+                    self.extract_c_node(&body, source, is_public, symbols, imports);
+                }
+            }
+            "namespace_definition" => {
+                if let Some(name_node) = node.child_by_field_name("name") {
+                    symbols.push(Symbol {
+                        name: self.node_text(&name_node, source),
+                        kind: SymbolKind::Module,
+                        signature: None,
+                        doc: self.extract_c_style_doc(node, source),
+                        line: node.start_position().row + 1,
+                        is_public,
+                    });
+                }
+                if let Some(body) = node.child_by_field_name("body") {
+                    self.extract_c_node(&body, source, is_public, symbols, imports);
+                }
+            }
+            "function_definition" | "declaration" | "field_declaration" => {
+                // Only declarations with a function declarator are functions;
+                // plain variables and fields are skipped
+                if let Some(name) = self.c_function_name(node, source) {
+                    // `static` only means file-local outside of a class body
+                    let in_class = node
+                        .parent()
+                        .is_some_and(|p| p.kind() == "field_declaration_list");
+                    let is_local = !in_class && self.has_c_storage_class(node, source, "static");
+
+                    symbols.push(Symbol {
+                        name,
+                        kind: SymbolKind::Function,
+                        signature: Some(self.extract_declaration_signature(node, source)),
+                        doc: self.extract_c_style_doc(node, source),
+                        line: node.start_position().row + 1,
+                        is_public: is_public && !is_local,
+                    });
+                }
+            }
+            "struct_specifier" | "union_specifier" | "class_specifier" | "enum_specifier" => {
+                if let Some(name_node) = node.child_by_field_name("name") {
+                    self.extract_c_type(
+                        node,
+                        self.node_text(&name_node, source),
+                        source,
+                        is_public,
+                        symbols,
+                        imports,
+                    );
+                }
+            }
+            "type_definition" => {
+                let Some(declarator) = node.child_by_field_name("declarator") else {
+                    return;
+                };
+                let name = self.node_text(&declarator, source);
+
+                match node.child_by_field_name("type") {
+                    Some(type_node) if type_node.child_by_field_name("body").is_some() => {
+                        self.extract_c_type(&type_node, name, source, is_public, symbols, imports);
+                    }
+                    _ => {
+                        symbols.push(Symbol {
+                            name,
+                            kind: SymbolKind::Type,
+                            signature: None,
+                            doc: self.extract_c_style_doc(node, source),
+                            line: node.start_position().row + 1,
+                            is_public,
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Extract a C struct/union/enum or C++ class with its member functions
+    fn extract_c_type(
+        &self,
+        node: &tree_sitter::Node,
+        name: String,
+        source: &str,
+        is_public: bool,
+        symbols: &mut Vec<Symbol>,
+        imports: &mut Vec<Import>,
+    ) {
+        // Forward declarations (struct foo;) have no body
+        let Some(body) = node.child_by_field_name("body") else {
+            return;
+        };
+
+        let kind = match node.kind() {
+            "class_specifier" => SymbolKind::Class,
+            "enum_specifier" => SymbolKind::Enum,
+            _ => SymbolKind::Struct,
+        };
+
+        // The doc comment sits above the enclosing typedef, if any
+        let doc_node = match node.parent() {
+            Some(parent) if parent.kind() == "type_definition" => parent,
+            _ => *node,
+        };
+
+        symbols.push(Symbol {
+            name,
+            kind: kind.clone(),
+            signature: None,
+            doc: self.extract_c_style_doc(&doc_node, source),
+            line: doc_node.start_position().row + 1,
+            is_public,
+        });
+
+        if kind == SymbolKind::Enum {
+            return;
+        }
+
+        // Class members default to private, struct members to public
+        let mut member_public = is_public && kind != SymbolKind::Class;
+        let mut cursor = body.walk();
+        for member in body.children(&mut cursor) {
+            if member.kind() == "access_specifier" {
+                member_public = is_public && self.node_text(&member, source).trim() == "public";
+            } else {
+                self.extract_c_node(&member, source, member_public, symbols, imports);
+            }
+        }
+    }
+
+    /// Name of the function declared by a C/C++ declaration, if it declares one
+    fn c_function_name(&self, node: &tree_sitter::Node, source: &str) -> Option<String> {
+        let mut declarator = node.child_by_field_name("declarator")?;
+
+        // Unwrap pointer/reference declarators: char *name(...)
+        while declarator.kind() != "function_declarator" {
+            declarator = declarator.child_by_field_name("declarator")?;
+        }
+
+        // Function pointers (int (*cb)(int)) are fields, not functions
+        let name_node = declarator.child_by_field_name("declarator")?;
+        if name_node.kind() == "parenthesized_declarator" {
+            return None;
+        }
+        Some(self.node_text(&name_node, source))
+    }
+
+    /// Check for a C storage class specifier such as `static` or `extern`
+    fn has_c_storage_class(&self, node: &tree_sitter::Node, source: &str, keyword: &str) -> bool {
+        let mut cursor = node.walk();
+        let result = node.children(&mut cursor).any(|c| {
+            c.kind() == "storage_class_specifier" && self.node_text(&c, source) == keyword
+        });
+        result
+    }
+
+    /// Extract symbols and imports from Ruby AST nodes
+    fn extract_ruby_node(
+        &self,
+        node: &tree_sitter::Node,
+        source: &str,
+        symbols: &mut Vec<Symbol>,
+        imports: &mut Vec<Import>,
+    ) {
+        match node.kind() {
+            "call" => {
+                if let Some(import) = self.extract_ruby_require(node, source) {
+                    imports.push(import);
+                }
+            }
+            "class" | "module" => {
+                self.extract_ruby_scope(node, source, symbols, imports);
+            }
+            "method" | "singleton_method" => {
+                self.extract_ruby_method(node, source, true, symbols);
+            }
+            "assignment" => {
+                self.extract_ruby_constant(node, source, symbols);
+            }
+            _ => {}
+        }
+    }
+
+    /// Extract a Ruby class or module along with its methods
+    fn extract_ruby_scope(
+        &self,
+        node: &tree_sitter::Node,
+        source: &str,
+        symbols: &mut Vec<Symbol>,
+        imports: &mut Vec<Import>,
+    ) {
+        let Some(name_node) = node.child_by_field_name("name") else {
+            return;
+        };
+
+        symbols.push(Symbol {
+            name: self.node_text(&name_node, source),
+            kind: if node.kind() == "module" {
+                SymbolKind::Module
+            } else {
+                SymbolKind::Class
+            },
+            signature: None,
+            doc: self.extract_line_doc(node, source, "#"),
+            line: node.start_position().row + 1,
+            is_public: true,
+        });
+
+        let Some(body) = node.child_by_field_name("body") else {
+            return;
+        };
+
+        // A bare `private`/`protected` applies to every method defined after it
+        let mut is_public = true;
+        let mut cursor = body.walk();
+        for child in body.children(&mut cursor) {
+            match child.kind() {
+                "identifier" => match self.node_text(&child, source).as_str() {
+                    "private" | "protected" => is_public = false,
+                    "public" => is_public = true,
+                    _ => {}
+                },
+                "method" => {
+                    self.extract_ruby_method(&child, source, is_public, symbols);
+                }
+                // Class methods are unaffected by `private`
+                "singleton_method" => {
+                    self.extract_ruby_method(&child, source, true, symbols);
+                }
+                // private def helper ... end
+                "call" => {
+                    let visibility = child
+                        .child_by_field_name("method")
+                        .map(|m| self.node_text(&m, source))
+                        .unwrap_or_default();
+                    let args = child.child_by_field_name("arguments");
+
+                    match (visibility.as_str(), args) {
+                        ("private" | "protected" | "public", Some(args)) => {
+                            let mut args_cursor = args.walk();
+                            for arg in args.children(&mut args_cursor) {
+                                if arg.kind() == "method" {
+                                    let public = visibility == "public";
+                                    self.extract_ruby_method(&arg, source, public, symbols);
+                                }
+                            }
+                        }
+                        _ => self.extract_ruby_node(&child, source, symbols, imports),
+                    }
+                }
+                _ => self.extract_ruby_node(&child, source, symbols, imports),
+            }
+        }
+    }
+
+    /// Extract a Ruby method definition
+    fn extract_ruby_method(
+        &self,
+        node: &tree_sitter::Node,
+        source: &str,
+        is_public: bool,
+        symbols: &mut Vec<Symbol>,
+    ) {
+        let Some(name_node) = node.child_by_field_name("name") else {
+            return;
+        };
+        let name = self.node_text(&name_node, source);
+
+        let mut signature = String::from("def ");
+        if node.kind() == "singleton_method" {
+            signature.push_str("self.");
+        }
+        signature.push_str(&name);
+        if let Some(params) = node.child_by_field_name("parameters") {
+            let params = self.node_text(&params, source);
+            if params.starts_with('(') {
+                signature.push_str(&params);
+            } else {
+                signature.push_str(&format!("({})", params));
+            }
+        }
+
+        symbols.push(Symbol {
+            name,
+            kind: SymbolKind::Function,
+            signature: Some(signature),
+            doc: self.extract_line_doc(node, source, "#"),
+            line: node.start_position().row + 1,
+            is_public,
+        });
+    }
+
+    /// Extract a Ruby constant assignment (MAX = 10)
+    fn extract_ruby_constant(
+        &self,
+        node: &tree_sitter::Node,
+        source: &str,
+        symbols: &mut Vec<Symbol>,
+    ) {
+        if let Some(left) = node.child_by_field_name("left") {
+            if left.kind() == "constant" {
+                symbols.push(Symbol {
+                    name: self.node_text(&left, source),
+                    kind: SymbolKind::Constant,
+                    signature: None,
+                    doc: self.extract_line_doc(node, source, "#"),
+                    line: node.start_position().row + 1,
+                    is_public: true,
+                });
+            }
+        }
+    }
+
+    /// Extract a Ruby require/require_relative call
+    fn extract_ruby_require(&self, node: &tree_sitter::Node, source: &str) -> Option<Import> {
+        let method = self.node_text(&node.child_by_field_name("method")?, source);
+        if !matches!(method.as_str(), "require" | "require_relative" | "load") {
+            return None;
+        }
+
+        let args = node.child_by_field_name("arguments")?;
+        let mut cursor = args.walk();
+        let path_node = args.children(&mut cursor).find(|c| c.kind() == "string")?;
+        let path = self
+            .node_text(&path_node, source)
+            .trim_matches(|c| c == '\'' || c == '"')
+            .to_string();
+
+        // require_relative always points into the project
+        let is_external = method == "require" && !path.starts_with('.');
+
+        Some(Import {
+            path,
+            items: vec![],
+            is_external,
+        })
+    }
+
+    /// Extract symbols and imports from Kotlin AST nodes
+    fn extract_kotlin_node(
+        &self,
+        node: &tree_sitter::Node,
+        source: &str,
+        symbols: &mut Vec<Symbol>,
+        imports: &mut Vec<Import>,
+    ) {
+        match node.kind() {
+            "import" | "import_header" => {
+                let text = self.node_text(node, source);
+                let path = text.trim().trim_start_matches("import").trim();
+
+                // Aliased imports: import foo.Bar as Baz
+                let path = path.split(" as ").next().unwrap_or(path).trim().to_string();
+                let is_external = self.is_external_package(node, source, &path);
+
+                imports.push(Import {
+                    path,
+                    items: vec![],
+                    is_external,
+                });
+            }
+            "class_declaration" | "object_declaration" => {
+                self.extract_kotlin_type(node, source, false, symbols);
+            }
+            "function_declaration" => {
+                self.extract_kotlin_function(node, source, false, symbols);
+            }
+            "property_declaration" => {
+                // Only top-level vals are treated as constants
+                let mut cursor = node.walk();
+                let children: Vec<_> = node.children(&mut cursor).collect();
+                let is_val = children.iter().any(|c| c.kind() == "val");
+                let name = children
+                    .iter()
+                    .find(|c| c.kind() == "variable_declaration")
+                    .and_then(|decl| self.kotlin_name(decl, source));
+
+                if let (true, Some(name)) = (is_val, name) {
+                    symbols.push(Symbol {
+                        name,
+                        kind: SymbolKind::Constant,
+                        signature: None,
+                        doc: self.extract_c_style_doc(node, source),
+                        line: node.start_position().row + 1,
+                        is_public: self.is_kotlin_public(node, source),
+                    });
+                }
+            }
+            "type_alias" => {
+                if let Some(name) = self.kotlin_name(node, source) {
+                    symbols.push(Symbol {
+                        name,
+                        kind: SymbolKind::Type,
+                        signature: None,
+                        doc: self.extract_c_style_doc(node, source),
+                        line: node.start_position().row + 1,
+                        is_public: self.is_kotlin_public(node, source),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Extract a Kotlin class, interface, enum or object with its members
+    fn extract_kotlin_type(
+        &self,
+        node: &tree_sitter::Node,
+        source: &str,
+        in_interface: bool,
+        symbols: &mut Vec<Symbol>,
+    ) {
+        let Some(name) = self.kotlin_name(node, source) else {
+            return;
+        };
+
+        let mut cursor = node.walk();
+        let children: Vec<_> = node.children(&mut cursor).collect();
+        let kind = if children.iter().any(|c| c.kind() == "interface") {
+            SymbolKind::Interface
+        } else if children.iter().any(|c| c.kind() == "enum_class_body")
+            || self.has_modifier(node, source, "enum")
+        {
+            SymbolKind::Enum
+        } else {
+            SymbolKind::Class
+        };
+        let is_interface = kind == SymbolKind::Interface;
+
+        symbols.push(Symbol {
+            name,
+            kind,
+            signature: None,
+            doc: self.extract_c_style_doc(node, source),
+            line: node.start_position().row + 1,
+            is_public: in_interface || self.is_kotlin_public(node, source),
+        });
+
+        for body in children
+            .iter()
+            .filter(|c| c.kind() == "class_body" || c.kind() == "enum_class_body")
+        {
+            let mut body_cursor = body.walk();
+            for member in body.children(&mut body_cursor) {
+                match member.kind() {
+                    "function_declaration" => {
+                        self.extract_kotlin_function(&member, source, is_interface, symbols);
+                    }
+                    "class_declaration" | "object_declaration" => {
+                        self.extract_kotlin_type(&member, source, is_interface, symbols);
+                    }
+                    // Companion object members belong to the class itself
+                    "companion_object" => {
+                        let mut inner_cursor = member.walk();
+                        for inner in member.children(&mut inner_cursor) {
+                            if inner.kind() == "class_body" {
+                                let mut fn_cursor = inner.walk();
+                                for item in inner.children(&mut fn_cursor) {
+                                    if item.kind() == "function_declaration" {
+                                        self.extract_kotlin_function(&item, source, false, symbols);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// Extract a Kotlin function declaration
+    fn extract_kotlin_function(
+        &self,
+        node: &tree_sitter::Node,
+        source: &str,
+        in_interface: bool,
+        symbols: &mut Vec<Symbol>,
+    ) {
+        if let Some(name) = self.kotlin_name(node, source) {
+            symbols.push(Symbol {
+                name,
+                kind: SymbolKind::Function,
+                signature: Some(self.extract_declaration_signature(node, source)),
+                doc: self.extract_c_style_doc(node, source),
+                line: node.start_position().row + 1,
+                is_public: if in_interface {
+                    !self.has_modifier(node, source, "private")
+                } else {
+                    self.is_kotlin_public(node, source)
+                },
+            });
+        }
+    }
+
+    /// Get the declared name of a Kotlin node
+    fn kotlin_name(&self, node: &tree_sitter::Node, source: &str) -> Option<String> {
+        if let Some(name_node) = node.child_by_field_name("name") {
+            return Some(self.node_text(&name_node, source));
+        }
+
+        // Some declarations (type aliases, properties) expose the name as a bare identifier
+        let mut cursor = node.walk();
+        let name = node
+            .children(&mut cursor)
+            .find(|c| {
+                matches!(
+                    c.kind(),
+                    "identifier" | "simple_identifier" | "type_identifier"
+                )
+            })
+            .map(|c| self.node_text(&c, source));
+        name
+    }
+
+    /// Kotlin declarations are public unless marked otherwise
+    fn is_kotlin_public(&self, node: &tree_sitter::Node, source: &str) -> bool {
+        !["private", "internal", "protected"]
+            .iter()
+            .any(|keyword| self.has_modifier(node, source, keyword))
+    }
+
+    /// Check whether a declaration carries a modifier keyword
+    ///
+    /// Java and Kotlin group modifiers in a `modifiers` node while C#
+    /// emits one `modifier` node per keyword.
+    fn has_modifier(&self, node: &tree_sitter::Node, source: &str, keyword: &str) -> bool {
+        let mut cursor = node.walk();
+        let result = node
+            .children(&mut cursor)
+            .filter(|c| c.kind() == "modifiers" || c.kind() == "modifier")
+            .any(|c| {
+                self.node_text(&c, source)
+                    .split_whitespace()
+                    .any(|word| word == keyword)
+            });
+        result
+    }
+
+    /// Whether a qualified import (Java/C#/Kotlin) lies outside the file's own package
+    ///
+    /// Imports sharing the project root of the declared package or namespace
+    /// are treated as internal: the first two segments for reverse-domain
+    /// packages (com.example.*), otherwise the first one (MyApp.*).
+    fn is_external_package(&self, node: &tree_sitter::Node, source: &str, path: &str) -> bool {
+        let mut root = *node;
+        while let Some(parent) = root.parent() {
+            root = parent;
+        }
+
+        let mut cursor = root.walk();
+        let package = root
+            .children(&mut cursor)
+            .find(|c| {
+                matches!(
+                    c.kind(),
+                    "package_declaration"
+                        | "package_header"
+                        | "namespace_declaration"
+                        | "file_scoped_namespace_declaration"
+                )
+            })
+            .and_then(|decl| {
+                let mut decl_cursor = decl.walk();
+                let name = decl
+                    .children(&mut decl_cursor)
+                    .find(|c| {
+                        matches!(
+                            c.kind(),
+                            "scoped_identifier"
+                                | "qualified_identifier"
+                                | "qualified_name"
+                                | "identifier"
+                        )
+                    })
+                    .map(|c| self.node_text(&c, source));
+                name
+            });
+
+        let Some(package) = package else {
+            return true;
+        };
+
+        let root_segments = match package.split('.').next() {
+            Some("com" | "org" | "net" | "io" | "dev" | "edu" | "gov" | "co") => 2,
+            _ => 1,
+        };
+        let prefix = package
+            .split('.')
+            .take(root_segments)
+            .collect::<Vec<_>>()
+            .join(".");
+        !path.starts_with(&format!("{}.", prefix))
+    }
+
+    /// Extract a signature from a declaration: everything between the
+    /// modifiers and the body, with whitespace collapsed
+    fn extract_declaration_signature(&self, node: &tree_sitter::Node, source: &str) -> String {
+        let mut start = node.start_byte();
+        let mut end = node.end_byte();
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            match child.kind() {
+                "modifiers" | "modifier" | "attribute_list" | "storage_class_specifier" => {
+                    start = child.end_byte();
+                }
+                "block"
+                | "constructor_body"
+                | "compound_statement"
+                | "function_body"
+                | "arrow_expression_clause"
+                | "field_initializer_list" => {
+                    end = child.start_byte();
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        source[start..end]
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .trim_end_matches(';')
+            .trim()
+            .to_string()
+    }
+
+    /// Extract a `/** */`, `/* */` or `//` comment directly above a node
+    fn extract_c_style_doc(&self, node: &tree_sitter::Node, source: &str) -> Option<String> {
+        let before = source[..node.start_byte()].trim_end();
+        if !before.ends_with("*/") {
+            return self.extract_line_doc(node, source, "//");
+        }
+
+        // Block comments don't nest, so the last opener starts this one
+        let start = before.rfind("/*")?;
+        let doc_lines: Vec<&str> = before[start..before.len() - 2]
+            .lines()
+            .map(|line| {
+                line.trim()
+                    .trim_start_matches('/')
+                    .trim_start_matches('*')
+                    .trim()
+            })
+            .filter(|line| !line.is_empty())
+            // Stop at block tags such as @param/@return
+            .take_while(|line| !line.starts_with('@'))
+            .collect();
+
+        if doc_lines.is_empty() {
+            None
+        } else {
+            Some(doc_lines.join(" "))
+        }
+    }
+
+    /// Extract consecutive line comments (`//`, `///`, `#`) directly above a node
+    fn extract_line_doc(
+        &self,
+        node: &tree_sitter::Node,
+        source: &str,
+        marker: &str,
+    ) -> Option<String> {
+        let lines: Vec<&str> = source.lines().collect();
+        let mut doc_lines = Vec::new();
+
+        let mut row = node.start_position().row;
+        while row > 0 {
+            row -= 1;
+            let line = lines.get(row)?.trim();
+            if !line.starts_with(marker) {
+                break;
+            }
+
+            let first = marker.chars().next().unwrap_or('/');
+            doc_lines.insert(0, line.trim_start_matches(first).trim());
+        }
+
+        if doc_lines.is_empty() {
+            None
+        } else {
+            Some(doc_lines.join(" "))
+        }
+    }
+
+    /// Collect call sites, attributing each call to its enclosing function
+    ///
+    /// Calls outside any function (module-level code) are ignored.
+    fn collect_calls(
+        &self,
+        node: &tree_sitter::Node,
+        source: &str,
+        language: SupportedLanguage,
+        caller: Option<&str>,
+        calls: &mut Vec<CallSite>,
+    ) {
+        let function_name = self.function_name(node, source, language);
+        let caller = function_name.as_deref().or(caller);
+
+        if let Some(caller) = caller {
+            if let Some((callee, receiver)) = self.call_target(node, source, language) {
+                calls.push(CallSite {
+                    caller: caller.to_string(),
+                    callee,
+                    receiver,
+                    line: node.start_position().row + 1,
+                });
+            }
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.collect_calls(&child, source, language, caller, calls);
+        }
+    }
+
+    /// Name of the function or method a node defines, if any
+    fn function_name(
+        &self,
+        node: &tree_sitter::Node,
+        source: &str,
+        language: SupportedLanguage,
+    ) -> Option<String> {
+        let kinds: &[&str] = match language {
+            SupportedLanguage::Rust => &["function_item"],
+            SupportedLanguage::Python => &["function_definition"],
+            SupportedLanguage::JavaScript | SupportedLanguage::TypeScript => &[
+                "function_declaration",
+                "generator_function_declaration",
+                "method_definition",
+                "variable_declarator",
+            ],
+            SupportedLanguage::Go => &["function_declaration", "method_declaration"],
+            SupportedLanguage::Java => &["method_declaration", "constructor_declaration"],
+            SupportedLanguage::CSharp => &[
+                "method_declaration",
+                "constructor_declaration",
+                "local_function_statement",
+            ],
+            SupportedLanguage::C | SupportedLanguage::Cpp => &["function_definition"],
+            SupportedLanguage::Ruby => &["method", "singleton_method"],
+            SupportedLanguage::Kotlin => &["function_declaration"],
+        };
+        if !kinds.contains(&node.kind()) {
+            return None;
+        }
+
+        // const handler = () => {...} defines a function; other variables don't
+        if node.kind() == "variable_declarator" {
+            let value = node.child_by_field_name("value")?;
+            if !matches!(
+                value.kind(),
+                "arrow_function" | "function_expression" | "function"
+            ) {
+                return None;
+            }
+        }
+
+        match language {
+            SupportedLanguage::C | SupportedLanguage::Cpp => self.c_function_name(node, source),
+            SupportedLanguage::Kotlin => self.kotlin_name(node, source),
+            _ => node
+                .child_by_field_name("name")
+                .map(|name| self.node_text(&name, source)),
+        }
+    }
+
+    /// Callee name and receiver of a call node, if the node is a call
+    fn call_target(
+        &self,
+        node: &tree_sitter::Node,
+        source: &str,
+        language: SupportedLanguage,
+    ) -> Option<(String, Option<String>)> {
+        match (language, node.kind()) {
+            (SupportedLanguage::Java, "method_invocation") => {
+                let name = node.child_by_field_name("name")?;
+                let receiver = node.child_by_field_name("object");
+                Some((
+                    self.node_text(&name, source),
+                    receiver.map(|r| self.node_text(&r, source)),
+                ))
+            }
+            (SupportedLanguage::Ruby, "call") => {
+                let name = node.child_by_field_name("method")?;
+                let receiver = node.child_by_field_name("receiver");
+                Some((
+                    self.node_text(&name, source),
+                    receiver.map(|r| self.node_text(&r, source)),
+                ))
+            }
+            (SupportedLanguage::Python, "call")
+            | (SupportedLanguage::CSharp, "invocation_expression")
+            | (_, "call_expression") => {
+                // Kotlin call expressions have no field names; the callee comes first
+                let function = node
+                    .child_by_field_name("function")
+                    .or_else(|| node.named_child(0))?;
+                self.callee_name(&function, source)
+            }
+            _ => None,
+        }
+    }
+
+    /// Resolve the callee expression of a call to (name, receiver)
+    ///
+    /// Member and path expressions (`a.b`, `a::b`, `a->b`) yield their last
+    /// identifier as the name and the qualifier as the receiver.
+    fn callee_name(
+        &self,
+        node: &tree_sitter::Node,
+        source: &str,
+    ) -> Option<(String, Option<String>)> {
+        let kind = node.kind();
+        if matches!(
+            kind,
+            "generic_function" | "template_function" | "generic_name"
+        ) {
+            let inner = node
+                .child_by_field_name("function")
+                .or_else(|| node.child_by_field_name("name"))
+                .or_else(|| node.named_child(0))?;
+            return self.callee_name(&inner, source);
+        }
+
+        let is_name = |kind: &str| {
+            (kind.ends_with("identifier")
+                && kind != "scoped_identifier"
+                && kind != "qualified_identifier")
+                || kind == "constant"
+        };
+        if is_name(kind) {
+            return Some((self.node_text(node, source), None));
+        }
+
+        let name_node = ["name", "field", "property", "attribute"]
+            .iter()
+            .find_map(|field| node.child_by_field_name(field))
+            .or_else(|| node.named_child(node.named_child_count().checked_sub(1)?))?;
+        // C# `Util.Format<int>()` names the method through a generic_name
+        let name_node = if name_node.kind() == "generic_name" {
+            name_node.named_child(0)?
+        } else {
+            name_node
+        };
+        if !is_name(name_node.kind()) {
+            // Nested paths (a::b::c) resolve through the inner path
+            return self.callee_name(&name_node, source);
+        }
+
+        let receiver = node
+            .named_child(0)
+            .filter(|receiver| receiver.id() != name_node.id())
+            .map(|receiver| self.node_text(&receiver, source));
+        Some((self.node_text(&name_node, source), receiver))
+    }
+
+    /// Collect test functions: Rust `#[test]`, pytest `test_*` functions and
+    /// `Test*` classes, Jest/Vitest `describe`/`it` blocks and Go `TestXxx`
+    fn collect_tests(
+        &self,
+        node: &tree_sitter::Node,
+        source: &str,
+        language: SupportedLanguage,
+        suite: Option<&str>,
+        tests: &mut Vec<TestCase>,
+    ) {
+        let name = |node: &tree_sitter::Node| {
+            node.child_by_field_name("name")
+                .map(|name| self.node_text(&name, source))
+                .unwrap_or_default()
+        };
+        let test = |name, doc, body: &tree_sitter::Node, line: usize| TestCase {
+            name,
+            suite: suite.map(str::to_string),
+            doc,
+            calls: self.test_calls(body, source, language),
+            line: line + 1,
+        };
+        let line = node.start_position().row;
+
+        match (language, node.kind()) {
+            (SupportedLanguage::Rust, "function_item") => {
+                if let Some(attribute) = self.rust_test_attribute(node, source) {
+                    let doc = self.extract_rust_doc_comment(&attribute, source);
+                    tests.push(test(name(node), doc, node, line));
+                }
+                return;
+            }
+            (SupportedLanguage::Python, "class_definition") => {
+                // pytest only collects classes named Test*
+                let class = name(node);
+                if class.starts_with("Test") {
+                    if let Some(body) = node.child_by_field_name("body") {
+                        self.collect_tests(&body, source, language, Some(&class), tests);
+                    }
+                }
+                return;
+            }
+            (SupportedLanguage::Python, "function_definition") => {
+                let function = name(node);
+                if function.starts_with("test") {
+                    let doc = self.extract_python_docstring(node, source);
+                    tests.push(test(function, doc, node, line));
+                }
+                return;
+            }
+            (SupportedLanguage::JavaScript | SupportedLanguage::TypeScript, "call_expression") => {
+                let function = node
+                    .child_by_field_name("function")
+                    .map(|f| self.node_text(&f, source))
+                    .unwrap_or_default();
+                // it.only / describe.skip / test.concurrent
+                let base = function.split('.').next().unwrap_or_default();
+                let arguments = node.child_by_field_name("arguments");
+                let title = arguments
+                    .and_then(|args| args.named_child(0))
+                    .filter(|arg| matches!(arg.kind(), "string" | "template_string"))
+                    .map(|arg| {
+                        self.node_text(&arg, source)
+                            .trim_matches(['\'', '"', '`'])
+                            .to_string()
+                    });
+                match (base, title, arguments) {
+                    ("describe" | "context" | "suite", Some(title), Some(arguments)) => {
+                        let nested = match suite {
+                            Some(suite) => format!("{} {}", suite, title),
+                            None => title,
+                        };
+                        self.collect_tests(&arguments, source, language, Some(&nested), tests);
+                        return;
+                    }
+                    ("it" | "test" | "specify", Some(title), Some(arguments)) => {
+                        tests.push(test(title, None, &arguments, line));
+                        return;
+                    }
+                    _ => {}
+                }
+            }
+            (SupportedLanguage::Go, "function_declaration") => {
+                let function = name(node);
+                let is_test = function
+                    .strip_prefix("Test")
+                    .is_some_and(|rest| !rest.starts_with(|c: char| c.is_lowercase()));
+                let takes_t = node
+                    .child_by_field_name("parameters")
+                    .is_some_and(|params| self.node_text(&params, source).contains("*testing.T"));
+                if is_test && takes_t {
+                    let doc = self.extract_line_doc(node, source, "//");
+                    tests.push(test(function, doc, node, line));
+                }
+                return;
+            }
+            _ => {}
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.collect_tests(&child, source, language, suite, tests);
+        }
+    }
+
+    /// First test attribute (`#[test]`, `#[tokio::test]`, `#[rstest]`) of a
+    /// Rust function, if any
+    fn rust_test_attribute<'t>(
+        &self,
+        node: &tree_sitter::Node<'t>,
+        source: &str,
+    ) -> Option<tree_sitter::Node<'t>> {
+        let mut first = None;
+        let mut is_test = false;
+        let mut sibling = node.prev_sibling();
+        while let Some(attribute) = sibling {
+            match attribute.kind() {
+                "attribute_item" => {
+                    let text = self.node_text(&attribute, source);
+                    let path = text
+                        .trim_start_matches("#[")
+                        .split(['(', ']'])
+                        .next()
+                        .unwrap_or_default();
+                    let last = path.rsplit("::").next().unwrap_or_default().trim();
+                    is_test |= matches!(last, "test" | "rstest" | "test_case");
+                    first = Some(attribute);
+                }
+                "line_comment" | "block_comment" => {}
+                _ => break,
+            }
+            sibling = attribute.prev_sibling();
+        }
+        first.filter(|_| is_test)
+    }
+
+    /// Names of the functions called within a test, without duplicates
+    ///
+    /// Rust macro arguments are unparsed token trees, so calls inside
+    /// `assert_eq!(parse(x), ..)` are found as an identifier followed by a
+    /// parenthesized token tree.
+    fn test_calls(
+        &self,
+        node: &tree_sitter::Node,
+        source: &str,
+        language: SupportedLanguage,
+    ) -> Vec<String> {
+        let mut calls: Vec<String> = Vec::new();
+        let mut stack = vec![*node];
+        while let Some(node) = stack.pop() {
+            let callee = if node.kind() == "identifier"
+                && node.parent().is_some_and(|p| p.kind() == "token_tree")
+            {
+                node.next_sibling()
+                    .filter(|next| {
+                        next.kind() == "token_tree" && self.node_text(next, source).starts_with('(')
+                    })
+                    .map(|_| self.node_text(&node, source))
+            } else {
+                self.call_target(&node, source, language)
+                    .map(|(callee, _)| callee)
+            };
+            if let Some(callee) = callee {
+                if !calls.contains(&callee) {
+                    calls.push(callee);
+                }
+            }
+
+            // Visit children in source order
+            let mut cursor = node.walk();
+            let children: Vec<_> = node.children(&mut cursor).collect();
+            stack.extend(children.into_iter().rev());
+        }
+        calls
+    }
+
+    /// Get text content of a node
+    fn node_text(&self, node: &tree_sitter::Node, source: &str) -> String {
+        let start = node.start_byte();
+        let end = node.end_byte();
+        source[start..end].to_string()
+    }
+}
+
+impl Default for AstAnalyzer {
+    fn default() -> Self {
+        Self::new().expect("Failed to initialize AST analyzer")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_language_from_extension() {
+        assert_eq!(
+            SupportedLanguage::from_extension("rs"),
+            Some(SupportedLanguage::Rust)
+        );
+        assert_eq!(
+            SupportedLanguage::from_extension("py"),
+            Some(SupportedLanguage::Python)
+        );
+        assert_eq!(
+            SupportedLanguage::from_extension("js"),
+            Some(SupportedLanguage::JavaScript)
+        );
+        assert_eq!(
+            SupportedLanguage::from_extension("ts"),
+            Some(SupportedLanguage::TypeScript)
+        );
+        assert_eq!(
+            SupportedLanguage::from_extension("go"),
+            Some(SupportedLanguage::Go)
+        );
+        assert_eq!(
+            SupportedLanguage::from_extension("java"),
+            Some(SupportedLanguage::Java)
+        );
+        assert_eq!(
+            SupportedLanguage::from_extension("cs"),
+            Some(SupportedLanguage::CSharp)
+        );
+        assert_eq!(
+            SupportedLanguage::from_extension("h"),
+            Some(SupportedLanguage::C)
+        );
+        assert_eq!(
+            SupportedLanguage::from_extension("cpp"),
+            Some(SupportedLanguage::Cpp)
+        );
+        assert_eq!(
+            SupportedLanguage::from_extension("rb"),
+            Some(SupportedLanguage::Ruby)
+        );
+        assert_eq!(
+            SupportedLanguage::from_extension("kt"),
+            Some(SupportedLanguage::Kotlin)
+        );
+        assert_eq!(SupportedLanguage::from_extension("txt"), None);
+    }
+
+    #[test]
+    fn test_extract_calls() {
+        let mut analyzer = AstAnalyzer::new().unwrap();
+        let content = r#"
+pub fn run() {
+    helper();
+    utils::fmt::pretty(1);
+    Vec::<u8>::new();
+}
+
+fn helper() {
+    self.log("x");
+}
+
+static LOG: () = init();
+"#;
+
+        let module = analyzer.parse_file(&PathBuf::from("lib.rs"), content).unwrap();
+        let calls: Vec<_> = module
+            .calls
+            .iter()
+            .map(|c| (c.caller.as_str(), c.callee.as_str(), c.receiver.as_deref(), c.line))
+            .collect();
+        assert_eq!(
+            calls,
+            vec![
+                ("run", "helper", None, 3),
+                ("run", "pretty", Some("utils::fmt"), 4),
+                ("run", "new", Some("Vec::<u8>"), 5),
+                ("helper", "log", Some("self"), 9),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_calls_other_languages() {
+        let mut analyzer = AstAnalyzer::new().unwrap();
+        let cases = [
+            ("a.py", "def main():\n    os.path.join(a)\n", "main", "join", Some("os.path")),
+            ("a.js", "const main = () => { api.get(); };", "main", "get", Some("api")),
+            ("a.go", "package a\nfunc main() { fmt.Println(x) }", "main", "Println", Some("fmt")),
+            ("A.java", "class A { A() { Util.fmt(x); } }", "A", "fmt", Some("Util")),
+            ("A.cs", "class A { void Main() { Util.Fmt<int>(x); } }", "Main", "Fmt", Some("Util")),
+            ("a.cpp", "int main() { p->go(); }", "main", "go", Some("p")),
+            ("a.rb", "def self.main\n  Util.fmt(x)\nend\n", "main", "fmt", Some("Util")),
+            ("a.kt", "fun main() { obj.run() }", "main", "run", Some("obj")),
+        ];
+
+        for (path, content, caller, callee, receiver) in cases {
+            let module = analyzer.parse_file(&PathBuf::from(path), content).unwrap();
+            assert_eq!(module.calls.len(), 1, "{}", path);
+            let call = &module.calls[0];
+            assert_eq!(call.caller, caller, "{}", path);
+            assert_eq!(call.callee, callee, "{}", path);
+            assert_eq!(call.receiver.as_deref(), receiver, "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_parse_rust_file() {
+        let mut analyzer = AstAnalyzer::new().unwrap();
+        let content = r#"
+use std::path::Path;
+use crate::models::Foo;
+
+/// A test function
+pub fn test_function(x: i32) -> String {
+    x.to_string()
+}
+
+struct TestStruct {
+    field: String,
+}
+
+pub enum TestEnum {
+    A,
+    B,
+}
+"#;
+
+        let result = analyzer.parse_file(&PathBuf::from("test.rs"), content);
+        assert!(result.is_ok());
+
+        let module = result.unwrap();
+        assert_eq!(module.name, "test");
+        assert_eq!(module.language, SupportedLanguage::Rust);
+
+        // Check symbols
+        let function = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "test_function")
+            .expect("Should find test_function");
+        assert_eq!(function.kind, SymbolKind::Function);
+        assert!(function.is_public);
+        assert!(function.signature.as_ref().unwrap().contains("i32"));
+
+        let struct_symbol = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "TestStruct")
+            .expect("Should find TestStruct");
+        assert_eq!(struct_symbol.kind, SymbolKind::Struct);
+
+        let enum_symbol = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "TestEnum")
+            .expect("Should find TestEnum");
+        assert_eq!(enum_symbol.kind, SymbolKind::Enum);
+
+        // Check imports
+        assert!(module.imports.len() >= 2);
+        let external_import = module.imports.iter().find(|i| i.path.contains("std"));
+        assert!(external_import.is_some());
+    }
+
+    #[test]
+    fn test_parse_python_file() {
+        let mut analyzer = AstAnalyzer::new().unwrap();
+        let content = r#"
+import os
+from typing import List
+
+def public_function(x: int) -> str:
+    """This is a docstring."""
+    return str(x)
+
+def _private_function():
+    pass
+
+class TestClass:
+    """A test class."""
+    def method(self):
+        pass
+"#;
+
+        let result = analyzer.parse_file(&PathBuf::from("test.py"), content);
+        assert!(result.is_ok());
+
+        let module = result.unwrap();
+        assert_eq!(module.language, SupportedLanguage::Python);
+
+        let public_fn = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "public_function")
+            .expect("Should find public_function");
+        assert!(public_fn.is_public);
+        assert!(public_fn.doc.is_some());
+
+        let private_fn = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "_private_function")
+            .expect("Should find _private_function");
+        assert!(!private_fn.is_public);
+
+        let class = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "TestClass")
+            .expect("Should find TestClass");
+        assert_eq!(class.kind, SymbolKind::Class);
+    }
+
+    #[test]
+    fn test_parse_javascript_file() {
+        let mut analyzer = AstAnalyzer::new().unwrap();
+        let content = r#"
+import { something } from './module';
+import external from 'external-package';
+
+function regularFunction(x) {
+    return x;
+}
+
+const arrowFunction = (y) => y * 2;
+
+class TestClass {
+    constructor() {}
+}
+"#;
+
+        let result = analyzer.parse_file(&PathBuf::from("test.js"), content);
+        assert!(result.is_ok());
+
+        let module = result.unwrap();
         assert_eq!(module.language, SupportedLanguage::JavaScript);
 
         // Check function was found
@@ -1354,6 +2877,540 @@ type PublicInterface interface {
         assert_eq!(public_interface.kind, SymbolKind::Interface);
     }
 
+    #[test]
+    fn test_parse_java_file() {
+        let mut analyzer = AstAnalyzer::new().unwrap();
+        let content = r#"
+package com.example.app;
+
+import java.util.List;
+import com.example.app.model.User;
+
+/**
+ * Service for users.
+ */
+public class UserService {
+    /** Find a user by id. */
+    public User find(String id) {
+        return null;
+    }
+
+    private void helper() {
+    }
+}
+
+interface Repository {
+    void save(User user);
+}
+"#;
+
+        let result = analyzer.parse_file(&PathBuf::from("UserService.java"), content);
+        assert!(result.is_ok());
+
+        let module = result.unwrap();
+        assert_eq!(module.language, SupportedLanguage::Java);
+
+        let class = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "UserService")
+            .expect("Should find UserService");
+        assert_eq!(class.kind, SymbolKind::Class);
+        assert!(class.is_public);
+        assert_eq!(class.doc.as_deref(), Some("Service for users."));
+
+        let find = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "find")
+            .expect("Should find find method");
+        assert!(find.is_public);
+        assert_eq!(find.signature.as_deref(), Some("User find(String id)"));
+        assert_eq!(find.doc.as_deref(), Some("Find a user by id."));
+
+        let helper = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "helper")
+            .expect("Should find helper method");
+        assert!(!helper.is_public);
+
+        let repository = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "Repository")
+            .expect("Should find Repository");
+        assert_eq!(repository.kind, SymbolKind::Interface);
+        assert!(!repository.is_public);
+
+        let save = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "save")
+            .expect("Should find interface method");
+        assert!(save.is_public);
+
+        assert_eq!(module.imports.len(), 2);
+        let external = module
+            .imports
+            .iter()
+            .find(|i| i.path == "java.util.List")
+            .expect("Should find java.util.List import");
+        assert!(external.is_external);
+        let internal = module
+            .imports
+            .iter()
+            .find(|i| i.path == "com.example.app.model.User")
+            .expect("Should find internal import");
+        assert!(!internal.is_external);
+    }
+
+    #[test]
+    fn test_parse_csharp_file() {
+        let mut analyzer = AstAnalyzer::new().unwrap();
+        let content = r#"
+using System;
+using MyApp.Models;
+
+namespace MyApp.Services
+{
+    /// <summary>
+    /// Handles users.
+    /// </summary>
+    public class UserService
+    {
+        public User Find(string id) { return null; }
+        private void Helper() {}
+    }
+
+    internal interface IRepository
+    {
+        void Save(User user);
+    }
+
+    public struct Point { public int X; }
+}
+"#;
+
+        let result = analyzer.parse_file(&PathBuf::from("UserService.cs"), content);
+        assert!(result.is_ok());
+
+        let module = result.unwrap();
+        assert_eq!(module.language, SupportedLanguage::CSharp);
+
+        let class = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "UserService")
+            .expect("Should find UserService");
+        assert_eq!(class.kind, SymbolKind::Class);
+        assert!(class.is_public);
+        assert_eq!(class.doc.as_deref(), Some("Handles users."));
+
+        let find = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "Find")
+            .expect("Should find Find method");
+        assert!(find.is_public);
+        assert_eq!(find.signature.as_deref(), Some("User Find(string id)"));
+
+        let helper = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "Helper")
+            .expect("Should find Helper method");
+        assert!(!helper.is_public);
+
+        let repository = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "IRepository")
+            .expect("Should find IRepository");
+        assert_eq!(repository.kind, SymbolKind::Interface);
+        assert!(!repository.is_public);
+
+        let point = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "Point")
+            .expect("Should find Point");
+        assert_eq!(point.kind, SymbolKind::Struct);
+
+        let system = module
+            .imports
+            .iter()
+            .find(|i| i.path == "System")
+            .expect("Should find System import");
+        assert!(system.is_external);
+        let models = module
+            .imports
+            .iter()
+            .find(|i| i.path == "MyApp.Models")
+            .expect("Should find MyApp.Models import");
+        assert!(!models.is_external);
+    }
+
+    #[test]
+    fn test_parse_c_file() {
+        let mut analyzer = AstAnalyzer::new().unwrap();
+        let content = r#"
+#include <stdio.h>
+#include "util.h"
+
+#define MAX_USERS 10
+
+/* A point in space. */
+struct point {
+    int x;
+    int y;
+};
+
+typedef struct {
+    int id;
+} user_t;
+
+// Add two numbers.
+int add(int a, int b) {
+    return a + b;
+}
+
+static void helper(void) {
+}
+
+char *name_of(const user_t *user);
+"#;
+
+        let result = analyzer.parse_file(&PathBuf::from("util.c"), content);
+        assert!(result.is_ok());
+
+        let module = result.unwrap();
+        assert_eq!(module.language, SupportedLanguage::C);
+
+        let add = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "add")
+            .expect("Should find add");
+        assert_eq!(add.kind, SymbolKind::Function);
+        assert!(add.is_public);
+        assert_eq!(add.signature.as_deref(), Some("int add(int a, int b)"));
+        assert_eq!(add.doc.as_deref(), Some("Add two numbers."));
+
+        let helper = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "helper")
+            .expect("Should find helper");
+        assert!(!helper.is_public);
+
+        assert!(module.symbols.iter().any(|s| s.name == "name_of"));
+
+        let point = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "point")
+            .expect("Should find struct point");
+        assert_eq!(point.kind, SymbolKind::Struct);
+        assert_eq!(point.doc.as_deref(), Some("A point in space."));
+
+        let user = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "user_t")
+            .expect("Should find typedef user_t");
+        assert_eq!(user.kind, SymbolKind::Struct);
+
+        let max = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "MAX_USERS")
+            .expect("Should find MAX_USERS");
+        assert_eq!(max.kind, SymbolKind::Constant);
+
+        let stdio = module
+            .imports
+            .iter()
+            .find(|i| i.path == "stdio.h")
+            .expect("Should find stdio.h include");
+        assert!(stdio.is_external);
+        let util = module
+            .imports
+            .iter()
+            .find(|i| i.path == "util.h")
+            .expect("Should find util.h include");
+        assert!(!util.is_external);
+    }
+
+    #[test]
+    fn test_parse_cpp_file() {
+        let mut analyzer = AstAnalyzer::new().unwrap();
+        let content = r#"
+#include <vector>
+
+namespace geo {
+
+/// A drawable shape.
+class Shape {
+public:
+    virtual double area() const = 0;
+    int id() const { return id_; }
+private:
+    void reset();
+    int id_;
+};
+
+struct Point {
+    double length() const;
+};
+
+}
+"#;
+
+        let result = analyzer.parse_file(&PathBuf::from("shape.hpp"), content);
+        assert!(result.is_ok());
+
+        let module = result.unwrap();
+        assert_eq!(module.language, SupportedLanguage::Cpp);
+
+        let namespace = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "geo")
+            .expect("Should find namespace geo");
+        assert_eq!(namespace.kind, SymbolKind::Module);
+
+        let shape = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "Shape")
+            .expect("Should find Shape");
+        assert_eq!(shape.kind, SymbolKind::Class);
+        assert_eq!(shape.doc.as_deref(), Some("A drawable shape."));
+
+        let area = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "area")
+            .expect("Should find area");
+        assert!(area.is_public);
+
+        let id = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "id")
+            .expect("Should find id");
+        assert!(id.is_public);
+
+        let reset = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "reset")
+            .expect("Should find reset");
+        assert!(!reset.is_public);
+
+        // Struct members default to public
+        let length = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "length")
+            .expect("Should find length");
+        assert!(length.is_public);
+
+        assert!(!module.symbols.iter().any(|s| s.name == "id_"));
+        assert!(module
+            .imports
+            .iter()
+            .any(|i| i.path == "vector" && i.is_external));
+    }
+
+    #[test]
+    fn test_parse_ruby_file() {
+        let mut analyzer = AstAnalyzer::new().unwrap();
+        let content = r#"
+require "json"
+require_relative "models/user"
+
+# Account management.
+module Accounts
+  # Handles users.
+  class UserService < Base
+    MAX_USERS = 10
+
+    # Find a user.
+    def find(id)
+      nil
+    end
+
+    def self.build
+    end
+
+    private
+
+    def helper
+    end
+  end
+end
+"#;
+
+        let result = analyzer.parse_file(&PathBuf::from("user_service.rb"), content);
+        assert!(result.is_ok());
+
+        let module = result.unwrap();
+        assert_eq!(module.language, SupportedLanguage::Ruby);
+
+        let accounts = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "Accounts")
+            .expect("Should find Accounts");
+        assert_eq!(accounts.kind, SymbolKind::Module);
+
+        let class = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "UserService")
+            .expect("Should find UserService");
+        assert_eq!(class.kind, SymbolKind::Class);
+        assert_eq!(class.doc.as_deref(), Some("Handles users."));
+
+        let find = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "find")
+            .expect("Should find find");
+        assert!(find.is_public);
+        assert_eq!(find.signature.as_deref(), Some("def find(id)"));
+        assert_eq!(find.doc.as_deref(), Some("Find a user."));
+
+        let build = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "build")
+            .expect("Should find build");
+        assert!(build.is_public);
+
+        let helper = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "helper")
+            .expect("Should find helper");
+        assert!(!helper.is_public);
+
+        assert!(module
+            .symbols
+            .iter()
+            .any(|s| s.name == "MAX_USERS" && s.kind == SymbolKind::Constant));
+
+        let json = module
+            .imports
+            .iter()
+            .find(|i| i.path == "json")
+            .expect("Should find json require");
+        assert!(json.is_external);
+        let user = module
+            .imports
+            .iter()
+            .find(|i| i.path == "models/user")
+            .expect("Should find require_relative");
+        assert!(!user.is_external);
+    }
+
+    #[test]
+    fn test_parse_kotlin_file() {
+        let mut analyzer = AstAnalyzer::new().unwrap();
+        let content = r#"
+package com.example.app
+
+import kotlinx.coroutines.flow.Flow
+import com.example.app.model.User
+
+/**
+ * Service for users.
+ */
+class UserService(private val repo: Repository) {
+    /** Find a user. */
+    fun find(id: String): User? = null
+
+    private fun helper() {}
+}
+
+internal interface Repository {
+    fun save(user: User)
+}
+
+enum class Color { RED }
+
+fun topLevel(x: Int): Int = x
+"#;
+
+        let result = analyzer.parse_file(&PathBuf::from("UserService.kt"), content);
+        assert!(result.is_ok());
+
+        let module = result.unwrap();
+        assert_eq!(module.language, SupportedLanguage::Kotlin);
+
+        let class = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "UserService")
+            .expect("Should find UserService");
+        assert_eq!(class.kind, SymbolKind::Class);
+        assert!(class.is_public);
+        assert_eq!(class.doc.as_deref(), Some("Service for users."));
+
+        let find = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "find")
+            .expect("Should find find");
+        assert!(find.is_public);
+        assert_eq!(
+            find.signature.as_deref(),
+            Some("fun find(id: String): User?")
+        );
+
+        let helper = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "helper")
+            .expect("Should find helper");
+        assert!(!helper.is_public);
+
+        let repository = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "Repository")
+            .expect("Should find Repository");
+        assert_eq!(repository.kind, SymbolKind::Interface);
+        assert!(!repository.is_public);
+
+        let color = module
+            .symbols
+            .iter()
+            .find(|s| s.name == "Color")
+            .expect("Should find Color");
+        assert_eq!(color.kind, SymbolKind::Enum);
+
+        assert!(module
+            .symbols
+            .iter()
+            .any(|s| s.name == "topLevel" && s.is_public));
+
+        let flow = module
+            .imports
+            .iter()
+            .find(|i| i.path == "kotlinx.coroutines.flow.Flow")
+            .expect("Should find Flow import");
+        assert!(flow.is_external);
+        let user = module
+            .imports
+            .iter()
+            .find(|i| i.path == "com.example.app.model.User")
+            .expect("Should find User import");
+        assert!(!user.is_external);
+    }
+
     #[test]
     fn test_unsupported_extension() {
         let mut analyzer = AstAnalyzer::new().unwrap();
@@ -1399,6 +3456,10 @@ type PublicInterface interface {
                     is_external: false,
                 },
             ],
+            calls: vec![],
+            interfaces: vec![],
+            entities: vec![],
+            tests: vec![],
         });
 
         assert_eq!(context.total_symbols(), 2);
@@ -0,0 +1,181 @@
+//! Fillback Analysis Cache
+//!
+//! Persists parsed modules under `.agentd/cache`, keyed by source path and
+//! validated by a content hash, so re-running fillback only re-parses files
+//! that changed since the previous run.
+
+use crate::fillback::ast::ModuleInfo;
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Cache file name inside the cache directory
+const CACHE_FILE: &str = "fillback.json";
+
+/// Bumped when the cached `ModuleInfo` layout changes
+const CACHE_FORMAT: u32 = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    hash: String,
+    module: ModuleInfo,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    /// agentd version and cache format that wrote the cache; extractor output
+    /// may change between releases
+    version: String,
+    entries: HashMap<String, CacheEntry>,
+}
+
+/// On-disk parse cache for code fillback
+pub struct AnalysisCache {
+    path: PathBuf,
+    entries: HashMap<String, CacheEntry>,
+    hits: usize,
+}
+
+impl AnalysisCache {
+    /// Load the cache from `dir`
+    ///
+    /// A missing, unreadable or outdated cache starts empty rather than failing.
+    pub fn load(dir: &Path) -> Self {
+        let path = dir.join(CACHE_FILE);
+        let entries = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<CacheFile>(&content).ok())
+            .filter(|file| file.version == cache_version())
+            .map(|file| file.entries)
+            .unwrap_or_default();
+
+        Self {
+            path,
+            entries,
+            hits: 0,
+        }
+    }
+
+    /// Get the cached module for `key` if the file content is unchanged
+    pub fn get(&mut self, key: &str, hash: &str) -> Option<ModuleInfo> {
+        let entry = self.entries.get(key).filter(|entry| entry.hash == hash)?;
+        self.hits += 1;
+        Some(entry.module.clone())
+    }
+
+    /// Record the parse result for `key`
+    pub fn insert(&mut self, key: String, hash: String, module: ModuleInfo) {
+        self.entries.insert(key, CacheEntry { hash, module });
+    }
+
+    /// Drop entries whose source file no longer exists
+    pub fn prune(&mut self) {
+        self.entries.retain(|key, _| Path::new(key).exists());
+    }
+
+    /// Write the cache back to disk
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let file = CacheFile {
+            version: cache_version(),
+            entries: self.entries.clone(),
+        };
+        fs::write(&self.path, serde_json::to_string(&file)?)?;
+        Ok(())
+    }
+
+    /// Number of lookups served from the cache since loading
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Number of cached modules
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the cache holds no modules
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+fn cache_version() -> String {
+    format!("{}+{}", env!("CARGO_PKG_VERSION"), CACHE_FORMAT)
+}
+
+/// Hash file content for cache validation
+pub fn content_hash(content: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fillback::ast::SupportedLanguage;
+    use tempfile::TempDir;
+
+    fn module(path: &str) -> ModuleInfo {
+        ModuleInfo {
+            name: "lib".to_string(),
+            path: path.to_string(),
+            language: SupportedLanguage::Rust,
+            symbols: vec![],
+            imports: vec![],
+            calls: vec![],
+            interfaces: vec![],
+            entities: vec![],
+            tests: vec![],
+        }
+    }
+
+    #[test]
+    fn test_cache_round_trip() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("lib.rs");
+        fs::write(&source, "fn main() {}").unwrap();
+        let key = source.to_string_lossy().to_string();
+        let hash = content_hash("fn main() {}");
+
+        let cache_dir = temp.path().join(".agentd/cache");
+        let mut cache = AnalysisCache::load(&cache_dir);
+        assert!(cache.is_empty());
+        cache.insert(key.clone(), hash.clone(), module(&key));
+        cache.save().unwrap();
+
+        let mut reloaded = AnalysisCache::load(&cache_dir);
+        assert_eq!(reloaded.len(), 1);
+        assert!(reloaded.get(&key, &hash).is_some());
+        assert!(reloaded.get(&key, &content_hash("fn main() { changed }")).is_none());
+        assert_eq!(reloaded.hits(), 1);
+    }
+
+    #[test]
+    fn test_cache_prune_and_version() {
+        let temp = TempDir::new().unwrap();
+        let cache_dir = temp.path().join("cache");
+
+        let mut cache = AnalysisCache::load(&cache_dir);
+        let missing = temp.path().join("deleted.rs").to_string_lossy().to_string();
+        cache.insert(missing.clone(), content_hash(""), module(&missing));
+        cache.prune();
+        assert!(cache.is_empty());
+
+        // A cache written by another agentd version is ignored
+        fs::create_dir_all(&cache_dir).unwrap();
+        fs::write(
+            cache_dir.join(CACHE_FILE),
+            r#"{"version":"0.0.0","entries":{"a.rs":{"hash":"x","module":{"name":"a","path":"a.rs","language":"rust","symbols":[],"imports":[]}}}}"#,
+        )
+        .unwrap();
+        assert!(AnalysisCache::load(&cache_dir).is_empty());
+    }
+}
@@ -0,0 +1,576 @@
+//! Call Graph Module
+//!
+//! Resolves the per-file call sites collected by the AST analyzer into a
+//! function-level call graph across modules. Resolution is name-based and best
+//! effort: calls that cannot be attributed to exactly one analyzed function
+//! (library calls, dynamic dispatch, ambiguous names) are left out.
+
+use crate::fillback::ast::{AnalysisContext, CallSite, ModuleInfo, SymbolKind};
+use crate::fillback::graph::DependencyGraph;
+use crate::mcp::tools::mermaid;
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+/// Maximum number of edges rendered in a single diagram
+const DIAGRAM_EDGE_LIMIT: usize = 50;
+
+/// Receivers that refer to the enclosing type or module
+const SELF_RECEIVERS: &[&str] = &["self", "Self", "this", "super", "@"];
+
+/// A function or method in the call graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionNode {
+    /// Unique id in the form `module::name`
+    pub id: String,
+    pub module: String,
+    pub name: String,
+    pub is_public: bool,
+}
+
+/// A resolved call between two functions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallEdge {
+    pub caller: String,
+    pub callee: String,
+    /// Line of the first call site in the caller's file
+    pub line: usize,
+}
+
+/// Function-level call graph
+///
+/// Functions are identified by module and name, so same-named methods of
+/// different types in one module share a node.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CallGraph {
+    pub functions: Vec<FunctionNode>,
+    pub edges: Vec<CallEdge>,
+}
+
+impl CallGraph {
+    /// Build the call graph from analysis context
+    pub fn from_analysis(context: &AnalysisContext) -> Self {
+        let mut graph = Self::default();
+        let mut known: HashSet<String> = HashSet::new();
+
+        // Function symbols first, then callers the extractor did not list
+        // (private methods, nested functions)
+        for module in &context.modules {
+            for symbol in &module.symbols {
+                if symbol.kind == SymbolKind::Function {
+                    graph.add_function(&mut known, &module.name, &symbol.name, symbol.is_public);
+                }
+            }
+            for call in &module.calls {
+                graph.add_function(&mut known, &module.name, &call.caller, false);
+            }
+        }
+
+        // Type names per module, for `Type::method()` and `Type.method()`
+        let mut type_modules: HashMap<&str, Vec<&str>> = HashMap::new();
+        for module in &context.modules {
+            for symbol in &module.symbols {
+                if matches!(
+                    symbol.kind,
+                    SymbolKind::Struct
+                        | SymbolKind::Enum
+                        | SymbolKind::Interface
+                        | SymbolKind::Class
+                        | SymbolKind::Module
+                        | SymbolKind::Type
+                ) {
+                    type_modules
+                        .entry(symbol.name.as_str())
+                        .or_default()
+                        .push(module.name.as_str());
+                }
+            }
+        }
+
+        let mut seen: HashSet<(String, String)> = HashSet::new();
+        for module in &context.modules {
+            for call in &module.calls {
+                let Some(target) = graph.resolve(context, module, call, &known, &type_modules)
+                else {
+                    continue;
+                };
+                let caller = function_id(&module.name, &call.caller);
+                let callee = function_id(&target, &call.callee);
+                if seen.insert((caller.clone(), callee.clone())) {
+                    graph.edges.push(CallEdge {
+                        caller,
+                        callee,
+                        line: call.line,
+                    });
+                }
+            }
+        }
+
+        graph
+    }
+
+    fn add_function(
+        &mut self,
+        known: &mut HashSet<String>,
+        module: &str,
+        name: &str,
+        is_public: bool,
+    ) {
+        let id = function_id(module, name);
+        if known.insert(id.clone()) {
+            self.functions.push(FunctionNode {
+                id,
+                module: module.to_string(),
+                name: name.to_string(),
+                is_public,
+            });
+        }
+    }
+
+    /// Find the module defining the function a call refers to
+    fn resolve(
+        &self,
+        context: &AnalysisContext,
+        module: &ModuleInfo,
+        call: &CallSite,
+        known: &HashSet<String>,
+        type_modules: &HashMap<&str, Vec<&str>>,
+    ) -> Option<String> {
+        let defines = |module: &str| known.contains(&function_id(module, &call.callee));
+        let receiver = call.receiver.as_deref().map(last_segment);
+
+        // Bare calls and self calls prefer the current module
+        if receiver.is_none_or(|r| SELF_RECEIVERS.contains(&r)) && defines(&module.name) {
+            return Some(module.name.clone());
+        }
+
+        // Qualified calls: `utils::format()`, `Parser.parse()`
+        if let Some(receiver) = receiver.filter(|r| !SELF_RECEIVERS.contains(r)) {
+            if context.modules.iter().any(|m| m.name == receiver) && defines(receiver) {
+                return Some(receiver.to_string());
+            }
+            let owners: Vec<&str> = type_modules
+                .get(receiver)
+                .into_iter()
+                .flatten()
+                .copied()
+                .filter(|m| defines(m))
+                .collect();
+            if let [owner] = owners.as_slice() {
+                return Some(owner.to_string());
+            }
+        }
+
+        // Imported modules defining the callee
+        let imported: BTreeSet<String> = module
+            .imports
+            .iter()
+            .filter(|import| !import.is_external || import.items.contains(&call.callee))
+            .map(|import| DependencyGraph::extract_base_module(&import.path))
+            .filter(|base| defines(base))
+            .collect();
+        if imported.len() == 1 {
+            return imported.into_iter().next();
+        }
+        if !imported.is_empty() {
+            return None;
+        }
+
+        // Bare calls into a unique public function elsewhere (same package
+        // calls in Go, Java, C and friends need no import)
+        if call.receiver.is_none() {
+            let candidates: Vec<&FunctionNode> = self
+                .functions
+                .iter()
+                .filter(|f| f.is_public && f.name == call.callee && f.module != module.name)
+                .collect();
+            if let [candidate] = candidates.as_slice() {
+                return Some(candidate.module.clone());
+            }
+        }
+
+        None
+    }
+
+    /// Look up a function by id
+    pub fn function(&self, id: &str) -> Option<&FunctionNode> {
+        self.functions.iter().find(|f| f.id == id)
+    }
+
+    /// Find functions by name across all modules
+    pub fn find(&self, name: &str) -> Vec<&FunctionNode> {
+        self.functions.iter().filter(|f| f.name == name).collect()
+    }
+
+    /// Functions that call `id`
+    pub fn callers(&self, id: &str) -> Vec<&FunctionNode> {
+        self.edges
+            .iter()
+            .filter(|e| e.callee == id)
+            .filter_map(|e| self.function(&e.caller))
+            .collect()
+    }
+
+    /// Functions called by `id`
+    pub fn callees(&self, id: &str) -> Vec<&FunctionNode> {
+        self.edges
+            .iter()
+            .filter(|e| e.caller == id)
+            .filter_map(|e| self.function(&e.callee))
+            .collect()
+    }
+
+    /// All functions transitively reachable from `id`, nearest first
+    pub fn reachable_from(&self, id: &str) -> Vec<&FunctionNode> {
+        let mut visited: HashSet<&str> = HashSet::new();
+        let mut queue: VecDeque<&str> = VecDeque::from([id]);
+        let mut reachable = Vec::new();
+
+        while let Some(current) = queue.pop_front() {
+            for edge in self.edges.iter().filter(|e| e.caller == current) {
+                if visited.insert(edge.callee.as_str()) {
+                    if let Some(function) = self.function(&edge.callee) {
+                        reachable.push(function);
+                    }
+                    queue.push_back(edge.callee.as_str());
+                }
+            }
+        }
+
+        reachable
+    }
+
+    /// Whether `to` can be reached from `from` through calls
+    pub fn is_reachable(&self, from: &str, to: &str) -> bool {
+        self.reachable_from(from).iter().any(|f| f.id == to)
+    }
+
+    /// Distinct (caller module, callee module) pairs for cross-module calls
+    pub fn module_calls(&self) -> Vec<(String, String)> {
+        let mut pairs = Vec::new();
+        let mut seen = HashSet::new();
+        for (caller, callee) in self.edges.iter().filter_map(|e| self.edge_modules(e)) {
+            if caller != callee && seen.insert((caller, callee)) {
+                pairs.push((caller.to_string(), callee.to_string()));
+            }
+        }
+        pairs
+    }
+
+    fn edge_modules(&self, edge: &CallEdge) -> Option<(&str, &str)> {
+        Some((
+            self.function(&edge.caller)?.module.as_str(),
+            self.function(&edge.callee)?.module.as_str(),
+        ))
+    }
+
+    /// Edges whose caller lives in `module`
+    fn module_edges(&self, module: &str) -> Vec<&CallEdge> {
+        self.edges
+            .iter()
+            .filter(|e| {
+                self.edge_modules(e)
+                    .is_some_and(|(caller, _)| caller == module)
+            })
+            .take(DIAGRAM_EDGE_LIMIT)
+            .collect()
+    }
+
+    /// Render calls made from `module` as a Mermaid flowchart
+    ///
+    /// Returns `None` when the module makes no resolved calls.
+    pub fn to_flowchart(&self, module: &str) -> Result<Option<String>> {
+        let edges = self.module_edges(module);
+        if edges.is_empty() {
+            return Ok(None);
+        }
+
+        let mut nodes = Vec::new();
+        let mut local = Vec::new();
+        let mut added = HashSet::new();
+        for id in edges.iter().flat_map(|e| [&e.caller, &e.callee]) {
+            let Some(function) = self.function(id) else {
+                continue;
+            };
+            if !added.insert(id.as_str()) {
+                continue;
+            }
+            let node_id = mermaid_id(id);
+            if function.module == module {
+                nodes.push(json!({ "id": node_id, "label": mermaid_text(&function.name), "shape": "rounded" }));
+                local.push(node_id);
+            } else {
+                nodes.push(json!({ "id": node_id, "label": mermaid_text(&function.id), "shape": "subroutine" }));
+            }
+        }
+
+        let edges: Vec<_> = edges
+            .iter()
+            .map(|e| {
+                let external = self.edge_modules(e).is_some_and(|(from, to)| from != to);
+                json!({
+                    "from": mermaid_id(&e.caller),
+                    "to": mermaid_id(&e.callee),
+                    "style": if external { "dotted" } else { "arrow" }
+                })
+            })
+            .collect();
+
+        mermaid::call_tool(
+            "generate_mermaid_flowchart",
+            &json!({
+                "direction": "LR",
+                "nodes": nodes,
+                "edges": edges,
+                "subgraphs": [{ "id": format!("mod_{}", mermaid_id(module)), "title": mermaid_text(module), "nodes": local }]
+            }),
+        )
+        .map(Some)
+    }
+
+    /// Render calls from `module` into other modules as a Mermaid sequence diagram
+    ///
+    /// Returns `None` when the module makes no cross-module calls.
+    pub fn to_sequence_diagram(&self, module: &str) -> Result<Option<String>> {
+        let mut edges: Vec<(&CallEdge, &str)> = self
+            .module_edges(module)
+            .into_iter()
+            .filter_map(|e| match self.edge_modules(e) {
+                Some((_, to)) if to != module => Some((e, to)),
+                _ => None,
+            })
+            .collect();
+        if edges.is_empty() {
+            return Ok(None);
+        }
+        edges.sort_by_key(|(e, _)| e.line);
+
+        let participant_id = |name: &str| format!("m_{}", mermaid_id(name));
+        let mut participants =
+            vec![json!({ "id": participant_id(module), "label": mermaid_text(module) })];
+        let mut added = HashSet::from([module]);
+        for (_, to) in &edges {
+            if added.insert(to) {
+                participants.push(json!({ "id": participant_id(to), "label": mermaid_text(to) }));
+            }
+        }
+
+        let messages: Vec<_> = edges
+            .iter()
+            .filter_map(|(e, to)| {
+                let caller = self.function(&e.caller)?;
+                let callee = self.function(&e.callee)?;
+                Some(json!({
+                    "from": participant_id(module),
+                    "to": participant_id(to),
+                    "text": format!("{} calls {}", mermaid_text(&caller.name), mermaid_text(&callee.name)),
+                    "type": "solid_open"
+                }))
+            })
+            .collect();
+
+        mermaid::call_tool(
+            "generate_mermaid_sequence",
+            &json!({ "participants": participants, "messages": messages }),
+        )
+        .map(Some)
+    }
+}
+
+fn function_id(module: &str, name: &str) -> String {
+    format!("{}::{}", module, name)
+}
+
+/// Last path segment of a call receiver (`crate::utils` -> `utils`)
+fn last_segment(receiver: &str) -> &str {
+    // Drop generic arguments: `Vec::<u8>` -> `Vec`
+    let receiver = receiver.split('<').next().unwrap_or(receiver);
+    receiver
+        .trim_end_matches(':')
+        .rsplit([':', '.', '>'])
+        .next()
+        .unwrap_or(receiver)
+        .trim_start_matches('@')
+}
+
+fn mermaid_id(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn mermaid_text(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| {
+            !matches!(
+                c,
+                '(' | ')' | '[' | ']' | '{' | '}' | '<' | '>' | '|' | '"' | ';' | '#' | '\n'
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fillback::ast::{Import, SupportedLanguage, Symbol};
+
+    fn function(name: &str, is_public: bool) -> Symbol {
+        Symbol {
+            name: name.to_string(),
+            kind: SymbolKind::Function,
+            signature: None,
+            doc: None,
+            line: 1,
+            is_public,
+        }
+    }
+
+    fn call(caller: &str, callee: &str, receiver: Option<&str>, line: usize) -> CallSite {
+        CallSite {
+            caller: caller.to_string(),
+            callee: callee.to_string(),
+            receiver: receiver.map(str::to_string),
+            line,
+        }
+    }
+
+    fn create_test_context() -> AnalysisContext {
+        let mut context = AnalysisContext::new();
+
+        context.modules.push(ModuleInfo {
+            name: "main".to_string(),
+            path: "src/main.rs".to_string(),
+            language: SupportedLanguage::Rust,
+            symbols: vec![function("main", true), function("setup", false)],
+            imports: vec![Import {
+                path: "crate::config".to_string(),
+                items: vec!["load".to_string()],
+                is_external: false,
+            }],
+            calls: vec![
+                call("main", "setup", None, 3),
+                call("main", "load", None, 4),
+                call("main", "render", Some("view"), 5),
+                call("main", "println", None, 6),
+                call("setup", "init", Some("Logger"), 10),
+            ],
+            interfaces: vec![],
+            entities: vec![],
+            tests: vec![],
+        });
+
+        context.modules.push(ModuleInfo {
+            name: "config".to_string(),
+            path: "src/config.rs".to_string(),
+            language: SupportedLanguage::Rust,
+            symbols: vec![function("load", true), function("parse", false)],
+            imports: vec![],
+            calls: vec![call("load", "parse", Some("self"), 2)],
+            interfaces: vec![],
+            entities: vec![],
+            tests: vec![],
+        });
+
+        context.modules.push(ModuleInfo {
+            name: "view".to_string(),
+            path: "src/view.rs".to_string(),
+            language: SupportedLanguage::Rust,
+            symbols: vec![
+                Symbol {
+                    name: "Logger".to_string(),
+                    kind: SymbolKind::Struct,
+                    signature: None,
+                    doc: None,
+                    line: 1,
+                    is_public: true,
+                },
+                function("render", true),
+                function("init", true),
+            ],
+            imports: vec![],
+            calls: vec![],
+            interfaces: vec![],
+            entities: vec![],
+            tests: vec![],
+        });
+
+        context
+    }
+
+    #[test]
+    fn test_resolve_calls() {
+        let graph = CallGraph::from_analysis(&create_test_context());
+
+        let callees: Vec<_> = graph
+            .callees("main::main")
+            .iter()
+            .map(|f| f.id.as_str())
+            .collect();
+        assert_eq!(callees, vec!["main::setup", "config::load", "view::render"]);
+        assert_eq!(graph.callees("main::setup")[0].id, "view::init");
+        assert_eq!(graph.callees("config::load")[0].id, "config::parse");
+        // println is not an analyzed function
+        assert!(graph.find("println").is_empty());
+    }
+
+    #[test]
+    fn test_callers_and_reachability() {
+        let graph = CallGraph::from_analysis(&create_test_context());
+
+        assert_eq!(graph.callers("config::parse")[0].id, "config::load");
+        assert!(graph.callers("main::main").is_empty());
+        assert!(graph.is_reachable("main::main", "config::parse"));
+        assert!(graph.is_reachable("main::main", "view::init"));
+        assert!(!graph.is_reachable("config::load", "main::main"));
+        assert_eq!(graph.reachable_from("main::main").len(), 5);
+
+        let modules = graph.module_calls();
+        assert!(modules.contains(&("main".to_string(), "config".to_string())));
+        assert!(modules.contains(&("main".to_string(), "view".to_string())));
+        assert_eq!(modules.len(), 2);
+    }
+
+    #[test]
+    fn test_ambiguous_calls_are_skipped() {
+        let mut context = create_test_context();
+        context.modules[2].symbols.push(function("load", true));
+        let graph = CallGraph::from_analysis(&context);
+
+        // The import still pins `load` to config
+        assert_eq!(graph.callers("config::load").len(), 1);
+        assert!(graph.callers("view::load").is_empty());
+
+        // Without the import both public `load`s are candidates
+        context.modules[0].imports.clear();
+        let graph = CallGraph::from_analysis(&context);
+        assert!(graph.callers("config::load").is_empty());
+        assert!(graph.callers("view::load").is_empty());
+    }
+
+    #[test]
+    fn test_diagrams() {
+        let graph = CallGraph::from_analysis(&create_test_context());
+
+        let flowchart = graph.to_flowchart("main").unwrap().unwrap();
+        assert!(flowchart.starts_with("flowchart LR"));
+        assert!(flowchart.contains("subgraph mod_main[\"main\"]"));
+        assert!(flowchart.contains("main__main(main)"));
+        assert!(flowchart.contains("config__load[[config::load]]"));
+        assert!(flowchart.contains("main__main --> main__setup"));
+        assert!(flowchart.contains("main__main -.-> config__load"));
+
+        let sequence = graph.to_sequence_diagram("main").unwrap().unwrap();
+        assert!(sequence.starts_with("sequenceDiagram"));
+        assert!(sequence.contains("participant m_config as config"));
+        assert!(sequence.contains("m_main->>m_config: main calls load"));
+        assert!(sequence.contains("m_main->>m_view: setup calls init"));
+
+        // config only calls itself; view calls nothing
+        assert!(graph.to_sequence_diagram("config").unwrap().is_none());
+        assert!(graph.to_flowchart("view").unwrap().is_none());
+    }
+}
@@ -5,37 +5,73 @@
 //! and incremental update support.
 
 use crate::fillback::ast::{AnalysisContext, AstAnalyzer, ModuleInfo, ParseError, SupportedLanguage};
+use crate::fillback::cache::{content_hash, AnalysisCache};
+use crate::fillback::callgraph::CallGraph;
+use crate::fillback::datamodel::{is_schema_file, parse_schema_file, DataModel};
 use crate::fillback::graph::{DependencyGraph, GraphStats};
+use crate::fillback::interfaces::{self, Interface, InterfaceKind};
+use crate::fillback::scenarios::{self, MinedScenario};
 use crate::fillback::strategy::ImportStrategy;
+use crate::fillback::workspace::{Package, Workspace};
+use crate::models::{FillbackConfig, SpecFormatRules};
+use crate::services::spec_service::{
+    acceptance_heading, render_requirement, render_scenario, RequirementData, ScenarioData,
+};
 use crate::Result;
 use async_trait::async_trait;
-use colored::Colorize;
+use colored::{ColoredString, Colorize};
 use dialoguer::{Confirm, Input, MultiSelect};
 use ignore::WalkBuilder;
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Configuration for the code analysis strategy
 #[derive(Debug, Clone)]
 pub struct CodeStrategyConfig {
     /// Path to analyze (defaults to current directory)
     pub path: Option<String>,
-    /// Specific module to analyze (optional filter)
-    pub module: Option<String>,
+    /// Workspace package to analyze, by manifest or spec folder name
+    pub package: Option<String>,
     /// Force overwrite without confirmation
     pub force: bool,
     /// Output directory for specs (default: agentd/specs/)
     pub output_dir: Option<String>,
+    /// Max source files to analyze (0 = no limit)
+    pub max_files: usize,
+    /// Skip files larger than this many bytes
+    pub max_file_size: u64,
+    /// Parser threads (0 = one per available CPU)
+    pub jobs: usize,
+    /// Parse cache directory (None disables caching)
+    pub cache_dir: Option<PathBuf>,
+    /// Print analysis progress to stderr, keeping stdout for machine-readable output
+    pub stderr_progress: bool,
+}
+
+impl CodeStrategyConfig {
+    /// Apply the `[fillback]` limits from agentd/config.toml
+    pub fn with_limits(mut self, limits: &FillbackConfig) -> Self {
+        self.max_files = limits.max_files;
+        self.max_file_size = limits.max_file_size;
+        self.jobs = limits.jobs;
+        self
+    }
 }
 
 impl Default for CodeStrategyConfig {
     fn default() -> Self {
+        let limits = FillbackConfig::default();
         Self {
             path: None,
-            module: None,
+            package: None,
             force: false,
             output_dir: None,
+            max_files: limits.max_files,
+            max_file_size: limits.max_file_size,
+            jobs: limits.jobs,
+            cache_dir: None,
+            stderr_progress: false,
         }
     }
 }
@@ -60,14 +96,14 @@ impl CodeStrategy {
     }
 
     /// Scan source directory and collect files for analysis
-    fn scan_files(&self, source: &Path) -> Result<Vec<(String, String)>> {
+    ///
+    /// Directories in `exclude` (nested workspace packages) are skipped.
+    fn scan_files(&self, source: &Path, exclude: &[PathBuf]) -> Result<Vec<(String, String)>> {
         let mut files = Vec::new();
-        let max_files = 500; // Higher limit since we're using AST
-        let max_file_size = 100_000; // 100KB limit per file
+        let max_files = self.config.max_files;
+        let max_file_size = self.config.max_file_size;
 
-        let walker = WalkBuilder::new(source)
-            .standard_filters(true)
-            .build();
+        let walker = Self::walker(source, exclude);
 
         let mut skipped_count = 0;
 
@@ -81,7 +117,7 @@ impl CodeStrategy {
 
             // Check file size
             if let Ok(metadata) = fs::metadata(path) {
-                if metadata.len() > max_file_size as u64 {
+                if metadata.len() > max_file_size {
                     skipped_count += 1;
                     continue;
                 }
@@ -98,11 +134,13 @@ impl CodeStrategy {
                             .to_string();
                         files.push((relative_path, content));
 
-                        if files.len() >= max_files {
-                            println!(
-                                "{}",
-                                format!("  Reached file limit ({}). Some files were skipped.", max_files)
-                                    .yellow()
+                        if max_files > 0 && files.len() >= max_files {
+                            self.progress(
+                                format!(
+                                    "  Reached file limit ({}). Some files were skipped; raise [fillback] max_files in agentd/config.toml.",
+                                    max_files
+                                )
+                                .yellow(),
                             );
                             break;
                         }
@@ -112,48 +150,156 @@ impl CodeStrategy {
         }
 
         if skipped_count > 0 {
-            println!(
-                "{}",
-                format!("  Skipped {} files (too large)", skipped_count).bright_black()
-            );
+            self.progress(format!("  Skipped {} files (too large)", skipped_count).bright_black());
+        }
+
+        Ok(files)
+    }
+
+    /// Collect SQL migrations and Prisma schemas, ordered by path so
+    /// migrations apply in sequence
+    fn scan_schema_files(
+        &self,
+        source: &Path,
+        exclude: &[PathBuf],
+    ) -> Result<Vec<(String, String)>> {
+        let mut files = Vec::new();
+        let walker = Self::walker(source, exclude);
+
+        for entry in walker {
+            let entry = entry?;
+            let path = entry.path();
+            if !path.is_file() || !is_schema_file(path) {
+                continue;
+            }
+            if fs::metadata(path).is_ok_and(|m| m.len() > self.config.max_file_size) {
+                continue;
+            }
+            if let Ok(content) = fs::read_to_string(path) {
+                let relative_path = path
+                    .strip_prefix(source)
+                    .unwrap_or(path)
+                    .to_string_lossy()
+                    .to_string();
+                files.push((relative_path, content));
+            }
         }
 
+        files.sort();
         Ok(files)
     }
 
+    /// Walk a source tree with ignore rules, skipping `exclude` directories
+    fn walker(source: &Path, exclude: &[PathBuf]) -> ignore::Walk {
+        let exclude = exclude.to_vec();
+        WalkBuilder::new(source)
+            .standard_filters(true)
+            .filter_entry(move |entry| !exclude.iter().any(|dir| entry.path() == dir))
+            .build()
+    }
+
     /// Analyze codebase using AST parser
+    ///
+    /// With a `package` configured, only that workspace package is analyzed.
     pub fn analyze_codebase(&self, source: &Path) -> Result<(AnalysisContext, Vec<ParseError>)> {
-        let mut analyzer = AstAnalyzer::new()?;
+        match self.config.package {
+            Some(ref name) => {
+                let workspace = Workspace::detect(source)?;
+                self.analyze_package(&workspace, workspace.require(name)?)
+            }
+            None => self.analyze_tree(source, &[]),
+        }
+    }
+
+    /// Analyze one workspace package, without the packages nested in it
+    pub fn analyze_package(
+        &self,
+        workspace: &Workspace,
+        package: &Package,
+    ) -> Result<(AnalysisContext, Vec<ParseError>)> {
+        self.analyze_tree(&package.root, &workspace.nested_roots(package))
+    }
+
+    fn analyze_tree(
+        &self,
+        source: &Path,
+        exclude: &[PathBuf],
+    ) -> Result<(AnalysisContext, Vec<ParseError>)> {
         let mut context = AnalysisContext::new();
         let mut parse_errors = Vec::new();
 
-        let files = self.scan_files(source)?;
+        let files = self.scan_files(source, exclude)?;
 
         if files.is_empty() {
             anyhow::bail!("No supported source files found in: {}", source.display());
         }
 
-        println!(
-            "{}",
-            format!("  Analyzing {} files with tree-sitter...", files.len()).bright_black()
+        // Serve unchanged files from the cache, keyed by canonical path
+        let mut cache = self.config.cache_dir.as_deref().map(AnalysisCache::load);
+        let source_root = fs::canonicalize(source).unwrap_or_else(|_| source.to_path_buf());
+
+        let mut results: Vec<Option<std::result::Result<ModuleInfo, ParseError>>> =
+            Vec::with_capacity(files.len());
+        let mut pending = Vec::new();
+        for (index, (rel_path, content)) in files.iter().enumerate() {
+            let key = source_root.join(rel_path).to_string_lossy().to_string();
+            let hash = content_hash(content);
+
+            match cache.as_mut().and_then(|cache| cache.get(&key, &hash)) {
+                Some(mut module) => {
+                    module.path = source.join(rel_path).display().to_string();
+                    results.push(Some(Ok(module)));
+                }
+                None => {
+                    results.push(None);
+                    pending.push((index, key, hash));
+                }
+            }
+        }
+
+        let jobs = self.parser_threads(pending.len());
+        self.progress(
+            format!(
+                "  Analyzing {} files with tree-sitter ({} cached, {} thread{})...",
+                files.len(),
+                files.len() - pending.len(),
+                jobs,
+                if jobs == 1 { "" } else { "s" }
+            )
+            .bright_black(),
         );
 
-        for (rel_path, content) in files {
-            let full_path = source.join(&rel_path);
+        let to_parse: Vec<(&str, &str)> = pending
+            .iter()
+            .map(|(index, _, _)| (files[*index].0.as_str(), files[*index].1.as_str()))
+            .collect();
+        let parsed = self.parse_files(source, &to_parse, jobs)?;
+
+        for ((index, key, hash), result) in pending.into_iter().zip(parsed) {
+            if let (Some(cache), Ok(module)) = (cache.as_mut(), &result) {
+                cache.insert(key, hash, module.clone());
+            }
+            results[index] = Some(result);
+        }
+
+        if let Some(cache) = cache.as_mut() {
+            cache.prune();
+            if let Err(err) = cache.save() {
+                self.progress(format!("  Could not write analysis cache: {}", err).yellow());
+            }
+        }
 
-            match analyzer.parse_file(&full_path, &content) {
+        for ((rel_path, _), result) in files.iter().zip(results) {
+            let Some(result) = result else {
+                continue;
+            };
+
+            match result {
                 Ok(module) => {
                     // Update language counts
                     let lang_name = module.language.display_name().to_string();
                     *context.language_counts.entry(lang_name).or_insert(0) += 1;
 
-                    // Filter by module name if specified
-                    if let Some(ref filter) = self.config.module {
-                        if !module.name.contains(filter) {
-                            continue;
-                        }
-                    }
-
                     context.modules.push(module);
                 }
                 Err(err) => {
@@ -163,17 +309,81 @@ impl CodeStrategy {
             }
         }
 
+        for (rel_path, content) in self.scan_schema_files(source, exclude)? {
+            let path = source.join(&rel_path).display().to_string();
+            context.entities.extend(parse_schema_file(&path, &content));
+        }
+
         if context.modules.is_empty() {
-            if let Some(ref filter) = self.config.module {
-                anyhow::bail!("No modules matching '{}' found", filter);
-            } else {
-                anyhow::bail!("Failed to parse any source files");
-            }
+            anyhow::bail!("Failed to parse any source files");
         }
 
         Ok((context, parse_errors))
     }
 
+    /// Print an analysis progress line
+    fn progress(&self, line: ColoredString) {
+        if self.config.stderr_progress {
+            eprintln!("{}", line);
+        } else {
+            println!("{}", line);
+        }
+    }
+
+    /// Number of parser threads to use for `pending` files
+    fn parser_threads(&self, pending: usize) -> usize {
+        let jobs = if self.config.jobs == 0 {
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
+        } else {
+            self.config.jobs
+        };
+
+        jobs.min(pending).max(1)
+    }
+
+    /// Parse files across `jobs` threads, returning results in input order
+    ///
+    /// Each thread owns its `AstAnalyzer` since tree-sitter parsers are not shareable.
+    fn parse_files(
+        &self,
+        source: &Path,
+        files: &[(&str, &str)],
+        jobs: usize,
+    ) -> Result<Vec<std::result::Result<ModuleInfo, ParseError>>> {
+        if files.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let chunk_size = files.len().div_ceil(jobs);
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = files
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || -> Result<Vec<_>> {
+                        let mut analyzer = AstAnalyzer::new()?;
+                        Ok(chunk
+                            .iter()
+                            .map(|(rel_path, content)| {
+                                analyzer.parse_file(&source.join(rel_path), content)
+                            })
+                            .collect())
+                    })
+                })
+                .collect();
+
+            let mut results = Vec::with_capacity(files.len());
+            for handle in handles {
+                let chunk = handle
+                    .join()
+                    .map_err(|_| anyhow::anyhow!("Parser thread panicked"))??;
+                results.extend(chunk);
+            }
+            Ok(results)
+        })
+    }
+
     /// Display analysis summary
     pub fn display_summary(&self, context: &AnalysisContext, graph: &DependencyGraph) {
         let stats = GraphStats::from_graph(graph);
@@ -193,6 +403,30 @@ impl CodeStrategy {
             "  External deps:    {}",
             stats.external_dependencies.to_string().yellow()
         );
+        let interfaces = Self::interfaces(context);
+        if !interfaces.is_empty() {
+            let http = interfaces
+                .iter()
+                .filter(|(_, i)| i.kind == InterfaceKind::Http)
+                .count();
+            println!(
+                "  Interfaces:       {} HTTP, {} CLI",
+                http.to_string().green(),
+                (interfaces.len() - http).to_string().green()
+            );
+        }
+        let tests: usize = context.modules.iter().map(|m| m.tests.len()).sum();
+        if tests > 0 {
+            println!("  Tests mined:      {}", tests.to_string().green());
+        }
+        let model = DataModel::from_analysis(context);
+        if !model.is_empty() {
+            println!(
+                "  Data model:       {} entities, {} relationships",
+                model.entities.len().to_string().green(),
+                model.relationships.len().to_string().green()
+            );
+        }
 
         // Language breakdown
         if !context.language_counts.is_empty() {
@@ -404,13 +638,31 @@ impl CodeStrategy {
         fs::write(&overview_path, overview_content)?;
         created_files.push("_overview.md".to_string());
 
+        // Generate interfaces spec when routes or commands were detected
+        if let Some(interfaces_content) = self.generate_interfaces_spec(context)? {
+            fs::write(output_dir.join("_interfaces.md"), interfaces_content)?;
+            created_files.push("_interfaces.md".to_string());
+        }
+
+        // Generate data-model spec when schemas or ORM models were detected
+        if let Some(data_model_content) = self.generate_data_model_spec(context)? {
+            fs::write(output_dir.join("_data-model.md"), data_model_content)?;
+            created_files.push("_data-model.md".to_string());
+        }
+
         // Generate spec for each major module
+        let calls = CallGraph::from_analysis(context);
+        let scenarios = scenarios::mine_scenarios(context);
         for module in &context.modules {
             if module.symbols.is_empty() {
                 continue;
             }
 
-            let spec_content = self.generate_module_spec(module);
+            let module_scenarios = scenarios
+                .get(&module.name)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let spec_content = self.generate_module_spec(module, &calls, module_scenarios)?;
             let spec_name = format!("{}.md", module.name);
             let spec_path = output_dir.join(&spec_name);
             fs::write(&spec_path, spec_content)?;
@@ -510,8 +762,188 @@ impl CodeStrategy {
         content
     }
 
+    /// All detected interfaces, paired with the module declaring them
+    fn interfaces(context: &AnalysisContext) -> Vec<(&ModuleInfo, &Interface)> {
+        context
+            .modules
+            .iter()
+            .flat_map(|module| module.interfaces.iter().map(move |i| (module, i)))
+            .collect()
+    }
+
+    /// Generate the interfaces specification: one requirement per HTTP
+    /// endpoint or CLI command, plus sequence diagram skeletons
+    fn generate_interfaces_spec(&self, context: &AnalysisContext) -> Result<Option<String>> {
+        let interfaces = Self::interfaces(context);
+        if interfaces.is_empty() {
+            return Ok(None);
+        }
+        let http = interfaces
+            .iter()
+            .filter(|(_, i)| i.kind == InterfaceKind::Http)
+            .count();
+
+        let mut content = String::new();
+        content.push_str("# Specification: Interfaces\n\n");
+
+        content.push_str("## Overview\n\n");
+        content.push_str(&format!(
+            "Externally visible interfaces detected in the codebase: {} HTTP endpoints and {} CLI commands.\n\n",
+            http,
+            interfaces.len() - http
+        ));
+
+        content.push_str("## Requirements\n\n");
+        for (index, (module, interface)) in interfaces.iter().enumerate() {
+            let handler = interface
+                .handler
+                .as_deref()
+                .map(|h| format!("`{}`", h))
+                .unwrap_or_else(|| "an inline handler".to_string());
+            let mut description = match interface.kind {
+                InterfaceKind::Http => format!(
+                    "The system SHALL handle `{}` requests with {}.",
+                    interface.title(),
+                    handler
+                ),
+                InterfaceKind::Cli => format!(
+                    "The CLI SHALL provide the `{}` command, handled by {}.",
+                    interface.path, handler
+                ),
+            };
+            if let Some(doc) = &interface.doc {
+                description.push_str(&format!(" {}", doc));
+            }
+            description.push_str(&format!(
+                "\n\nDeclared with {} in `{}:{}`.",
+                interface.framework, module.path, interface.line
+            ));
+            if !interface.params.is_empty() {
+                description.push_str("\n\nParameters:\n");
+                for param in &interface.params {
+                    let ty = param
+                        .ty
+                        .as_deref()
+                        .map(|ty| format!(": `{}`", ty))
+                        .unwrap_or_default();
+                    description.push_str(&format!("\n- `{}`{} ({})", param.name, ty, param.source));
+                }
+            }
+
+            content.push_str(&render_requirement(&RequirementData {
+                id: format!("R{}", index + 1),
+                title: interface.title(),
+                description,
+                priority: "medium".to_string(),
+            }));
+        }
+
+        let entries: Vec<(&str, &Interface)> = interfaces
+            .iter()
+            .map(|(module, interface)| (module.name.as_str(), *interface))
+            .collect();
+        let diagrams = [
+            ("HTTP API", InterfaceKind::Http),
+            ("CLI", InterfaceKind::Cli),
+        ];
+        content.push_str("## Flow Diagram\n");
+        for (heading, kind) in diagrams {
+            if let Some(diagram) = interfaces::to_sequence_diagram(&entries, kind)? {
+                content.push_str(&format!(
+                    "\n### {}\n\n```mermaid\n{}```\n",
+                    heading, diagram
+                ));
+            }
+        }
+
+        Ok(Some(content))
+    }
+
+    /// Generate the data-model specification: one requirement per entity,
+    /// an ERD and the JSON Schema of all entities
+    fn generate_data_model_spec(&self, context: &AnalysisContext) -> Result<Option<String>> {
+        let model = DataModel::from_analysis(context);
+        let Some(erd) = model.to_erd()? else {
+            return Ok(None);
+        };
+
+        let mut content = String::new();
+        content.push_str("# Specification: Data Model\n\n");
+
+        content.push_str("## Overview\n\n");
+        content.push_str(&format!(
+            "Persistent data model detected in schemas and ORM models: {} entities and {} relationships.\n\n",
+            model.entities.len(),
+            model.relationships.len()
+        ));
+
+        content.push_str("## Requirements\n\n");
+        for (index, entity) in model.entities.iter().enumerate() {
+            let table = entity
+                .table
+                .as_deref()
+                .map(|table| format!(" in table `{}`", table))
+                .unwrap_or_default();
+            let mut description = format!(
+                "The system SHALL persist `{}` records{}.\n\nDeclared with {} in `{}:{}`.\n\nFields:\n",
+                entity.name, table, entity.source, entity.path, entity.line
+            );
+            for field in &entity.fields {
+                let mut notes: Vec<String> = field.key.iter().map(|k| k.to_string()).collect();
+                if field.nullable {
+                    notes.push("nullable".to_string());
+                }
+                let notes = if notes.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({})", notes.join(", "))
+                };
+                description.push_str(&format!("\n- `{}`: `{}`{}", field.name, field.ty, notes));
+            }
+            let relationships: Vec<_> = model
+                .relationships
+                .iter()
+                .filter(|rel| rel.from == entity.name)
+                .collect();
+            if !relationships.is_empty() {
+                description.push_str("\n\nRelationships:\n");
+                for rel in relationships {
+                    let via = rel
+                        .label
+                        .as_deref()
+                        .map(|label| format!(" via `{}`", label))
+                        .unwrap_or_default();
+                    description.push_str(&format!("\n- {} `{}`{}", rel.cardinality, rel.to, via));
+                }
+            }
+
+            content.push_str(&render_requirement(&RequirementData {
+                id: format!("R{}", index + 1),
+                title: entity.name.clone(),
+                description,
+                priority: "medium".to_string(),
+            }));
+        }
+
+        content.push_str(&format!(
+            "## Entity Relationships\n\n```mermaid\n{}```\n\n",
+            erd
+        ));
+
+        content.push_str("## Data Model\n\n```json\n");
+        content.push_str(&serde_json::to_string_pretty(&model.to_json_schema())?);
+        content.push_str("\n```\n");
+
+        Ok(Some(content))
+    }
+
     /// Generate module-specific specification
-    fn generate_module_spec(&self, module: &ModuleInfo) -> String {
+    fn generate_module_spec(
+        &self,
+        module: &ModuleInfo,
+        calls: &CallGraph,
+        scenarios: &[MinedScenario],
+    ) -> Result<String> {
         let mut content = String::new();
 
         content.push_str(&format!("# Specification: {}\n\n", module.name));
@@ -557,6 +989,43 @@ impl CodeStrategy {
             content.push_str("```\n");
         }
 
+        // Call flow
+        if let Some(flowchart) = calls.to_flowchart(&module.name)? {
+            content.push_str("\n## Call Flow\n\n");
+            content.push_str(&format!("```mermaid\n{}```\n", flowchart));
+
+            if let Some(sequence) = calls.to_sequence_diagram(&module.name)? {
+                content.push_str("\n### Cross-Module Calls\n\n");
+                content.push_str(&format!("```mermaid\n{}```\n", sequence));
+            }
+        }
+
+        // Scenarios mined from tests, noting the source test for traceability
+        if !scenarios.is_empty() {
+            let spec_rules = SpecFormatRules::spec_defaults();
+            content.push_str(&format!("\n## {}\n\n", acceptance_heading(&spec_rules)));
+            for scenario in scenarios {
+                content.push_str(&render_scenario(
+                    &ScenarioData {
+                        name: scenario.name.clone(),
+                        given: None,
+                        when: scenario.when.clone(),
+                        then: scenario.then.clone(),
+                    },
+                    &spec_rules,
+                ));
+                let covers = scenario
+                    .covers
+                    .as_deref()
+                    .map(|function| format!(", covers `{}`", function))
+                    .unwrap_or_default();
+                content.push_str(&format!(
+                    "Source: `{}` in `{}:{}`{}\n\n",
+                    scenario.test, scenario.path, scenario.line, covers
+                ));
+            }
+        }
+
         // Imports
         if !module.imports.is_empty() {
             content.push_str("\n## Dependencies\n\n");
@@ -570,7 +1039,110 @@ impl CodeStrategy {
             }
         }
 
-        content
+        Ok(content)
+    }
+
+    /// Analyze every package of a workspace and write one spec folder per
+    /// package, plus an overview of inter-package dependencies
+    fn execute_workspace(&self, workspace: &Workspace) -> Result<()> {
+        println!(
+            "{}",
+            format!(
+                "  Detected workspace with {} packages",
+                workspace.packages.len()
+            )
+            .cyan()
+        );
+
+        let mut analyzed = Vec::new();
+        for package in &workspace.packages {
+            println!();
+            println!(
+                "{}",
+                format!("Package: {} ({})", package.name, package.kind)
+                    .cyan()
+                    .bold()
+            );
+            match self.analyze_package(workspace, package) {
+                Ok((context, parse_errors)) => {
+                    let graph = DependencyGraph::from_analysis(&context);
+                    self.display_summary(&context, &graph);
+                    self.print_parse_errors(&parse_errors);
+                    analyzed.push((package, context, graph));
+                }
+                Err(err) => println!("{}", format!("  Skipped: {}", err).yellow()),
+            }
+        }
+        if analyzed.is_empty() {
+            anyhow::bail!(
+                "No workspace package contains supported source files: {}",
+                workspace.root.display()
+            );
+        }
+
+        println!(
+            "{}",
+            "Clarification questions are skipped for workspaces; run with --package <name> to answer them for one package."
+                .bright_black()
+        );
+
+        let output_dir = self.output_dir()?;
+        let mut existing_specs = self.check_existing_specs(&output_dir)?;
+        for (package, _, _) in &analyzed {
+            let slug = package.slug();
+            for file in self.check_existing_specs(&output_dir.join(&slug))? {
+                existing_specs.push(format!("{}/{}", slug, file));
+            }
+        }
+        if !self.confirm_overwrite(&existing_specs)? {
+            println!("{}", "Cancelled by user.".yellow());
+            return Ok(());
+        }
+
+        println!();
+        println!("{}", "Generating specifications...".cyan());
+        fs::create_dir_all(&output_dir)?;
+        fs::write(output_dir.join("_packages.md"), workspace.to_markdown()?)?;
+        let mut created_files = vec!["_packages.md".to_string()];
+        for (package, context, graph) in &analyzed {
+            let slug = package.slug();
+            let files =
+                self.generate_specs(context, graph, &output_dir.join(&slug), &HashMap::new())?;
+            created_files.extend(files.into_iter().map(|file| format!("{}/{}", slug, file)));
+        }
+
+        self.display_created_files(&output_dir, &created_files);
+        Ok(())
+    }
+
+    /// Output directory for specs (default: agentd/specs/)
+    fn output_dir(&self) -> Result<PathBuf> {
+        match self.config.output_dir {
+            Some(ref dir) => Ok(PathBuf::from(dir)),
+            None => Ok(std::env::current_dir()?.join("agentd/specs")),
+        }
+    }
+
+    /// Print the generated files
+    fn display_created_files(&self, output_dir: &Path, created_files: &[String]) {
+        println!();
+        println!("{}", "Generated Files".green().bold());
+        println!("{}", "---------------".bright_black());
+        for file in created_files {
+            println!("  {}", output_dir.join(file).display());
+        }
+
+        println!();
+        println!(
+            "{}",
+            format!(
+                "Generated {} specification files in {}",
+                created_files.len(),
+                output_dir.display()
+            )
+            .green()
+            .bold()
+        );
     }
 
     /// Print summary of skipped files with errors
@@ -607,64 +1179,64 @@ impl ImportStrategy for CodeStrategy {
             format!("Scanning codebase at: {}", source.display()).cyan()
         );
 
-        // Step 1: Analyze codebase with AST
-        let (context, parse_errors) = self.analyze_codebase(source)?;
+        // Step 1: Detect workspace packages; without --package, a monorepo
+        // gets one spec folder per package
+        let workspace = Workspace::detect(source)?;
+        let package = match self.config.package {
+            Some(ref name) => Some(workspace.require(name)?),
+            None if workspace.is_multi_package() => return self.execute_workspace(&workspace),
+            None => None,
+        };
+
+        // Step 2: Analyze codebase with AST
+        let (context, parse_errors) = match package {
+            Some(package) => {
+                println!(
+                    "{}",
+                    format!("  Package: {} ({})", package.name, package.kind).bright_black()
+                );
+                self.analyze_package(&workspace, package)?
+            }
+            None => self.analyze_codebase(source)?,
+        };
 
-        // Step 2: Build dependency graph
+        // Step 3: Build dependency graph
         let graph = DependencyGraph::from_analysis(&context);
 
-        // Step 3: Display analysis summary
+        // Step 4: Display analysis summary
         self.display_summary(&context, &graph);
 
-        // Step 4: Display dependency graph
+        // Step 5: Display dependency graph
         self.display_dependency_graph(&graph);
 
-        // Step 5: Print any parse errors
+        // Step 6: Print any parse errors
         self.print_parse_errors(&parse_errors);
 
-        // Step 6: Run interactive clarification
+        // Step 7: Run interactive clarification
         let clarifications = self.run_clarification(&context)?;
 
-        // Step 7: Determine output directory
-        let output_dir = if let Some(ref dir) = self.config.output_dir {
-            std::path::PathBuf::from(dir)
-        } else {
-            std::env::current_dir()?.join("agentd/specs")
-        };
+        // Step 8: Determine output directory
+        let mut output_dir = self.output_dir()?;
+        if let Some(package) = package {
+            output_dir.push(package.slug());
+        }
 
-        // Step 8: Check for existing specs
+        // Step 9: Check for existing specs
         let existing_specs = self.check_existing_specs(&output_dir)?;
 
-        // Step 9: Confirm overwrite if needed
+        // Step 10: Confirm overwrite if needed
         if !self.confirm_overwrite(&existing_specs)? {
             println!("{}", "Cancelled by user.".yellow());
             return Ok(());
         }
 
-        // Step 10: Generate specification files
+        // Step 11: Generate specification files
         println!();
         println!("{}", "Generating specifications...".cyan());
         let created_files = self.generate_specs(&context, &graph, &output_dir, &clarifications)?;
 
-        // Step 11: Summary
-        println!();
-        println!("{}", "Generated Files".green().bold());
-        println!("{}", "---------------".bright_black());
-        for file in &created_files {
-            println!("  {}", output_dir.join(file).display());
-        }
-
-        println!();
-        println!(
-            "{}",
-            format!(
-                "Generated {} specification files in {}",
-                created_files.len(),
-                output_dir.display()
-            )
-            .green()
-            .bold()
-        );
+        // Step 12: Summary
+        self.display_created_files(&output_dir, &created_files);
 
         Ok(())
     }
@@ -748,7 +1320,9 @@ enum InternalEnum {
         create_test_project(temp_dir.path());
 
         let strategy = CodeStrategy::new();
-        let files = strategy.scan_files(&temp_dir.path().join("src")).unwrap();
+        let files = strategy
+            .scan_files(&temp_dir.path().join("src"), &[])
+            .unwrap();
 
         assert_eq!(files.len(), 3);
         assert!(files.iter().any(|(path, _)| path.contains("main.rs")));
@@ -777,22 +1351,119 @@ enum InternalEnum {
         assert!(context.language_counts.contains_key("Rust"));
     }
 
+    #[tokio::test]
+    async fn test_execute_workspace_writes_spec_folder_per_package() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(
+            root.join("package.json"),
+            r#"{ "private": true, "workspaces": ["packages/*"] }"#,
+        )
+        .unwrap();
+        for (name, deps) in [("api", r#"{ "@acme/shared": "*" }"#), ("shared", "{}")] {
+            let dir = root.join("packages").join(name);
+            fs::create_dir_all(dir.join("src")).unwrap();
+            fs::write(
+                dir.join("package.json"),
+                format!(
+                    r#"{{ "name": "@acme/{}", "dependencies": {} }}"#,
+                    name, deps
+                ),
+            )
+            .unwrap();
+            fs::write(
+                dir.join("src/index.js"),
+                format!("export function {}Main() {{}}\n", name),
+            )
+            .unwrap();
+        }
+
+        let output_dir = root.join("specs");
+        let strategy = CodeStrategy::with_config(CodeStrategyConfig {
+            force: true,
+            output_dir: Some(output_dir.to_string_lossy().to_string()),
+            ..Default::default()
+        });
+        strategy.execute(root, "fillback").await.unwrap();
+
+        let overview = fs::read_to_string(output_dir.join("_packages.md")).unwrap();
+        assert!(overview.contains("- `@acme/api` depends on `@acme/shared`"));
+        assert!(output_dir.join("acme-api/_overview.md").exists());
+        assert!(output_dir.join("acme-api/index.md").exists());
+        assert!(output_dir.join("acme-shared/_overview.md").exists());
+    }
+
     #[test]
-    fn test_analyze_with_module_filter() {
+    fn test_scan_files_respects_limits() {
         let temp_dir = TempDir::new().unwrap();
         create_test_project(temp_dir.path());
+        let src = temp_dir.path().join("src");
 
-        let strategy = CodeStrategy::with_config(CodeStrategyConfig {
-            module: Some("main".to_string()),
+        let limited = CodeStrategy::with_config(CodeStrategyConfig {
+            max_files: 2,
             ..Default::default()
         });
+        assert_eq!(limited.scan_files(&src, &[]).unwrap().len(), 2);
 
-        let (context, _) = strategy
-            .analyze_codebase(&temp_dir.path().join("src"))
-            .unwrap();
+        // main.rs is the only file under 128 bytes
+        let small_only = CodeStrategy::with_config(CodeStrategyConfig {
+            max_file_size: 128,
+            ..Default::default()
+        });
+        let files = small_only.scan_files(&src, &[]).unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].0.contains("main.rs"));
+    }
+
+    #[test]
+    fn test_analyze_parallel_matches_serial() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_project(temp_dir.path());
+        let src = temp_dir.path().join("src");
+
+        let names = |jobs| {
+            let strategy = CodeStrategy::with_config(CodeStrategyConfig {
+                jobs,
+                ..Default::default()
+            });
+            let (context, _) = strategy.analyze_codebase(&src).unwrap();
+            context
+                .modules
+                .iter()
+                .map(|m| (m.name.clone(), m.symbols.len()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(1), names(3));
+    }
 
-        assert_eq!(context.modules.len(), 1);
-        assert_eq!(context.modules[0].name, "main");
+    #[test]
+    fn test_analyze_uses_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_project(temp_dir.path());
+        let src = temp_dir.path().join("src");
+        let cache_dir = temp_dir.path().join(".agentd/cache");
+
+        let strategy = CodeStrategy::with_config(CodeStrategyConfig {
+            cache_dir: Some(cache_dir.clone()),
+            ..Default::default()
+        });
+        let (first, _) = strategy.analyze_codebase(&src).unwrap();
+        assert_eq!(AnalysisCache::load(&cache_dir).len(), 3);
+
+        // Only the changed file misses the cache
+        fs::write(src.join("utils.rs"), "pub fn changed() {}\n").unwrap();
+        let (second, _) = strategy.analyze_codebase(&src).unwrap();
+        assert_eq!(second.modules.len(), first.modules.len());
+        let utils = second.modules.iter().find(|m| m.name == "utils").unwrap();
+        assert!(utils.symbols.iter().any(|s| s.name == "changed"));
+
+        let mut cache = AnalysisCache::load(&cache_dir);
+        let main_path = fs::canonicalize(src.join("main.rs")).unwrap();
+        let main_content = fs::read_to_string(&main_path).unwrap();
+        assert!(cache
+            .get(&main_path.to_string_lossy(), &content_hash(&main_content))
+            .is_some());
     }
 
     #[test]
@@ -844,21 +1515,145 @@ enum InternalEnum {
                 items: vec![],
                 is_external: true,
             }],
+            calls: vec![],
+            interfaces: vec![],
+            entities: vec![],
+            tests: vec![],
         };
 
         let strategy = CodeStrategy::new();
-        let spec = strategy.generate_module_spec(&module);
+        let spec = strategy
+            .generate_module_spec(&module, &CallGraph::default(), &[])
+            .unwrap();
 
         assert!(spec.contains("# Specification: test_module"));
         assert!(spec.contains("public_fn"));
         assert!(spec.contains("TestStruct"));
         assert!(spec.contains("std::collections"));
+        assert!(!spec.contains("## Acceptance Criteria"));
+
+        let scenario = MinedScenario {
+            name: "Public fn rejects negative input".to_string(),
+            when: "public fn".to_string(),
+            then: "rejects negative input".to_string(),
+            test: "test_public_fn_rejects_negative_input".to_string(),
+            path: "tests/test_module.rs".to_string(),
+            line: 3,
+            covers: Some("public_fn".to_string()),
+        };
+        let spec = strategy
+            .generate_module_spec(&module, &CallGraph::default(), &[scenario])
+            .unwrap();
+
+        assert!(spec.contains(
+            "## Acceptance Criteria\n\n### Scenario: Public fn rejects negative input\n"
+        ));
+        assert!(spec.contains("- **WHEN** public fn\n- **THEN** rejects negative input\n"));
+        assert!(spec.contains(
+            "Source: `test_public_fn_rejects_negative_input` in `tests/test_module.rs:3`, covers `public_fn`"
+        ));
+    }
+
+    #[test]
+    fn test_generate_interfaces_spec() {
+        let temp_dir = TempDir::new().unwrap();
+        let src = temp_dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(
+            src.join("routes.rs"),
+            "use axum::Router;\n\npub fn router() -> Router {\n    Router::new().route(\"/users/:id\", get(get_user))\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src.join("cli.rs"),
+            "#[derive(Subcommand)]\npub enum Commands {\n    /// Start the server\n    Serve { port: u16 },\n}\n",
+        )
+        .unwrap();
+
+        let strategy = CodeStrategy::new();
+        let (context, _) = strategy.analyze_codebase(&src).unwrap();
+        let spec = strategy
+            .generate_interfaces_spec(&context)
+            .unwrap()
+            .unwrap();
+
+        assert!(spec.contains("1 HTTP endpoints and 1 CLI commands"));
+        assert!(spec.contains("### R1 - GET /users/:id\n"));
+        assert!(spec.contains("requests with `get_user`"));
+        assert!(spec.contains("- `id` (path)"));
+        assert!(spec.contains("### R2 - command serve\n"));
+        assert!(spec.contains("handled by `Commands::Serve`. Start the server"));
+        assert!(spec.contains("- `port`: `u16` (argument)"));
+        assert!(spec.contains("### HTTP API"));
+        assert!(spec.contains("### CLI"));
+
+        // No routes or commands, no spec
+        let plain = TempDir::new().unwrap();
+        create_test_project(plain.path());
+        let (context, _) = strategy
+            .analyze_codebase(&plain.path().join("src"))
+            .unwrap();
+        assert!(strategy
+            .generate_interfaces_spec(&context)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_generate_data_model_spec() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("migrations")).unwrap();
+        fs::write(
+            root.join("src/schema.rs"),
+            "diesel::table! {\n    users (id) {\n        id -> Int4,\n        name -> Varchar,\n    }\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("migrations/0001_posts.sql"),
+            "CREATE TABLE posts (\n    id SERIAL PRIMARY KEY,\n    user_id INTEGER NOT NULL REFERENCES users(id)\n);\n",
+        )
+        .unwrap();
+
+        let strategy = CodeStrategy::new();
+        let (context, _) = strategy.analyze_codebase(root).unwrap();
+        assert_eq!(context.entities.len(), 1);
+        let spec = strategy
+            .generate_data_model_spec(&context)
+            .unwrap()
+            .unwrap();
+
+        assert!(spec.contains("2 entities and 1 relationships"));
+        assert!(spec.contains("### R1 - posts\n"));
+        assert!(spec.contains("Declared with SQL in"));
+        assert!(spec.contains("- `user_id`: `INTEGER` (FK)"));
+        assert!(spec.contains("- many-to-one `users` via `user_id`"));
+        assert!(spec.contains("### R2 - users\n"));
+        assert!(spec.contains("```mermaid\nerDiagram"));
+        assert!(spec.contains("## Data Model\n\n```json"));
+
+        // No schemas or models, no spec
+        let plain = TempDir::new().unwrap();
+        create_test_project(plain.path());
+        let (context, _) = strategy
+            .analyze_codebase(&plain.path().join("src"))
+            .unwrap();
+        assert!(strategy
+            .generate_data_model_spec(&context)
+            .unwrap()
+            .is_none());
     }
 
     #[test]
     fn test_generate_specs_creates_files() {
         let temp_dir = TempDir::new().unwrap();
         create_test_project(temp_dir.path());
+        fs::write(
+            temp_dir.path().join("src/app.rs"),
+            "pub fn run() {\n    setup();\n}\n\nfn setup() {}\n",
+        )
+        .unwrap();
 
         let strategy = CodeStrategy::new();
         let (context, _) = strategy
@@ -879,6 +1674,11 @@ enum InternalEnum {
 
         // At least one module spec should exist
         assert!(created.iter().any(|f| !f.starts_with('_')));
+
+        // Module specs render their call flow
+        let app_spec = fs::read_to_string(output_dir.join("app.md")).unwrap();
+        assert!(app_spec.contains("## Call Flow"));
+        assert!(app_spec.contains("app__run --> app__setup"));
     }
 
     #[test]
@@ -0,0 +1,1630 @@
+//! Data Model Extraction
+//!
+//! Extracts entities, fields and relationships from database schemas and ORM
+//! models: SQL migrations, Prisma schemas, Diesel `schema.rs` and model
+//! classes of SQLAlchemy, Django and GORM. The merged [`DataModel`] renders as
+//! a Mermaid ERD and a JSON Schema for the generated data-model spec.
+
+use crate::fillback::ast::{AnalysisContext, Import, SupportedLanguage};
+use crate::mcp::tools::mermaid;
+use crate::Result;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::OnceLock;
+use tree_sitter::Node;
+
+/// Keywords ending the type of a SQL column definition
+const SQL_CONSTRAINTS: &[&str] = &[
+    "NOT",
+    "NULL",
+    "PRIMARY",
+    "REFERENCES",
+    "UNIQUE",
+    "DEFAULT",
+    "CHECK",
+    "CONSTRAINT",
+    "GENERATED",
+    "COLLATE",
+    "AUTO_INCREMENT",
+    "AUTOINCREMENT",
+    "IDENTITY",
+    "ON",
+];
+
+/// Where an entity was declared
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SchemaSource {
+    Sql,
+    Prisma,
+    Diesel,
+    SqlAlchemy,
+    Django,
+    Gorm,
+}
+
+impl std::fmt::Display for SchemaSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Sql => write!(f, "SQL"),
+            Self::Prisma => write!(f, "Prisma"),
+            Self::Diesel => write!(f, "Diesel"),
+            Self::SqlAlchemy => write!(f, "SQLAlchemy"),
+            Self::Django => write!(f, "Django"),
+            Self::Gorm => write!(f, "GORM"),
+        }
+    }
+}
+
+/// Key role of a field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeyKind {
+    #[serde(rename = "PK")]
+    Primary,
+    #[serde(rename = "FK")]
+    Foreign,
+    #[serde(rename = "UK")]
+    Unique,
+}
+
+impl std::fmt::Display for KeyKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Primary => write!(f, "PK"),
+            Self::Foreign => write!(f, "FK"),
+            Self::Unique => write!(f, "UK"),
+        }
+    }
+}
+
+/// Relationship cardinality, read from the declaring entity's side
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Cardinality {
+    OneToOne,
+    OneToMany,
+    ManyToOne,
+    ManyToMany,
+}
+
+impl std::fmt::Display for Cardinality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OneToOne => write!(f, "one-to-one"),
+            Self::OneToMany => write!(f, "one-to-many"),
+            Self::ManyToOne => write!(f, "many-to-one"),
+            Self::ManyToMany => write!(f, "many-to-many"),
+        }
+    }
+}
+
+/// A column or attribute of an entity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityField {
+    pub name: String,
+    pub ty: String,
+    pub key: Option<KeyKind>,
+    pub nullable: bool,
+}
+
+/// A relationship declared on an entity, by target name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Relation {
+    /// Target entity or table name, resolved when the model is merged
+    pub target: String,
+    pub cardinality: Cardinality,
+    /// Field holding the reference, if any
+    pub field: Option<String>,
+}
+
+/// A table or model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entity {
+    pub name: String,
+    /// Table name when it differs from the model name
+    pub table: Option<String>,
+    pub source: SchemaSource,
+    /// File the entity was declared in
+    #[serde(default)]
+    pub path: String,
+    pub fields: Vec<EntityField>,
+    pub relations: Vec<Relation>,
+    pub line: usize,
+}
+
+impl Entity {
+    fn new(name: &str, source: SchemaSource, path: &str, line: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            table: None,
+            source,
+            path: path.to_string(),
+            fields: Vec::new(),
+            relations: Vec::new(),
+            line,
+        }
+    }
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut EntityField> {
+        self.fields
+            .iter_mut()
+            .find(|f| f.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Whether `name` refers to this entity by model or table name
+    fn is_named(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name)
+            || self
+                .table
+                .as_deref()
+                .is_some_and(|table| table.eq_ignore_ascii_case(name))
+    }
+
+    /// Add fields and relations of a later declaration (e.g. `ALTER TABLE`)
+    fn merge(&mut self, other: Entity) {
+        for field in other.fields {
+            match self.field_mut(&field.name) {
+                Some(existing) => {
+                    if field.key.is_some() {
+                        existing.key = field.key;
+                    }
+                }
+                None => self.fields.push(field),
+            }
+        }
+        self.relations.extend(other.relations);
+        if self.table.is_none() {
+            self.table = other.table;
+        }
+    }
+}
+
+/// A resolved relationship between two entities
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Relationship {
+    pub from: String,
+    pub to: String,
+    pub cardinality: Cardinality,
+    pub label: Option<String>,
+}
+
+/// All entities of a codebase with resolved relationships
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DataModel {
+    pub entities: Vec<Entity>,
+    pub relationships: Vec<Relationship>,
+}
+
+impl DataModel {
+    /// Merge schema-file and ORM entities of the analysis into one model
+    ///
+    /// Entities declared more than once (a migration altering a table, a
+    /// Diesel schema mirroring the migrations) are merged by name.
+    pub fn from_analysis(context: &AnalysisContext) -> Self {
+        let module_entities = context.modules.iter().flat_map(|module| {
+            module.entities.iter().cloned().map(|mut entity| {
+                entity.path = module.path.clone();
+                entity
+            })
+        });
+
+        let mut model = Self::default();
+        for entity in context.entities.iter().cloned().chain(module_entities) {
+            let existing = model.entities.iter_mut().find(|e| {
+                e.is_named(&entity.name) || entity.table.as_deref().is_some_and(|t| e.is_named(t))
+            });
+            match existing {
+                Some(existing) => existing.merge(entity),
+                None => model.entities.push(entity),
+            }
+        }
+
+        let mut seen = HashSet::new();
+        for entity in &model.entities {
+            for relation in &entity.relations {
+                let Some(target) = model.entities.iter().find(|e| e.is_named(&relation.target))
+                else {
+                    continue;
+                };
+                let key = (
+                    entity.name.clone(),
+                    target.name.clone(),
+                    relation.field.clone(),
+                );
+                if seen.insert(key) {
+                    model.relationships.push(Relationship {
+                        from: entity.name.clone(),
+                        to: target.name.clone(),
+                        cardinality: relation.cardinality,
+                        label: relation.field.clone(),
+                    });
+                }
+            }
+        }
+
+        model
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Render the model as a Mermaid ERD; `None` when there are no entities
+    pub fn to_erd(&self) -> Result<Option<String>> {
+        if self.entities.is_empty() {
+            return Ok(None);
+        }
+
+        let entities: Vec<Value> = self
+            .entities
+            .iter()
+            .map(|entity| {
+                let attributes: Vec<Value> = entity
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        let mut attribute = json!({
+                            "name": erd_name(&field.name),
+                            "type": erd_type(&field.ty),
+                            "nullable": field.nullable
+                        });
+                        if let Some(key) = field.key {
+                            attribute["key"] = json!(key.to_string());
+                        }
+                        attribute
+                    })
+                    .collect();
+                json!({ "name": erd_name(&entity.name), "attributes": attributes })
+            })
+            .collect();
+
+        let relationships: Vec<Value> = self
+            .relationships
+            .iter()
+            .map(|rel| {
+                json!({
+                    "from": erd_name(&rel.from),
+                    "to": erd_name(&rel.to),
+                    "cardinality": rel.cardinality.to_string(),
+                    "label": rel.label.as_deref().map(erd_name).unwrap_or_else(|| "relates".to_string())
+                })
+            })
+            .collect();
+
+        mermaid::call_tool(
+            "generate_mermaid_erd",
+            &json!({ "entities": entities, "relationships": relationships }),
+        )
+        .map(Some)
+    }
+
+    /// Describe the model as a JSON Schema with one definition per entity
+    pub fn to_json_schema(&self) -> Value {
+        let mut definitions = Map::new();
+        for entity in &self.entities {
+            let mut properties = Map::new();
+            let mut required = Vec::new();
+            for field in &entity.fields {
+                let mut property = json!({ "type": json_type(&field.ty) });
+                if let Some(relation) = entity
+                    .relations
+                    .iter()
+                    .find(|r| r.field.as_deref() == Some(field.name.as_str()))
+                {
+                    property["description"] = json!(format!("References {}", relation.target));
+                }
+                properties.insert(field.name.clone(), property);
+                if !field.nullable {
+                    required.push(json!(field.name));
+                }
+            }
+            definitions.insert(
+                entity.name.clone(),
+                json!({ "type": "object", "properties": properties, "required": required }),
+            );
+        }
+        json!({ "type": "object", "definitions": definitions })
+    }
+}
+
+/// Whether a file is a schema file parsed by [`parse_schema_file`]
+pub fn is_schema_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("sql") | Some("prisma")
+    )
+}
+
+/// Parse entities from a SQL migration or Prisma schema
+///
+/// Down migrations (`down.sql`, `*.down.sql`, `*_down.sql`) are skipped since
+/// they undo the schema rather than describe it.
+pub fn parse_schema_file(path: &str, content: &str) -> Vec<Entity> {
+    let file_name = Path::new(path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    if file_name.ends_with(".prisma") {
+        return parse_prisma(path, content);
+    }
+    if file_name == "down.sql"
+        || file_name.ends_with(".down.sql")
+        || file_name.ends_with("_down.sql")
+    {
+        return vec![];
+    }
+    parse_sql(path, content)
+}
+
+/// Detect ORM models and Diesel tables in a parsed source file
+pub fn detect_entities(
+    root: &Node,
+    source: &str,
+    language: SupportedLanguage,
+    imports: &[Import],
+) -> Vec<Entity> {
+    let uses = |package: &str| imports.iter().any(|i| i.path.starts_with(package));
+    let mut entities = Vec::new();
+    match language {
+        SupportedLanguage::Rust => detect_diesel(root, source, &mut entities),
+        SupportedLanguage::Python => {
+            let django = uses("django");
+            let sqlalchemy = uses("sqlalchemy") || uses("flask_sqlalchemy");
+            if django || sqlalchemy {
+                detect_python_models(root, source, django, &mut entities);
+            }
+        }
+        SupportedLanguage::Go if uses("gorm.io") || uses("github.com/jinzhu/gorm") => {
+            detect_gorm(root, source, &mut entities)
+        }
+        _ => {}
+    }
+    entities
+}
+
+// SQL
+
+fn parse_sql(path: &str, content: &str) -> Vec<Entity> {
+    static CREATE: OnceLock<Regex> = OnceLock::new();
+    static ALTER: OnceLock<Regex> = OnceLock::new();
+    let create = CREATE.get_or_init(|| {
+        Regex::new(r"(?is)^CREATE\s+(?:UNLOGGED\s+)?TABLE\s+(?:IF\s+NOT\s+EXISTS\s+)?([^\s(]+)\s*\((.*)\)[^)]*$").unwrap()
+    });
+    let alter = ALTER.get_or_init(|| {
+        Regex::new(r"(?is)^ALTER\s+TABLE\s+(?:ONLY\s+)?(?:IF\s+EXISTS\s+)?([^\s(]+)\s+(.*)$")
+            .unwrap()
+    });
+
+    let sql = strip_sql_comments(content);
+    let mut entities: Vec<Entity> = Vec::new();
+    let mut offset = 0;
+    for statement in sql.split(';') {
+        let leading = statement.len() - statement.trim_start().len();
+        let line = sql[..offset + leading].matches('\n').count() + 1;
+        offset += statement.len() + 1;
+        let statement = statement.trim();
+
+        if let Some(captures) = create.captures(statement) {
+            let name = sql_identifier(&captures[1]);
+            let mut entity = Entity::new(&name, SchemaSource::Sql, path, line);
+            for item in split_top_level(&captures[2]) {
+                sql_table_item(&mut entity, &item);
+            }
+            entities.push(entity);
+        } else if let Some(captures) = alter.captures(statement) {
+            let name = sql_identifier(&captures[1]);
+            let mut entity = Entity::new(&name, SchemaSource::Sql, path, line);
+            for action in split_top_level(&captures[2]) {
+                let words: Vec<&str> = action.split_whitespace().collect();
+                if !words.first().is_some_and(|w| w.eq_ignore_ascii_case("ADD")) {
+                    continue;
+                }
+                let mut rest = &words[1..];
+                if rest
+                    .first()
+                    .is_some_and(|w| w.eq_ignore_ascii_case("COLUMN"))
+                {
+                    rest = &rest[1..];
+                }
+                if rest.len() >= 3 && rest[..3].join(" ").eq_ignore_ascii_case("IF NOT EXISTS") {
+                    rest = &rest[3..];
+                }
+                sql_table_item(&mut entity, &rest.join(" "));
+            }
+            match entities.iter_mut().find(|e| e.is_named(&name)) {
+                Some(existing) => existing.merge(entity),
+                None => entities.push(entity),
+            }
+        }
+    }
+    entities
+}
+
+/// Apply one item of a `CREATE TABLE` body: a column or a table constraint
+fn sql_table_item(entity: &mut Entity, item: &str) {
+    let words: Vec<&str> = item.split_whitespace().collect();
+    let Some(first) = words.first() else {
+        return;
+    };
+    let upper: Vec<String> = words.iter().map(|w| w.to_uppercase()).collect();
+
+    match upper[0].as_str() {
+        "CONSTRAINT" => {
+            if words.len() > 2 {
+                sql_table_item(entity, &words[2..].join(" "));
+            }
+        }
+        "PRIMARY" => {
+            for column in paren_list(item) {
+                if let Some(field) = entity.field_mut(&column) {
+                    field.key = Some(KeyKind::Primary);
+                    field.nullable = false;
+                }
+            }
+        }
+        "FOREIGN" => {
+            let columns = paren_list(item);
+            let target = upper
+                .iter()
+                .position(|w| w == "REFERENCES")
+                .and_then(|i| words.get(i + 1))
+                .map(|t| sql_identifier(t.split('(').next().unwrap_or(t)));
+            if let (Some(column), Some(target)) = (columns.first(), target) {
+                if let Some(field) = entity.field_mut(column) {
+                    field.key = Some(KeyKind::Foreign);
+                }
+                entity.relations.push(Relation {
+                    target,
+                    cardinality: Cardinality::ManyToOne,
+                    field: Some(column.clone()),
+                });
+            }
+        }
+        "UNIQUE" => {
+            if let [column] = paren_list(item).as_slice() {
+                if let Some(field) = entity.field_mut(column) {
+                    field.key.get_or_insert(KeyKind::Unique);
+                }
+            }
+        }
+        "KEY" | "INDEX" | "CHECK" | "EXCLUDE" | "FULLTEXT" | "SPATIAL" | "LIKE" | "PERIOD" => {}
+        _ => {
+            let name = sql_identifier(first);
+            let type_end = upper
+                .iter()
+                .skip(1)
+                .position(|w| SQL_CONSTRAINTS.contains(&w.as_str()))
+                .map(|i| i + 1)
+                .unwrap_or(words.len());
+            let ty = words[1..type_end].join(" ");
+            let text = upper.join(" ");
+
+            let unique = upper.iter().any(|w| w == "UNIQUE");
+            let references = upper
+                .iter()
+                .position(|w| w == "REFERENCES")
+                .and_then(|i| words.get(i + 1))
+                .map(|t| sql_identifier(t.split('(').next().unwrap_or(t)));
+            let key = if text.contains("PRIMARY KEY") {
+                Some(KeyKind::Primary)
+            } else if references.is_some() {
+                Some(KeyKind::Foreign)
+            } else if unique {
+                Some(KeyKind::Unique)
+            } else {
+                None
+            };
+
+            entity.fields.push(EntityField {
+                name: name.clone(),
+                ty: if ty.is_empty() {
+                    "TEXT".to_string()
+                } else {
+                    ty
+                },
+                key,
+                nullable: !text.contains("NOT NULL") && key != Some(KeyKind::Primary),
+            });
+            if let Some(target) = references {
+                entity.relations.push(Relation {
+                    target,
+                    cardinality: if unique {
+                        Cardinality::OneToOne
+                    } else {
+                        Cardinality::ManyToOne
+                    },
+                    field: Some(name),
+                });
+            }
+        }
+    }
+}
+
+fn strip_sql_comments(sql: &str) -> String {
+    static COMMENTS: OnceLock<Regex> = OnceLock::new();
+    let comments = COMMENTS.get_or_init(|| Regex::new(r"(?s)/\*.*?\*/|--[^\n]*").unwrap());
+    // Keep newlines inside block comments so statement lines stay accurate
+    comments
+        .replace_all(sql, |caps: &regex::Captures| {
+            "\n".repeat(caps[0].matches('\n').count())
+        })
+        .to_string()
+}
+
+/// Unquote an identifier and drop its schema: `"public"."users"` -> `users`
+fn sql_identifier(name: &str) -> String {
+    name.rsplit('.')
+        .next()
+        .unwrap_or(name)
+        .trim_matches(['"', '`', '[', ']', ','])
+        .to_string()
+}
+
+/// Identifiers of the first parenthesized list: `PRIMARY KEY (a, b)` -> [a, b]
+fn paren_list(item: &str) -> Vec<String> {
+    let Some(start) = item.find('(') else {
+        return vec![];
+    };
+    let end = item[start..]
+        .find(')')
+        .map(|e| start + e)
+        .unwrap_or(item.len());
+    item[start + 1..end]
+        .split(',')
+        .map(sql_identifier)
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .collect()
+}
+
+/// Split on commas outside parentheses
+fn split_top_level(body: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut depth = 0;
+    let mut current = String::new();
+    for c in body.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                items.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    if !current.trim().is_empty() {
+        items.push(current.trim().to_string());
+    }
+    items
+}
+
+// Prisma
+
+fn parse_prisma(path: &str, content: &str) -> Vec<Entity> {
+    static BLOCK: OnceLock<Regex> = OnceLock::new();
+    static RELATION_FIELDS: OnceLock<Regex> = OnceLock::new();
+    let block =
+        BLOCK.get_or_init(|| Regex::new(r"(?m)^\s*(model|enum|type)\s+(\w+)\s*\{").unwrap());
+    let relation_fields =
+        RELATION_FIELDS.get_or_init(|| Regex::new(r"fields:\s*\[\s*(\w+)").unwrap());
+
+    let models: HashSet<&str> = block
+        .captures_iter(content)
+        .filter(|c| &c[1] == "model")
+        .map(|c| c.get(2).map(|m| m.as_str()).unwrap_or_default())
+        .collect();
+
+    let mut entities = Vec::new();
+    for captures in block.captures_iter(content) {
+        if &captures[1] != "model" {
+            continue;
+        }
+        let start = captures.get(0).map(|m| m.end()).unwrap_or_default();
+        let body = &content[start..];
+        let body = &body[..body.find("\n}").unwrap_or(body.len())];
+        let line = content[..start].matches('\n').count() + 1;
+        let mut entity = Entity::new(&captures[2], SchemaSource::Prisma, path, line);
+        let mut foreign_keys = Vec::new();
+
+        for field_line in body.lines() {
+            let field_line = field_line.split("//").next().unwrap_or_default().trim();
+            if let Some(map) = field_line.strip_prefix("@@map(") {
+                entity.table = Some(map.trim_end_matches(')').trim_matches('"').to_string());
+                continue;
+            }
+            let words: Vec<&str> = field_line.split_whitespace().collect();
+            let [name, ty, attributes @ ..] = words.as_slice() else {
+                continue;
+            };
+            if name.starts_with('@') {
+                continue;
+            }
+            let base = ty.trim_end_matches(['?', '[', ']']);
+            let attributes = attributes.join(" ");
+
+            if models.contains(base) {
+                // Relation fields are not columns; only the side holding the
+                // foreign key declares the relationship
+                if let Some(fk) = relation_fields.captures(&attributes) {
+                    foreign_keys.push(fk[1].to_string());
+                    entity.relations.push(Relation {
+                        target: base.to_string(),
+                        cardinality: Cardinality::ManyToOne,
+                        field: Some(fk[1].to_string()),
+                    });
+                }
+                continue;
+            }
+
+            let key = if attributes.contains("@id") {
+                Some(KeyKind::Primary)
+            } else if attributes.contains("@unique") {
+                Some(KeyKind::Unique)
+            } else {
+                None
+            };
+            entity.fields.push(EntityField {
+                name: name.to_string(),
+                ty: ty.trim_end_matches('?').to_string(),
+                key,
+                nullable: ty.ends_with('?'),
+            });
+        }
+
+        for fk in foreign_keys {
+            let Some(field) = entity.field_mut(&fk) else {
+                continue;
+            };
+            // A unique foreign key makes the relation one-to-one
+            let unique = field.key == Some(KeyKind::Unique);
+            field.key = Some(KeyKind::Foreign);
+            if unique {
+                for relation in &mut entity.relations {
+                    if relation.field.as_deref() == Some(fk.as_str()) {
+                        relation.cardinality = Cardinality::OneToOne;
+                    }
+                }
+            }
+        }
+        entities.push(entity);
+    }
+    entities
+}
+
+// Diesel
+
+fn detect_diesel(node: &Node, source: &str, entities: &mut Vec<Entity>) {
+    let mut joinables = Vec::new();
+    collect_diesel(node, source, entities, &mut joinables);
+
+    // joinable!(posts -> users (user_id));
+    for (child, parent, column) in joinables {
+        if let Some(entity) = entities.iter_mut().find(|e| e.name == child) {
+            if let Some(field) = entity.field_mut(&column) {
+                field.key = Some(KeyKind::Foreign);
+            }
+            entity.relations.push(Relation {
+                target: parent,
+                cardinality: Cardinality::ManyToOne,
+                field: Some(column),
+            });
+        }
+    }
+}
+
+fn collect_diesel(
+    node: &Node,
+    source: &str,
+    entities: &mut Vec<Entity>,
+    joinables: &mut Vec<(String, String, String)>,
+) {
+    if node.kind() == "macro_invocation" {
+        let name = node
+            .child_by_field_name("macro")
+            .and_then(|m| m.utf8_text(source.as_bytes()).ok())
+            .unwrap_or_default();
+        let body = node
+            .named_child(node.named_child_count().saturating_sub(1))
+            .filter(|b| b.kind() == "token_tree")
+            .and_then(|b| b.utf8_text(source.as_bytes()).ok())
+            .unwrap_or_default();
+        let line = node.start_position().row + 1;
+        match name.rsplit("::").next().unwrap_or(name) {
+            "table" => entities.extend(parse_diesel_tables(body, line)),
+            "joinable" => {
+                static JOINABLE: OnceLock<Regex> = OnceLock::new();
+                let joinable = JOINABLE
+                    .get_or_init(|| Regex::new(r"(\w+)\s*->\s*(\w+)\s*\(\s*(\w+)\s*\)").unwrap());
+                if let Some(c) = joinable.captures(body) {
+                    joinables.push((c[1].to_string(), c[2].to_string(), c[3].to_string()));
+                }
+            }
+            _ => {}
+        }
+        return;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_diesel(&child, source, entities, joinables);
+    }
+}
+
+/// Tables of one `table!` body: `users (id) { id -> Int4, ... }`
+fn parse_diesel_tables(body: &str, line: usize) -> Vec<Entity> {
+    static TABLE: OnceLock<Regex> = OnceLock::new();
+    static COLUMN: OnceLock<Regex> = OnceLock::new();
+    let table = TABLE
+        .get_or_init(|| Regex::new(r"(?m)^\s*(?:\w+\.)?(\w+)\s*(?:\(([^)]*)\))?\s*\{").unwrap());
+    let column = COLUMN.get_or_init(|| Regex::new(r"(?m)^\s*(\w+)\s*->\s*([^,\n]+),?").unwrap());
+
+    // Strip the macro's own braces, attributes and doc comments
+    let inner = body.trim().trim_start_matches('{').trim_end_matches('}');
+    let inner: String = inner
+        .lines()
+        .filter(|l| !l.trim_start().starts_with('#') && !l.trim_start().starts_with("//"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut entities = Vec::new();
+    for captures in table.captures_iter(&inner) {
+        let start = captures.get(0).map(|m| m.end()).unwrap_or_default();
+        let columns = &inner[start..];
+        let columns = &columns[..columns.find('}').unwrap_or(columns.len())];
+        let primary: Vec<String> = captures
+            .get(2)
+            .map(|pk| {
+                pk.as_str()
+                    .split(',')
+                    .map(|c| c.trim().to_string())
+                    .collect()
+            })
+            .unwrap_or_else(|| vec!["id".to_string()]);
+
+        let mut entity = Entity::new(&captures[1], SchemaSource::Diesel, "", line);
+        for c in column.captures_iter(columns) {
+            let ty = c[2].trim().to_string();
+            let nullable = ty.starts_with("Nullable<");
+            let is_primary = primary.contains(&c[1].to_string());
+            entity.fields.push(EntityField {
+                name: c[1].to_string(),
+                ty: ty
+                    .strip_prefix("Nullable<")
+                    .and_then(|t| t.strip_suffix('>'))
+                    .unwrap_or(&ty)
+                    .to_string(),
+                key: is_primary.then_some(KeyKind::Primary),
+                nullable: nullable && !is_primary,
+            });
+        }
+        entities.push(entity);
+    }
+    entities
+}
+
+// Python: SQLAlchemy and Django
+
+fn detect_python_models(node: &Node, source: &str, django: bool, entities: &mut Vec<Entity>) {
+    if node.kind() == "class_definition" {
+        if let Some(entity) = python_model(node, source, django) {
+            entities.push(entity);
+        }
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        detect_python_models(&child, source, django, entities);
+    }
+}
+
+fn python_model(class: &Node, source: &str, django: bool) -> Option<Entity> {
+    let text = |n: &Node| {
+        n.utf8_text(source.as_bytes())
+            .unwrap_or_default()
+            .to_string()
+    };
+    let name = text(&class.child_by_field_name("name")?);
+    let superclasses = class
+        .child_by_field_name("superclasses")
+        .map(|s| text(&s))?;
+    let body = class.child_by_field_name("body")?;
+
+    let source_kind = if django && superclasses.contains("Model") {
+        SchemaSource::Django
+    } else {
+        SchemaSource::SqlAlchemy
+    };
+    let mut entity = Entity::new(&name, source_kind, "", class.start_position().row + 1);
+
+    let mut cursor = body.walk();
+    for statement in body.named_children(&mut cursor) {
+        let Some(assignment) = statement
+            .named_child(0)
+            .filter(|a| a.kind() == "assignment")
+        else {
+            continue;
+        };
+        let (Some(left), Some(right)) = (
+            assignment.child_by_field_name("left"),
+            assignment.child_by_field_name("right"),
+        ) else {
+            continue;
+        };
+        let field_name = text(&left);
+        if field_name == "__tablename__" {
+            entity.table = Some(text(&right).trim_matches(['"', '\'']).to_string());
+            continue;
+        }
+        if right.kind() != "call" {
+            continue;
+        }
+        let function = right
+            .child_by_field_name("function")
+            .map(|f| text(&f))
+            .unwrap_or_default();
+        let function = function.rsplit('.').next().unwrap_or_default();
+        let args = call_arguments(&right);
+        let annotation = assignment.child_by_field_name("type").map(|t| text(&t));
+
+        match source_kind {
+            SchemaSource::Django => {
+                django_field(&mut entity, &field_name, function, &args, source, &name)
+            }
+            _ => sqlalchemy_field(
+                &mut entity,
+                &field_name,
+                function,
+                &args,
+                annotation,
+                source,
+            ),
+        }
+    }
+
+    if entity.fields.is_empty() && entity.table.is_none() {
+        return None;
+    }
+    // Django adds an implicit auto primary key
+    if source_kind == SchemaSource::Django
+        && !entity
+            .fields
+            .iter()
+            .any(|f| f.key == Some(KeyKind::Primary))
+    {
+        entity.fields.insert(
+            0,
+            EntityField {
+                name: "id".to_string(),
+                ty: "AutoField".to_string(),
+                key: Some(KeyKind::Primary),
+                nullable: false,
+            },
+        );
+    }
+    Some(entity)
+}
+
+fn call_arguments<'t>(call: &Node<'t>) -> Vec<Node<'t>> {
+    let Some(arguments) = call.child_by_field_name("arguments") else {
+        return vec![];
+    };
+    let mut cursor = arguments.walk();
+    arguments.named_children(&mut cursor).collect()
+}
+
+/// `name=value` keyword arguments of a call, as source text
+fn keyword_arguments(args: &[Node], source: &str) -> HashMap<String, String> {
+    args.iter()
+        .filter(|a| a.kind() == "keyword_argument")
+        .filter_map(|a| {
+            let name = a
+                .child_by_field_name("name")?
+                .utf8_text(source.as_bytes())
+                .ok()?;
+            let value = a
+                .child_by_field_name("value")?
+                .utf8_text(source.as_bytes())
+                .ok()?;
+            Some((name.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// `id = Column(Integer, ForeignKey("users.id"), primary_key=True)` and
+/// `user_id: Mapped[Optional[int]] = mapped_column(ForeignKey("users.id"))`
+fn sqlalchemy_field(
+    entity: &mut Entity,
+    name: &str,
+    function: &str,
+    args: &[Node],
+    annotation: Option<String>,
+    source: &str,
+) {
+    if function != "Column" && function != "mapped_column" {
+        return;
+    }
+    let text = |n: &Node| {
+        n.utf8_text(source.as_bytes())
+            .unwrap_or_default()
+            .to_string()
+    };
+    let keywords = keyword_arguments(args, source);
+
+    let mut column_type = None;
+    let mut foreign_key = None;
+    for arg in args.iter().filter(|a| a.kind() != "keyword_argument") {
+        let arg_text = text(arg);
+        if arg_text.starts_with("ForeignKey(") {
+            foreign_key = arg_text
+                .split(['"', '\''])
+                .nth(1)
+                .and_then(|target| target.split('.').next())
+                .map(str::to_string);
+        } else if arg.kind() != "string" && column_type.is_none() {
+            column_type = Some(arg_text);
+        }
+    }
+
+    // Mapped[Optional[int]] carries both type and nullability
+    let mapped = annotation
+        .as_deref()
+        .and_then(|a| a.strip_prefix("Mapped[")?.strip_suffix(']'))
+        .map(str::to_string);
+    let optional = mapped
+        .as_deref()
+        .and_then(|m| m.strip_prefix("Optional[")?.strip_suffix(']'))
+        .map(str::to_string);
+
+    let primary = keywords.get("primary_key").is_some_and(|v| v == "True");
+    let unique = keywords.get("unique").is_some_and(|v| v == "True");
+    let nullable = match keywords.get("nullable") {
+        Some(value) => value == "True",
+        None if function == "mapped_column" && mapped.is_some() => optional.is_some(),
+        None => !primary,
+    };
+    let key = if primary {
+        Some(KeyKind::Primary)
+    } else if foreign_key.is_some() {
+        Some(KeyKind::Foreign)
+    } else if unique {
+        Some(KeyKind::Unique)
+    } else {
+        None
+    };
+
+    entity.fields.push(EntityField {
+        name: name.to_string(),
+        ty: column_type
+            .or(optional)
+            .or(mapped)
+            .unwrap_or_else(|| "Column".to_string()),
+        key,
+        nullable: nullable && !primary,
+    });
+    if let Some(target) = foreign_key {
+        entity.relations.push(Relation {
+            target,
+            cardinality: if unique {
+                Cardinality::OneToOne
+            } else {
+                Cardinality::ManyToOne
+            },
+            field: Some(name.to_string()),
+        });
+    }
+}
+
+/// `author = models.ForeignKey(User, on_delete=models.CASCADE)`
+fn django_field(
+    entity: &mut Entity,
+    name: &str,
+    function: &str,
+    args: &[Node],
+    source: &str,
+    model: &str,
+) {
+    if !function.ends_with("Field") && function != "ForeignKey" {
+        return;
+    }
+    let keywords = keyword_arguments(args, source);
+    let target = args
+        .iter()
+        .find(|a| a.kind() != "keyword_argument")
+        .and_then(|a| a.utf8_text(source.as_bytes()).ok())
+        .or_else(|| keywords.get("to").map(String::as_str))
+        .map(|t| {
+            t.trim_matches(['"', '\''])
+                .rsplit('.')
+                .next()
+                .unwrap_or_default()
+                .to_string()
+        })
+        .map(|t| if t == "self" { model.to_string() } else { t });
+
+    let cardinality = match function {
+        "ForeignKey" => Some(Cardinality::ManyToOne),
+        "OneToOneField" => Some(Cardinality::OneToOne),
+        "ManyToManyField" => Some(Cardinality::ManyToMany),
+        _ => None,
+    };
+    if let (Some(cardinality), Some(target)) = (cardinality, target) {
+        entity.relations.push(Relation {
+            target,
+            cardinality,
+            field: (cardinality != Cardinality::ManyToMany).then(|| name.to_string()),
+        });
+        // Many-to-many lives in a join table, not a column
+        if cardinality == Cardinality::ManyToMany {
+            return;
+        }
+    }
+
+    let flag = |key: &str| keywords.get(key).is_some_and(|v| v == "True");
+    let key = if flag("primary_key") {
+        Some(KeyKind::Primary)
+    } else if cardinality.is_some() {
+        Some(KeyKind::Foreign)
+    } else if flag("unique") {
+        Some(KeyKind::Unique)
+    } else {
+        None
+    };
+    entity.fields.push(EntityField {
+        name: name.to_string(),
+        ty: function.to_string(),
+        key,
+        nullable: flag("null"),
+    });
+}
+
+// Go: GORM
+
+fn detect_gorm(node: &Node, source: &str, entities: &mut Vec<Entity>) {
+    if node.kind() == "type_spec" {
+        if let Some(entity) = gorm_model(node, source) {
+            entities.push(entity);
+        }
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        detect_gorm(&child, source, entities);
+    }
+}
+
+fn gorm_model(spec: &Node, source: &str) -> Option<Entity> {
+    let text = |n: &Node| {
+        n.utf8_text(source.as_bytes())
+            .unwrap_or_default()
+            .to_string()
+    };
+    let name = text(&spec.child_by_field_name("name")?);
+    let structure = spec
+        .child_by_field_name("type")
+        .filter(|t| t.kind() == "struct_type")?;
+    let fields = structure.named_child(0)?;
+
+    let mut cursor = fields.walk();
+    let declarations: Vec<Node> = fields
+        .named_children(&mut cursor)
+        .filter(|f| f.kind() == "field_declaration")
+        .collect();
+    let is_model = declarations.iter().any(|d| {
+        d.child_by_field_name("name").is_none()
+            && d.child_by_field_name("type").map(|t| text(&t)).as_deref() == Some("gorm.Model")
+            || d.child_by_field_name("tag")
+                .is_some_and(|t| text(&t).contains("gorm:"))
+    });
+    if !is_model {
+        return None;
+    }
+
+    let field_names: HashSet<String> = declarations
+        .iter()
+        .filter_map(|d| d.child_by_field_name("name").map(|n| text(&n)))
+        .collect();
+    let mut entity = Entity::new(&name, SchemaSource::Gorm, "", spec.start_position().row + 1);
+
+    for declaration in declarations {
+        let ty = declaration
+            .child_by_field_name("type")
+            .map(|t| text(&t))
+            .unwrap_or_default();
+        let tag = declaration
+            .child_by_field_name("tag")
+            .map(|t| text(&t))
+            .unwrap_or_default();
+        let gorm_tag = tag
+            .split("gorm:\"")
+            .nth(1)
+            .and_then(|t| t.split('"').next())
+            .unwrap_or_default()
+            .to_string();
+        if gorm_tag == "-" {
+            continue;
+        }
+
+        let Some(field_name) = declaration.child_by_field_name("name").map(|n| text(&n)) else {
+            if ty == "gorm.Model" {
+                entity.fields.extend(gorm_model_fields());
+            }
+            continue;
+        };
+
+        // Associations: []Post (has many), *User / User with UserID (belongs to)
+        let element = ty.trim_start_matches("[]").trim_start_matches('*');
+        let is_struct_ref =
+            element.chars().next().is_some_and(char::is_uppercase) && !element.contains('.');
+        if is_struct_ref && ty.starts_with("[]") {
+            let cardinality = if gorm_tag.contains("many2many") {
+                Cardinality::ManyToMany
+            } else {
+                Cardinality::OneToMany
+            };
+            entity.relations.push(Relation {
+                target: element.to_string(),
+                cardinality,
+                field: None,
+            });
+            continue;
+        }
+        let foreign_key =
+            gorm_setting(&gorm_tag, "foreignKey").unwrap_or_else(|| format!("{}ID", field_name));
+        if is_struct_ref && (ty.starts_with('*') || field_names.contains(&foreign_key)) {
+            if field_names.contains(&foreign_key) {
+                entity.relations.push(Relation {
+                    target: element.to_string(),
+                    cardinality: Cardinality::ManyToOne,
+                    field: Some(snake_case(&foreign_key)),
+                });
+            }
+            continue;
+        }
+
+        let column = gorm_setting(&gorm_tag, "column").unwrap_or_else(|| snake_case(&field_name));
+        let settings: Vec<String> = gorm_tag
+            .split(';')
+            .map(|s| s.trim().to_lowercase())
+            .collect();
+        let primary = settings
+            .iter()
+            .any(|s| s == "primarykey" || s == "primary_key")
+            || field_name == "ID";
+        let unique = settings
+            .iter()
+            .any(|s| s == "unique" || s.starts_with("uniqueindex"));
+        entity.fields.push(EntityField {
+            name: column,
+            ty: ty.clone(),
+            key: if primary {
+                Some(KeyKind::Primary)
+            } else if unique {
+                Some(KeyKind::Unique)
+            } else {
+                None
+            },
+            nullable: !primary
+                && !settings.iter().any(|s| s == "not null")
+                && (ty.starts_with('*') || ty.starts_with("sql.Null")),
+        });
+    }
+
+    // Mark belongs-to columns as foreign keys
+    let foreign: Vec<String> = entity
+        .relations
+        .iter()
+        .filter_map(|r| r.field.clone())
+        .collect();
+    for column in foreign {
+        if let Some(field) = entity.field_mut(&column) {
+            field.key = Some(KeyKind::Foreign);
+        }
+    }
+    Some(entity)
+}
+
+/// Columns contributed by an embedded `gorm.Model`
+fn gorm_model_fields() -> Vec<EntityField> {
+    [
+        ("id", "uint", Some(KeyKind::Primary), false),
+        ("created_at", "time.Time", None, false),
+        ("updated_at", "time.Time", None, false),
+        ("deleted_at", "gorm.DeletedAt", None, true),
+    ]
+    .into_iter()
+    .map(|(name, ty, key, nullable)| EntityField {
+        name: name.to_string(),
+        ty: ty.to_string(),
+        key,
+        nullable,
+    })
+    .collect()
+}
+
+/// Value of a `key:value` setting in a gorm tag
+fn gorm_setting(tag: &str, key: &str) -> Option<String> {
+    tag.split(';')
+        .find_map(|s| s.trim().strip_prefix(key)?.strip_prefix(':'))
+        .map(str::to_string)
+}
+
+/// GORM's column naming: `UserID` -> `user_id`
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut result = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        let next_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+        if c.is_uppercase() && i > 0 && (chars[i - 1].is_lowercase() || next_lower) {
+            result.push('_');
+        }
+        result.extend(c.to_lowercase());
+    }
+    result
+}
+
+/// Entity and attribute names accepted by Mermaid ERDs
+fn erd_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Attribute types must be a single word in Mermaid ERDs
+fn erd_type(ty: &str) -> String {
+    let ty = ty.trim_start_matches(['*', '[', ']', '&']);
+    let ty = ty.split('(').next().unwrap_or(ty).trim();
+    let ty = erd_name(ty);
+    if ty.is_empty() {
+        "unknown".to_string()
+    } else {
+        ty
+    }
+}
+
+/// Rough JSON Schema type of a column type
+fn json_type(ty: &str) -> &'static str {
+    let ty = ty.to_lowercase();
+    if ty.contains("bool") {
+        "boolean"
+    } else if ["int", "serial", "uint"].iter().any(|t| ty.contains(t)) {
+        "integer"
+    } else if ["float", "double", "decimal", "numeric", "real", "money"]
+        .iter()
+        .any(|t| ty.contains(t))
+    {
+        "number"
+    } else {
+        "string"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fillback::ast::AstAnalyzer;
+    use std::path::PathBuf;
+
+    fn detect(path: &str, content: &str) -> Vec<Entity> {
+        let mut analyzer = AstAnalyzer::new().unwrap();
+        analyzer
+            .parse_file(&PathBuf::from(path), content)
+            .unwrap()
+            .entities
+    }
+
+    fn field<'a>(entity: &'a Entity, name: &str) -> &'a EntityField {
+        entity
+            .fields
+            .iter()
+            .find(|f| f.name == name)
+            .unwrap_or_else(|| panic!("missing field {}", name))
+    }
+
+    fn model(entities: Vec<Entity>) -> DataModel {
+        let mut context = AnalysisContext::new();
+        context.entities = entities;
+        DataModel::from_analysis(&context)
+    }
+
+    #[test]
+    fn test_parse_sql_migrations() {
+        let entities = parse_schema_file(
+            "migrations/001_init/up.sql",
+            r#"
+-- Users of the system
+CREATE TABLE IF NOT EXISTS "public"."users" (
+    id SERIAL PRIMARY KEY,
+    email VARCHAR(255) NOT NULL UNIQUE,
+    name TEXT
+);
+
+CREATE TABLE posts (
+    id BIGINT NOT NULL,
+    author_id INTEGER NOT NULL,
+    body TEXT,
+    PRIMARY KEY (id),
+    CONSTRAINT fk_author FOREIGN KEY (author_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+ALTER TABLE posts ADD COLUMN editor_id INTEGER REFERENCES users (id);
+"#,
+        );
+
+        assert_eq!(entities.len(), 2);
+        let users = &entities[0];
+        assert_eq!(users.name, "users");
+        assert_eq!(users.line, 3);
+        assert_eq!(field(users, "id").key, Some(KeyKind::Primary));
+        assert!(!field(users, "id").nullable);
+        assert_eq!(field(users, "email").ty, "VARCHAR(255)");
+        assert_eq!(field(users, "email").key, Some(KeyKind::Unique));
+        assert!(field(users, "name").nullable);
+
+        let posts = &entities[1];
+        assert_eq!(field(posts, "id").key, Some(KeyKind::Primary));
+        assert_eq!(field(posts, "author_id").key, Some(KeyKind::Foreign));
+        assert_eq!(field(posts, "editor_id").key, Some(KeyKind::Foreign));
+        assert_eq!(posts.relations.len(), 2);
+        assert!(posts.relations.iter().all(|r| r.target == "users"));
+
+        assert!(
+            parse_schema_file("migrations/001_init/down.sql", "CREATE TABLE x (id INT);")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_parse_prisma_schema() {
+        let entities = parse_schema_file(
+            "prisma/schema.prisma",
+            r#"
+model User {
+  id      Int      @id @default(autoincrement())
+  email   String   @unique
+  name    String?
+  posts   Post[]
+  profile Profile?
+  @@map("users")
+}
+
+model Post {
+  id       Int    @id
+  title    String
+  author   User   @relation(fields: [authorId], references: [id])
+  authorId Int
+}
+
+model Profile {
+  id     Int  @id
+  user   User @relation(fields: [userId], references: [id])
+  userId Int  @unique
+}
+"#,
+        );
+
+        assert_eq!(entities.len(), 3);
+        let user = &entities[0];
+        assert_eq!(user.table.as_deref(), Some("users"));
+        assert_eq!(user.fields.len(), 3);
+        assert!(field(user, "name").nullable);
+        assert_eq!(field(user, "name").ty, "String");
+        assert_eq!(field(user, "email").key, Some(KeyKind::Unique));
+
+        let post = &entities[1];
+        assert_eq!(field(post, "authorId").key, Some(KeyKind::Foreign));
+        assert_eq!(post.relations[0].target, "User");
+        assert_eq!(post.relations[0].cardinality, Cardinality::ManyToOne);
+        assert_eq!(entities[2].relations[0].cardinality, Cardinality::OneToOne);
+    }
+
+    #[test]
+    fn test_detect_diesel_schema() {
+        let entities = detect(
+            "src/schema.rs",
+            r#"
+diesel::table! {
+    users (id) {
+        id -> Int4,
+        name -> Varchar,
+        bio -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    posts (id) {
+        id -> Int4,
+        user_id -> Int4,
+        title -> Varchar,
+    }
+}
+
+diesel::joinable!(posts -> users (user_id));
+"#,
+        );
+
+        assert_eq!(entities.len(), 2);
+        let users = &entities[0];
+        assert_eq!(users.source, SchemaSource::Diesel);
+        assert_eq!(field(users, "id").key, Some(KeyKind::Primary));
+        assert!(field(users, "bio").nullable);
+        assert_eq!(field(users, "bio").ty, "Text");
+        assert_eq!(field(&entities[1], "user_id").key, Some(KeyKind::Foreign));
+        assert_eq!(entities[1].relations[0].target, "users");
+    }
+
+    #[test]
+    fn test_detect_sqlalchemy_models() {
+        let entities = detect(
+            "models.py",
+            r#"
+from typing import Optional
+from sqlalchemy import Column, ForeignKey, Integer, String
+from sqlalchemy.orm import Mapped, mapped_column
+
+class User(Base):
+    __tablename__ = "users"
+    id = Column(Integer, primary_key=True)
+    email = Column(String(120), unique=True, nullable=False)
+
+class Address(Base):
+    __tablename__ = "addresses"
+    id: Mapped[int] = mapped_column(primary_key=True)
+    user_id: Mapped[Optional[int]] = mapped_column(ForeignKey("users.id"))
+    street: Mapped[str]
+
+class Helper:
+    pass
+"#,
+        );
+
+        assert_eq!(entities.len(), 2);
+        let user = &entities[0];
+        assert_eq!(user.table.as_deref(), Some("users"));
+        assert_eq!(user.source, SchemaSource::SqlAlchemy);
+        assert_eq!(field(user, "id").key, Some(KeyKind::Primary));
+        assert_eq!(field(user, "email").key, Some(KeyKind::Unique));
+        assert_eq!(field(user, "email").ty, "String(120)");
+        assert!(!field(user, "email").nullable);
+
+        let address = &entities[1];
+        assert_eq!(field(address, "user_id").ty, "int");
+        assert!(field(address, "user_id").nullable);
+        assert_eq!(field(address, "user_id").key, Some(KeyKind::Foreign));
+        assert_eq!(address.relations[0].target, "users");
+
+        let merged = model(vec![]);
+        assert!(merged.is_empty());
+    }
+
+    #[test]
+    fn test_detect_django_models() {
+        let entities = detect(
+            "blog/models.py",
+            r#"
+from django.db import models
+
+class Author(models.Model):
+    name = models.CharField(max_length=100)
+    email = models.EmailField(unique=True)
+
+class Book(models.Model):
+    title = models.CharField(max_length=200)
+    author = models.ForeignKey(Author, on_delete=models.CASCADE)
+    summary = models.TextField(null=True)
+    tags = models.ManyToManyField("Tag")
+
+    def __str__(self):
+        return self.title
+"#,
+        );
+
+        assert_eq!(entities.len(), 2);
+        let author = &entities[0];
+        assert_eq!(author.source, SchemaSource::Django);
+        assert_eq!(author.fields[0].name, "id");
+        assert_eq!(author.fields[0].key, Some(KeyKind::Primary));
+        assert_eq!(field(author, "email").key, Some(KeyKind::Unique));
+
+        let book = &entities[1];
+        assert_eq!(field(book, "author").key, Some(KeyKind::Foreign));
+        assert!(field(book, "summary").nullable);
+        assert!(!book.fields.iter().any(|f| f.name == "tags"));
+        assert_eq!(book.relations.len(), 2);
+        assert_eq!(book.relations[1].target, "Tag");
+        assert_eq!(book.relations[1].cardinality, Cardinality::ManyToMany);
+    }
+
+    #[test]
+    fn test_detect_gorm_models() {
+        let entities = detect(
+            "models.go",
+            r#"
+package models
+
+import "gorm.io/gorm"
+
+type User struct {
+    gorm.Model
+    Email   string `gorm:"uniqueIndex;not null"`
+    Name    *string
+    Posts   []Post
+    Secret  string `gorm:"-"`
+}
+
+type Post struct {
+    ID     uint   `gorm:"primaryKey"`
+    Title  string `gorm:"column:headline"`
+    UserID uint
+    User   User
+}
+
+type Options struct {
+    Verbose bool
+}
+"#,
+        );
+
+        assert_eq!(entities.len(), 2);
+        let user = &entities[0];
+        assert_eq!(field(user, "id").key, Some(KeyKind::Primary));
+        assert!(field(user, "deleted_at").nullable);
+        assert_eq!(field(user, "email").key, Some(KeyKind::Unique));
+        assert!(field(user, "name").nullable);
+        assert!(!user.fields.iter().any(|f| f.name == "secret"));
+        assert_eq!(user.relations[0].target, "Post");
+        assert_eq!(user.relations[0].cardinality, Cardinality::OneToMany);
+
+        let post = &entities[1];
+        assert!(post.fields.iter().any(|f| f.name == "headline"));
+        assert_eq!(field(post, "user_id").key, Some(KeyKind::Foreign));
+        assert_eq!(post.relations[0].target, "User");
+        assert_eq!(post.relations[0].cardinality, Cardinality::ManyToOne);
+    }
+
+    #[test]
+    fn test_data_model_merge_and_erd() {
+        let mut entities = parse_schema_file(
+            "migrations/1_users.sql",
+            "CREATE TABLE users (id SERIAL PRIMARY KEY, name TEXT);",
+        );
+        entities.extend(parse_schema_file(
+            "migrations/2_posts.sql",
+            "CREATE TABLE posts (id SERIAL PRIMARY KEY, user_id INT REFERENCES users(id), missing_id INT REFERENCES missing(id));\nALTER TABLE users ADD email TEXT UNIQUE;",
+        ));
+        let model = model(entities);
+
+        assert_eq!(model.entities.len(), 2);
+        assert_eq!(model.entities[0].fields.len(), 3);
+        assert_eq!(model.relationships.len(), 1);
+        assert_eq!(model.relationships[0].from, "posts");
+        assert_eq!(model.relationships[0].to, "users");
+
+        let erd = model.to_erd().unwrap().unwrap();
+        assert!(erd.starts_with("erDiagram"));
+        assert!(erd.contains("SERIAL id PK"));
+        assert!(erd.contains("posts }o..|| users : \"user_id\""));
+
+        let schema = model.to_json_schema();
+        let posts = &schema["definitions"]["posts"];
+        assert_eq!(posts["properties"]["id"]["type"], "integer");
+        assert_eq!(
+            posts["properties"]["user_id"]["description"],
+            "References users"
+        );
+        assert_eq!(posts["required"], json!(["id"]));
+
+        assert!(DataModel::default().to_erd().unwrap().is_none());
+    }
+}
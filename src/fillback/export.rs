@@ -0,0 +1,469 @@
+//! Export agentd specs to OpenSpec and Speckit
+//!
+//! The reverse of the OpenSpec and Speckit importers: agentd specs are read
+//! into the importers' [`ImportedSpec`] model and rendered in a layout the
+//! matching importer reads back (`specs/<id>.yaml` for OpenSpec,
+//! `specs/<id>/spec.md` for Speckit).
+
+use crate::fillback::import::{ImportedRequirement, ImportedScenario, ImportedSpec};
+use crate::fillback::openspec::{OpenSpecDocument, OpenSpecRequirement, OpenSpecScenario};
+use crate::parser::{parse_headings, parse_sections, parse_spec_outline};
+use crate::Result;
+use std::path::{Path, PathBuf};
+
+/// Target format of an export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    OpenSpec,
+    Speckit,
+}
+
+impl ExportFormat {
+    pub fn parse(format: &str) -> Result<Self> {
+        match format {
+            "openspec" => Ok(Self::OpenSpec),
+            "speckit" => Ok(Self::Speckit),
+            other => anyhow::bail!("Unknown format '{}': expected openspec or speckit", other),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::OpenSpec => "openspec",
+            Self::Speckit => "speckit",
+        }
+    }
+}
+
+/// Export the main specs, or one change's specs, to `output`
+///
+/// Returns the written files. Specs without requirements (such as generated
+/// overviews) are skipped.
+pub fn export_specs(
+    change_id: Option<&str>,
+    format: ExportFormat,
+    output: &Path,
+    project_root: &Path,
+) -> Result<Vec<PathBuf>> {
+    let specs_dir = match change_id {
+        Some(id) => {
+            let change_dir = project_root.join("agentd/changes").join(id);
+            if !change_dir.exists() {
+                anyhow::bail!("Change '{}' not found", id);
+            }
+            change_dir.join("specs")
+        }
+        None => project_root.join("agentd/specs"),
+    };
+    if !specs_dir.exists() {
+        anyhow::bail!("No specs found at {}", specs_dir.display());
+    }
+
+    let mut files: Vec<PathBuf> = walkdir::WalkDir::new(&specs_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "md"))
+        .filter(|e| !e.file_name().to_string_lossy().starts_with('_'))
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+
+    let mut written = Vec::new();
+    for path in files {
+        let id = path
+            .strip_prefix(&specs_dir)
+            .unwrap_or(&path)
+            .with_extension("")
+            .to_string_lossy()
+            .replace(['/', '\\'], "-");
+        let spec = read_spec(&path, &id)?;
+        if spec.requirements.is_empty() {
+            continue;
+        }
+
+        let (target, content) = match format {
+            ExportFormat::OpenSpec => (
+                output.join("specs").join(format!("{}.yaml", id)),
+                render_openspec(&spec)?,
+            ),
+            ExportFormat::Speckit => (
+                output.join("specs").join(&id).join("spec.md"),
+                render_speckit(&spec),
+            ),
+        };
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&target, content)?;
+        written.push(target);
+    }
+
+    Ok(written)
+}
+
+/// Read an agentd spec into the importers' model
+pub fn read_spec(path: &Path, id: &str) -> Result<ImportedSpec> {
+    let content = std::fs::read_to_string(path)?;
+    let outline = parse_spec_outline(&content);
+
+    let title = parse_headings(&content)
+        .into_iter()
+        .find(|h| h.level == 1)
+        .map(|h| {
+            let title = h.title.trim();
+            ["Specification:", "Spec:"]
+                .iter()
+                .find_map(|prefix| title.strip_prefix(prefix))
+                .unwrap_or(title)
+                .trim()
+                .to_string()
+        })
+        .unwrap_or_else(|| id.to_string());
+
+    let overview = parse_sections(&content)
+        .into_iter()
+        .find(|s| s.heading.level == 2 && s.heading.title.trim().eq_ignore_ascii_case("overview"))
+        .map(|s| prose(s.body(&content)))
+        .unwrap_or_default();
+
+    let requirements = outline
+        .requirements
+        .iter()
+        .map(|r| {
+            let body = r.section.body(&content);
+            ImportedRequirement {
+                id: r.id.clone(),
+                title: r.title.clone(),
+                description: prose(body),
+                priority: yaml_field(body, "priority").unwrap_or_default(),
+            }
+        })
+        .collect();
+
+    let scenarios = outline
+        .scenarios
+        .iter()
+        .map(|s| {
+            let mut scenario = ImportedScenario {
+                name: s.name.clone(),
+                ..Default::default()
+            };
+            // AND lines continue whichever of GIVEN/THEN came last
+            let mut last_then = false;
+            for line in s.section.body(&content).lines() {
+                let text = line.trim().trim_start_matches(['-', '*']).trim().replace("**", "");
+                if let Some(rest) = text.strip_prefix("GIVEN") {
+                    scenario.given.push(rest.trim().to_string());
+                    last_then = false;
+                } else if let Some(rest) = text.strip_prefix("WHEN") {
+                    scenario.when = rest.trim().to_string();
+                } else if let Some(rest) = text.strip_prefix("THEN") {
+                    scenario.then.push(rest.trim().to_string());
+                    last_then = true;
+                } else if let Some(rest) = text.strip_prefix("AND") {
+                    let target = if last_then { &mut scenario.then } else { &mut scenario.given };
+                    target.push(rest.trim().to_string());
+                }
+            }
+            scenario
+        })
+        .collect();
+
+    Ok(ImportedSpec {
+        id: id.to_string(),
+        title,
+        overview,
+        requirements,
+        scenarios,
+        source: path.to_path_buf(),
+    })
+}
+
+/// Render a spec as an OpenSpec YAML document
+pub fn render_openspec(spec: &ImportedSpec) -> Result<String> {
+    let doc = OpenSpecDocument {
+        name: spec.title.clone(),
+        version: String::new(),
+        description: spec.overview.clone(),
+        requirements: spec
+            .requirements
+            .iter()
+            .enumerate()
+            .map(|(i, r)| OpenSpecRequirement {
+                id: r.id.clone().unwrap_or_else(|| format!("R{}", i + 1)),
+                title: r.title.clone(),
+                description: r.description.clone(),
+                priority: r.priority.clone(),
+            })
+            .collect(),
+        scenarios: spec
+            .scenarios
+            .iter()
+            .map(|s| OpenSpecScenario {
+                name: s.name.clone(),
+                given: s.given.clone(),
+                when: s.when.clone(),
+                then: s.then.clone(),
+            })
+            .collect(),
+    };
+    Ok(serde_yaml::to_string(&doc)?)
+}
+
+/// Render a spec as a Speckit feature specification
+///
+/// Requirements become `**ID**:` functional requirement bullets; an ID kept
+/// as a title prefix on import (`FR-001: ...`) is restored.
+pub fn render_speckit(spec: &ImportedSpec) -> String {
+    let mut output = String::new();
+    output.push_str(&format!("# Feature Specification: {}\n\n", spec.title));
+
+    output.push_str("## Overview\n\n");
+    if !spec.overview.is_empty() {
+        output.push_str(&format!("{}\n\n", spec.overview));
+    }
+
+    output.push_str("## User Scenarios & Testing\n\n");
+    for scenario in &spec.scenarios {
+        output.push_str(&format!("### {}\n\n", scenario.name));
+        let mut line = String::from("1. ");
+        if !scenario.given.is_empty() {
+            line.push_str(&format!("**Given** {}, ", scenario.given.join(" and ")));
+        }
+        line.push_str(&format!(
+            "**When** {}, **Then** {}",
+            scenario.when,
+            scenario.then.join(" and ")
+        ));
+        output.push_str(&format!("{}\n\n", line));
+    }
+
+    output.push_str("## Requirements\n\n");
+    output.push_str("### Functional Requirements\n\n");
+    for (i, requirement) in spec.requirements.iter().enumerate() {
+        let (id, description) = speckit_requirement(requirement, i);
+        output.push_str(&format!("- **{}**: {}\n", id, description));
+    }
+
+    output
+}
+
+/// Speckit ID and single-line description of a requirement
+fn speckit_requirement(requirement: &ImportedRequirement, index: usize) -> (String, String) {
+    let description = requirement.description.split_whitespace().collect::<Vec<_>>().join(" ");
+    if let Some((prefix, _)) = requirement.title.split_once(": ") {
+        let is_source_id = prefix.contains('-')
+            && prefix.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            && prefix.chars().last().is_some_and(|c| c.is_ascii_digit());
+        if is_source_id {
+            return (prefix.to_string(), description);
+        }
+    }
+    let id = requirement
+        .id
+        .clone()
+        .unwrap_or_else(|| format!("FR-{:03}", index + 1));
+    (id, description)
+}
+
+/// Section text without headings, code blocks or scenario steps
+fn prose(body: &str) -> String {
+    let mut lines = Vec::new();
+    let mut in_fence = false;
+    for line in body.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        // Nested headings start scenarios or sub-sections
+        if trimmed.starts_with('#') {
+            break;
+        }
+        if !trimmed.is_empty() {
+            lines.push(trimmed);
+        }
+    }
+    lines.join("\n")
+}
+
+/// Value of `key:` inside the first fenced yaml block of a section
+fn yaml_field(body: &str, key: &str) -> Option<String> {
+    let start = body.find("```yaml")?;
+    let block = &body[start + 7..];
+    let block = &block[..block.find("```")?];
+    block.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix(key)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .map(|value| value.trim().to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fillback::openspec::OpenSpecStrategy;
+    use crate::fillback::speckit::SpeckitStrategy;
+    use tempfile::TempDir;
+
+    const SPEC: &str = r#"---
+id: auth
+type: spec
+---
+
+<spec>
+
+# Specification: User Auth
+
+## Overview
+
+Authentication for the web app, covering login and logout.
+
+## Requirements
+
+### R1 - Login
+
+```yaml
+id: R1
+priority: high
+status: draft
+```
+
+Users can log in with email and password.
+
+### R2 - Logout
+
+Users can log out from any page.
+
+## Acceptance Criteria
+
+### Scenario: Valid login
+
+- **GIVEN** a registered user
+- **WHEN** the user submits valid credentials
+- **THEN** a session is created
+- **AND** the dashboard is shown
+
+### Scenario: Logout
+
+- **WHEN** the user clicks logout
+- **THEN** the session ends
+
+</spec>
+"#;
+
+    fn project() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let specs = temp_dir.path().join("agentd/specs");
+        std::fs::create_dir_all(&specs).unwrap();
+        std::fs::write(specs.join("auth.md"), SPEC).unwrap();
+        std::fs::write(specs.join("_overview.md"), "# Overview\n").unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn test_read_spec() {
+        let temp_dir = project();
+        let spec = read_spec(&temp_dir.path().join("agentd/specs/auth.md"), "auth").unwrap();
+
+        assert_eq!(spec.title, "User Auth");
+        assert_eq!(spec.overview, "Authentication for the web app, covering login and logout.");
+        assert_eq!(spec.requirements.len(), 2);
+        assert_eq!(spec.requirements[0].id.as_deref(), Some("R1"));
+        assert_eq!(spec.requirements[0].description, "Users can log in with email and password.");
+        assert_eq!(spec.requirements[0].priority, "high");
+        assert_eq!(spec.scenarios[0].given, vec!["a registered user"]);
+        assert_eq!(spec.scenarios[0].then, vec!["a session is created", "the dashboard is shown"]);
+    }
+
+    #[test]
+    fn test_openspec_round_trip() {
+        let temp_dir = project();
+        let output = temp_dir.path().join("out");
+        let files = export_specs(None, ExportFormat::OpenSpec, &output, temp_dir.path()).unwrap();
+        assert_eq!(files, vec![output.join("specs/auth.yaml")]);
+
+        let original = read_spec(&temp_dir.path().join("agentd/specs/auth.md"), "auth").unwrap();
+        let imported = OpenSpecStrategy::new().import(&output).unwrap();
+        let spec = &imported.specs[0];
+
+        assert_eq!(spec.title, original.title);
+        assert_eq!(spec.overview, original.overview);
+        for (a, b) in spec.requirements.iter().zip(&original.requirements) {
+            assert_eq!(a.id, b.id);
+            assert_eq!(a.title, b.title);
+            assert_eq!(a.description, b.description);
+            assert_eq!(a.priority, b.priority);
+        }
+        assert_eq!(spec.requirements.len(), original.requirements.len());
+        for (a, b) in spec.scenarios.iter().zip(&original.scenarios) {
+            assert_eq!(a.name, b.name);
+            assert_eq!(a.given, b.given);
+            assert_eq!(a.when, b.when);
+            assert_eq!(a.then, b.then);
+        }
+        assert_eq!(spec.scenarios.len(), original.scenarios.len());
+
+        // The import is a valid agentd change again
+        imported.write("auth-again", temp_dir.path(), false).unwrap();
+        let written = read_spec(
+            &temp_dir.path().join("agentd/changes/auth-again/specs/user-auth.md"),
+            "user-auth",
+        )
+        .unwrap();
+        assert_eq!(written.requirements[1].title, "Logout");
+        assert_eq!(written.scenarios[0].then, vec!["a session is created; the dashboard is shown"]);
+    }
+
+    #[test]
+    fn test_speckit_round_trip() {
+        let temp_dir = project();
+        let output = temp_dir.path().join("out");
+        let files = export_specs(None, ExportFormat::Speckit, &output, temp_dir.path()).unwrap();
+        assert_eq!(files, vec![output.join("specs/auth/spec.md")]);
+
+        let original = read_spec(&temp_dir.path().join("agentd/specs/auth.md"), "auth").unwrap();
+        let imported = SpeckitStrategy::new().import(&output).unwrap();
+        let spec = &imported.specs[0];
+
+        assert_eq!(spec.id, "auth");
+        assert_eq!(spec.title, original.title);
+        assert_eq!(spec.overview, original.overview);
+        assert_eq!(spec.requirements.len(), 2);
+        for (a, b) in spec.requirements.iter().zip(&original.requirements) {
+            assert_eq!(a.id, b.id);
+            assert_eq!(a.description, b.description);
+        }
+        assert_eq!(spec.scenarios.len(), 2);
+        for (a, b) in spec.scenarios.iter().zip(&original.scenarios) {
+            assert_eq!(a.name, b.name);
+            assert_eq!(a.given, b.given);
+            assert_eq!(a.when, b.when);
+            assert_eq!(a.then.join(" and "), b.then.join(" and "));
+        }
+    }
+
+    #[test]
+    fn test_export_change_restores_speckit_ids() {
+        let temp_dir = TempDir::new().unwrap();
+        let specs = temp_dir.path().join("agentd/changes/albums/specs");
+        std::fs::create_dir_all(&specs).unwrap();
+        std::fs::write(
+            specs.join("albums.md"),
+            "# Albums\n\n## Overview\n\nAlbums.\n\n## Requirements\n\n### R1 - FR-001: Create albums\n\nSystem MUST let users create albums.\n\n## Acceptance Criteria\n\n### Scenario: Create\n\n- **WHEN** a user creates an album\n- **THEN** it is listed\n",
+        )
+        .unwrap();
+
+        let output = temp_dir.path().join("out");
+        export_specs(Some("albums"), ExportFormat::Speckit, &output, temp_dir.path()).unwrap();
+        let content = std::fs::read_to_string(output.join("specs/albums/spec.md")).unwrap();
+        assert!(content.contains("- **FR-001**: System MUST let users create albums."));
+        assert!(content.contains("1. **When** a user creates an album, **Then** it is listed"));
+
+        assert!(export_specs(Some("missing"), ExportFormat::Speckit, &output, temp_dir.path()).is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::fillback::code::CodeStrategy;
+use crate::fillback::code::{CodeStrategy, CodeStrategyConfig};
 use crate::fillback::openspec::OpenSpecStrategy;
 use crate::fillback::speckit::SpeckitStrategy;
 use crate::fillback::strategy::ImportStrategy;
@@ -27,12 +27,24 @@ impl StrategyFactory {
     pub fn create(
         strategy_type: &str,
         source: &Path,
+    ) -> Result<Box<dyn ImportStrategy>> {
+        Self::create_with_config(strategy_type, source, CodeStrategyConfig::default())
+    }
+
+    /// Create a strategy configured with the fillback command options
+    ///
+    /// `config` configures the code strategy; its `force` flag also lets the
+    /// OpenSpec and Speckit importers replace an existing change.
+    pub fn create_with_config(
+        strategy_type: &str,
+        source: &Path,
+        config: CodeStrategyConfig,
     ) -> Result<Box<dyn ImportStrategy>> {
         match strategy_type {
-            "openspec" => Ok(Box::new(OpenSpecStrategy::new())),
-            "speckit" => Ok(Box::new(SpeckitStrategy::new())),
-            "code" => Ok(Box::new(CodeStrategy::new())),
-            "auto" => Self::auto_detect(source),
+            "openspec" => Ok(Box::new(OpenSpecStrategy::with_force(config.force))),
+            "speckit" => Ok(Box::new(SpeckitStrategy::with_force(config.force))),
+            "code" => Ok(Box::new(CodeStrategy::with_config(config))),
+            "auto" => Self::auto_detect(source, config),
             _ => {
                 anyhow::bail!(
                     "Invalid strategy: '{}'. Supported strategies: auto, openspec, speckit, code",
@@ -45,8 +57,8 @@ impl StrategyFactory {
     /// Auto-detect the appropriate strategy for the given source
     ///
     /// Tries each strategy's `can_handle` method in order:
-    /// 1. OpenSpec (YAML/JSON files)
-    /// 2. Speckit (Markdown files)
+    /// 1. OpenSpec (YAML/JSON files, OpenSpec markdown trees)
+    /// 2. Speckit (Markdown files, feature directories)
     /// 3. Code (directories)
     ///
     /// # Arguments
@@ -57,12 +69,12 @@ impl StrategyFactory {
     ///
     /// # Errors
     /// Returns an error if no strategy can handle the source
-    fn auto_detect(source: &Path) -> Result<Box<dyn ImportStrategy>> {
+    fn auto_detect(source: &Path, config: CodeStrategyConfig) -> Result<Box<dyn ImportStrategy>> {
         // Try strategies in order of specificity
         let strategies: Vec<Box<dyn ImportStrategy>> = vec![
-            Box::new(OpenSpecStrategy::new()),
-            Box::new(SpeckitStrategy::new()),
-            Box::new(CodeStrategy::new()),
+            Box::new(OpenSpecStrategy::with_force(config.force)),
+            Box::new(SpeckitStrategy::with_force(config.force)),
+            Box::new(CodeStrategy::with_config(config)),
         ];
 
         for strategy in strategies {
@@ -145,6 +157,39 @@ mod tests {
         assert_eq!(strategy.name(), "code");
     }
 
+    #[test]
+    fn test_auto_detect_spec_trees() {
+        let temp_dir = TempDir::new().unwrap();
+        let openspec_dir = temp_dir.path().join("openspec/specs/auth");
+        fs::create_dir_all(&openspec_dir).unwrap();
+        fs::write(
+            openspec_dir.join("spec.md"),
+            "# Auth Specification\n\n## Requirements\n### Requirement: Login\nUsers SHALL log in.\n",
+        )
+        .unwrap();
+        let strategy = StrategyFactory::create("auto", temp_dir.path()).unwrap();
+        assert_eq!(strategy.name(), "openspec");
+
+        let temp_dir = TempDir::new().unwrap();
+        let feature_dir = temp_dir.path().join("specs/001-login");
+        fs::create_dir_all(&feature_dir).unwrap();
+        fs::write(
+            feature_dir.join("spec.md"),
+            "# Feature Specification: Login\n\n## Requirements\n- **FR-001**: System MUST log users in\n",
+        )
+        .unwrap();
+        let strategy = StrategyFactory::create("auto", temp_dir.path()).unwrap();
+        assert_eq!(strategy.name(), "speckit");
+
+        // A plain README does not make a code project a Speckit tree
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("src")).unwrap();
+        fs::write(temp_dir.path().join("src/main.rs"), "fn main() {}").unwrap();
+        fs::write(temp_dir.path().join("README.md"), "# App\n\n## Requirements\nRust 1.80\n").unwrap();
+        let strategy = StrategyFactory::create("auto", temp_dir.path()).unwrap();
+        assert_eq!(strategy.name(), "code");
+    }
+
     #[test]
     fn test_auto_detect_failure() {
         let temp_dir = TempDir::new().unwrap();
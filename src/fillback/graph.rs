@@ -4,6 +4,7 @@
 //! Outputs graphs as Mermaid flowcharts for visualization.
 
 use crate::fillback::ast::AnalysisContext;
+use crate::fillback::callgraph::CallGraph;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
@@ -117,11 +118,30 @@ impl DependencyGraph {
         // Deduplicate edges
         graph.deduplicate_edges();
 
+        graph.add_call_edges(&CallGraph::from_analysis(context));
+
         graph
     }
 
+    /// Add call edges between modules that call each other without an import
+    ///
+    /// Same-package calls in Go, Java, C and similar languages need no import,
+    /// so these relationships are otherwise missing from the graph.
+    pub fn add_call_edges(&mut self, calls: &CallGraph) {
+        for (from, to) in calls.module_calls() {
+            let linked = self.edges.iter().any(|e| e.from == from && e.to == to);
+            if !linked {
+                self.edges.push(Dependency {
+                    from,
+                    to,
+                    dependency_type: DependencyType::Call,
+                });
+            }
+        }
+    }
+
     /// Extract base module name from import path
-    fn extract_base_module(path: &str) -> String {
+    pub(crate) fn extract_base_module(path: &str) -> String {
         // Handle different import formats:
         // - Rust: std::path::Path -> std
         // - Python: os.path -> os
@@ -445,6 +465,10 @@ mod tests {
                     is_external: true,
                 },
             ],
+            calls: vec![],
+            interfaces: vec![],
+            entities: vec![],
+            tests: vec![],
         });
 
         // Module B - depends on external only
@@ -465,6 +489,10 @@ mod tests {
                 items: vec![],
                 is_external: true,
             }],
+            calls: vec![],
+            interfaces: vec![],
+            entities: vec![],
+            tests: vec![],
         });
 
         // Module C - depends on A and B
@@ -485,6 +513,10 @@ mod tests {
                     is_external: false,
                 },
             ],
+            calls: vec![],
+            interfaces: vec![],
+            entities: vec![],
+            tests: vec![],
         });
 
         context
@@ -512,6 +544,32 @@ mod tests {
         assert_eq!(module_a_deps.len(), 2);
     }
 
+    #[test]
+    fn test_call_edges() {
+        use crate::fillback::ast::CallSite;
+
+        let mut context = create_test_context();
+        let call = |caller: &str, callee: &str| CallSite {
+            caller: caller.to_string(),
+            callee: callee.to_string(),
+            receiver: None,
+            line: 2,
+        };
+        // module_a already imports module_b; module_b calls back without an import
+        context.modules[0].calls.push(call("func_a", "func_b"));
+        context.modules[1].calls.push(call("func_b", "func_a"));
+
+        let graph = DependencyGraph::from_analysis(&context);
+        let calls: Vec<_> = graph
+            .edges
+            .iter()
+            .filter(|e| e.dependency_type == DependencyType::Call)
+            .map(|e| (e.from.as_str(), e.to.as_str()))
+            .collect();
+        assert_eq!(calls, vec![("module_b", "module_a")]);
+        assert!(graph.to_mermaid().contains("module_b -.-> module_a"));
+    }
+
     #[test]
     fn test_extract_base_module() {
         // Rust paths
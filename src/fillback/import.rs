@@ -0,0 +1,696 @@
+//! Shared model for spec importers
+//!
+//! The OpenSpec and Speckit strategies parse their sources into an
+//! [`ImportedChange`], which is written through the proposal, spec and tasks
+//! services so an imported change has the same shape as one planned by agentd.
+
+use crate::parser::spec_sections::parse_requirement_heading;
+use crate::services::proposal_service::{
+    create_proposal, AffectedSpec, CreateProposalInput, ImpactData,
+};
+use crate::services::spec_service::{create_spec, CreateSpecInput, RequirementData, ScenarioData};
+use crate::services::tasks_service::{create_tasks, CreateTasksInput, FileActionData, TaskData};
+use crate::Result;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// File recorded for imported tasks whose source names no file
+pub const UNSPECIFIED_FILE: &str = "(unspecified)";
+
+/// A requirement read from an imported spec
+#[derive(Debug, Clone, Default)]
+pub struct ImportedRequirement {
+    /// Source identifier (e.g. `R1`, `FR-001`); numbered when absent
+    pub id: Option<String>,
+    pub title: String,
+    pub description: String,
+    pub priority: String,
+}
+
+/// An acceptance scenario read from an imported spec
+#[derive(Debug, Clone, Default)]
+pub struct ImportedScenario {
+    pub name: String,
+    pub given: Vec<String>,
+    pub when: String,
+    pub then: Vec<String>,
+}
+
+/// One spec document of an imported tree
+#[derive(Debug, Clone, Default)]
+pub struct ImportedSpec {
+    pub id: String,
+    pub title: String,
+    pub overview: String,
+    pub requirements: Vec<ImportedRequirement>,
+    pub scenarios: Vec<ImportedScenario>,
+    /// File the spec was read from
+    pub source: PathBuf,
+}
+
+/// A task read from an imported task list
+#[derive(Debug, Clone, Default)]
+pub struct ImportedTask {
+    pub title: String,
+    /// Section the task was listed under (used to pick a layer)
+    pub group: String,
+    pub file: Option<String>,
+    /// Spec the task belongs to, when the source groups tasks per spec
+    pub spec_id: Option<String>,
+}
+
+/// Everything imported from one source, ready to become a change
+#[derive(Debug, Clone, Default)]
+pub struct ImportedChange {
+    /// Source format label used in generated prose (e.g. "OpenSpec")
+    pub format: String,
+    pub summary: String,
+    pub why: String,
+    pub what_changes: Vec<String>,
+    pub specs: Vec<ImportedSpec>,
+    pub tasks: Vec<ImportedTask>,
+}
+
+impl ImportedChange {
+    pub fn new(format: &str) -> Self {
+        Self {
+            format: format.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Write the import as `agentd/changes/<change_id>`
+    ///
+    /// Specs without requirements are skipped. An existing change directory
+    /// is only replaced when `force` is set.
+    pub fn write(&self, change_id: &str, project_root: &Path, force: bool) -> Result<Vec<String>> {
+        // Validate before touching the filesystem: `force` removes the directory
+        if change_id.is_empty()
+            || !change_id
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        {
+            anyhow::bail!(
+                "Invalid change_id '{}': must be lowercase alphanumeric with hyphens only",
+                change_id
+            );
+        }
+
+        let specs: Vec<&ImportedSpec> = self
+            .specs
+            .iter()
+            .filter(|s| !s.requirements.is_empty())
+            .collect();
+        if specs.is_empty() {
+            anyhow::bail!("No {} specs with requirements found to import", self.format);
+        }
+
+        let change_dir = project_root.join("agentd/changes").join(change_id);
+        if change_dir.exists() {
+            if !force {
+                anyhow::bail!(
+                    "Change '{}' already exists. Use --force to replace it.",
+                    change_id
+                );
+            }
+            std::fs::remove_dir_all(&change_dir)?;
+        }
+
+        // Spec ids must be unique within the change
+        let mut spec_ids = Vec::new();
+        let mut seen = HashSet::new();
+        for spec in &specs {
+            let base = match slugify(&spec.id) {
+                id if id.is_empty() => "imported-spec".to_string(),
+                id => id,
+            };
+            let mut id = base.clone();
+            let mut n = 2;
+            while !seen.insert(id.clone()) {
+                id = format!("{}-{}", base, n);
+                n += 1;
+            }
+            spec_ids.push(id);
+        }
+
+        let tasks = self.task_data(&specs, &spec_ids, project_root);
+        let new_files = tasks.iter().filter(|t| t.file.action == "CREATE").count();
+
+        create_proposal(
+            CreateProposalInput {
+                change_id: change_id.to_string(),
+                summary: self.summary_text(specs.len()),
+                why: self.why_text(),
+                what_changes: self.what_changes_text(&specs, &spec_ids),
+                impact: ImpactData {
+                    scope: "minor".to_string(),
+                    affected_files: tasks
+                        .iter()
+                        .filter(|t| t.file.path != UNSPECIFIED_FILE)
+                        .count() as i64,
+                    new_files: new_files as i64,
+                    affected_specs: spec_ids
+                        .iter()
+                        .map(|id| AffectedSpec {
+                            id: id.clone(),
+                            depends: Vec::new(),
+                        })
+                        .collect(),
+                    affected_code: Vec::new(),
+                    breaking_changes: None,
+                },
+                depends_on_changes: Vec::new(),
+            },
+            project_root,
+        )?;
+
+        for (spec, spec_id) in specs.iter().zip(&spec_ids) {
+            create_spec(self.spec_input(spec, spec_id, change_id, project_root), project_root)?;
+        }
+
+        create_tasks(
+            CreateTasksInput {
+                change_id: change_id.to_string(),
+                tasks,
+            },
+            project_root,
+        )?;
+
+        let mut files = vec!["proposal.md".to_string()];
+        files.extend(spec_ids.iter().map(|id| format!("specs/{}.md", id)));
+        files.push("tasks.md".to_string());
+        Ok(files)
+    }
+
+    fn summary_text(&self, spec_count: usize) -> String {
+        let summary = self.summary.trim();
+        if summary.len() >= 10 {
+            summary.to_string()
+        } else {
+            format!("Import {} {} spec(s) into agentd", spec_count, self.format)
+        }
+    }
+
+    fn why_text(&self) -> String {
+        let default = format!(
+            "These specifications were written with {} and are imported so they can be \
+             reviewed, implemented and archived through the agentd workflow.",
+            self.format
+        );
+        let why = self.why.trim();
+        if why.is_empty() {
+            default
+        } else if why.len() < 50 {
+            format!("{} {}", why, default)
+        } else {
+            why.to_string()
+        }
+    }
+
+    fn what_changes_text(&self, specs: &[&ImportedSpec], spec_ids: &[String]) -> Vec<String> {
+        if !self.what_changes.is_empty() {
+            return self.what_changes.clone();
+        }
+        specs
+            .iter()
+            .zip(spec_ids)
+            .map(|(spec, id)| {
+                format!(
+                    "Add `{}` ({}) with {} requirement(s)",
+                    id,
+                    spec.title,
+                    spec.requirements.len()
+                )
+            })
+            .collect()
+    }
+
+    fn spec_input(
+        &self,
+        spec: &ImportedSpec,
+        spec_id: &str,
+        change_id: &str,
+        project_root: &Path,
+    ) -> CreateSpecInput {
+        let requirements = requirement_data(&spec.requirements);
+
+        let mut scenarios: Vec<ScenarioData> = spec.scenarios.iter().map(scenario_data).collect();
+        if scenarios.is_empty() {
+            // The source has no scenarios; derive one per requirement so the
+            // spec still states an observable outcome
+            scenarios = requirements
+                .iter()
+                .map(|r| ScenarioData {
+                    name: r.title.clone(),
+                    given: None,
+                    when: format!("the behaviour covered by {} is exercised", r.id),
+                    then: first_sentence(&r.description),
+                })
+                .collect();
+        }
+
+        let source = spec
+            .source
+            .strip_prefix(project_root)
+            .unwrap_or(&spec.source)
+            .display()
+            .to_string();
+        let title = if spec.title.trim().is_empty() {
+            spec_id.to_string()
+        } else {
+            spec.title.trim().to_string()
+        };
+        let note = format!("Imported from {} source `{}`.", self.format, source);
+        let overview = match spec.overview.trim() {
+            "" => format!("{} {}", title, note),
+            text if text.len() < 50 => format!("{} {}", text, note),
+            text => text.to_string(),
+        };
+
+        CreateSpecInput {
+            change_id: change_id.to_string(),
+            spec_id: spec_id.to_string(),
+            title,
+            overview,
+            requirements,
+            scenarios,
+            flow_diagram: None,
+            data_model: None,
+        }
+    }
+
+    /// Tasks from the source, or one task per spec when it lists none
+    fn task_data(&self, specs: &[&ImportedSpec], spec_ids: &[String], project_root: &Path) -> Vec<TaskData> {
+        let id_for: HashMap<&str, &str> = specs
+            .iter()
+            .zip(spec_ids)
+            .map(|(spec, id)| (spec.id.as_str(), id.as_str()))
+            .collect();
+        let default_spec = spec_ids[0].as_str();
+
+        let mut numbers: HashMap<&'static str, u32> = HashMap::new();
+        let mut next = |layer: &'static str| {
+            let n = numbers.entry(layer).or_insert(0);
+            *n += 1;
+            *n
+        };
+
+        if self.tasks.is_empty() {
+            return specs
+                .iter()
+                .zip(spec_ids)
+                .map(|(spec, id)| TaskData {
+                    layer: "logic".to_string(),
+                    number: next("logic"),
+                    title: format!("Implement {}", spec.title.trim()),
+                    file: FileActionData {
+                        path: UNSPECIFIED_FILE.to_string(),
+                        action: "MODIFY".to_string(),
+                    },
+                    spec_ref: format!("specs/{}.md", id),
+                    description: format!(
+                        "Implement the {} requirement(s) of `{}` imported from {}.",
+                        spec.requirements.len(),
+                        id,
+                        self.format
+                    ),
+                    depends: Vec::new(),
+                })
+                .collect();
+        }
+
+        self.tasks
+            .iter()
+            .map(|task| {
+                let layer = task_layer(&task.group, &task.title);
+                let spec_id = task
+                    .spec_id
+                    .as_deref()
+                    .and_then(|id| id_for.get(id).copied())
+                    .unwrap_or(default_spec);
+                let (path, action) = match &task.file {
+                    Some(file) if project_root.join(file).exists() => (file.clone(), "MODIFY"),
+                    Some(file) => (file.clone(), "CREATE"),
+                    None => (UNSPECIFIED_FILE.to_string(), "MODIFY"),
+                };
+                let description = if task.group.is_empty() {
+                    format!("Imported from {}.", self.format)
+                } else {
+                    format!("Imported from {} ({}).", self.format, task.group)
+                };
+                TaskData {
+                    layer: layer.to_string(),
+                    number: next(layer),
+                    title: task.title.clone(),
+                    file: FileActionData {
+                        path,
+                        action: action.to_string(),
+                    },
+                    spec_ref: format!("specs/{}.md", spec_id),
+                    description,
+                    depends: Vec::new(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Keep source requirement IDs agentd can parse (`R1`, `AUTH-R2`), number the
+/// rest and keep their source ID as a title prefix (`FR-001: ...`)
+fn requirement_data(requirements: &[ImportedRequirement]) -> Vec<RequirementData> {
+    let mut seen = HashSet::new();
+    let mut next = 0;
+    requirements
+        .iter()
+        .map(|req| {
+            let source_id = req.id.as_deref().map(str::trim).filter(|id| !id.is_empty());
+            let kept = source_id.filter(|id| is_requirement_id(id) && !seen.contains(*id));
+            let id = match kept {
+                Some(id) => id.to_string(),
+                None => loop {
+                    next += 1;
+                    let candidate = format!("R{}", next);
+                    if !seen.contains(&candidate) {
+                        break candidate;
+                    }
+                },
+            };
+            seen.insert(id.clone());
+
+            let mut title = match req.title.trim() {
+                "" => first_sentence(&req.description),
+                title => title.to_string(),
+            };
+            if let (Some(source_id), None) = (source_id, kept) {
+                title = format!("{}: {}", source_id, title);
+            }
+            let description = match req.description.trim() {
+                "" => title.clone(),
+                text => text.to_string(),
+            };
+            let priority = match req.priority.trim().to_lowercase().as_str() {
+                "high" | "p1" | "must" => "high",
+                "low" | "p3" | "p4" | "could" => "low",
+                _ => "medium",
+            };
+
+            RequirementData {
+                id,
+                title,
+                description,
+                priority: priority.to_string(),
+            }
+        })
+        .collect()
+}
+
+fn scenario_data(scenario: &ImportedScenario) -> ScenarioData {
+    let given = scenario
+        .given
+        .iter()
+        .map(|g| g.trim())
+        .filter(|g| !g.is_empty())
+        .collect::<Vec<_>>();
+    let then = scenario
+        .then
+        .iter()
+        .map(|t| t.trim())
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>();
+
+    ScenarioData {
+        name: scenario.name.trim().to_string(),
+        given: (!given.is_empty()).then(|| given.join(" and ")),
+        when: match scenario.when.trim() {
+            "" => format!("\"{}\" is exercised", scenario.name.trim()),
+            when => when.to_string(),
+        },
+        then: if then.is_empty() {
+            "the outcome described by the scenario is observed".to_string()
+        } else {
+            then.join("; ")
+        },
+    }
+}
+
+/// Layer for an imported task, from its section heading or title
+fn task_layer(group: &str, title: &str) -> &'static str {
+    let text = format!("{} {}", group, title).to_lowercase();
+    if text.contains("test") {
+        "testing"
+    } else if ["setup", "foundation", "model", "schema", "migration", "data"]
+        .iter()
+        .any(|k| text.contains(k))
+    {
+        "data"
+    } else if ["integration", "polish", "docs", "documentation", "deploy"]
+        .iter()
+        .any(|k| text.contains(k))
+    {
+        "integration"
+    } else {
+        "logic"
+    }
+}
+
+/// Whether `id` is a requirement ID the spec outline parser recognises
+fn is_requirement_id(id: &str) -> bool {
+    matches!(parse_requirement_heading(id), Some((Some(parsed), rest)) if parsed == id && rest.is_empty())
+}
+
+fn first_sentence(text: &str) -> String {
+    let line = text.trim().lines().next().unwrap_or("").trim();
+    match line.find(". ") {
+        Some(end) => line[..=end].trim().to_string(),
+        None => line.to_string(),
+    }
+}
+
+/// Lowercase, hyphen-separated identifier for spec and change ids
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.trim().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('-') && !slug.is_empty() {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// File path mentioned in an imported task line, if any
+///
+/// Prefers a backticked path, then the last word that looks like a path.
+pub fn task_file(text: &str) -> Option<String> {
+    if let Some(start) = text.find('`') {
+        if let Some(len) = text[start + 1..].find('`') {
+            let candidate = &text[start + 1..start + 1 + len];
+            if looks_like_path(candidate) {
+                return Some(candidate.to_string());
+            }
+        }
+    }
+    text.split_whitespace()
+        .rev()
+        .map(|w| w.trim_matches(|c: char| matches!(c, ',' | ';' | ')' | '(' | '`' | '\'' | '"')))
+        .map(|w| w.trim_end_matches('.'))
+        .find(|w| looks_like_path(w))
+        .map(String::from)
+}
+
+fn looks_like_path(word: &str) -> bool {
+    !word.contains(' ')
+        && !word.starts_with("http")
+        && (word.contains('/')
+            || Path::new(word)
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| {
+                    ext.len() <= 5
+                        && ext.chars().all(|c| c.is_ascii_alphanumeric())
+                        && ext.chars().any(|c| c.is_ascii_alphabetic())
+                }))
+        && word.chars().any(|c| c.is_ascii_alphabetic())
+}
+
+/// Tasks from a checkbox task list (OpenSpec and Speckit `tasks.md`)
+///
+/// Leading task numbers (`1.1`, `T001`) and markers such as `[P]` or `[US1]`
+/// are dropped; `##` headings become the task group.
+pub fn parse_task_list(content: &str) -> Vec<ImportedTask> {
+    let mut tasks = Vec::new();
+    let mut group = String::new();
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if let Some(heading) = trimmed.strip_prefix("## ") {
+            group = heading.trim().to_string();
+            continue;
+        }
+        let Some(rest) = ["- [ ]", "- [x]", "- [X]", "* [ ]", "* [x]"]
+            .iter()
+            .find_map(|marker| trimmed.strip_prefix(marker))
+        else {
+            continue;
+        };
+
+        let mut words: Vec<&str> = rest.split_whitespace().collect();
+        while let Some(first) = words.first() {
+            let is_number = first.chars().all(|c| c.is_ascii_digit() || c == '.')
+                || (first.starts_with('T') && first[1..].chars().all(|c| c.is_ascii_digit()) && first.len() > 1);
+            let is_marker = first.starts_with('[') && first.ends_with(']');
+            if is_number || is_marker {
+                words.remove(0);
+            } else {
+                break;
+            }
+        }
+        let raw = words.join(" ");
+        let title = strip_markdown(&raw);
+        if title.is_empty() {
+            continue;
+        }
+
+        tasks.push(ImportedTask {
+            file: task_file(&raw),
+            title,
+            group: group.clone(),
+            spec_id: None,
+        });
+    }
+
+    tasks
+}
+
+/// Level-2 sections of a markdown document as (heading, body) pairs
+pub fn h2_sections(content: &str) -> Vec<(String, String)> {
+    let mut sections: Vec<(String, String)> = Vec::new();
+    for line in content.lines() {
+        if let Some(heading) = line.strip_prefix("## ") {
+            sections.push((heading.trim().to_string(), String::new()));
+        } else if let Some((_, body)) = sections.last_mut() {
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+    sections
+}
+
+/// Remove emphasis and code markers from inline markdown
+pub fn strip_markdown(text: &str) -> String {
+    text.replace("**", "").replace('`', "").trim().to_string()
+}
+
+/// Directories never searched when walking an import source
+pub fn is_ignored_dir(name: &str) -> bool {
+    name.starts_with('.') || matches!(name, "node_modules" | "target" | "archive" | "vendor")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::validate_proposal::validate_proposal;
+    use crate::models::ValidationOptions;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_imported_change_passes_validation() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        let mut import = ImportedChange::new("OpenSpec");
+        import.summary = "Import auth specs".to_string();
+        import.specs.push(ImportedSpec {
+            id: "user-auth".to_string(),
+            title: "User Auth".to_string(),
+            overview: "Login".to_string(),
+            requirements: vec![ImportedRequirement {
+                id: Some("FR-001".to_string()),
+                title: String::new(),
+                description: "Users can log in. Sessions last a day.".to_string(),
+                priority: "P1".to_string(),
+            }],
+            scenarios: Vec::new(),
+            source: root.join("specs/user-auth/spec.md"),
+        });
+        import.tasks.push(ImportedTask {
+            title: "Add login handler in src/auth.rs".to_string(),
+            group: "Phase 2: Core".to_string(),
+            file: task_file("Add login handler in src/auth.rs"),
+            spec_id: None,
+        });
+
+        let files = import.write("import-auth", root, false).unwrap();
+        assert_eq!(files, vec!["proposal.md", "specs/user-auth.md", "tasks.md"]);
+
+        let spec = std::fs::read_to_string(root.join("agentd/changes/import-auth/specs/user-auth.md")).unwrap();
+        assert!(spec.contains("### R1 - FR-001: Users can log in."));
+        assert!(spec.contains("Scenario: FR-001: Users can log in."));
+        let tasks = std::fs::read_to_string(root.join("agentd/changes/import-auth/tasks.md")).unwrap();
+        assert!(tasks.contains("file: src/auth.rs"));
+        assert!(tasks.contains("action: CREATE"));
+
+        let options = ValidationOptions::new().with_json(true);
+        let summary = validate_proposal("import-auth", &root.to_path_buf(), &options).unwrap();
+        assert!(summary.is_valid(), "{:?}", summary.errors);
+
+        // Existing changes are only replaced with force
+        assert!(import.write("import-auth", root, false).is_err());
+        assert!(import.write("import-auth", root, true).is_ok());
+
+        // Ids that escape agentd/changes/ are rejected before anything is removed
+        std::fs::create_dir_all(root.join("keep")).unwrap();
+        for change_id in ["../../keep", "..", "Import-Auth", ""] {
+            let err = import.write(change_id, root, true).unwrap_err();
+            assert!(
+                err.to_string().contains("Invalid change_id"),
+                "{}",
+                change_id
+            );
+        }
+        assert!(root.join("keep").is_dir());
+        assert!(root.join("agentd/changes").is_dir());
+    }
+
+    #[test]
+    fn test_requirement_ids() {
+        let requirement = |id: Option<&str>, title: &str| ImportedRequirement {
+            id: id.map(String::from),
+            title: title.to_string(),
+            ..Default::default()
+        };
+        let data = requirement_data(&[
+            requirement(Some("R2"), "Kept"),
+            requirement(None, "Numbered"),
+            requirement(Some("FR-003"), "Prefixed"),
+            requirement(Some("R2"), "Duplicate"),
+        ]);
+        let ids: Vec<&str> = data.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["R2", "R1", "R3", "R4"]);
+        assert_eq!(data[2].title, "FR-003: Prefixed");
+        assert_eq!(data[3].title, "R2: Duplicate");
+    }
+
+    #[test]
+    fn test_parse_task_list() {
+        let tasks = parse_task_list(
+            "## Phase 1: Setup\n- [ ] T001 [P] Create `src/db.rs` schema\n\n## 2. Core\n- [x] 2.1 Wire handler in src/api/routes.rs\n- not a task\n",
+        );
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].title, "Create src/db.rs schema");
+        assert_eq!(tasks[0].group, "Phase 1: Setup");
+        assert_eq!(tasks[0].file.as_deref(), Some("src/db.rs"));
+        assert_eq!(tasks[1].title, "Wire handler in src/api/routes.rs");
+        assert_eq!(tasks[1].file.as_deref(), Some("src/api/routes.rs"));
+    }
+
+    #[test]
+    fn test_slugify_and_task_file() {
+        assert_eq!(slugify("001 User Auth!"), "001-user-auth");
+        assert_eq!(slugify("Payments_API"), "payments-api");
+        assert_eq!(task_file("T012 [P] Create model in src/models/user.py"), Some("src/models/user.py".to_string()));
+        assert_eq!(task_file("Update `config.yaml` defaults"), Some("config.yaml".to_string()));
+        assert_eq!(task_file("Write the docs"), None);
+    }
+}
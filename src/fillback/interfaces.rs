@@ -0,0 +1,1454 @@
+//! Interface Detection
+//!
+//! Detects the externally visible surface of a codebase from syntax trees:
+//! HTTP routes declared through common web frameworks (axum, actix-web,
+//! Express, Fastify, Flask, FastAPI, Go net/http and gin) and CLI commands
+//! declared with clap. Detection is syntactic and best effort; routes mounted
+//! under a prefix in another file keep their local path.
+
+use crate::fillback::ast::{Import, SupportedLanguage};
+use crate::mcp::tools::mermaid;
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use tree_sitter::Node;
+
+/// HTTP methods recognized in route helpers (`get(...)`, `app.post(...)`)
+const HTTP_METHODS: &[&str] = &[
+    "get", "post", "put", "delete", "patch", "head", "options", "trace", "any", "all",
+];
+
+/// Maximum number of interfaces drawn in one sequence diagram
+const DIAGRAM_LIMIT: usize = 50;
+
+/// Kind of externally visible interface
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InterfaceKind {
+    Http,
+    Cli,
+}
+
+impl std::fmt::Display for InterfaceKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Http => write!(f, "http"),
+            Self::Cli => write!(f, "cli"),
+        }
+    }
+}
+
+/// Where an interface parameter comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParamSource {
+    /// Placeholder in the route path (`:id`, `{id}`, `<int:id>`)
+    Path,
+    /// Parameter of the handler function (extractors, query, body)
+    Handler,
+    /// CLI argument or flag
+    Argument,
+}
+
+impl std::fmt::Display for ParamSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Path => write!(f, "path"),
+            Self::Handler => write!(f, "handler"),
+            Self::Argument => write!(f, "argument"),
+        }
+    }
+}
+
+/// A parameter of an endpoint or command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterfaceParam {
+    pub name: String,
+    pub ty: Option<String>,
+    pub source: ParamSource,
+}
+
+/// An HTTP endpoint or CLI command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interface {
+    pub kind: InterfaceKind,
+    /// Framework the interface was declared with (e.g. "axum", "clap")
+    pub framework: String,
+    /// HTTP method in upper case; `None` for commands
+    pub method: Option<String>,
+    /// Route path, or command name for CLI commands
+    pub path: String,
+    /// Handler function or command variant, `None` for inline closures
+    pub handler: Option<String>,
+    pub params: Vec<InterfaceParam>,
+    pub doc: Option<String>,
+    pub line: usize,
+}
+
+impl Interface {
+    /// Short title such as `GET /users/:id` or `command init`
+    pub fn title(&self) -> String {
+        match self.kind {
+            InterfaceKind::Http => {
+                format!("{} {}", self.method.as_deref().unwrap_or("ANY"), self.path)
+            }
+            InterfaceKind::Cli => format!("command {}", self.path),
+        }
+    }
+
+    fn http(
+        framework: &str,
+        method: &str,
+        path: String,
+        handler: Option<String>,
+        node: &Node,
+    ) -> Self {
+        Self {
+            kind: InterfaceKind::Http,
+            framework: framework.to_string(),
+            method: Some(method.to_uppercase()),
+            path,
+            handler,
+            params: vec![],
+            doc: None,
+            line: node.start_position().row + 1,
+        }
+    }
+}
+
+/// Detect HTTP routes and CLI commands declared in a parsed file
+pub fn detect_interfaces(
+    root: &Node,
+    source: &str,
+    language: SupportedLanguage,
+    imports: &[Import],
+) -> Vec<Interface> {
+    let mut detector = Detector {
+        source,
+        language,
+        imports,
+        interfaces: Vec::new(),
+        functions: HashMap::new(),
+        groups: HashMap::new(),
+    };
+    detector.walk(root);
+    // Outer calls of a method chain are visited before inner ones
+    detector.interfaces.sort_by_key(|interface| interface.line);
+
+    // Attach handler parameters once every function in the file is known
+    let Detector {
+        mut interfaces,
+        functions,
+        ..
+    } = detector;
+    for interface in &mut interfaces {
+        if interface.kind == InterfaceKind::Http {
+            let mut params = path_params(&interface.path);
+            params.append(&mut interface.params);
+            interface.params = params;
+        }
+        let Some(params) = interface
+            .handler
+            .as_deref()
+            .and_then(|handler| functions.get(last_segment(handler)))
+        else {
+            continue;
+        };
+        for param in params {
+            match interface.params.iter_mut().find(|p| p.name == param.name) {
+                Some(existing) => existing.ty = param.ty.clone(),
+                None => interface.params.push(param.clone()),
+            }
+        }
+    }
+
+    interfaces
+}
+
+/// Render a sequence diagram skeleton for interfaces of one kind
+///
+/// Each entry pairs an interface with the module declaring it; the module
+/// becomes the participant answering the request. Returns `None` when no
+/// interface of `kind` is given.
+pub fn to_sequence_diagram(
+    entries: &[(&str, &Interface)],
+    kind: InterfaceKind,
+) -> Result<Option<String>> {
+    let entries: Vec<_> = entries
+        .iter()
+        .filter(|(_, interface)| interface.kind == kind)
+        .take(DIAGRAM_LIMIT)
+        .collect();
+    if entries.is_empty() {
+        return Ok(None);
+    }
+
+    let caller = match kind {
+        InterfaceKind::Http => json!({ "id": "client", "label": "Client", "type": "actor" }),
+        InterfaceKind::Cli => json!({ "id": "user", "label": "User", "type": "actor" }),
+    };
+    let caller_id = caller["id"].as_str().unwrap_or_default().to_string();
+    let mut participants = vec![caller];
+    let mut messages = Vec::new();
+    for (module, interface) in entries {
+        let module_id = format!("m_{}", mermaid_id(module));
+        if !participants.iter().any(|p| p["id"] == module_id.as_str()) {
+            participants.push(json!({ "id": module_id, "label": mermaid_text(module) }));
+        }
+
+        let request = match &interface.handler {
+            Some(handler) => format!("{} via {}", interface.title(), handler),
+            None => interface.title(),
+        };
+        let reply = match kind {
+            InterfaceKind::Http => "response",
+            InterfaceKind::Cli => "output and exit code",
+        };
+        messages.push(json!({ "from": caller_id, "to": module_id, "text": mermaid_text(&request), "type": "solid_open" }));
+        messages.push(
+            json!({ "from": module_id, "to": caller_id, "text": reply, "type": "dotted_open" }),
+        );
+    }
+
+    mermaid::call_tool(
+        "generate_mermaid_sequence",
+        &json!({ "participants": participants, "messages": messages }),
+    )
+    .map(Some)
+}
+
+struct Detector<'a> {
+    source: &'a str,
+    language: SupportedLanguage,
+    imports: &'a [Import],
+    interfaces: Vec<Interface>,
+    /// Parameters of every function in the file, by name
+    functions: HashMap<String, Vec<InterfaceParam>>,
+    /// gin router groups: variable name -> path prefix
+    groups: HashMap<String, String>,
+}
+
+impl Detector<'_> {
+    fn walk(&mut self, node: &Node) {
+        match self.language {
+            SupportedLanguage::Rust => self.detect_rust(node),
+            SupportedLanguage::Python => self.detect_python(node),
+            SupportedLanguage::JavaScript | SupportedLanguage::TypeScript => self.detect_js(node),
+            SupportedLanguage::Go => self.detect_go(node),
+            _ => return,
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.walk(&child);
+        }
+    }
+
+    fn text(&self, node: &Node) -> &str {
+        node.utf8_text(self.source.as_bytes()).unwrap_or_default()
+    }
+
+    /// Whether the file imports (or requires) the given package
+    fn uses(&self, package: &str) -> bool {
+        self.imports.iter().any(|import| {
+            import.path == package
+                || import.path.starts_with(&format!("{}::", package))
+                || import.path.starts_with(&format!("{}.", package))
+                || import.path.starts_with(&format!("{}/", package))
+        }) || self.source.contains(&format!("require('{}')", package))
+            || self.source.contains(&format!("require(\"{}\")", package))
+    }
+
+    /// Value of a plain string literal; interpolated strings yield `None`
+    fn string_value(&self, node: &Node) -> Option<String> {
+        if !node.kind().contains("string") {
+            return None;
+        }
+        let text = self.text(node);
+        if text.contains("${") || text.starts_with('f') {
+            return None;
+        }
+        Some(
+            text.trim_start_matches(['r', 'b', 'u', '#'])
+                .trim_matches(['"', '\'', '`', '#'])
+                .to_string(),
+        )
+    }
+
+    /// Handler name from a handler expression; inline closures have none
+    fn handler_name(&self, node: &Node) -> Option<String> {
+        match node.kind() {
+            "identifier"
+            | "scoped_identifier"
+            | "member_expression"
+            | "selector_expression"
+            | "field_expression"
+            | "attribute" => Some(self.text(node).to_string()),
+            _ => None,
+        }
+    }
+
+    fn record_function(&mut self, name: &str, params: Vec<InterfaceParam>) {
+        self.functions.entry(name.to_string()).or_insert(params);
+    }
+
+    // Rust: axum/actix routes, actix/rocket attributes, clap commands
+
+    fn detect_rust(&mut self, node: &Node) {
+        match node.kind() {
+            "function_item" => {
+                let Some(name) = node.child_by_field_name("name") else {
+                    return;
+                };
+                let name = self.text(&name).to_string();
+                let params = self.rust_params(node);
+                self.record_function(&name, params);
+
+                // #[get("/path")] on the handler (actix-web, rocket)
+                let framework = if self.uses("rocket") {
+                    "rocket"
+                } else {
+                    "actix-web"
+                };
+                for attribute in preceding_attributes(node) {
+                    if let Some((method, path)) = self.rust_route_attribute(&attribute) {
+                        let interface =
+                            Interface::http(framework, &method, path, Some(name.clone()), node);
+                        self.interfaces.push(interface);
+                    }
+                }
+            }
+            "enum_item" => {
+                let is_subcommand = preceding_attributes(node).iter().any(|a| {
+                    self.text(a).contains("derive") && self.text(a).contains("Subcommand")
+                });
+                if is_subcommand {
+                    self.clap_subcommands(node);
+                }
+            }
+            "call_expression" => {
+                let Some(function) = node.child_by_field_name("function") else {
+                    return;
+                };
+                if function.kind() != "field_expression" {
+                    return;
+                }
+                let method = function
+                    .child_by_field_name("field")
+                    .map(|f| self.text(&f).to_string())
+                    .unwrap_or_default();
+                let args = arguments(node);
+                match method.as_str() {
+                    "route" => self.rust_route_call(&function, &args),
+                    "subcommand" => {
+                        if let Some(command) = args.first() {
+                            self.clap_builder(command);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn rust_params(&self, function: &Node) -> Vec<InterfaceParam> {
+        let Some(parameters) = function.child_by_field_name("parameters") else {
+            return vec![];
+        };
+        let mut cursor = parameters.walk();
+        parameters
+            .named_children(&mut cursor)
+            .filter(|p| p.kind() == "parameter")
+            .filter_map(|p| {
+                Some(InterfaceParam {
+                    name: self.text(&p.child_by_field_name("pattern")?).to_string(),
+                    ty: p
+                        .child_by_field_name("type")
+                        .map(|t| self.text(&t).to_string()),
+                    source: ParamSource::Handler,
+                })
+            })
+            .collect()
+    }
+
+    /// `#[get("/users")]` -> ("get", "/users")
+    fn rust_route_attribute(&self, attribute_item: &Node) -> Option<(String, String)> {
+        let attribute = attribute_item.named_child(0)?;
+        let name = attribute.named_child(0)?;
+        let method = last_segment(self.text(&name)).to_string();
+        if !HTTP_METHODS.contains(&method.as_str()) {
+            return None;
+        }
+        let arguments = attribute.child_by_field_name("arguments")?;
+        let path = arguments
+            .named_child(0)
+            .and_then(|p| self.string_value(&p))?;
+        path.starts_with('/').then_some((method, path))
+    }
+
+    /// `.route("/p", get(a).post(b))` (axum), `.route("/p", web::get().to(a))`
+    /// and `web::resource("/p").route(web::get().to(a))` (actix-web)
+    fn rust_route_call(&mut self, function: &Node, args: &[Node]) {
+        let (path, router) = match args {
+            [path, router] => match self.string_value(path) {
+                Some(path) => (path, *router),
+                None => return,
+            },
+            [router] => {
+                let resource = function
+                    .child_by_field_name("value")
+                    .and_then(|receiver| self.find_call(&receiver, &["resource"]));
+                match resource
+                    .and_then(|r| arguments(&r).first().and_then(|p| self.string_value(p)))
+                {
+                    Some(path) => (path, *router),
+                    None => return,
+                }
+            }
+            _ => return,
+        };
+
+        let mut methods = Vec::new();
+        let framework = if self.actix_route(&router, &mut methods) {
+            "actix-web"
+        } else {
+            self.axum_methods(&router, &mut methods);
+            "axum"
+        };
+        // Chained .route() calls share a start line; the router argument doesn't
+        for (method, handler) in methods {
+            self.interfaces.push(Interface::http(
+                framework,
+                &method,
+                path.clone(),
+                handler,
+                &router,
+            ));
+        }
+    }
+
+    /// Collect `get(a).post(b)` method routers
+    fn axum_methods(&self, node: &Node, methods: &mut Vec<(String, Option<String>)>) {
+        if node.kind() != "call_expression" {
+            return;
+        }
+        let Some(function) = node.child_by_field_name("function") else {
+            return;
+        };
+        let handler = arguments(node).first().and_then(|h| self.handler_name(h));
+        let method = match function.kind() {
+            "field_expression" => {
+                if let Some(receiver) = function.child_by_field_name("value") {
+                    self.axum_methods(&receiver, methods);
+                }
+                function.child_by_field_name("field").map(|f| self.text(&f))
+            }
+            "identifier" | "scoped_identifier" => Some(last_segment(self.text(&function))),
+            _ => None,
+        };
+        if let Some(method) = method.filter(|m| HTTP_METHODS.contains(m)) {
+            methods.push((method.to_string(), handler));
+        }
+    }
+
+    /// `web::get().to(handler)`
+    fn actix_route(&self, node: &Node, methods: &mut Vec<(String, Option<String>)>) -> bool {
+        let Some(function) = node.child_by_field_name("function") else {
+            return false;
+        };
+        if function.kind() != "field_expression"
+            || function.child_by_field_name("field").map(|f| self.text(&f)) != Some("to")
+        {
+            return false;
+        }
+        let method = function
+            .child_by_field_name("value")
+            .and_then(|receiver| receiver.child_by_field_name("function"))
+            .map(|f| last_segment(self.text(&f)).to_string())
+            .filter(|m| HTTP_METHODS.contains(&m.as_str()));
+        let Some(method) = method else {
+            return false;
+        };
+        let handler = arguments(node).first().and_then(|h| self.handler_name(h));
+        methods.push((method, handler));
+        true
+    }
+
+    /// Find a call to one of `names` along a method chain
+    fn find_call<'t>(&self, node: &Node<'t>, names: &[&str]) -> Option<Node<'t>> {
+        let mut current = *node;
+        loop {
+            if current.kind() != "call_expression" {
+                return None;
+            }
+            let function = current.child_by_field_name("function")?;
+            if names.contains(&last_segment(self.text(&function))) {
+                return Some(current);
+            }
+            current = function.child_by_field_name("value")?;
+        }
+    }
+
+    /// Variants of a `#[derive(Subcommand)]` enum
+    fn clap_subcommands(&mut self, node: &Node) {
+        let enum_name = node
+            .child_by_field_name("name")
+            .map(|n| self.text(&n).to_string())
+            .unwrap_or_default();
+        let Some(body) = node.child_by_field_name("body") else {
+            return;
+        };
+
+        let mut cursor = body.walk();
+        for variant in body.named_children(&mut cursor) {
+            if variant.kind() != "enum_variant" {
+                continue;
+            }
+            let Some(name) = variant.child_by_field_name("name") else {
+                continue;
+            };
+            let name = self.text(&name).to_string();
+
+            let attributes = preceding_attributes(&variant);
+            let command = attributes
+                .iter()
+                .find_map(|a| self.clap_name_override(a))
+                .unwrap_or_else(|| kebab_case(&name));
+
+            let params = variant
+                .child_by_field_name("body")
+                .map(|fields| self.clap_fields(&fields))
+                .unwrap_or_default();
+
+            self.interfaces.push(Interface {
+                kind: InterfaceKind::Cli,
+                framework: "clap".to_string(),
+                method: None,
+                path: command,
+                handler: Some(format!("{}::{}", enum_name, name)),
+                params,
+                doc: preceding_doc(&variant, self.source),
+                line: variant.start_position().row + 1,
+            });
+        }
+    }
+
+    /// `#[command(name = "x")]`
+    fn clap_name_override(&self, attribute_item: &Node) -> Option<String> {
+        let text = self.text(attribute_item);
+        if !text.contains("command") && !text.contains("clap") {
+            return None;
+        }
+        let arguments = attribute_item
+            .named_child(0)?
+            .child_by_field_name("arguments")?;
+        let mut cursor = arguments.walk();
+        let tokens: Vec<Node> = arguments.children(&mut cursor).collect();
+        tokens.windows(3).find_map(|window| {
+            (self.text(&window[0]) == "name" && self.text(&window[1]) == "=")
+                .then(|| self.string_value(&window[2]))
+                .flatten()
+        })
+    }
+
+    fn clap_fields(&self, fields: &Node) -> Vec<InterfaceParam> {
+        let mut cursor = fields.walk();
+        fields
+            .named_children(&mut cursor)
+            .filter_map(|field| match field.kind() {
+                "field_declaration" => Some(InterfaceParam {
+                    name: self.text(&field.child_by_field_name("name")?).to_string(),
+                    ty: field
+                        .child_by_field_name("type")
+                        .map(|t| self.text(&t).to_string()),
+                    source: ParamSource::Argument,
+                }),
+                kind if kind == "attribute_item" || kind.contains("comment") => None,
+                // Tuple variants wrap an Args struct: `Add(AddArgs)`
+                _ => Some(InterfaceParam {
+                    name: self.text(&field).to_string(),
+                    ty: Some(self.text(&field).to_string()),
+                    source: ParamSource::Argument,
+                }),
+            })
+            .collect()
+    }
+
+    /// `.subcommand(Command::new("serve").about("...").arg(Arg::new("port")))`
+    fn clap_builder(&mut self, node: &Node) {
+        let mut name = None;
+        let mut doc = None;
+        let mut params = Vec::new();
+        let mut current = *node;
+
+        while current.kind() == "call_expression" {
+            let Some(function) = current.child_by_field_name("function") else {
+                break;
+            };
+            let args = arguments(&current);
+            let first = args.first().and_then(|a| self.string_value(a));
+            match function.kind() {
+                "field_expression" => {
+                    let method = function.child_by_field_name("field").map(|f| self.text(&f));
+                    match method {
+                        Some("about") | Some("long_about") => doc = doc.or(first),
+                        Some("arg") => {
+                            let arg = args
+                                .first()
+                                .filter(|a| a.kind() == "call_expression")
+                                .and_then(|a| {
+                                    arguments(a).first().and_then(|n| self.string_value(n))
+                                });
+                            if let Some(arg) = arg {
+                                params.insert(
+                                    0,
+                                    InterfaceParam {
+                                        name: arg,
+                                        ty: None,
+                                        source: ParamSource::Argument,
+                                    },
+                                );
+                            }
+                        }
+                        _ => {}
+                    }
+                    let Some(receiver) = function.child_by_field_name("value") else {
+                        break;
+                    };
+                    current = receiver;
+                }
+                "scoped_identifier" => {
+                    let path = self.text(&function);
+                    if ["Command::new", "App::new", "SubCommand::with_name"]
+                        .iter()
+                        .any(|ctor| path.ends_with(ctor))
+                    {
+                        name = first;
+                    }
+                    break;
+                }
+                _ => break,
+            }
+        }
+
+        if let Some(name) = name {
+            self.interfaces.push(Interface {
+                kind: InterfaceKind::Cli,
+                framework: "clap".to_string(),
+                method: None,
+                path: name,
+                handler: None,
+                params,
+                doc,
+                line: node.start_position().row + 1,
+            });
+        }
+    }
+
+    // Python: Flask and FastAPI decorators
+
+    fn detect_python(&mut self, node: &Node) {
+        match node.kind() {
+            "function_definition" => {
+                if let Some(name) = node.child_by_field_name("name") {
+                    let name = self.text(&name).to_string();
+                    let params = self.python_params(node);
+                    self.record_function(&name, params);
+                }
+            }
+            "decorated_definition" => {
+                let framework = if self.uses("fastapi") {
+                    "fastapi"
+                } else if self.uses("flask") {
+                    "flask"
+                } else {
+                    return;
+                };
+                let Some(definition) = node.child_by_field_name("definition") else {
+                    return;
+                };
+                let handler = definition
+                    .child_by_field_name("name")
+                    .map(|n| self.text(&n).to_string());
+
+                let mut cursor = node.walk();
+                let decorators: Vec<Node> = node
+                    .named_children(&mut cursor)
+                    .filter(|c| c.kind() == "decorator")
+                    .collect();
+                for decorator in decorators {
+                    for (method, path) in self.python_routes(&decorator) {
+                        let mut interface =
+                            Interface::http(framework, &method, path, handler.clone(), &decorator);
+                        interface.doc = python_docstring(&definition, self.source);
+                        self.interfaces.push(interface);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn python_params(&self, function: &Node) -> Vec<InterfaceParam> {
+        let Some(parameters) = function.child_by_field_name("parameters") else {
+            return vec![];
+        };
+        let mut cursor = parameters.walk();
+        parameters
+            .named_children(&mut cursor)
+            .filter_map(|p| {
+                let (name, ty) = match p.kind() {
+                    "identifier" => (p, None),
+                    "typed_parameter" => (p.named_child(0)?, p.child_by_field_name("type")),
+                    "default_parameter" | "typed_default_parameter" => (
+                        p.child_by_field_name("name")?,
+                        p.child_by_field_name("type"),
+                    ),
+                    _ => return None,
+                };
+                let name = self.text(&name);
+                (name != "self" && name != "cls").then(|| InterfaceParam {
+                    name: name.to_string(),
+                    ty: ty.map(|t| self.text(&t).to_string()),
+                    source: ParamSource::Handler,
+                })
+            })
+            .collect()
+    }
+
+    /// `@app.route("/p", methods=[...])`, `@app.get("/p")`, `@router.post("/p")`
+    fn python_routes(&self, decorator: &Node) -> Vec<(String, String)> {
+        let Some(call) = decorator.named_child(0).filter(|c| c.kind() == "call") else {
+            return vec![];
+        };
+        let Some(attribute) = call
+            .child_by_field_name("function")
+            .filter(|f| f.kind() == "attribute")
+            .and_then(|f| f.child_by_field_name("attribute"))
+        else {
+            return vec![];
+        };
+        let args = arguments(&call);
+        let Some(path) = args.first().and_then(|p| self.string_value(p)) else {
+            return vec![];
+        };
+
+        match self.text(&attribute) {
+            "route" | "api_route" => {
+                let methods: Vec<String> = args
+                    .iter()
+                    .filter(|a| a.kind() == "keyword_argument")
+                    .filter(|a| {
+                        a.child_by_field_name("name").map(|n| self.text(&n)) == Some("methods")
+                    })
+                    .filter_map(|a| a.child_by_field_name("value"))
+                    .flat_map(|list| {
+                        let mut cursor = list.walk();
+                        list.named_children(&mut cursor).collect::<Vec<_>>()
+                    })
+                    .filter_map(|m| self.string_value(&m))
+                    .collect();
+                let methods = if methods.is_empty() {
+                    vec!["GET".to_string()]
+                } else {
+                    methods
+                };
+                methods.into_iter().map(|m| (m, path.clone())).collect()
+            }
+            method if HTTP_METHODS.contains(&method) => vec![(method.to_string(), path)],
+            _ => vec![],
+        }
+    }
+
+    // JavaScript/TypeScript: Express and Fastify
+
+    fn detect_js(&mut self, node: &Node) {
+        match node.kind() {
+            "function_declaration" | "method_definition" => {
+                if let Some(name) = node.child_by_field_name("name") {
+                    let name = self.text(&name).to_string();
+                    let params = self.js_params(node);
+                    self.record_function(&name, params);
+                }
+            }
+            "variable_declarator" => {
+                let value = node.child_by_field_name("value");
+                if let (Some(name), Some(value)) = (node.child_by_field_name("name"), value) {
+                    if matches!(
+                        value.kind(),
+                        "arrow_function" | "function_expression" | "function"
+                    ) {
+                        let name = self.text(&name).to_string();
+                        let params = self.js_params(&value);
+                        self.record_function(&name, params);
+                    }
+                }
+            }
+            "call_expression" => {
+                let framework = if self.uses("fastify") {
+                    "fastify"
+                } else if self.uses("express") {
+                    "express"
+                } else {
+                    return;
+                };
+                self.js_route(node, framework);
+            }
+            _ => {}
+        }
+    }
+
+    fn js_params(&self, function: &Node) -> Vec<InterfaceParam> {
+        let Some(parameters) = function.child_by_field_name("parameters") else {
+            return vec![];
+        };
+        let mut cursor = parameters.walk();
+        parameters
+            .named_children(&mut cursor)
+            .filter_map(|p| {
+                let (name, ty) = match p.kind() {
+                    "identifier" => (p, None),
+                    "required_parameter" | "optional_parameter" => (
+                        p.child_by_field_name("pattern")?,
+                        p.child_by_field_name("type"),
+                    ),
+                    "assignment_pattern" => (p.child_by_field_name("left")?, None),
+                    _ => return None,
+                };
+                Some(InterfaceParam {
+                    name: self.text(&name).to_string(),
+                    ty: ty.map(|t| self.text(&t).trim_start_matches(':').trim().to_string()),
+                    source: ParamSource::Handler,
+                })
+            })
+            .collect()
+    }
+
+    /// `app.get('/p', ...handlers)`, `app.route('/p').get(h)` and
+    /// `fastify.route({ method, url, handler })`
+    fn js_route(&mut self, node: &Node, framework: &str) {
+        let Some(function) = node
+            .child_by_field_name("function")
+            .filter(|f| f.kind() == "member_expression")
+        else {
+            return;
+        };
+        let Some(method) = function
+            .child_by_field_name("property")
+            .map(|p| self.text(&p).to_string())
+        else {
+            return;
+        };
+        let args = arguments(node);
+
+        if method == "route" {
+            if let Some(options) = args.first().filter(|a| a.kind() == "object") {
+                self.js_route_object(node, options, framework);
+            }
+            return;
+        }
+        if !HTTP_METHODS.contains(&method.as_str()) {
+            return;
+        }
+
+        let path = match args.first().and_then(|a| self.string_value(a)) {
+            Some(path) if args.len() >= 2 => path,
+            // Express route chain: app.route('/p').get(handler)
+            _ => {
+                let route = function
+                    .child_by_field_name("object")
+                    .filter(|o| o.kind() == "call_expression")
+                    .filter(|o| {
+                        o.child_by_field_name("function")
+                            .and_then(|f| f.child_by_field_name("property"))
+                            .map(|p| self.text(&p))
+                            == Some("route")
+                    });
+                match route.and_then(|r| arguments(&r).first().and_then(|p| self.string_value(p))) {
+                    Some(path) => path,
+                    None => return,
+                }
+            }
+        };
+        if !path.starts_with('/') && path != "*" {
+            return;
+        }
+
+        let handler_node = args.last();
+        let handler = handler_node.and_then(|h| self.handler_name(h));
+        let mut interface = Interface::http(framework, &method, path, handler, node);
+        if let Some(inline) = handler_node.filter(|h| {
+            matches!(
+                h.kind(),
+                "arrow_function" | "function_expression" | "function"
+            )
+        }) {
+            interface.params = self.js_params(inline);
+        }
+        self.interfaces.push(interface);
+    }
+
+    fn js_route_object(&mut self, node: &Node, options: &Node, framework: &str) {
+        let mut fields: HashMap<String, Node> = HashMap::new();
+        let mut cursor = options.walk();
+        for pair in options
+            .named_children(&mut cursor)
+            .filter(|p| p.kind() == "pair")
+        {
+            if let (Some(key), Some(value)) = (
+                pair.child_by_field_name("key"),
+                pair.child_by_field_name("value"),
+            ) {
+                fields.insert(self.text(&key).trim_matches(['"', '\'']).to_string(), value);
+            }
+        }
+
+        let path = fields
+            .get("url")
+            .or_else(|| fields.get("path"))
+            .and_then(|p| self.string_value(p));
+        let Some(path) = path else {
+            return;
+        };
+        let handler = fields.get("handler").and_then(|h| self.handler_name(h));
+        let methods: Vec<String> = match fields.get("method") {
+            Some(method) if method.kind() == "array" => {
+                let mut cursor = method.walk();
+                method
+                    .named_children(&mut cursor)
+                    .filter_map(|m| self.string_value(&m))
+                    .collect()
+            }
+            Some(method) => self.string_value(method).into_iter().collect(),
+            None => vec![],
+        };
+        for method in methods {
+            self.interfaces.push(Interface::http(
+                framework,
+                &method,
+                path.clone(),
+                handler.clone(),
+                node,
+            ));
+        }
+    }
+
+    // Go: net/http and gin
+
+    fn detect_go(&mut self, node: &Node) {
+        match node.kind() {
+            "function_declaration" | "method_declaration" => {
+                if let Some(name) = node.child_by_field_name("name") {
+                    let name = self.text(&name).to_string();
+                    let params = self.go_params(node);
+                    self.record_function(&name, params);
+                }
+            }
+            // v1 := router.Group("/v1")
+            "short_var_declaration" | "assignment_statement" => {
+                let left = node
+                    .child_by_field_name("left")
+                    .and_then(|l| l.named_child(0));
+                let right = node
+                    .child_by_field_name("right")
+                    .and_then(|r| r.named_child(0));
+                if let (Some(left), Some(right)) = (left, right) {
+                    if let Some((operand, "Group", args)) = self.go_selector_call(&right) {
+                        if let Some(path) = args.first().and_then(|p| self.string_value(p)) {
+                            let prefix = self.groups.get(&operand).cloned().unwrap_or_default();
+                            self.groups.insert(
+                                self.text(&left).to_string(),
+                                format!("{}{}", prefix, path),
+                            );
+                        }
+                    }
+                }
+            }
+            "call_expression" => self.go_route(node),
+            _ => {}
+        }
+    }
+
+    fn go_params(&self, function: &Node) -> Vec<InterfaceParam> {
+        let Some(parameters) = function.child_by_field_name("parameters") else {
+            return vec![];
+        };
+        let mut params = Vec::new();
+        let mut cursor = parameters.walk();
+        for declaration in parameters.named_children(&mut cursor) {
+            let ty = declaration
+                .child_by_field_name("type")
+                .map(|t| self.text(&t).to_string());
+            let mut names = declaration.walk();
+            for name in declaration.children_by_field_name("name", &mut names) {
+                params.push(InterfaceParam {
+                    name: self.text(&name).to_string(),
+                    ty: ty.clone(),
+                    source: ParamSource::Handler,
+                });
+            }
+        }
+        params
+    }
+
+    /// Split `operand.Method(args)` into its parts
+    fn go_selector_call<'t>(&self, node: &Node<'t>) -> Option<(String, &str, Vec<Node<'t>>)> {
+        if node.kind() != "call_expression" {
+            return None;
+        }
+        let function = node.child_by_field_name("function")?;
+        if function.kind() != "selector_expression" {
+            return None;
+        }
+        let operand = self
+            .text(&function.child_by_field_name("operand")?)
+            .to_string();
+        let field = self.text(&function.child_by_field_name("field")?);
+        Some((operand, field, arguments(node)))
+    }
+
+    fn go_route(&mut self, node: &Node) {
+        let Some((operand, method, args)) = self.go_selector_call(node) else {
+            return;
+        };
+        let Some(pattern) = args.first().and_then(|p| self.string_value(p)) else {
+            return;
+        };
+        let handler = args.last().and_then(|h| self.handler_name(h));
+
+        match method {
+            "HandleFunc" | "Handle" if self.uses("net/http") => {
+                // Go 1.22 patterns may carry the method: "GET /items/{id}"
+                let (method, path) = match pattern.split_once(' ') {
+                    Some((method, path)) => (method.to_string(), path.trim().to_string()),
+                    None => ("ANY".to_string(), pattern),
+                };
+                self.interfaces
+                    .push(Interface::http("net/http", &method, path, handler, node));
+            }
+            "GET" | "POST" | "PUT" | "DELETE" | "PATCH" | "HEAD" | "OPTIONS" | "Any"
+                if self.uses("github.com/gin-gonic/gin") =>
+            {
+                let prefix = self.groups.get(&operand).cloned().unwrap_or_default();
+                let path = format!("{}{}", prefix, pattern);
+                self.interfaces
+                    .push(Interface::http("gin", method, path, handler, node));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Named call arguments, skipping comments
+fn arguments<'t>(call: &Node<'t>) -> Vec<Node<'t>> {
+    let Some(arguments) = call.child_by_field_name("arguments") else {
+        return vec![];
+    };
+    let mut cursor = arguments.walk();
+    arguments
+        .named_children(&mut cursor)
+        .filter(|a| !a.kind().contains("comment"))
+        .collect()
+}
+
+/// Attribute items directly above a Rust item, skipping doc comments
+fn preceding_attributes<'t>(node: &Node<'t>) -> Vec<Node<'t>> {
+    let mut attributes = Vec::new();
+    let mut sibling = node.prev_named_sibling();
+    while let Some(current) = sibling {
+        match current.kind() {
+            "attribute_item" => attributes.push(current),
+            "line_comment" | "block_comment" => {}
+            _ => break,
+        }
+        sibling = current.prev_named_sibling();
+    }
+    attributes
+}
+
+/// `///` doc comment above a Rust item
+fn preceding_doc(node: &Node, source: &str) -> Option<String> {
+    let mut lines = Vec::new();
+    let mut sibling = node.prev_named_sibling();
+    while let Some(current) = sibling {
+        match current.kind() {
+            "attribute_item" => {}
+            "line_comment" => {
+                let text = current.utf8_text(source.as_bytes()).unwrap_or_default();
+                match text.strip_prefix("///") {
+                    Some(doc) => lines.push(doc.trim().to_string()),
+                    None => break,
+                }
+            }
+            _ => break,
+        }
+        sibling = current.prev_named_sibling();
+    }
+    lines.reverse();
+    (!lines.is_empty()).then(|| lines.join(" "))
+}
+
+/// First line of a Python function docstring
+fn python_docstring(function: &Node, source: &str) -> Option<String> {
+    let body = function.child_by_field_name("body")?;
+    let statement = body
+        .named_child(0)
+        .filter(|s| s.kind() == "expression_statement")?;
+    let string = statement.named_child(0).filter(|s| s.kind() == "string")?;
+    let text = string.utf8_text(source.as_bytes()).ok()?;
+    let doc = text.trim_matches(['"', '\'']).trim();
+    doc.lines().next().map(str::to_string)
+}
+
+/// Placeholders in a route path: `:id`, `{id}`, `{id:int}`, `<int:id>`
+fn path_params(path: &str) -> Vec<InterfaceParam> {
+    path.split('/')
+        .filter_map(|segment| {
+            let name = if let Some(name) = segment.strip_prefix(':') {
+                name
+            } else if let Some(inner) = segment.strip_prefix('{').and_then(|s| s.strip_suffix('}'))
+            {
+                inner.split(':').next()?.trim_end_matches("...")
+            } else if let Some(inner) = segment.strip_prefix('<').and_then(|s| s.strip_suffix('>'))
+            {
+                inner.rsplit(':').next()?
+            } else {
+                return None;
+            };
+            (!name.is_empty()).then(|| InterfaceParam {
+                name: name.to_string(),
+                ty: None,
+                source: ParamSource::Path,
+            })
+        })
+        .collect()
+}
+
+/// Last path segment of a handler (`handlers::list` -> `list`)
+fn last_segment(path: &str) -> &str {
+    path.rsplit([':', '.']).next().unwrap_or(path)
+}
+
+/// clap's default command name for a variant (`AddTask` -> `add-task`)
+fn kebab_case(name: &str) -> String {
+    let mut result = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            result.push('-');
+        }
+        result.extend(c.to_lowercase());
+    }
+    result
+}
+
+fn mermaid_id(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn mermaid_text(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !matches!(c, '{' | '}' | '"' | ';' | '#' | '\n'))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fillback::ast::AstAnalyzer;
+    use std::path::PathBuf;
+
+    fn detect(path: &str, content: &str) -> Vec<Interface> {
+        let mut analyzer = AstAnalyzer::new().unwrap();
+        analyzer
+            .parse_file(&PathBuf::from(path), content)
+            .unwrap()
+            .interfaces
+    }
+
+    fn titles(interfaces: &[Interface]) -> Vec<String> {
+        interfaces.iter().map(|i| i.title()).collect()
+    }
+
+    fn param<'a>(interface: &'a Interface, name: &str) -> &'a InterfaceParam {
+        interface
+            .params
+            .iter()
+            .find(|p| p.name == name)
+            .unwrap_or_else(|| panic!("missing param {}", name))
+    }
+
+    #[test]
+    fn test_detect_axum_routes() {
+        let interfaces = detect(
+            "routes.rs",
+            r#"
+use axum::{routing::get, Json, Router};
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/users", get(list_users).post(handlers::create_user))
+        .route("/users/:id", get(get_user))
+}
+
+async fn get_user(Path(id): Path<u32>) -> Json<User> {
+    todo!()
+}
+"#,
+        );
+
+        assert_eq!(
+            titles(&interfaces),
+            vec!["GET /users", "POST /users", "GET /users/:id"]
+        );
+        assert!(interfaces.iter().all(|i| i.framework == "axum"));
+        assert_eq!(
+            interfaces[1].handler.as_deref(),
+            Some("handlers::create_user")
+        );
+
+        let get_user = &interfaces[2];
+        assert_eq!(param(get_user, "id").source, ParamSource::Path);
+        assert_eq!(param(get_user, "Path(id)").ty.as_deref(), Some("Path<u32>"));
+    }
+
+    #[test]
+    fn test_detect_actix_routes() {
+        let interfaces = detect(
+            "api.rs",
+            r#"
+use actix_web::{get, web, App};
+
+#[get("/items/{id}")]
+async fn item(path: web::Path<u32>) -> impl Responder {
+    ""
+}
+
+fn config(cfg: &mut web::ServiceConfig) {
+    cfg.route("/health", web::get().to(health))
+        .service(web::resource("/items").route(web::post().to(create_item)));
+}
+"#,
+        );
+
+        assert_eq!(
+            titles(&interfaces),
+            vec!["GET /items/{id}", "GET /health", "POST /items"]
+        );
+        assert!(interfaces.iter().all(|i| i.framework == "actix-web"));
+        assert_eq!(param(&interfaces[0], "id").source, ParamSource::Path);
+        assert_eq!(interfaces[2].handler.as_deref(), Some("create_item"));
+    }
+
+    #[test]
+    fn test_detect_clap_commands() {
+        let interfaces = detect(
+            "main.rs",
+            r#"
+use clap::{Command, Parser, Subcommand};
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Initialize a project
+    Init {
+        /// Project name
+        #[arg(long)]
+        name: String,
+    },
+    #[command(name = "ls")]
+    ListTasks(ListArgs),
+}
+
+fn build() -> Command {
+    Command::new("app").subcommand(Command::new("serve").about("Run the server").arg(Arg::new("port")))
+}
+"#,
+        );
+
+        assert_eq!(
+            titles(&interfaces),
+            vec!["command init", "command ls", "command serve"]
+        );
+        let init = &interfaces[0];
+        assert_eq!(init.framework, "clap");
+        assert_eq!(init.handler.as_deref(), Some("Commands::Init"));
+        assert_eq!(init.doc.as_deref(), Some("Initialize a project"));
+        assert_eq!(param(init, "name").ty.as_deref(), Some("String"));
+        assert_eq!(
+            param(&interfaces[1], "ListArgs").source,
+            ParamSource::Argument
+        );
+        assert_eq!(interfaces[2].doc.as_deref(), Some("Run the server"));
+        assert_eq!(interfaces[2].params[0].name, "port");
+    }
+
+    #[test]
+    fn test_detect_python_routes() {
+        let flask = detect(
+            "app.py",
+            r#"
+from flask import Flask
+
+@app.route("/users/<int:uid>", methods=["GET", "DELETE"])
+def user(uid):
+    """Fetch or delete a user."""
+    pass
+"#,
+        );
+        assert_eq!(
+            titles(&flask),
+            vec!["GET /users/<int:uid>", "DELETE /users/<int:uid>"]
+        );
+        assert_eq!(flask[0].framework, "flask");
+        assert_eq!(flask[0].doc.as_deref(), Some("Fetch or delete a user."));
+        assert_eq!(param(&flask[0], "uid").source, ParamSource::Path);
+
+        let fastapi = detect(
+            "main.py",
+            r#"
+from fastapi import FastAPI
+
+@router.post("/items/{item_id}")
+async def update(item_id: int, item: Item, q: str = None):
+    pass
+"#,
+        );
+        assert_eq!(titles(&fastapi), vec!["POST /items/{item_id}"]);
+        assert_eq!(fastapi[0].framework, "fastapi");
+        assert_eq!(param(&fastapi[0], "item_id").ty.as_deref(), Some("int"));
+        assert_eq!(param(&fastapi[0], "item").source, ParamSource::Handler);
+        assert_eq!(param(&fastapi[0], "q").ty.as_deref(), Some("str"));
+
+        // Decorators of unknown frameworks are ignored
+        assert!(detect("x.py", "@app.get('/x')\ndef x():\n    pass\n").is_empty());
+    }
+
+    #[test]
+    fn test_detect_js_routes() {
+        let express = detect(
+            "server.js",
+            r#"
+const express = require('express');
+const app = express();
+
+app.get('/users/:id', auth, getUser);
+app.route('/books').post((req, res) => {});
+cache.get('key', fallback);
+
+function getUser(req, res) {}
+"#,
+        );
+        assert_eq!(titles(&express), vec!["GET /users/:id", "POST /books"]);
+        assert_eq!(express[0].framework, "express");
+        assert_eq!(express[0].handler.as_deref(), Some("getUser"));
+        assert_eq!(param(&express[0], "req").source, ParamSource::Handler);
+        assert_eq!(express[1].handler, None);
+
+        let fastify = detect(
+            "server.ts",
+            r#"
+import Fastify from 'fastify';
+
+fastify.route({ method: ['PUT', 'PATCH'], url: '/items/:id', handler: update });
+fastify.get('/health', { logLevel: 'warn' }, async () => 'ok');
+
+const update = async (request: ItemRequest) => {};
+"#,
+        );
+        assert_eq!(
+            titles(&fastify),
+            vec!["PUT /items/:id", "PATCH /items/:id", "GET /health"]
+        );
+        assert_eq!(fastify[0].framework, "fastify");
+        assert_eq!(
+            param(&fastify[0], "request").ty.as_deref(),
+            Some("ItemRequest")
+        );
+    }
+
+    #[test]
+    fn test_detect_go_routes() {
+        let net_http = detect(
+            "main.go",
+            r#"
+package main
+
+import "net/http"
+
+func main() {
+	http.HandleFunc("/health", health)
+	mux.HandleFunc("GET /items/{id}", getItem)
+}
+
+func getItem(w http.ResponseWriter, r *http.Request) {}
+"#,
+        );
+        assert_eq!(titles(&net_http), vec!["ANY /health", "GET /items/{id}"]);
+        assert_eq!(net_http[1].framework, "net/http");
+        assert_eq!(
+            param(&net_http[1], "r").ty.as_deref(),
+            Some("*http.Request")
+        );
+
+        let gin = detect(
+            "router.go",
+            r#"
+package api
+
+import "github.com/gin-gonic/gin"
+
+func Routes(r *gin.Engine) {
+	v1 := r.Group("/v1")
+	admin := v1.Group("/admin")
+	v1.GET("/users/:id", h.GetUser)
+	admin.POST("/reset", reset)
+}
+"#,
+        );
+        assert_eq!(
+            titles(&gin),
+            vec!["GET /v1/users/:id", "POST /v1/admin/reset"]
+        );
+        assert_eq!(gin[0].framework, "gin");
+        assert_eq!(gin[0].handler.as_deref(), Some("h.GetUser"));
+    }
+
+    #[test]
+    fn test_sequence_diagram() {
+        let interfaces = detect(
+            "routes.rs",
+            "use axum::Router;\nfn r() { Router::new().route(\"/users\", get(list)); }",
+        );
+        let entries: Vec<(&str, &Interface)> = interfaces.iter().map(|i| ("routes", i)).collect();
+
+        let diagram = to_sequence_diagram(&entries, InterfaceKind::Http)
+            .unwrap()
+            .unwrap();
+        assert!(diagram.contains("actor client as Client"));
+        assert!(diagram.contains("participant m_routes as routes"));
+        assert!(diagram.contains("client->>m_routes: GET /users via list"));
+        assert!(diagram.contains("m_routes-->>client: response"));
+
+        assert!(to_sequence_diagram(&entries, InterfaceKind::Cli)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_path_params_and_names() {
+        let names =
+            |path: &str| -> Vec<String> { path_params(path).into_iter().map(|p| p.name).collect() };
+        assert_eq!(
+            names("/a/:id/b/{slug}/<int:n>/{rest...}"),
+            vec!["id", "slug", "n", "rest"]
+        );
+        assert!(names("/plain/path").is_empty());
+        assert_eq!(kebab_case("AddTask"), "add-task");
+        assert_eq!(last_segment("handlers::list"), "list");
+    }
+}
@@ -1,13 +1,29 @@
 pub mod ast;
+pub mod cache;
+pub mod callgraph;
 pub mod code;
+pub mod datamodel;
+pub mod export;
 pub mod factory;
 pub mod graph;
+pub mod import;
+pub mod interfaces;
 pub mod openspec;
+pub mod scenarios;
 pub mod speckit;
 pub mod strategy;
+pub mod workspace;
 
-pub use ast::{AnalysisContext, AstAnalyzer, Import, ModuleInfo, ParseError, SupportedLanguage, Symbol, SymbolKind};
+pub use ast::{AnalysisContext, AstAnalyzer, CallSite, Import, ModuleInfo, ParseError, SupportedLanguage, Symbol, SymbolKind, TestCase};
+pub use cache::AnalysisCache;
+pub use callgraph::{CallEdge, CallGraph, FunctionNode};
 pub use code::{CodeStrategy, CodeStrategyConfig};
+pub use datamodel::{Cardinality, DataModel, Entity, EntityField, KeyKind, Relation, Relationship, SchemaSource};
+pub use export::{export_specs, ExportFormat};
 pub use factory::StrategyFactory;
 pub use graph::{Dependency, DependencyGraph, DependencyType, GraphStats, ModuleNode};
+pub use import::{ImportedChange, ImportedRequirement, ImportedScenario, ImportedSpec, ImportedTask};
+pub use interfaces::{Interface, InterfaceKind, InterfaceParam, ParamSource};
+pub use scenarios::MinedScenario;
 pub use strategy::ImportStrategy;
+pub use workspace::{Package, PackageKind, Workspace};
@@ -1,52 +1,67 @@
+use crate::fillback::import::{
+    h2_sections, is_ignored_dir, parse_task_list, strip_markdown, ImportedChange,
+    ImportedRequirement, ImportedScenario, ImportedSpec,
+};
 use crate::fillback::strategy::ImportStrategy;
 use crate::Result;
 use async_trait::async_trait;
+use colored::Colorize;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// OpenSpec import strategy
 ///
-/// Converts OpenSpec YAML/JSON files to Agentd Markdown specifications.
-/// OpenSpec is a specification format that uses YAML or JSON.
-pub struct OpenSpecStrategy;
+/// Converts OpenSpec sources to an Agentd change. A source is either a single
+/// YAML/JSON document or a tree of OpenSpec markdown specs (`### Requirement:`
+/// headings with `#### Scenario:` blocks).
+pub struct OpenSpecStrategy {
+    force: bool,
+}
 
 /// OpenSpec document structure
 #[derive(Debug, Deserialize, Serialize)]
-struct OpenSpecDocument {
-    #[serde(default)]
-    name: String,
+pub(crate) struct OpenSpecDocument {
     #[serde(default)]
-    version: String,
+    pub(crate) name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub(crate) version: String,
     #[serde(default)]
-    description: String,
+    pub(crate) description: String,
     #[serde(default)]
-    requirements: Vec<OpenSpecRequirement>,
+    pub(crate) requirements: Vec<OpenSpecRequirement>,
     #[serde(default)]
-    scenarios: Vec<OpenSpecScenario>,
+    pub(crate) scenarios: Vec<OpenSpecScenario>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
-struct OpenSpecRequirement {
-    id: String,
-    description: String,
+pub(crate) struct OpenSpecRequirement {
+    pub(crate) id: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub(crate) title: String,
+    pub(crate) description: String,
     #[serde(default)]
-    priority: String,
+    pub(crate) priority: String,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
-struct OpenSpecScenario {
-    name: String,
+pub(crate) struct OpenSpecScenario {
+    pub(crate) name: String,
     #[serde(default)]
-    given: Vec<String>,
+    pub(crate) given: Vec<String>,
     #[serde(default)]
-    when: String,
+    pub(crate) when: String,
     #[serde(default)]
-    then: Vec<String>,
+    pub(crate) then: Vec<String>,
 }
 
 impl OpenSpecStrategy {
     pub fn new() -> Self {
-        Self
+        Self { force: false }
+    }
+
+    /// Replace an existing change directory instead of failing
+    pub fn with_force(force: bool) -> Self {
+        Self { force }
     }
 
     /// Parse OpenSpec from YAML file
@@ -63,134 +78,283 @@ impl OpenSpecStrategy {
         Ok(doc)
     }
 
-    /// Convert OpenSpec document to Agentd proposal markdown
-    fn to_proposal_md(&self, doc: &OpenSpecDocument, change_id: &str) -> String {
-        let mut output = String::new();
-
-        output.push_str(&format!("# Change: {}\n\n", change_id));
+    /// Parse a YAML/JSON document, `None` for other files
+    fn parse_document(&self, path: &Path) -> Option<OpenSpecDocument> {
+        match path.extension().and_then(|s| s.to_str()) {
+            Some("json") => self.parse_json(path).ok(),
+            Some("yaml") | Some("yml") => self.parse_yaml(path).ok(),
+            _ => None,
+        }
+    }
 
-        output.push_str("## Summary\n");
-        if !doc.description.is_empty() {
-            output.push_str(&format!("{}\n\n", doc.description));
-        } else {
-            output.push_str("(Imported from OpenSpec)\n\n");
+    /// Convert a YAML/JSON document to an imported spec
+    fn document_spec(&self, doc: OpenSpecDocument, path: &Path) -> ImportedSpec {
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut overview = doc.description.clone();
+        if !doc.version.is_empty() {
+            overview.push_str(&format!(" (OpenSpec version {})", doc.version));
         }
 
-        output.push_str("## Why\n");
-        output.push_str("(To be filled in)\n\n");
+        ImportedSpec {
+            id: if doc.name.is_empty() { stem.clone() } else { doc.name.clone() },
+            title: if doc.name.is_empty() { stem } else { doc.name },
+            overview,
+            requirements: doc
+                .requirements
+                .into_iter()
+                .map(|r| ImportedRequirement {
+                    id: Some(r.id),
+                    title: r.title,
+                    description: r.description,
+                    priority: r.priority,
+                })
+                .collect(),
+            scenarios: doc
+                .scenarios
+                .into_iter()
+                .map(|s| ImportedScenario {
+                    name: s.name,
+                    given: s.given,
+                    when: s.when,
+                    then: s.then,
+                })
+                .collect(),
+            source: path.to_path_buf(),
+        }
+    }
 
-        output.push_str("## What Changes\n");
-        output.push_str("(To be filled in)\n\n");
+    /// Parse an OpenSpec markdown spec (`### Requirement:` / `#### Scenario:`)
+    ///
+    /// Delta specs are accepted too; `REMOVED` requirements are skipped.
+    fn parse_markdown_spec(&self, path: &Path) -> Result<ImportedSpec> {
+        let content = std::fs::read_to_string(path)?;
+        let id = if path.file_name().is_some_and(|n| n == "spec.md") {
+            path.parent().and_then(|p| p.file_name())
+        } else {
+            path.file_stem()
+        }
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+        let mut spec = ImportedSpec {
+            id: id.clone(),
+            title: id,
+            source: path.to_path_buf(),
+            ..Default::default()
+        };
+        let mut section = String::new();
+        let mut in_requirement = false;
+        let mut in_scenario = false;
+        // Continuation lines (`- **AND**`) extend the last GIVEN or THEN
+        let mut last_then = false;
+
+        for line in content.lines() {
+            let trimmed = line.trim();
+            if let Some(title) = trimmed.strip_prefix("# ") {
+                spec.title = title.trim().trim_end_matches("Specification").trim().to_string();
+            } else if let Some(heading) = trimmed.strip_prefix("## ") {
+                section = heading.trim().to_lowercase();
+                in_requirement = false;
+                in_scenario = false;
+            } else if section.starts_with("removed") {
+                continue;
+            } else if let Some(name) = trimmed.strip_prefix("### Requirement:") {
+                spec.requirements.push(ImportedRequirement {
+                    title: name.trim().to_string(),
+                    ..Default::default()
+                });
+                in_requirement = true;
+                in_scenario = false;
+            } else if let Some(name) = trimmed.strip_prefix("#### Scenario:") {
+                spec.scenarios.push(ImportedScenario {
+                    name: name.trim().to_string(),
+                    ..Default::default()
+                });
+                in_scenario = true;
+            } else if in_scenario {
+                let Some(scenario) = spec.scenarios.last_mut() else {
+                    continue;
+                };
+                let text = strip_markdown(trimmed.trim_start_matches(['-', '*']));
+                if let Some(rest) = text.strip_prefix("GIVEN") {
+                    scenario.given.push(rest.trim().to_string());
+                    last_then = false;
+                } else if let Some(rest) = text.strip_prefix("WHEN") {
+                    scenario.when = rest.trim().to_string();
+                } else if let Some(rest) = text.strip_prefix("THEN") {
+                    scenario.then.push(rest.trim().to_string());
+                    last_then = true;
+                } else if let Some(rest) = text.strip_prefix("AND") {
+                    let target = if last_then { &mut scenario.then } else { &mut scenario.given };
+                    target.push(rest.trim().to_string());
+                }
+            } else if in_requirement {
+                if let Some(requirement) = spec.requirements.last_mut() {
+                    if !trimmed.is_empty() {
+                        if !requirement.description.is_empty() {
+                            requirement.description.push(' ');
+                        }
+                        requirement.description.push_str(trimmed);
+                    }
+                }
+            } else if (section == "purpose" || section == "overview") && !trimmed.is_empty() {
+                if !spec.overview.is_empty() {
+                    spec.overview.push(' ');
+                }
+                spec.overview.push_str(trimmed);
+            }
+        }
 
-        output.push_str("## Impact\n");
-        output.push_str("- **Affected Specs:** (To be determined)\n");
-        output.push_str("- **Affected Code:** (To be determined)\n");
-        output.push_str("- **Breaking Changes:** (To be determined)\n");
+        Ok(spec)
+    }
 
-        output
+    /// Whether a file is an OpenSpec spec (YAML/JSON document or markdown)
+    fn is_spec_file(&self, path: &Path) -> bool {
+        match path.extension().and_then(|s| s.to_str()) {
+            Some("md") => std::fs::read_to_string(path)
+                .is_ok_and(|content| content.contains("### Requirement:")),
+            _ => self
+                .parse_document(path)
+                .is_some_and(|doc| !doc.requirements.is_empty()),
+        }
     }
 
-    /// Convert OpenSpec document to Agentd spec markdown
-    fn to_spec_md(&self, doc: &OpenSpecDocument) -> String {
-        let mut output = String::new();
+    fn read_spec(&self, path: &Path) -> Result<ImportedSpec> {
+        if path.extension().is_some_and(|ext| ext == "md") {
+            return self.parse_markdown_spec(path);
+        }
+        let doc = self
+            .parse_document(path)
+            .ok_or_else(|| anyhow::anyhow!("Not an OpenSpec document: {}", path.display()))?;
+        Ok(self.document_spec(doc, path))
+    }
 
-        let name = if !doc.name.is_empty() {
-            &doc.name
+    /// Root of an OpenSpec tree (`<source>/openspec` when present)
+    fn tree_root(source: &Path) -> PathBuf {
+        let nested = source.join("openspec");
+        if nested.is_dir() {
+            nested
         } else {
-            "Imported Specification"
-        };
+            source.to_path_buf()
+        }
+    }
 
-        output.push_str(&format!("# Specification: {}\n\n", name));
+    /// Spec files in a tree, limited to `specs/` when the tree has one
+    fn spec_files(&self, root: &Path, max_depth: usize) -> Vec<PathBuf> {
+        let specs_dir = root.join("specs");
+        let search = if specs_dir.is_dir() { specs_dir } else { root.to_path_buf() };
+        let mut files: Vec<PathBuf> = walkdir::WalkDir::new(&search)
+            .max_depth(max_depth)
+            .into_iter()
+            .filter_entry(|e| e.depth() == 0 || !is_ignored_dir(&e.file_name().to_string_lossy()))
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .map(|e| e.into_path())
+            .filter(|p| {
+                !p.file_name()
+                    .is_some_and(|n| n == "proposal.md" || n == "tasks.md" || n == "design.md")
+            })
+            .filter(|p| self.is_spec_file(p))
+            .collect();
+        files.sort();
+        files
+    }
 
-        output.push_str("## Overview\n");
-        if !doc.description.is_empty() {
-            output.push_str(&format!("{}\n\n", doc.description));
-        } else {
-            output.push_str("(Imported from OpenSpec)\n\n");
+    /// Read a single OpenSpec document or a whole OpenSpec tree
+    ///
+    /// A tree may be a project (`openspec/specs/<capability>/spec.md`), an
+    /// OpenSpec change (`proposal.md`, `tasks.md`, `specs/`), or any directory
+    /// of YAML/JSON documents.
+    pub fn import(&self, source: &Path) -> Result<ImportedChange> {
+        let mut import = ImportedChange::new("OpenSpec");
+
+        if source.is_file() {
+            let spec = self.read_spec(source)?;
+            import.summary = spec.overview.clone();
+            import.specs.push(spec);
+            return Ok(import);
         }
 
-        if !doc.requirements.is_empty() {
-            output.push_str("## Requirements\n\n");
-            for req in &doc.requirements {
-                output.push_str(&format!("### {}\n", req.id));
-                output.push_str(&format!("{}\n\n", req.description));
-            }
+        let root = Self::tree_root(source);
+        for path in self.spec_files(&root, usize::MAX) {
+            import.specs.push(self.read_spec(&path)?);
         }
 
-        if !doc.scenarios.is_empty() {
-            output.push_str("## Acceptance Criteria\n\n");
-            for scenario in &doc.scenarios {
-                output.push_str(&format!("### Scenario: {}\n", scenario.name));
-                if !scenario.when.is_empty() {
-                    output.push_str(&format!("- **WHEN** {}\n", scenario.when));
-                }
-                for then in &scenario.then {
-                    output.push_str(&format!("- **THEN** {}\n", then));
+        let proposal = root.join("proposal.md");
+        if proposal.exists() {
+            let content = std::fs::read_to_string(&proposal)?;
+            if let Some(title) = content.lines().find_map(|l| l.strip_prefix("# ")) {
+                import.summary = title.trim_start_matches("Change:").trim().to_string();
+            }
+            for (heading, body) in h2_sections(&content) {
+                match heading.to_lowercase().as_str() {
+                    "why" => import.why = body.trim().to_string(),
+                    "what changes" => {
+                        import.what_changes = body
+                            .lines()
+                            .filter_map(|l| l.trim().strip_prefix("- "))
+                            .map(strip_markdown)
+                            .collect()
+                    }
+                    _ => {}
                 }
-                output.push_str("\n");
             }
         }
+        if import.summary.is_empty() {
+            let titles: Vec<&str> = import.specs.iter().map(|s| s.title.as_str()).collect();
+            import.summary = format!("Import OpenSpec specs: {}", titles.join(", "));
+        }
+
+        let tasks = root.join("tasks.md");
+        if tasks.exists() {
+            import.tasks = parse_task_list(&std::fs::read_to_string(&tasks)?);
+        }
 
-        output
+        Ok(import)
+    }
+}
+
+impl Default for OpenSpecStrategy {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 #[async_trait]
 impl ImportStrategy for OpenSpecStrategy {
     async fn execute(&self, source: &Path, change_id: &str) -> Result<()> {
-        // Parse the OpenSpec file (try YAML first, then JSON)
-        let doc = if source.extension().and_then(|s| s.to_str()) == Some("json") {
-            self.parse_json(source)?
-        } else {
-            self.parse_yaml(source)?
-        };
-
-        // Create change directory
-        let current_dir = std::env::current_dir()?;
-        let change_dir = current_dir.join("agentd/changes").join(change_id);
-        std::fs::create_dir_all(&change_dir)?;
-
-        // Generate proposal.md
-        let proposal_content = self.to_proposal_md(&doc, change_id);
-        std::fs::write(change_dir.join("proposal.md"), proposal_content)?;
-
-        // Generate spec file in specs/ directory
-        let specs_dir = change_dir.join("specs");
-        std::fs::create_dir_all(&specs_dir)?;
-
-        let spec_content = self.to_spec_md(&doc);
-        let spec_filename = if !doc.name.is_empty() {
-            format!("{}.md", doc.name.to_lowercase().replace(' ', "_"))
-        } else {
-            "imported_spec.md".to_string()
-        };
-        std::fs::write(specs_dir.join(spec_filename), spec_content)?;
-
-        // Generate tasks.md skeleton
-        let tasks_content = "# Tasks\n\n## 1. Implementation\n- [ ] 1.1 Implement imported requirements\n  - File: (To be determined)\n  - Do: Review and implement the requirements from the imported spec.\n";
-        std::fs::write(change_dir.join("tasks.md"), tasks_content)?;
+        let import = self.import(source)?;
+        let project_root = std::env::current_dir()?;
+        let files = import.write(change_id, &project_root, self.force)?;
+
+        println!(
+            "{}",
+            format!(
+                "Imported {} OpenSpec spec(s) into agentd/changes/{}",
+                files.len() - 2,
+                change_id
+            )
+            .green()
+        );
+        for file in &files {
+            println!("  agentd/changes/{}/{}", change_id, file);
+        }
 
         Ok(())
     }
 
     fn can_handle(&self, source: &Path) -> bool {
-        if !source.is_file() {
-            return false;
+        if source.is_file() {
+            // An explicit document only needs to parse; trees are stricter
+            return self.parse_document(source).is_some() || self.is_spec_file(source);
         }
-
-        // Check file extension
-        if let Some(ext) = source.extension().and_then(|s| s.to_str()) {
-            if ext == "yaml" || ext == "yml" || ext == "json" {
-                // Try to parse as OpenSpec
-                if ext == "json" {
-                    return self.parse_json(source).is_ok();
-                } else {
-                    return self.parse_yaml(source).is_ok();
-                }
-            }
+        if !source.is_dir() {
+            return false;
         }
-
-        false
+        source.join("openspec").is_dir() || !self.spec_files(source, 4).is_empty()
     }
 
     fn name(&self) -> &'static str {
@@ -256,4 +420,54 @@ description: Test spec
         let strategy = OpenSpecStrategy::new();
         assert!(!strategy.can_handle(&text_file));
     }
+
+    #[test]
+    fn test_import_openspec_tree() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("openspec");
+        for (capability, requirement) in [("auth", "Login"), ("billing", "Invoices")] {
+            let dir = root.join("specs").join(capability);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(
+                dir.join("spec.md"),
+                format!(
+                    "# {cap} Specification\n\n## Purpose\nHandles {cap}.\n\n## Requirements\n### Requirement: {req}\nThe system SHALL support {req}.\n\n#### Scenario: {req} works\n- **GIVEN** a user\n- **WHEN** the user asks for {req}\n- **THEN** it is provided\n- **AND** it is logged\n",
+                    cap = capability,
+                    req = requirement
+                ),
+            )
+            .unwrap();
+        }
+        std::fs::write(
+            root.join("proposal.md"),
+            "# Change: Adopt auth and billing\n\n## Why\nTeams need both.\n\n## What Changes\n- Add **auth**\n- Add billing\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("tasks.md"), "## 1. Auth\n- [ ] 1.1 Add `src/auth.rs`\n").unwrap();
+
+        let strategy = OpenSpecStrategy::new();
+        assert!(strategy.can_handle(temp_dir.path()));
+        let import = strategy.import(temp_dir.path()).unwrap();
+
+        assert_eq!(import.summary, "Adopt auth and billing");
+        assert_eq!(import.what_changes, vec!["Add auth", "Add billing"]);
+        assert_eq!(import.specs.len(), 2);
+        let auth = &import.specs[0];
+        assert_eq!(auth.id, "auth");
+        assert_eq!(auth.title, "auth");
+        assert_eq!(auth.requirements[0].title, "Login");
+        assert_eq!(auth.requirements[0].description, "The system SHALL support Login.");
+        assert_eq!(auth.scenarios[0].given, vec!["a user"]);
+        assert_eq!(auth.scenarios[0].then, vec!["it is provided", "it is logged"]);
+        assert_eq!(import.tasks.len(), 1);
+
+        let files = import.write("adopt-openspec", temp_dir.path(), false).unwrap();
+        assert_eq!(files.len(), 4);
+        let spec = std::fs::read_to_string(
+            temp_dir.path().join("agentd/changes/adopt-openspec/specs/billing.md"),
+        )
+        .unwrap();
+        assert!(spec.contains("### R1 - Invoices"));
+        assert!(spec.contains("- **THEN** it is provided; it is logged"));
+    }
 }
@@ -0,0 +1,529 @@
+//! Test Mining
+//!
+//! Turns the tests found by the AST analyzer into WHEN/THEN scenarios for
+//! the module under test. Tests are matched to a module by the functions they
+//! call, their file name (`users_test.go`, `test_users.py`, `users.spec.ts`)
+//! and their imports; test names and doc comments become the scenario text.
+
+use crate::fillback::ast::{AnalysisContext, ModuleInfo, TestCase};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Words that introduce the condition of a test name: `rejects_input_when_empty`
+const CONDITION_WORDS: &[&str] = &["when", "if", "given", "unless", "without", "after"];
+
+/// Words that introduce the expected outcome of a test name: `parse_empty_returns_none`
+const OUTCOME_WORDS: &[&str] = &[
+    "returns", "return", "should", "must", "fails", "errors", "rejects", "raises", "throws",
+    "panics", "yields", "produces", "succeeds", "creates", "emits", "keeps", "ignores", "skips",
+];
+
+/// A scenario mined from one test
+#[derive(Debug, Clone)]
+pub struct MinedScenario {
+    pub name: String,
+    pub when: String,
+    pub then: String,
+    /// Qualified test name (`suite::test`)
+    pub test: String,
+    /// File and line of the test, for traceability
+    pub path: String,
+    pub line: usize,
+    /// Function of the module under test exercised by the test
+    pub covers: Option<String>,
+}
+
+/// Mine the tests of all modules into scenarios, keyed by module under test
+pub fn mine_scenarios(context: &AnalysisContext) -> HashMap<String, Vec<MinedScenario>> {
+    let mut scenarios: HashMap<String, Vec<MinedScenario>> = HashMap::new();
+    let root = common_root(context);
+
+    for module in context.modules.iter().filter(|m| !m.tests.is_empty()) {
+        let relative = Path::new(&module.path)
+            .strip_prefix(&root)
+            .unwrap_or(Path::new(&module.path));
+        let inline = !is_test_file(relative);
+        let candidates = candidate_modules(module, context);
+        for test in &module.tests {
+            let target = if inline {
+                Some(module)
+            } else {
+                target_module(test, &candidates)
+            };
+            let Some(target) = target else {
+                continue;
+            };
+            let covers = covered_function(test, target);
+            scenarios
+                .entry(target.name.clone())
+                .or_default()
+                .push(scenario_from_test(test, &module.path, covers));
+        }
+    }
+
+    scenarios
+}
+
+/// Turn a test into a scenario
+///
+/// `returns_error_when_input_is_empty` reads as WHEN "input is empty" THEN
+/// "returns error"; `parse_empty_returns_none` as WHEN "parse empty" THEN
+/// "returns none". The test's doc comment, when present, states the outcome.
+pub fn scenario_from_test(test: &TestCase, path: &str, covers: Option<String>) -> MinedScenario {
+    let phrase = humanize(&test.name);
+    let words: Vec<&str> = phrase.split_whitespace().collect();
+    let position = |markers: &[&str]| {
+        words
+            .iter()
+            .skip(1)
+            .position(|w| markers.contains(&w.to_lowercase().as_str()))
+            .map(|i| i + 1)
+    };
+
+    let (when, outcome) = if let Some(i) = position(CONDITION_WORDS) {
+        let condition = match words[i].to_lowercase().as_str() {
+            // Keep the word where it carries meaning: "without a token"
+            "without" | "after" => words[i..].join(" "),
+            _ => words[i + 1..].join(" "),
+        };
+        (condition, Some(words[..i].join(" ")))
+    } else if let Some(i) = position(OUTCOME_WORDS) {
+        (words[..i].join(" "), Some(words[i..].join(" ")))
+    } else {
+        (String::new(), None)
+    };
+
+    // Without a condition in the name, the whole name states the outcome of
+    // exercising the covered function or suite
+    let when = if !when.is_empty() {
+        when
+    } else if let Some(function) = &covers {
+        format!("`{}` is called", function)
+    } else if let Some(suite) = &test.suite {
+        format!("{} is exercised", suite)
+    } else {
+        phrase.clone()
+    };
+    let outcome = outcome.or_else(|| (when != phrase).then(|| phrase.clone()));
+    let then = test
+        .doc
+        .clone()
+        .or(outcome.filter(|o| !o.is_empty()))
+        .unwrap_or_else(|| format!("the expectations of `{}` hold", test.name));
+
+    let name = match &test.suite {
+        Some(suite) if !phrase.is_empty() => format!("{} {}", suite, phrase),
+        _ => phrase,
+    };
+    let test_name = match &test.suite {
+        Some(suite) => format!("{}::{}", suite, test.name),
+        None => test.name.clone(),
+    };
+
+    MinedScenario {
+        name: capitalize(&name),
+        when,
+        then,
+        test: test_name,
+        path: path.to_string(),
+        line: test.line,
+        covers,
+    }
+}
+
+/// Modules a test file may exercise: the one named like the file, then
+/// imported modules
+fn candidate_modules<'a>(module: &ModuleInfo, context: &'a AnalysisContext) -> Vec<&'a ModuleInfo> {
+    let by_name = |name: &str| context.modules.iter().find(|m| m.name == name);
+
+    let mut candidates: Vec<&ModuleInfo> = Vec::new();
+    if let Some(stem) = tested_module_name(&module.path) {
+        candidates.extend(by_name(&stem));
+    }
+    for import in &module.imports {
+        let segments = import
+            .path
+            .split([':', '.', '/', '\\'])
+            .chain(import.items.iter().map(String::as_str));
+        for segment in segments.filter(|s| !s.is_empty() && *s != module.name) {
+            if let Some(target) = by_name(segment) {
+                if !candidates.iter().any(|c| c.path == target.path) {
+                    candidates.push(target);
+                }
+            }
+        }
+    }
+    candidates
+}
+
+/// Module a test file's test exercises: the candidate defining most of the
+/// called functions
+fn target_module<'a>(test: &TestCase, candidates: &[&'a ModuleInfo]) -> Option<&'a ModuleInfo> {
+    let mut best: Option<(&ModuleInfo, usize)> = None;
+    for candidate in candidates {
+        let score = test
+            .calls
+            .iter()
+            .filter(|call| defines(candidate, call))
+            .count();
+        if score > 0 && best.is_none_or(|(_, best_score)| score > best_score) {
+            best = Some((candidate, score));
+        }
+    }
+    best.map(|(module, _)| module)
+        .or_else(|| candidates.first().copied())
+}
+
+/// Function of the module a test exercises: the one named in the test, else
+/// the first one called other than a constructor
+fn covered_function(test: &TestCase, module: &ModuleInfo) -> Option<String> {
+    let normalize = |name: &str| name.to_lowercase().replace(['_', ' '], "");
+    let test_name = normalize(&test.name);
+    let called: Vec<&String> = test
+        .calls
+        .iter()
+        .filter(|call| defines(module, call))
+        .collect();
+    called
+        .iter()
+        .find(|call| test_name.contains(&normalize(call)))
+        .or_else(|| {
+            called
+                .iter()
+                .find(|call| !matches!(call.as_str(), "new" | "default"))
+        })
+        .or_else(|| called.first())
+        .map(|call| call.to_string())
+}
+
+fn defines(module: &ModuleInfo, name: &str) -> bool {
+    module.symbols.iter().any(|symbol| symbol.name == name)
+}
+
+/// Deepest directory containing all analyzed modules, so test directories
+/// are only recognized inside the project
+pub(crate) fn common_root(context: &AnalysisContext) -> PathBuf {
+    let mut paths = context.modules.iter().map(|m| Path::new(&m.path));
+    let Some(first) = paths.next() else {
+        return PathBuf::new();
+    };
+    let mut root: PathBuf = first.parent().unwrap_or(first).to_path_buf();
+    for path in paths {
+        while !path.starts_with(&root) {
+            if !root.pop() {
+                break;
+            }
+        }
+    }
+    root
+}
+
+/// Whether a file only holds tests, by directory or naming convention
+pub(crate) fn is_test_file(path: &Path) -> bool {
+    let in_test_dir = path.components().any(|c| {
+        matches!(
+            c.as_os_str().to_str(),
+            Some("tests" | "test" | "__tests__" | "spec")
+        )
+    });
+    in_test_dir || tested_module_name(&path.to_string_lossy()).is_some()
+}
+
+/// Module named by a test file: `test_users.py`, `users_test.go`,
+/// `users.test.ts`, `users.spec.js` -> `users`
+fn tested_module_name(path: &str) -> Option<String> {
+    let stem = Path::new(path).file_stem()?.to_str()?;
+    let name = stem
+        .strip_prefix("test_")
+        .or_else(|| stem.strip_suffix("_test"))
+        .or_else(|| stem.strip_suffix("_tests"))
+        .or_else(|| stem.strip_suffix(".test"))
+        .or_else(|| stem.strip_suffix(".spec"))
+        .or_else(|| stem.strip_suffix("_spec"))?;
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// `test_parse_empty_input` / `TestParse_EmptyInput` -> `parse empty input`;
+/// free-text Jest titles are kept as written
+fn humanize(name: &str) -> String {
+    if name.contains(' ') {
+        return name.trim().to_string();
+    }
+    let name = name
+        .strip_prefix("test_")
+        .or_else(|| name.strip_prefix("Test"))
+        .or_else(|| name.strip_prefix("test"))
+        .unwrap_or(name);
+
+    let mut words = Vec::new();
+    for part in name.split('_').filter(|p| !p.is_empty()) {
+        let chars: Vec<char> = part.chars().collect();
+        let mut word = String::new();
+        for (i, c) in chars.iter().enumerate() {
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            let boundary = i > 0 && c.is_uppercase() && (chars[i - 1].is_lowercase() || next_lower);
+            if boundary && !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            word.push(*c);
+        }
+        words.push(word);
+    }
+
+    words
+        .into_iter()
+        .map(|word| {
+            // Keep acronyms such as HTTP or ID
+            if word.len() > 1 && word.chars().all(|c| c.is_uppercase() || c.is_ascii_digit()) {
+                word
+            } else {
+                word.to_lowercase()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fillback::ast::AstAnalyzer;
+    use std::path::PathBuf;
+
+    fn parse(path: &str, content: &str) -> ModuleInfo {
+        let mut analyzer = AstAnalyzer::new().unwrap();
+        analyzer.parse_file(&PathBuf::from(path), content).unwrap()
+    }
+
+    fn case(name: &str, doc: Option<&str>) -> TestCase {
+        TestCase {
+            name: name.to_string(),
+            suite: None,
+            doc: doc.map(str::to_string),
+            calls: vec![],
+            line: 1,
+        }
+    }
+
+    #[test]
+    fn test_detect_tests() {
+        let rust = parse(
+            "src/parser.rs",
+            r#"
+pub fn parse(input: &str) -> Option<u32> {
+    input.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Empty input has no value
+    #[test]
+    fn test_parse_empty_returns_none() {
+        assert_eq!(parse(""), None);
+    }
+
+    #[tokio::test]
+    async fn parses_number() {}
+
+    fn helper() {}
+}
+"#,
+        );
+        assert_eq!(rust.tests.len(), 2);
+        assert_eq!(rust.tests[0].name, "test_parse_empty_returns_none");
+        assert_eq!(
+            rust.tests[0].doc.as_deref(),
+            Some("Empty input has no value")
+        );
+        assert_eq!(rust.tests[0].calls, vec!["parse"]);
+        assert_eq!(rust.tests[0].line, 12);
+
+        let python = parse(
+            "tests/test_users.py",
+            r#"
+from app.users import create_user
+
+def test_create_user_rejects_duplicates():
+    """Duplicate emails are rejected."""
+    create_user("a@b.c")
+
+class TestLogin:
+    def test_login_without_password(self):
+        pass
+
+class Helper:
+    def test_ignored(self):
+        pass
+"#,
+        );
+        let names: Vec<_> = python.tests.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "test_create_user_rejects_duplicates",
+                "test_login_without_password"
+            ]
+        );
+        assert_eq!(
+            python.tests[0].doc.as_deref(),
+            Some("Duplicate emails are rejected.")
+        );
+        assert_eq!(python.tests[1].suite.as_deref(), Some("TestLogin"));
+
+        let js = parse(
+            "src/cart.test.ts",
+            r#"
+import { addItem } from './cart';
+
+describe('Cart', () => {
+  describe('addItem', () => {
+    it('increments the count when the item exists', () => {
+      expect(addItem([], 'a')).toHaveLength(1);
+    });
+  });
+  test.skip("is empty by default", () => {});
+});
+"#,
+        );
+        assert_eq!(js.tests.len(), 2);
+        assert_eq!(
+            js.tests[0].name,
+            "increments the count when the item exists"
+        );
+        assert_eq!(js.tests[0].suite.as_deref(), Some("Cart addItem"));
+        assert!(js.tests[0].calls.contains(&"addItem".to_string()));
+        assert_eq!(js.tests[1].suite.as_deref(), Some("Cart"));
+
+        let go = parse(
+            "users_test.go",
+            r#"
+package users
+
+import "testing"
+
+// Lookup of an unknown user fails
+func TestFindUser_Unknown(t *testing.T) {
+    FindUser("x")
+}
+
+func TestMain(m *testing.M) {}
+
+func helperTest(t *testing.T) {}
+"#,
+        );
+        assert_eq!(go.tests.len(), 1);
+        assert_eq!(
+            go.tests[0].doc.as_deref(),
+            Some("Lookup of an unknown user fails")
+        );
+        assert_eq!(go.tests[0].calls, vec!["FindUser"]);
+    }
+
+    #[test]
+    fn test_scenario_from_test() {
+        let scenario = scenario_from_test(
+            &case("test_returns_error_when_input_is_empty", None),
+            "a.rs",
+            None,
+        );
+        assert_eq!(scenario.name, "Returns error when input is empty");
+        assert_eq!(scenario.when, "input is empty");
+        assert_eq!(scenario.then, "returns error");
+
+        let scenario = scenario_from_test(
+            &case("TestParse_EmptyInput_ReturnsNone", None),
+            "a.go",
+            None,
+        );
+        assert_eq!(scenario.when, "parse empty input");
+        assert_eq!(scenario.then, "returns none");
+
+        let scenario = scenario_from_test(
+            &case("test_login_without_password", Some("Login is refused")),
+            "a.py",
+            Some("login".to_string()),
+        );
+        assert_eq!(scenario.when, "without password");
+        assert_eq!(scenario.then, "Login is refused");
+
+        let scenario = scenario_from_test(&case("test_roundtrip", None), "a.rs", None);
+        assert_eq!(scenario.when, "roundtrip");
+        assert_eq!(scenario.then, "the expectations of `test_roundtrip` hold");
+
+        let mut jest = case("creates a user", None);
+        jest.suite = Some("UserService".to_string());
+        let scenario = scenario_from_test(&jest, "a.test.js", None);
+        assert_eq!(scenario.name, "UserService creates a user");
+        assert_eq!(scenario.test, "UserService::creates a user");
+        assert_eq!(scenario.when, "UserService is exercised");
+        assert_eq!(scenario.then, "creates a user");
+    }
+
+    #[test]
+    fn test_mine_scenarios_targets_module_under_test() {
+        let mut context = AnalysisContext::new();
+        context.modules.push(parse(
+            "src/users.py",
+            "def create_user(email):\n    pass\n\ndef find_user(email):\n    pass\n",
+        ));
+        context
+            .modules
+            .push(parse("src/emails.py", "def send(to):\n    pass\n"));
+        context.modules.push(parse(
+            "tests/test_signup.py",
+            "from src import users, emails\n\ndef test_signup_creates_user():\n    users.create_user('a')\n    users.find_user('a')\n    emails.send('a')\n\ndef test_unrelated():\n    pass\n",
+        ));
+        context.modules.push(parse(
+            "src/emails_inline.rs",
+            "pub fn send() {}\n\n#[test]\nfn sends() {\n    send();\n}\n",
+        ));
+
+        let scenarios = mine_scenarios(&context);
+        let users = &scenarios["users"];
+        assert_eq!(users.len(), 2);
+        assert_eq!(users[0].covers.as_deref(), Some("create_user"));
+        assert_eq!(users[0].path, "tests/test_signup.py");
+        assert_eq!(users[0].when, "signup");
+        assert_eq!(users[0].then, "creates user");
+        // No calls resolve, so the first imported module is assumed
+        assert!(users[1].covers.is_none());
+        assert!(!scenarios.contains_key("emails"));
+        assert_eq!(
+            scenarios["emails_inline"][0].covers.as_deref(),
+            Some("send")
+        );
+    }
+
+    #[test]
+    fn test_tested_module_name() {
+        assert_eq!(
+            tested_module_name("tests/test_users.py").as_deref(),
+            Some("users")
+        );
+        assert_eq!(
+            tested_module_name("users_test.go").as_deref(),
+            Some("users")
+        );
+        assert_eq!(
+            tested_module_name("src/users.test.ts").as_deref(),
+            Some("users")
+        );
+        assert_eq!(
+            tested_module_name("src/users.spec.js").as_deref(),
+            Some("users")
+        );
+        assert_eq!(tested_module_name("src/users.rs"), None);
+        assert!(is_test_file(Path::new("tests/integration.rs")));
+        assert!(!is_test_file(Path::new("src/users.rs")));
+    }
+}
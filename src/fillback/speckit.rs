@@ -1,21 +1,39 @@
+use crate::fillback::import::{
+    h2_sections, is_ignored_dir, parse_task_list, strip_markdown, ImportedChange,
+    ImportedRequirement, ImportedScenario, ImportedSpec,
+};
 use crate::fillback::strategy::ImportStrategy;
 use crate::Result;
 use async_trait::async_trait;
-use pulldown_cmark::{Event, Parser, Tag, TagEnd};
-use std::path::Path;
+use colored::Colorize;
+use regex::Regex;
+use std::path::{Path, PathBuf};
 
 /// GitHub Speckit import strategy
 ///
 /// Parses GitHub Speckit markdown files and restructures them into Agentd specifications.
-/// Speckit uses a structured markdown format with specific sections.
-pub struct SpeckitStrategy;
+/// Speckit uses a structured markdown format with specific sections; a project
+/// keeps one feature per directory (`specs/<feature>/spec.md`, `tasks.md`, `plan.md`).
+pub struct SpeckitStrategy {
+    force: bool,
+}
 
 impl SpeckitStrategy {
     pub fn new() -> Self {
-        Self
+        Self { force: false }
+    }
+
+    /// Replace an existing change directory instead of failing
+    pub fn with_force(force: bool) -> Self {
+        Self { force }
     }
 
     /// Parse Speckit markdown file
+    ///
+    /// Requirements come from `###` headings or `**FR-001**:` bullets under a
+    /// requirements section; scenarios from `###` headings under a scenario,
+    /// acceptance or user story section, split into one scenario per
+    /// `**Given** ..., **When** ..., **Then** ...` line when present.
     fn parse_markdown(&self, path: &Path) -> Result<SpeckitDocument> {
         let content = std::fs::read_to_string(path)?;
 
@@ -26,191 +44,316 @@ impl SpeckitStrategy {
             scenarios: Vec::new(),
         };
 
-        let parser = Parser::new(&content);
-        let mut current_section: Option<String> = None;
-        let mut current_text = String::new();
-        let mut in_heading = false;
-        let mut heading_level = 0;
-
-        for event in parser {
-            match event {
-                Event::Start(Tag::Heading { level, .. }) => {
-                    in_heading = true;
-                    heading_level = level as usize;
+        let fr_re = Regex::new(r"^\s*[-*]\s+\*\*([A-Za-z]+(?:-[A-Za-z]+)*-?\d+)\*\*:?\s*(.+)$").unwrap();
+        let mut section = String::new();
+        let mut block: Option<(String, Vec<String>)> = None;
+
+        for line in content.lines() {
+            let trimmed = line.trim();
+            let heading = trimmed
+                .strip_prefix("### ")
+                .map(|h| (3, h))
+                .or_else(|| trimmed.strip_prefix("## ").map(|h| (2, h)))
+                .or_else(|| trimmed.strip_prefix("# ").map(|h| (1, h)));
+
+            if let Some((level, text)) = heading {
+                if let Some((name, body)) = block.take() {
+                    self.finish_block(&mut doc, &section, &name, &body);
                 }
-                Event::End(TagEnd::Heading { .. }) => {
-                    in_heading = false;
-                    if heading_level == 1 && doc.title.is_empty() {
-                        doc.title = current_text.trim().to_string();
-                    } else if heading_level == 2 {
-                        current_section = Some(current_text.trim().to_string());
-                    } else if heading_level == 3 {
-                        // This might be a requirement or scenario heading
-                        if let Some(ref section) = current_section {
-                            if section.to_lowercase().contains("requirement") {
-                                doc.requirements.push(current_text.trim().to_string());
-                            } else if section.to_lowercase().contains("scenario")
-                                || section.to_lowercase().contains("acceptance")
-                            {
-                                doc.scenarios.push(current_text.trim().to_string());
-                            }
-                        }
+                let text = strip_markdown(text);
+                match level {
+                    1 if doc.title.is_empty() => {
+                        doc.title = text.trim_start_matches("Feature Specification:").trim().to_string();
                     }
-                    current_text.clear();
+                    2 => section = text.to_lowercase(),
+                    3 => block = Some((text, Vec::new())),
+                    _ => {}
                 }
-                Event::Text(text) => {
-                    if in_heading {
-                        current_text.push_str(&text);
-                    } else if let Some(ref section) = current_section {
-                        if section.to_lowercase().contains("overview")
-                            || section.to_lowercase().contains("summary")
-                        {
-                            doc.overview.push_str(&text);
-                        }
-                    }
+                continue;
+            }
+
+            if section_is(&section, &["requirement"]) {
+                if let Some(caps) = fr_re.captures(line) {
+                    let text = strip_markdown(&caps[2]);
+                    doc.requirements.push(ImportedRequirement {
+                        id: Some(caps[1].to_string()),
+                        title: String::new(),
+                        description: text,
+                        priority: String::new(),
+                    });
+                    continue;
                 }
-                Event::SoftBreak | Event::HardBreak => {
-                    if !in_heading {
-                        current_text.push('\n');
-                    }
+            }
+
+            if let Some((_, body)) = block.as_mut() {
+                body.push(trimmed.to_string());
+            } else if section_is(&section, &["overview", "summary"]) && !trimmed.is_empty() {
+                if !doc.overview.is_empty() {
+                    doc.overview.push('\n');
                 }
-                _ => {}
+                doc.overview.push_str(trimmed);
             }
         }
+        if let Some((name, body)) = block.take() {
+            self.finish_block(&mut doc, &section, &name, &body);
+        }
 
         Ok(doc)
     }
 
-    /// Convert Speckit document to Agentd proposal markdown
-    fn to_proposal_md(&self, doc: &SpeckitDocument, change_id: &str) -> String {
-        let mut output = String::new();
-
-        output.push_str(&format!("# Change: {}\n\n", change_id));
+    /// Turn a `###` block into requirements or scenarios depending on its section
+    fn finish_block(&self, doc: &mut SpeckitDocument, section: &str, name: &str, body: &[String]) {
+        let text = body
+            .iter()
+            .filter(|l| !l.is_empty() && !l.starts_with("**"))
+            .map(|l| strip_markdown(l))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        if section_is(section, &["requirement"]) {
+            // Sub-headings like "Functional Requirements" group FR bullets
+            let lower = name.to_lowercase();
+            if lower.contains("requirements") || lower.contains("entities") {
+                return;
+            }
+            doc.requirements.push(ImportedRequirement {
+                id: None,
+                title: name.to_string(),
+                description: text,
+                priority: String::new(),
+            });
+        } else if section_is(section, &["scenario", "acceptance", "user stor"]) {
+            if name.to_lowercase().contains("edge case") {
+                return;
+            }
+            let name = name.split(" (Priority").next().unwrap_or(name).trim();
+
+            let gwt: Vec<ImportedScenario> = body.iter().filter_map(|l| parse_given_when_then(l)).collect();
+            if gwt.len() == 1 {
+                doc.scenarios.push(ImportedScenario {
+                    name: name.to_string(),
+                    ..gwt.into_iter().next().unwrap()
+                });
+                return;
+            }
+            if !gwt.is_empty() {
+                for (i, scenario) in gwt.into_iter().enumerate() {
+                    doc.scenarios.push(ImportedScenario {
+                        name: format!("{} ({})", name, i + 1),
+                        ..scenario
+                    });
+                }
+                return;
+            }
 
-        output.push_str("## Summary\n");
-        if !doc.overview.is_empty() {
-            output.push_str(&format!("{}\n\n", doc.overview.trim()));
-        } else if !doc.title.is_empty() {
-            output.push_str(&format!("{}\n\n", doc.title));
-        } else {
-            output.push_str("(Imported from GitHub Speckit)\n\n");
+            let mut scenario = ImportedScenario {
+                name: name.to_string(),
+                ..Default::default()
+            };
+            for line in body {
+                let line = strip_markdown(line.trim_start_matches(['-', '*']));
+                if let Some(rest) = line.strip_prefix("GIVEN") {
+                    scenario.given.push(rest.trim().to_string());
+                } else if let Some(rest) = line.strip_prefix("WHEN") {
+                    scenario.when = rest.trim().to_string();
+                } else if let Some(rest) = line.strip_prefix("THEN") {
+                    scenario.then.push(rest.trim().to_string());
+                }
+            }
+            if scenario.then.is_empty() && !text.is_empty() {
+                scenario.then.push(text);
+            }
+            doc.scenarios.push(scenario);
         }
+    }
 
-        output.push_str("## Why\n");
-        output.push_str("(To be filled in)\n\n");
-
-        output.push_str("## What Changes\n");
-        output.push_str("(To be filled in)\n\n");
+    /// Convert a parsed document to an imported spec
+    fn to_spec(&self, doc: SpeckitDocument, id: &str, path: &Path) -> ImportedSpec {
+        ImportedSpec {
+            id: id.to_string(),
+            title: if doc.title.is_empty() { id.to_string() } else { doc.title },
+            overview: doc.overview,
+            requirements: doc.requirements,
+            scenarios: doc.scenarios,
+            source: path.to_path_buf(),
+        }
+    }
 
-        output.push_str("## Impact\n");
-        output.push_str("- **Affected Specs:** (To be determined)\n");
-        output.push_str("- **Affected Code:** (To be determined)\n");
-        output.push_str("- **Breaking Changes:** (To be determined)\n");
+    /// Whether a markdown file looks like a Speckit spec
+    fn is_spec_file(path: &Path) -> bool {
+        if !path.extension().is_some_and(|ext| ext == "md" || ext == "markdown") {
+            return false;
+        }
+        // Basic heuristic: check for common Speckit sections
+        std::fs::read_to_string(path).is_ok_and(|content| {
+            let lower = content.to_lowercase();
+            lower.contains("# ") && (lower.contains("requirement") || lower.contains("scenario"))
+        })
+    }
 
-        output
+    /// Spec files of a Speckit tree: `spec.md` per feature directory, or any
+    /// spec-like markdown file when there are none
+    fn spec_files(source: &Path, max_depth: usize) -> Vec<PathBuf> {
+        let markdown: Vec<PathBuf> = walkdir::WalkDir::new(source)
+            .max_depth(max_depth)
+            .into_iter()
+            .filter_entry(|e| e.depth() == 0 || !is_ignored_dir(&e.file_name().to_string_lossy()))
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .map(|e| e.into_path())
+            .filter(|p| Self::is_spec_file(p))
+            .collect();
+
+        let mut files: Vec<PathBuf> = markdown
+            .iter()
+            .filter(|p| p.file_name().is_some_and(|n| n == "spec.md"))
+            .cloned()
+            .collect();
+        if files.is_empty() {
+            files = markdown
+                .into_iter()
+                .filter(|p| {
+                    !p.file_name()
+                        .is_some_and(|n| n == "tasks.md" || n == "plan.md" || n == "README.md")
+                })
+                .collect();
+        }
+        files.sort();
+        files
     }
 
-    /// Convert Speckit document to Agentd spec markdown
-    fn to_spec_md(&self, doc: &SpeckitDocument) -> String {
-        let mut output = String::new();
+    /// Read a single Speckit file or every feature of a Speckit tree
+    pub fn import(&self, source: &Path) -> Result<ImportedChange> {
+        let mut import = ImportedChange::new("Speckit");
 
-        let title = if !doc.title.is_empty() {
-            &doc.title
+        let files = if source.is_file() {
+            vec![source.to_path_buf()]
         } else {
-            "Imported Specification"
+            Self::spec_files(source, usize::MAX)
         };
 
-        output.push_str(&format!("# Specification: {}\n\n", title));
-
-        output.push_str("## Overview\n");
-        if !doc.overview.is_empty() {
-            output.push_str(&format!("{}\n\n", doc.overview.trim()));
-        } else {
-            output.push_str("(Imported from GitHub Speckit)\n\n");
-        }
-
-        if !doc.requirements.is_empty() {
-            output.push_str("## Requirements\n\n");
-            for (i, req) in doc.requirements.iter().enumerate() {
-                output.push_str(&format!("### R{}: {}\n\n", i + 1, req));
+        let mut why = Vec::new();
+        for path in &files {
+            let is_feature = path.file_name().is_some_and(|n| n == "spec.md");
+            let id = if is_feature {
+                path.parent().and_then(|p| p.file_name())
+            } else {
+                path.file_stem()
+            }
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+            let doc = self.parse_markdown(path)?;
+            let spec = self.to_spec(doc, &id, path);
+
+            if is_feature {
+                let dir = path.parent().unwrap_or(source);
+                let tasks = dir.join("tasks.md");
+                if tasks.exists() {
+                    let content = std::fs::read_to_string(&tasks)?;
+                    import.tasks.extend(parse_task_list(&content).into_iter().map(|mut task| {
+                        task.spec_id = Some(id.clone());
+                        task
+                    }));
+                }
+                let plan = dir.join("plan.md");
+                if plan.exists() {
+                    let content = std::fs::read_to_string(&plan)?;
+                    if let Some((_, body)) = h2_sections(&content)
+                        .into_iter()
+                        .find(|(h, _)| h.to_lowercase().starts_with("summary"))
+                    {
+                        why.push(body.trim().to_string());
+                    }
+                }
             }
-        }
 
-        if !doc.scenarios.is_empty() {
-            output.push_str("## Acceptance Criteria\n\n");
-            for scenario in &doc.scenarios {
-                output.push_str(&format!("### Scenario: {}\n", scenario));
-                output.push_str("- **WHEN** (To be specified)\n");
-                output.push_str("- **THEN** (To be specified)\n\n");
+            if why.is_empty() && !spec.overview.is_empty() && files.len() == 1 {
+                why.push(spec.overview.clone());
             }
+            import.specs.push(spec);
         }
 
-        output
+        import.why = why.join("\n\n");
+        import.summary = match import.specs.as_slice() {
+            [spec] => spec.title.clone(),
+            specs => format!(
+                "Import Speckit features: {}",
+                specs.iter().map(|s| s.title.as_str()).collect::<Vec<_>>().join(", ")
+            ),
+        };
+
+        Ok(import)
     }
 }
 
+impl Default for SpeckitStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn section_is(section: &str, keywords: &[&str]) -> bool {
+    keywords.iter().any(|k| section.contains(k))
+}
+
+/// Parse `1. **Given** a, **When** b, **Then** c` into a scenario
+fn parse_given_when_then(line: &str) -> Option<ImportedScenario> {
+    let text = strip_markdown(line);
+    let re = Regex::new(r"(?i)^(?:[-*]|\d+\.)?\s*(?:given\s+(.+?),?\s+)?when\s+(.+?),?\s+then\s+(.+)$").unwrap();
+    let caps = re.captures(&text)?;
+    Some(ImportedScenario {
+        name: String::new(),
+        given: caps.get(1).map(|g| vec![g.as_str().trim().to_string()]).unwrap_or_default(),
+        when: caps[2].trim().to_string(),
+        then: vec![caps[3].trim().trim_end_matches('.').to_string()],
+    })
+}
+
 /// Speckit document structure parsed from markdown
 struct SpeckitDocument {
     title: String,
     overview: String,
-    requirements: Vec<String>,
-    scenarios: Vec<String>,
+    requirements: Vec<ImportedRequirement>,
+    scenarios: Vec<ImportedScenario>,
 }
 
 #[async_trait]
 impl ImportStrategy for SpeckitStrategy {
     async fn execute(&self, source: &Path, change_id: &str) -> Result<()> {
-        // Parse the Speckit markdown file
-        let doc = self.parse_markdown(source)?;
-
-        // Create change directory
-        let current_dir = std::env::current_dir()?;
-        let change_dir = current_dir.join("agentd/changes").join(change_id);
-        std::fs::create_dir_all(&change_dir)?;
-
-        // Generate proposal.md
-        let proposal_content = self.to_proposal_md(&doc, change_id);
-        std::fs::write(change_dir.join("proposal.md"), proposal_content)?;
-
-        // Generate spec file in specs/ directory
-        let specs_dir = change_dir.join("specs");
-        std::fs::create_dir_all(&specs_dir)?;
-
-        let spec_content = self.to_spec_md(&doc);
-        let spec_filename = if !doc.title.is_empty() {
-            format!("{}.md", doc.title.to_lowercase().replace(' ', "_"))
-        } else {
-            "imported_spec.md".to_string()
-        };
-        std::fs::write(specs_dir.join(spec_filename), spec_content)?;
-
-        // Generate tasks.md skeleton
-        let tasks_content = "# Tasks\n\n## 1. Implementation\n- [ ] 1.1 Implement imported requirements\n  - File: (To be determined)\n  - Do: Review and implement the requirements from the imported spec.\n";
-        std::fs::write(change_dir.join("tasks.md"), tasks_content)?;
+        let import = self.import(source)?;
+        let project_root = std::env::current_dir()?;
+        let files = import.write(change_id, &project_root, self.force)?;
+
+        println!(
+            "{}",
+            format!(
+                "Imported {} Speckit spec(s) into agentd/changes/{}",
+                files.len() - 2,
+                change_id
+            )
+            .green()
+        );
+        for file in &files {
+            println!("  agentd/changes/{}/{}", change_id, file);
+        }
 
         Ok(())
     }
 
     fn can_handle(&self, source: &Path) -> bool {
-        if !source.is_file() {
-            return false;
+        if source.is_file() {
+            return Self::is_spec_file(source);
         }
-
-        // Check file extension
-        if let Some(ext) = source.extension().and_then(|s| s.to_str()) {
-            if ext == "md" || ext == "markdown" {
-                // Try to read and check if it looks like a Speckit file
-                // Speckit files typically have headings and structured content
-                if let Ok(content) = std::fs::read_to_string(source) {
-                    // Basic heuristic: check for common Speckit sections
-                    let lower = content.to_lowercase();
-                    return lower.contains("# ")
-                        && (lower.contains("requirement") || lower.contains("scenario"));
-                }
-            }
+        if !source.is_dir() {
+            return false;
         }
-
-        false
+        // Loose markdown would match most READMEs, so directories need a
+        // Speckit layout: `.specify/` or feature directories with `spec.md`
+        source.join(".specify").is_dir()
+            || Self::spec_files(source, 4)
+                .iter()
+                .any(|p| p.file_name().is_some_and(|n| n == "spec.md"))
     }
 
     fn name(&self) -> &'static str {
@@ -281,4 +424,74 @@ System rejects invalid inputs.
         // This might return false since there's no "requirement" or "scenario"
         assert!(!strategy.can_handle(&readme));
     }
+
+    #[test]
+    fn test_import_speckit_features() {
+        let temp_dir = TempDir::new().unwrap();
+        let feature = temp_dir.path().join("specs/001-photo-albums");
+        std::fs::create_dir_all(&feature).unwrap();
+        std::fs::write(
+            feature.join("spec.md"),
+            r#"# Feature Specification: Photo Albums
+
+## User Scenarios & Testing *(mandatory)*
+
+### User Story 1 - Create album (Priority: P1)
+
+Users group photos into albums.
+
+**Acceptance Scenarios**:
+
+1. **Given** a library with photos, **When** the user creates an album, **Then** the album is listed
+2. **When** the user renames an album, **Then** the new name is shown
+
+### Edge Cases
+
+- What happens when the album is empty?
+
+## Requirements *(mandatory)*
+
+### Functional Requirements
+
+- **FR-001**: System MUST let users create albums. Albums are sorted by date.
+- **FR-002**: System MUST let users rename albums
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            feature.join("tasks.md"),
+            "## Phase 1: Setup\n- [ ] T001 Create album model in src/models/album.py\n## Phase 3: User Story 1\n- [ ] T002 [US1] Album endpoints in src/api/albums.py\n",
+        )
+        .unwrap();
+        std::fs::write(
+            feature.join("plan.md"),
+            "# Plan\n\n## Summary\nAdd albums so users can organise a growing photo library.\n\n## Technical Context\n",
+        )
+        .unwrap();
+
+        let strategy = SpeckitStrategy::new();
+        assert!(strategy.can_handle(temp_dir.path()));
+        let import = strategy.import(temp_dir.path()).unwrap();
+
+        assert_eq!(import.summary, "Photo Albums");
+        assert!(import.why.contains("growing photo library"));
+        let spec = &import.specs[0];
+        assert_eq!(spec.id, "001-photo-albums");
+        assert_eq!(spec.requirements.len(), 2);
+        assert_eq!(spec.requirements[0].id.as_deref(), Some("FR-001"));
+        assert_eq!(spec.scenarios.len(), 2);
+        assert_eq!(spec.scenarios[0].name, "User Story 1 - Create album (1)");
+        assert_eq!(spec.scenarios[0].given, vec!["a library with photos"]);
+        assert_eq!(spec.scenarios[1].when, "the user renames an album");
+        assert_eq!(import.tasks.len(), 2);
+        assert_eq!(import.tasks[1].spec_id.as_deref(), Some("001-photo-albums"));
+
+        import.write("photo-albums", temp_dir.path(), false).unwrap();
+        let tasks = std::fs::read_to_string(
+            temp_dir.path().join("agentd/changes/photo-albums/tasks.md"),
+        )
+        .unwrap();
+        assert!(tasks.contains("file: src/models/album.py"));
+        assert!(tasks.contains("spec_ref: specs/001-photo-albums.md"));
+    }
 }
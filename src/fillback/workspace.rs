@@ -0,0 +1,734 @@
+//! Workspace Detection
+//!
+//! Detects the packages of a monorepo so fillback can analyze each one
+//! separately: Cargo workspace members, npm/pnpm workspaces, Go modules
+//! (`go.work` or nested `go.mod` files) and Python packages (`pyproject.toml`).
+
+use crate::mcp::tools::mermaid;
+use crate::Result;
+use ignore::WalkBuilder;
+use serde_json::json;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directories never searched for package manifests
+const SKIPPED_DIRS: &[&str] = &[
+    "node_modules",
+    "target",
+    "vendor",
+    "testdata",
+    "venv",
+    ".venv",
+];
+
+/// Build system a package belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageKind {
+    Cargo,
+    Npm,
+    Go,
+    Python,
+}
+
+impl std::fmt::Display for PackageKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cargo => write!(f, "Cargo"),
+            Self::Npm => write!(f, "npm"),
+            Self::Go => write!(f, "Go"),
+            Self::Python => write!(f, "Python"),
+        }
+    }
+}
+
+/// A package of the workspace
+#[derive(Debug, Clone)]
+pub struct Package {
+    /// Name declared in the manifest (Go: module path)
+    pub name: String,
+    pub kind: PackageKind,
+    /// Package directory
+    pub root: PathBuf,
+    /// Names of all declared dependencies, internal or not
+    pub dependencies: Vec<String>,
+}
+
+impl Package {
+    /// Folder name for the package's specs: `@acme/web` -> `acme-web`,
+    /// `github.com/acme/api/v2` -> `api`
+    pub fn slug(&self) -> String {
+        let name = match self.kind {
+            PackageKind::Go => self
+                .name
+                .rsplit('/')
+                .find(|segment| !is_go_major_version(segment))
+                .unwrap_or(&self.name),
+            _ => &self.name,
+        };
+        name.trim_start_matches('@')
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .collect()
+    }
+
+    /// Whether the package is known by `name` or its slug
+    fn is_named(&self, name: &str) -> bool {
+        self.name == name || self.slug() == name
+    }
+}
+
+/// Packages found under a source directory
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    pub root: PathBuf,
+    pub packages: Vec<Package>,
+}
+
+impl Workspace {
+    /// Detect the packages under `root`
+    ///
+    /// Cargo and npm/pnpm packages come from the workspace declarations of
+    /// the root manifest; Go modules and Python packages from the manifests
+    /// found in the tree. At most one package is kept per directory.
+    pub fn detect(root: &Path) -> Result<Self> {
+        let mut packages = Vec::new();
+        packages.extend(detect_cargo(root)?);
+        packages.extend(detect_npm(root)?);
+        packages.extend(detect_go(root)?);
+        packages.extend(detect_python(root)?);
+
+        let mut roots = HashSet::new();
+        packages.retain(|package: &Package| roots.insert(package.root.clone()));
+        packages.sort_by(|a, b| a.root.cmp(&b.root));
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            packages,
+        })
+    }
+
+    /// Whether the tree holds more than one package
+    pub fn is_multi_package(&self) -> bool {
+        self.packages.len() > 1
+    }
+
+    /// Find a package by manifest name or spec folder name
+    pub fn find(&self, name: &str) -> Option<&Package> {
+        self.packages.iter().find(|package| package.is_named(name))
+    }
+
+    /// Like [`Workspace::find`], failing with the available package names
+    pub fn require(&self, name: &str) -> Result<&Package> {
+        self.find(name).ok_or_else(|| {
+            let available: Vec<&str> = self.packages.iter().map(|p| p.name.as_str()).collect();
+            if available.is_empty() {
+                anyhow::anyhow!(
+                    "Package '{}' not found: no packages detected in {}",
+                    name,
+                    self.root.display()
+                )
+            } else {
+                anyhow::anyhow!(
+                    "Package '{}' not found. Available packages: {}",
+                    name,
+                    available.join(", ")
+                )
+            }
+        })
+    }
+
+    /// Package owning a file: the one with the deepest root containing it
+    pub fn package_of(&self, path: &Path) -> Option<&Package> {
+        self.packages
+            .iter()
+            .filter(|package| path.starts_with(&package.root))
+            .max_by_key(|package| package.root.components().count())
+    }
+
+    /// Roots of other packages nested inside `package`, excluded from its
+    /// analysis (e.g. members below a Cargo workspace root package)
+    pub fn nested_roots(&self, package: &Package) -> Vec<PathBuf> {
+        self.packages
+            .iter()
+            .filter(|other| other.root != package.root && other.root.starts_with(&package.root))
+            .map(|other| other.root.clone())
+            .collect()
+    }
+
+    /// Workspace packages `package` depends on
+    pub fn internal_dependencies(&self, package: &Package) -> Vec<&Package> {
+        let normalize = |name: &str| name.to_lowercase().replace('_', "-");
+        let declared: HashSet<String> = package.dependencies.iter().map(|d| normalize(d)).collect();
+        self.packages
+            .iter()
+            .filter(|other| {
+                other.root != package.root && declared.contains(&normalize(&other.name))
+            })
+            .collect()
+    }
+
+    /// Render inter-package dependencies as a Mermaid flowchart
+    pub fn to_mermaid(&self) -> Result<String> {
+        let id = |package: &Package| {
+            format!(
+                "pkg_{}",
+                package
+                    .slug()
+                    .chars()
+                    .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                    .collect::<String>()
+            )
+        };
+        let nodes: Vec<_> = self
+            .packages
+            .iter()
+            .map(|package| json!({ "id": id(package), "label": package.name.replace('"', "'"), "shape": "rounded" }))
+            .collect();
+        let edges: Vec<_> = self
+            .packages
+            .iter()
+            .flat_map(|package| {
+                self.internal_dependencies(package)
+                    .into_iter()
+                    .map(move |dep| json!({ "from": id(package), "to": id(dep), "style": "arrow" }))
+            })
+            .collect();
+
+        mermaid::call_tool(
+            "generate_mermaid_flowchart",
+            &json!({ "direction": "LR", "nodes": nodes, "edges": edges }),
+        )
+    }
+
+    /// Render the workspace overview specification
+    pub fn to_markdown(&self) -> Result<String> {
+        let mut content = String::new();
+        content.push_str("# Specification: Workspace Overview\n\n");
+
+        content.push_str("## Overview\n\n");
+        content.push_str(&format!(
+            "Workspace with {} packages. Each package has its own specification folder.\n\n",
+            self.packages.len()
+        ));
+
+        content.push_str("## Packages\n\n");
+        content.push_str("| Package | Kind | Path | Specs |\n");
+        content.push_str("|---------|------|------|-------|\n");
+        for package in &self.packages {
+            let path = package
+                .root
+                .strip_prefix(&self.root)
+                .unwrap_or(&package.root);
+            let path = if path.as_os_str().is_empty() {
+                ".".to_string()
+            } else {
+                path.display().to_string()
+            };
+            content.push_str(&format!(
+                "| {} | {} | `{}` | [{}/]({}/_overview.md) |\n",
+                package.name,
+                package.kind,
+                path,
+                package.slug(),
+                package.slug()
+            ));
+        }
+
+        content.push_str("\n## Dependencies\n\n");
+        let mut any = false;
+        for package in &self.packages {
+            let deps = self.internal_dependencies(package);
+            if deps.is_empty() {
+                continue;
+            }
+            any = true;
+            let names: Vec<String> = deps.iter().map(|dep| format!("`{}`", dep.name)).collect();
+            content.push_str(&format!(
+                "- `{}` depends on {}\n",
+                package.name,
+                names.join(", ")
+            ));
+        }
+        if !any {
+            content.push_str("No dependencies between workspace packages.\n");
+        }
+
+        content.push_str(&format!(
+            "\n## Flow Diagram\n\n```mermaid\n{}```\n",
+            self.to_mermaid()?
+        ));
+
+        Ok(content)
+    }
+}
+
+/// Cargo workspace members (and the root package, if any)
+fn detect_cargo(root: &Path) -> Result<Vec<Package>> {
+    let Some(manifest) = read_toml(&root.join("Cargo.toml"))? else {
+        return Ok(vec![]);
+    };
+
+    let mut packages = Vec::new();
+    if let Some(package) = cargo_package(root, &manifest) {
+        packages.push(package);
+    }
+    if let Some(workspace) = manifest.get("workspace") {
+        let patterns = string_array(workspace.get("members"));
+        let excluded = expand_patterns(root, &string_array(workspace.get("exclude")));
+        for dir in expand_patterns(root, &patterns) {
+            if excluded.contains(&dir) {
+                continue;
+            }
+            if let Some(member) = read_toml(&dir.join("Cargo.toml"))? {
+                packages.extend(cargo_package(&dir, &member));
+            }
+        }
+    }
+    Ok(packages)
+}
+
+fn cargo_package(dir: &Path, manifest: &toml::Value) -> Option<Package> {
+    let name = manifest.get("package")?.get("name")?.as_str()?;
+    let dependencies = ["dependencies", "dev-dependencies", "build-dependencies"]
+        .iter()
+        .filter_map(|table| manifest.get(table)?.as_table())
+        .flat_map(|table| table.keys().cloned())
+        .collect();
+    Some(Package {
+        name: name.to_string(),
+        kind: PackageKind::Cargo,
+        root: dir.to_path_buf(),
+        dependencies,
+    })
+}
+
+/// npm/yarn `workspaces` and pnpm `pnpm-workspace.yaml` members
+fn detect_npm(root: &Path) -> Result<Vec<Package>> {
+    let manifest = read_json(&root.join("package.json"))?;
+
+    let mut patterns = Vec::new();
+    if let Some(workspaces) = manifest.as_ref().and_then(|m| m.get("workspaces")) {
+        // Either a list or { "packages": [...] }
+        let list = workspaces.get("packages").unwrap_or(workspaces);
+        patterns.extend(
+            list.as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|p| p.as_str().map(str::to_string)),
+        );
+    }
+    let pnpm = root.join("pnpm-workspace.yaml");
+    if pnpm.is_file() {
+        let workspace: serde_yaml::Value = serde_yaml::from_str(&fs::read_to_string(&pnpm)?)?;
+        patterns.extend(
+            workspace
+                .get("packages")
+                .and_then(|p| p.as_sequence())
+                .into_iter()
+                .flatten()
+                .filter_map(|p| p.as_str().map(str::to_string)),
+        );
+    }
+
+    let mut packages = Vec::new();
+    if let Some(package) = manifest.as_ref().and_then(|m| npm_package(root, m)) {
+        packages.push(package);
+    }
+    let (excluded, included): (Vec<String>, Vec<String>) =
+        patterns.into_iter().partition(|p| p.starts_with('!'));
+    let excluded: Vec<String> = excluded.iter().map(|p| p[1..].to_string()).collect();
+    let excluded = expand_patterns(root, &excluded);
+    for dir in expand_patterns(root, &included) {
+        if excluded.contains(&dir) {
+            continue;
+        }
+        if let Some(member) = read_json(&dir.join("package.json"))? {
+            packages.extend(npm_package(&dir, &member));
+        }
+    }
+    Ok(packages)
+}
+
+fn npm_package(dir: &Path, manifest: &serde_json::Value) -> Option<Package> {
+    let name = manifest.get("name")?.as_str()?;
+    let dependencies = ["dependencies", "devDependencies", "peerDependencies"]
+        .iter()
+        .filter_map(|field| manifest.get(field)?.as_object())
+        .flat_map(|deps| deps.keys().cloned())
+        .collect();
+    Some(Package {
+        name: name.to_string(),
+        kind: PackageKind::Npm,
+        root: dir.to_path_buf(),
+        dependencies,
+    })
+}
+
+/// Go modules listed in `go.work`, or every `go.mod` in the tree
+fn detect_go(root: &Path) -> Result<Vec<Package>> {
+    let go_work = root.join("go.work");
+    let dirs = if go_work.is_file() {
+        go_directives(&fs::read_to_string(&go_work)?, "use")
+            .into_iter()
+            .map(|dir| root.join(dir))
+            .collect()
+    } else {
+        find_manifests(root, "go.mod")
+    };
+
+    let mut packages = Vec::new();
+    for dir in dirs {
+        let Ok(content) = fs::read_to_string(dir.join("go.mod")) else {
+            continue;
+        };
+        let Some(name) = go_directives(&content, "module").into_iter().next() else {
+            continue;
+        };
+        packages.push(Package {
+            name,
+            kind: PackageKind::Go,
+            root: normalize(&dir),
+            dependencies: go_directives(&content, "require"),
+        });
+    }
+    Ok(packages)
+}
+
+/// First argument of each `directive x` line and `directive ( ... )` block entry
+fn go_directives(content: &str, directive: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut in_block = false;
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        if in_block {
+            if line == ")" {
+                in_block = false;
+            } else if let Some(value) = line.split_whitespace().next() {
+                values.push(value.to_string());
+            }
+            continue;
+        }
+        let Some(rest) = line
+            .strip_prefix(directive)
+            .filter(|r| r.starts_with([' ', '\t', '(']))
+        else {
+            continue;
+        };
+        let rest = rest.trim();
+        if rest == "(" {
+            in_block = true;
+        } else if let Some(value) = rest.split_whitespace().next() {
+            values.push(value.to_string());
+        }
+    }
+    values
+}
+
+/// Python packages declared by `pyproject.toml` (PEP 621 or Poetry)
+fn detect_python(root: &Path) -> Result<Vec<Package>> {
+    let mut packages = Vec::new();
+    for dir in find_manifests(root, "pyproject.toml") {
+        let Some(manifest) = read_toml(&dir.join("pyproject.toml"))? else {
+            continue;
+        };
+        let poetry = manifest.get("tool").and_then(|t| t.get("poetry"));
+        let project = manifest.get("project");
+        let Some(name) = project
+            .or(poetry)
+            .and_then(|p| p.get("name"))
+            .and_then(|n| n.as_str())
+        else {
+            continue;
+        };
+
+        // PEP 508 requirements ("requests>=2") and Poetry dependency tables
+        let mut dependencies: Vec<String> =
+            string_array(project.and_then(|p| p.get("dependencies")))
+                .iter()
+                .filter_map(|requirement| {
+                    let end = requirement
+                        .find(|c: char| {
+                            !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+                        })
+                        .unwrap_or(requirement.len());
+                    (end > 0).then(|| requirement[..end].to_string())
+                })
+                .collect();
+        dependencies.extend(
+            poetry
+                .and_then(|p| p.get("dependencies"))
+                .and_then(|d| d.as_table())
+                .into_iter()
+                .flat_map(|table| table.keys().filter(|k| *k != "python").cloned()),
+        );
+
+        packages.push(Package {
+            name: name.to_string(),
+            kind: PackageKind::Python,
+            root: dir,
+            dependencies,
+        });
+    }
+    Ok(packages)
+}
+
+/// Directories below `root` (inclusive) containing a file named `manifest`
+fn find_manifests(root: &Path, manifest: &str) -> Vec<PathBuf> {
+    let walker = WalkBuilder::new(root)
+        .standard_filters(true)
+        .filter_entry(|entry| {
+            !entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| SKIPPED_DIRS.contains(&name))
+        })
+        .build();
+
+    let mut dirs: Vec<PathBuf> = walker
+        .flatten()
+        .filter(|entry| entry.file_name() == manifest && entry.path().is_file())
+        .filter_map(|entry| entry.path().parent().map(Path::to_path_buf))
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+/// Directories matching workspace member globs such as `crates/*`
+fn expand_patterns(root: &Path, patterns: &[String]) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    for pattern in patterns {
+        let pattern = root.join(pattern.trim_end_matches('/'));
+        let Ok(paths) = glob::glob(&pattern.to_string_lossy()) else {
+            continue;
+        };
+        for path in paths.flatten().filter(|path| path.is_dir()) {
+            let path = normalize(&path);
+            if !dirs.contains(&path) {
+                dirs.push(path);
+            }
+        }
+    }
+    dirs
+}
+
+/// Drop `.` components so `root/./app` and `root/app` compare equal
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| !matches!(c, std::path::Component::CurDir))
+        .collect()
+}
+
+/// `v2`, `v3`, ... suffixes of Go module paths
+fn is_go_major_version(segment: &str) -> bool {
+    segment
+        .strip_prefix('v')
+        .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn string_array(value: Option<&toml::Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.as_str().map(str::to_string))
+        .collect()
+}
+
+fn read_toml(path: &Path) -> Result<Option<toml::Value>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(path)?;
+    toml::from_str(&content)
+        .map(Some)
+        .map_err(|e| anyhow::anyhow!("Invalid manifest {}: {}", path.display(), e))
+}
+
+fn read_json(path: &Path) -> Result<Option<serde_json::Value>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(path)?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| anyhow::anyhow!("Invalid manifest {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn names(workspace: &Workspace) -> Vec<&str> {
+        workspace.packages.iter().map(|p| p.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_detect_cargo_workspace() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "Cargo.toml",
+            "[package]\nname = \"app\"\n\n[dependencies]\ncore = { path = \"crates/core\" }\n\n[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/old\"]\n",
+        );
+        write(
+            root,
+            "crates/core/Cargo.toml",
+            "[package]\nname = \"core\"\n\n[dependencies]\nserde = \"1\"\n",
+        );
+        write(
+            root,
+            "crates/cli/Cargo.toml",
+            "[package]\nname = \"my-cli\"\n\n[dependencies]\ncore = { path = \"../core\" }\n",
+        );
+        write(root, "crates/old/Cargo.toml", "[package]\nname = \"old\"\n");
+
+        let workspace = Workspace::detect(root).unwrap();
+        assert_eq!(names(&workspace), vec!["app", "my-cli", "core"]);
+        assert!(workspace.is_multi_package());
+
+        let app = workspace.find("app").unwrap();
+        assert_eq!(workspace.nested_roots(app).len(), 2);
+        let cli = workspace.find("my-cli").unwrap();
+        let deps: Vec<&str> = workspace
+            .internal_dependencies(cli)
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(deps, vec!["core"]);
+        assert_eq!(
+            workspace
+                .package_of(&root.join("crates/core/src/lib.rs"))
+                .unwrap()
+                .name,
+            "core"
+        );
+        assert_eq!(
+            workspace
+                .package_of(&root.join("src/main.rs"))
+                .unwrap()
+                .name,
+            "app"
+        );
+
+        let err = workspace.require("missing").unwrap_err().to_string();
+        assert!(err.contains("Available packages: app, my-cli, core"));
+    }
+
+    #[test]
+    fn test_detect_npm_and_pnpm_workspaces() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "package.json",
+            r#"{ "private": true, "workspaces": ["packages/*", "!packages/legacy"] }"#,
+        );
+        write(
+            root,
+            "packages/web/package.json",
+            r#"{ "name": "@acme/web", "dependencies": { "@acme/ui": "*", "react": "18" } }"#,
+        );
+        write(
+            root,
+            "packages/ui/package.json",
+            r#"{ "name": "@acme/ui" }"#,
+        );
+        write(
+            root,
+            "packages/legacy/package.json",
+            r#"{ "name": "legacy" }"#,
+        );
+
+        let workspace = Workspace::detect(root).unwrap();
+        assert_eq!(names(&workspace), vec!["@acme/ui", "@acme/web"]);
+        let web = workspace.find("acme-web").unwrap();
+        assert_eq!(web.slug(), "acme-web");
+        assert_eq!(workspace.internal_dependencies(web)[0].name, "@acme/ui");
+
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(root, "pnpm-workspace.yaml", "packages:\n  - 'apps/*'\n");
+        write(root, "apps/site/package.json", r#"{ "name": "site" }"#);
+        assert_eq!(names(&Workspace::detect(root).unwrap()), vec!["site"]);
+    }
+
+    #[test]
+    fn test_detect_go_modules_and_python_packages() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "go.work",
+            "go 1.22\n\nuse (\n    ./api\n    ./shared // common code\n)\n",
+        );
+        write(
+            root,
+            "api/go.mod",
+            "module github.com/acme/api/v2\n\ngo 1.22\n\nrequire (\n    github.com/acme/shared v0.0.0\n    github.com/gin-gonic/gin v1.9.1\n)\n",
+        );
+        write(root, "shared/go.mod", "module github.com/acme/shared\n");
+        write(
+            root,
+            "tools/pyproject.toml",
+            "[project]\nname = \"acme-tools\"\ndependencies = [\"requests>=2\", \"click\"]\n",
+        );
+        write(
+            root,
+            "node_modules/dep/pyproject.toml",
+            "[project]\nname = \"ignored\"\n",
+        );
+
+        let workspace = Workspace::detect(root).unwrap();
+        assert_eq!(
+            names(&workspace),
+            vec![
+                "github.com/acme/api/v2",
+                "github.com/acme/shared",
+                "acme-tools"
+            ]
+        );
+        let api = &workspace.packages[0];
+        assert_eq!(api.kind, PackageKind::Go);
+        assert_eq!(api.slug(), "api");
+        assert_eq!(
+            workspace.internal_dependencies(api)[0].name,
+            "github.com/acme/shared"
+        );
+        assert_eq!(
+            workspace.packages[2].dependencies,
+            vec!["requests", "click"]
+        );
+
+        let overview = workspace.to_markdown().unwrap();
+        assert!(overview.contains("# Specification: Workspace Overview"));
+        assert!(
+            overview.contains("| github.com/acme/api/v2 | Go | `api` | [api/](api/_overview.md) |")
+        );
+        assert!(overview.contains("- `github.com/acme/api/v2` depends on `github.com/acme/shared`"));
+        assert!(overview.contains("pkg_api --> pkg_shared"));
+    }
+
+    #[test]
+    fn test_single_package_project() {
+        let temp = TempDir::new().unwrap();
+        write(temp.path(), "Cargo.toml", "[package]\nname = \"solo\"\n");
+        let workspace = Workspace::detect(temp.path()).unwrap();
+        assert_eq!(names(&workspace), vec!["solo"]);
+        assert!(!workspace.is_multi_package());
+    }
+}
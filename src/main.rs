@@ -56,6 +56,95 @@ enum Commands {
     MergeChange {
         /// Change ID to merge
         change_id: String,
+
+        /// Show the spec diff the merge would produce without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Revert a merged change's spec edits and move it back to active changes
+    Unmerge {
+        /// Change ID to unmerge
+        change_id: String,
+
+        /// Revert even if later merges edited the same specs
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Show saved versions of a change document with diffs
+    History {
+        /// Change ID
+        change_id: String,
+
+        /// File: "proposal", "tasks", or spec name
+        file: String,
+
+        /// Print unified diffs between successive versions
+        #[arg(short, long)]
+        diff: bool,
+    },
+
+    /// Restore a change document to a saved version
+    Restore {
+        /// Change ID
+        change_id: String,
+
+        /// File: "proposal", "tasks", or spec name
+        file: String,
+
+        /// Version number (see 'agentd history')
+        version: u32,
+    },
+
+    /// Show the requirement → task → file → test traceability matrix
+    Trace {
+        /// Change ID
+        change_id: String,
+
+        /// Output format: markdown, json, or mermaid
+        #[arg(short, long, default_value = "markdown")]
+        format: String,
+
+        /// Git ref to diff the working tree against (default: the change's
+        /// baseline commit, else main)
+        #[arg(long)]
+        base: Option<String>,
+    },
+
+    /// Re-review a change's specs after main specs moved since planning
+    RebaseChange {
+        /// Change ID
+        change_id: String,
+
+        /// Review every delta spec, even if no main spec changed
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Report overlaps between active changes and suggest a merge order
+    Conflicts {
+        /// Only show conflicts involving this change
+        change_id: Option<String>,
+
+        /// Output format: markdown or json
+        #[arg(short, long, default_value = "markdown")]
+        format: String,
+    },
+
+    /// Export main specs (or a change's specs) to OpenSpec or Speckit
+    Export {
+        /// Target format: openspec or speckit
+        #[arg(short, long)]
+        format: String,
+
+        /// Export this change's specs instead of the main specs
+        #[arg(short, long)]
+        change: Option<String>,
+
+        /// Output directory (default: export/<format>)
+        #[arg(short, long)]
+        output: Option<String>,
     },
 
     /// Migrate files to XML format
@@ -102,19 +191,55 @@ enum Commands {
         check: bool,
     },
 
-    /// Bootstrap Agentd specs from existing codebase using AST analysis
+    /// Bootstrap Agentd specs from existing codebase using AST analysis,
+    /// or import OpenSpec/Speckit specs as a change
     Fillback {
-        /// Path to source directory to analyze (default: current directory)
+        /// Path to source directory (or spec file) to analyze (default: current directory)
         #[arg(short, long)]
         path: Option<String>,
 
-        /// Only generate spec for a specific module name
-        #[arg(short, long)]
-        module: Option<String>,
+        /// Only analyze the named workspace package (Cargo, npm/pnpm, Go or Python)
+        #[arg(long)]
+        package: Option<String>,
 
         /// Overwrite existing specs without confirmation
         #[arg(short, long)]
         force: bool,
+
+        /// Import strategy: auto, openspec, speckit, code
+        #[arg(short, long, default_value = "code")]
+        strategy: String,
+
+        /// Change ID to create when importing OpenSpec/Speckit specs
+        #[arg(short, long)]
+        change: Option<String>,
+
+        /// Parser threads for code analysis (default: [fillback] jobs, 0 = all CPUs)
+        #[arg(short, long)]
+        jobs: Option<usize>,
+
+        /// Re-parse every file instead of reusing .agentd/cache results
+        #[arg(long)]
+        no_cache: bool,
+    },
+
+    /// Report drift between main specs and code (exits 2 when drifted, 1 on errors)
+    Drift {
+        /// Path to source directory to analyze (default: current directory)
+        #[arg(short, long)]
+        path: Option<String>,
+
+        /// Only check the named workspace package
+        #[arg(long)]
+        package: Option<String>,
+
+        /// Output format: markdown or json
+        #[arg(short, long, default_value = "markdown")]
+        format: String,
+
+        /// Re-parse every file instead of reusing .agentd/cache results
+        #[arg(long)]
+        no_cache: bool,
     },
 
     /// Generate shell completions
@@ -142,8 +267,8 @@ enum Commands {
     Server(agentd::cli::server::ServerCommands),
 
     /// [DEPRECATED] Use 'server' instead. MCP server management.
-    #[command(subcommand, hide = true)]
-    McpServer(agentd::cli::mcp_server_mgmt::McpServerCommands),
+    #[command(hide = true)]
+    McpServer(agentd::cli::mcp_server_mgmt::McpServerArgs),
 
     /// Knowledge base operations
     #[command(subcommand)]
@@ -197,10 +322,10 @@ fn main() {
 }
 
 async fn run_async(cli: Cli) -> Result<()> {
-    // Auto-upgrade check for all commands except init, completions, archived, server, and CLI utility commands
+    // Auto-upgrade check for all commands except init, completions, drift, archived, server, and CLI utility commands
     let skip_upgrade = matches!(
         cli.command,
-        Commands::Init { .. } | Commands::Completions { .. } | Commands::Archived | Commands::Server(_) | Commands::McpServer(_) | Commands::Knowledge(_) | Commands::Spec(_) | Commands::File(_) | Commands::Proposal(_) | Commands::Tasks(_) | Commands::Implementation(_) | Commands::Clarifications(_)
+        Commands::Init { .. } | Commands::Completions { .. } | Commands::Drift { .. } | Commands::Archived | Commands::Server(_) | Commands::McpServer(_) | Commands::Knowledge(_) | Commands::Spec(_) | Commands::File(_) | Commands::Proposal(_) | Commands::Tasks(_) | Commands::Implementation(_) | Commands::Clarifications(_)
     );
 
     #[cfg(feature = "ui")]
@@ -238,9 +363,53 @@ async fn run_async(cli: Cli) -> Result<()> {
             // Result is used by skills for HITL decisions, CLI just needs success/error
         }
 
-        Commands::MergeChange { change_id } => {
+        Commands::MergeChange { change_id, dry_run } => {
             println!("{}", format!("📦 Merging change: {}", change_id).cyan());
-            agentd::cli::archive::run(&change_id).await?;
+            agentd::cli::archive::run(&change_id, dry_run).await?;
+        }
+
+        Commands::Unmerge { change_id, force } => {
+            agentd::cli::unmerge::run(&change_id, force)?;
+        }
+
+        Commands::History {
+            change_id,
+            file,
+            diff,
+        } => {
+            agentd::cli::history::run(&change_id, &file, diff)?;
+        }
+
+        Commands::Restore {
+            change_id,
+            file,
+            version,
+        } => {
+            agentd::cli::history::run_restore(&change_id, &file, version)?;
+        }
+
+        Commands::Trace {
+            change_id,
+            format,
+            base,
+        } => {
+            agentd::cli::trace::run(&change_id, &format, base.as_deref())?;
+        }
+
+        Commands::RebaseChange { change_id, force } => {
+            agentd::cli::rebase::run(&change_id, force).await?;
+        }
+
+        Commands::Conflicts { change_id, format } => {
+            agentd::cli::conflicts::run(change_id.as_deref(), &format)?;
+        }
+
+        Commands::Export {
+            format,
+            change,
+            output,
+        } => {
+            agentd::cli::export::run(&format, change.as_deref(), output.as_deref())?;
         }
 
         Commands::MigrateXml { change_id } => {
@@ -274,10 +443,36 @@ async fn run_async(cli: Cli) -> Result<()> {
 
         Commands::Fillback {
             path,
-            module,
+            package,
             force,
+            strategy,
+            change,
+            jobs,
+            no_cache,
         } => {
-            agentd::cli::fillback::run(path.as_deref(), module.as_deref(), force).await?;
+            agentd::cli::fillback::run(
+                path.as_deref(),
+                package.as_deref(),
+                force,
+                &strategy,
+                change.as_deref(),
+                jobs,
+                no_cache,
+            )
+            .await?;
+        }
+
+        Commands::Drift {
+            path,
+            package,
+            format,
+            no_cache,
+        } => {
+            let outcome =
+                agentd::cli::drift::run(path.as_deref(), package.as_deref(), &format, no_cache)?;
+            if outcome == agentd::cli::drift::DriftOutcome::Drifted {
+                std::process::exit(agentd::cli::drift::DRIFT_EXIT_CODE);
+            }
         }
 
         Commands::Completions { shell } => {
@@ -299,10 +494,17 @@ async fn run_async(cli: Cli) -> Result<()> {
             agentd::cli::server::run(cmd).await?;
         }
 
-        Commands::McpServer(cmd) => {
-            eprintln!("{}", "⚠ 'mcp-server' is deprecated. Use 'server' instead.".yellow());
-            agentd::cli::mcp_server_mgmt::run(cmd).await?;
-        }
+        Commands::McpServer(args) => match args.command {
+            Some(cmd) => {
+                eprintln!(
+                    "{}",
+                    "⚠ 'mcp-server' is deprecated. Use 'server' instead.".yellow()
+                );
+                agentd::cli::mcp_server_mgmt::run(cmd).await?;
+            }
+            // Stdio transport, launched by agent MCP configs
+            None => agentd::cli::mcp_server::run(args.tools.as_deref()).await?,
+        },
 
         Commands::Knowledge(cmd) => {
             agentd::cli::knowledge::run(cmd)?;
@@ -0,0 +1,125 @@
+//! MCP tool call audit log
+//!
+//! Every tool call handled by the registry, native or proxied, is appended as
+//! one JSON line to `agentd/logs/mcp-audit.jsonl` in the project.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Audit log location relative to the project root
+pub const AUDIT_LOG_PATH: &str = "agentd/logs/mcp-audit.jsonl";
+
+/// A single tool call record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: String,
+    /// Tool name as seen by the client (including any namespace prefix)
+    pub tool: String,
+    /// "native", "custom", or the name of the upstream server that handled the call
+    pub source: String,
+    /// Workflow stage the server was started for, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stage: Option<String>,
+    pub duration_ms: u64,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AuditEntry {
+    pub fn new(tool: &str, source: &str, stage: Option<&str>, duration_ms: u64) -> Self {
+        Self {
+            timestamp: Utc::now().to_rfc3339(),
+            tool: tool.to_string(),
+            source: source.to_string(),
+            stage: stage.map(String::from),
+            duration_ms,
+            success: true,
+            error: None,
+        }
+    }
+
+    pub fn with_error(mut self, error: &str) -> Self {
+        self.success = false;
+        self.error = Some(error.lines().next().unwrap_or_default().to_string());
+        self
+    }
+}
+
+/// Path of the audit log for a project
+pub fn audit_log_path(project_root: &Path) -> PathBuf {
+    project_root.join(AUDIT_LOG_PATH)
+}
+
+/// Append an entry to the project's audit log
+///
+/// Best-effort: projects without an `agentd/` directory are skipped and write
+/// failures are ignored, so auditing never fails a tool call.
+pub fn record(project_root: &Path, entry: &AuditEntry) {
+    if !project_root.join("agentd").is_dir() {
+        return;
+    }
+    let path = audit_log_path(project_root);
+    let Ok(line) = serde_json::to_string(entry) else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(mut file) = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+    {
+        let _ = writeln!(file, "{}", line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::tools::ToolRegistry;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_tool_calls_are_audited() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path();
+        let change_dir = project_root.join("agentd/changes/test-change");
+        std::fs::create_dir_all(&change_dir).unwrap();
+        std::fs::write(change_dir.join("proposal.md"), "# Proposal\n").unwrap();
+
+        let registry = ToolRegistry::new_for_stage("challenge");
+        let project_path = project_root.to_string_lossy();
+        registry
+            .call_tool(
+                "read_file",
+                &json!({ "project_path": project_path, "change_id": "test-change" }),
+            )
+            .await
+            .unwrap();
+        let missing = registry
+            .call_tool(
+                "read_file",
+                &json!({ "project_path": project_path, "change_id": "missing" }),
+            )
+            .await;
+        assert!(missing.is_err());
+
+        let log = std::fs::read_to_string(audit_log_path(project_root)).unwrap();
+        let entries: Vec<AuditEntry> = log
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].tool, "read_file");
+        assert_eq!(entries[0].source, "native");
+        assert_eq!(entries[0].stage.as_deref(), Some("challenge"));
+        assert!(entries[0].success);
+        assert!(!entries[1].success);
+        assert!(entries[1].error.is_some());
+    }
+}
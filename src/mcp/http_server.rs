@@ -10,28 +10,33 @@
 //! - Project isolation via URL scoping and X-Agentd-Project header
 //! - Multi-project support with single server instance
 //! - Configuration injection for frontend routing
+//! - Per-project upstream MCP tools, connected once and shared across requests
 
 use crate::mcp::server::{JsonRpcError, JsonRpcRequest, JsonRpcResponse};
+use crate::mcp::tools::ToolRegistry;
+use crate::mcp::upstream::{self, UpstreamServer};
 use crate::mcp::{McpServer, Registry};
+use crate::models::AgentdConfig;
+use crate::services::dependency_service;
 use crate::Result;
 use axum::{
-    extract::State,
+    extract::{Query, State},
     http::{HeaderMap, StatusCode},
     response::{Html, IntoResponse, Response},
     routing::{get, post},
     Json, Router,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 use tower_http::cors::CorsLayer;
 
 #[cfg(feature = "ui")]
 use axum::{extract::Path, http::header};
-#[cfg(feature = "ui")]
-use serde::Deserialize;
 
 // =============================================================================
 // Data Models (R6 Dashboard, R5 Config Injection)
@@ -62,6 +67,10 @@ pub struct ProjectInfo {
 pub struct ChangeInfo {
     pub id: String,
     pub status: String,
+    /// Prerequisite changes from `depends_on_changes`
+    pub depends_on: Vec<String>,
+    /// Prerequisites not yet far enough along for impl-change
+    pub blocked_by: Vec<String>,
 }
 
 /// Configuration injected into viewer HTML (R5)
@@ -82,16 +91,95 @@ pub struct InjectedConfig {
 pub struct AppState {
     /// Project registry (thread-safe)
     pub registry: Arc<RwLock<Registry>>,
+    /// MCP servers per project and stage
+    pub mcp: Arc<Mutex<McpServers>>,
 }
 
 impl AppState {
     pub fn new(registry: Registry) -> Self {
         Self {
             registry: Arc::new(RwLock::new(registry)),
+            mcp: Arc::new(Mutex::new(McpServers::default())),
         }
     }
 }
 
+/// Cache of MCP servers for `/mcp`
+///
+/// A project's `[[mcp.upstream]]` servers are connected on its first request
+/// and shared by the tool registries of every stage, next to its
+/// `[[mcp.custom_tools]]`; config changes apply after a server restart.
+#[derive(Default)]
+pub struct McpServers {
+    upstreams: HashMap<PathBuf, Vec<Arc<UpstreamServer>>>,
+    servers: HashMap<(Option<PathBuf>, Option<String>), Arc<McpServer>>,
+}
+
+impl McpServers {
+    /// Get the MCP server for a project (None = native tools only) and stage
+    /// (None = all tools)
+    async fn get(
+        &mut self,
+        project_root: Option<PathBuf>,
+        stage: Option<String>,
+    ) -> Result<Arc<McpServer>> {
+        let key = (project_root, stage);
+        if let Some(server) = self.servers.get(&key) {
+            return Ok(Arc::clone(server));
+        }
+
+        let (project_root, stage) = &key;
+        let mut tool_registry = match stage {
+            Some(s) => ToolRegistry::new_for_stage(s),
+            None => ToolRegistry::new(),
+        };
+
+        if let Some(root) = project_root {
+            let config = AgentdConfig::load(root)?;
+            if !self.upstreams.contains_key(root) {
+                let servers = upstream::connect_all(&config.mcp.upstream, None, root).await;
+                self.upstreams
+                    .insert(root.clone(), servers.into_iter().map(Arc::new).collect());
+            }
+            tool_registry.attach_upstreams(&self.upstreams[root], root);
+            tool_registry.register_custom_tools(&config.mcp.custom_tools, root);
+        }
+
+        let server = Arc::new(McpServer::with_registry(tool_registry));
+        self.servers.insert(key, Arc::clone(&server));
+        Ok(server)
+    }
+}
+
+/// Project and stage a `/mcp` request is served for
+///
+/// Given as query parameters (`/mcp?project=<name or path>&stage=implement`)
+/// or as the `X-Agentd-Project`, `X-Agentd-Cwd` and `X-Agentd-Stage` headers.
+#[derive(Debug, Default, Deserialize)]
+pub struct McpScope {
+    pub project: Option<String>,
+    pub stage: Option<String>,
+}
+
+impl McpScope {
+    /// Fill unset fields from the request headers
+    fn with_headers(mut self, headers: &HeaderMap) -> Self {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+        // A working directory outside a project root falls back to the name
+        let cwd =
+            header("x-agentd-cwd").filter(|cwd| std::path::Path::new(cwd).join("agentd").is_dir());
+        self.project = self.project.or(cwd).or_else(|| header("x-agentd-project"));
+        self.stage = self.stage.or_else(|| header("x-agentd-stage"));
+        self
+    }
+}
+
 // JSON-RPC error codes
 const INTERNAL_ERROR: i32 = -32603;
 
@@ -102,8 +190,19 @@ const INTERNAL_ERROR: i32 = -32603;
 /// Start unified HTTP server with MCP, Dashboard, and Viewer routes
 pub async fn start_server(port: u16, registry: Registry) -> Result<()> {
     let addr = SocketAddr::from(([127, 0, 0, 1], port));
-    let state = AppState::new(registry);
+    let app = app(AppState::new(registry));
+
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    println!("✓ Server listening on http://{}", addr);
+    println!("  Dashboard: http://{}/", addr);
+    println!("  MCP endpoint: http://{}/mcp", addr);
+
+    axum::serve(listener, app).await?;
+    Ok(())
+}
 
+/// Build the router serving MCP, Dashboard, and Viewer routes
+pub fn app(state: AppState) -> Router {
     // Build base router with MCP and dashboard
     #[allow(unused_mut)]
     let mut app = Router::new()
@@ -153,15 +252,7 @@ pub async fn start_server(port: u16, registry: Registry) -> Result<()> {
             .route("/view/:project/:change/api/close", post(api_viewer_close));
     }
 
-    let app = app.layer(CorsLayer::permissive()).with_state(state);
-
-    let listener = tokio::net::TcpListener::bind(&addr).await?;
-    println!("✓ Server listening on http://{}", addr);
-    println!("  Dashboard: http://{}/", addr);
-    println!("  MCP endpoint: http://{}/mcp", addr);
-
-    axum::serve(listener, app).await?;
-    Ok(())
+    app.layer(CorsLayer::permissive()).with_state(state)
 }
 
 /// Health check endpoint
@@ -231,7 +322,18 @@ fn scan_project_changes(project_path: &std::path::Path) -> Vec<ChangeInfo> {
             if entry.path().is_dir() {
                 let id = entry.file_name().to_string_lossy().to_string();
                 let status = read_change_status(&entry.path());
-                changes.push(ChangeInfo { id, status });
+                let dependencies =
+                    dependency_service::dependency_status(&id, project_path).unwrap_or_default();
+                changes.push(ChangeInfo {
+                    depends_on: dependencies.iter().map(|d| d.id.clone()).collect(),
+                    blocked_by: dependencies
+                        .iter()
+                        .filter(|d| !d.is_satisfied())
+                        .map(|d| d.id.clone())
+                        .collect(),
+                    id,
+                    status,
+                });
             }
         }
     }
@@ -702,15 +804,17 @@ async fn api_viewer_close() -> Json<serde_json::Value> {
 
 /// Main MCP request handler
 ///
-/// Project context is now determined by the `project_path` parameter in each tool call,
-/// not by HTTP headers. This allows tools to work with any project without header configuration.
+/// Native tools take their project from the `project_path` parameter in each
+/// tool call. A request scoped to a project (see [`McpScope`]) also gets that
+/// project's upstream and custom tools, and a stage narrows the tool list.
 async fn handle_mcp_request(
-    State(_state): State<AppState>,
-    _headers: HeaderMap,
+    State(state): State<AppState>,
+    Query(scope): Query<McpScope>,
+    headers: HeaderMap,
     Json(request): Json<JsonRpcRequest>,
 ) -> Response {
-    // Execute request directly - project_path is in tool arguments
-    match execute_mcp_request(request).await {
+    let scope = scope.with_headers(&headers);
+    match execute_mcp_request(&state, scope, request).await {
         Ok(Some(response)) => Json(response).into_response(),
         Ok(None) => {
             // Notification - return HTTP 202 Accepted with empty body
@@ -729,20 +833,51 @@ async fn handle_mcp_request(
 
 /// Execute MCP request
 ///
-/// The project context is determined by the `project_path` parameter in tool arguments,
-/// so we don't need to change directories or extract project from headers.
+/// Uses the cached MCP server for the request's project and stage, so
+/// upstream servers are not reconnected per request.
 async fn execute_mcp_request(
+    state: &AppState,
+    scope: McpScope,
     request: JsonRpcRequest,
 ) -> Result<Option<JsonRpcResponse>> {
-    // Create MCP server instance
-    let server = McpServer::new()?;
-
-    // Handle the request (project_path is in tool arguments)
+    let project_root = match scope.project {
+        Some(project) => Some(resolve_mcp_project(state, &project).await?),
+        None => None,
+    };
+    let server = state
+        .mcp
+        .lock()
+        .await
+        .get(project_root, scope.stage)
+        .await?;
+
+    // Handle the request (native tools take project_path from their arguments)
     let response = server.handle_request_json(&request).await;
 
     Ok(response)
 }
 
+/// Resolve a project given as a path to its root or as a registered name
+async fn resolve_mcp_project(state: &AppState, project: &str) -> Result<PathBuf> {
+    let path = PathBuf::from(project);
+    if path.is_absolute() && path.join("agentd").is_dir() {
+        return Ok(path.canonicalize().unwrap_or(path));
+    }
+
+    if let Some(path) = state.registry.read().await.get_project_path(project) {
+        return Ok(path.clone());
+    }
+
+    // The project may have been registered after the server started
+    let registry = Registry::load()?;
+    let path = registry
+        .get_project_path(project)
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("Project '{}' not registered", project))?;
+    *state.registry.write().await = registry;
+    Ok(path)
+}
+
 /// Create error response
 fn error_response(id: Option<Value>, code: i32, message: String) -> Response {
     let response = JsonRpcResponse {
@@ -776,4 +911,155 @@ mod tests {
         assert!(json.contains("base_path"));
         assert!(json.contains("myproj"));
     }
+
+    /// POST a JSON-RPC request to the router and return the response body
+    async fn post_mcp(router: &Router, uri: &str, body: Value) -> Value {
+        use axum::body::{to_bytes, Body};
+        use axum::http::Request;
+        use tower::Service;
+
+        // Router is always ready, so it can be called without poll_ready
+        let response = router
+            .clone()
+            .call(
+                Request::post(uri)
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    fn tool_names(response: &Value) -> Vec<String> {
+        response["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_mcp_endpoint_serves_upstream_tools() {
+        use serde_json::json;
+
+        // Fake upstream answering initialize, tools/list and tools/call
+        async fn handle(Json(request): Json<Value>) -> Response {
+            let id = request.get("id").cloned();
+            let result = match request["method"].as_str() {
+                Some("initialize") => json!({ "protocolVersion": "2024-11-05" }),
+                Some("tools/list") => json!({ "tools": [{ "name": "search", "inputSchema": { "type": "object" } }] }),
+                Some("tools/call") => json!({ "content": [{ "type": "text", "text": "found" }] }),
+                _ => return StatusCode::ACCEPTED.into_response(),
+            };
+            Json(json!({ "jsonrpc": "2.0", "id": id, "result": result })).into_response()
+        }
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, Router::new().route("/mcp", post(handle)))
+                .await
+                .unwrap();
+        });
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_root = temp_dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(project_root.join("agentd")).unwrap();
+        std::fs::write(
+            project_root.join("agentd/config.toml"),
+            format!(
+                "project_name = \"demo\"\nscripts_dir = \"agentd/scripts\"\n\n[[mcp.upstream]]\nname = \"docs\"\nurl = \"http://{}/mcp\"\nstages = [\"implement\"]\n",
+                addr
+            ),
+        )
+        .unwrap();
+        let other_dir = tempfile::TempDir::new().unwrap();
+
+        let state = AppState::new(Registry::new(0, 0));
+        let router = app(state.clone());
+        let list = json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list" });
+        let scoped =
+            |stage: &str| format!("/mcp?project={}&stage={}", project_root.display(), stage);
+
+        // Unscoped requests only see native tools
+        let response = post_mcp(&router, "/mcp", list.clone()).await;
+        assert!(!tool_names(&response).contains(&"docs__search".to_string()));
+
+        // The upstream is exposed for its stage only
+        let response = post_mcp(&router, &scoped("implement"), list.clone()).await;
+        assert!(tool_names(&response).contains(&"docs__search".to_string()));
+        let response = post_mcp(&router, &scoped("review"), list.clone()).await;
+        assert!(!tool_names(&response).contains(&"docs__search".to_string()));
+
+        // Forwarded calls are audited in the scoped project, not a caller's project_path
+        let call = json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": {
+                "name": "docs__search",
+                "arguments": { "project_path": other_dir.path().to_str().unwrap() }
+            }
+        });
+        let response = post_mcp(&router, &scoped("implement"), call).await;
+        assert_eq!(response["result"]["content"][0]["text"], "found");
+        assert!(crate::mcp::audit::audit_log_path(&project_root).exists());
+        assert!(!crate::mcp::audit::audit_log_path(other_dir.path()).exists());
+
+        // The upstream was connected once and shared by every stage
+        let mcp = state.mcp.lock().await;
+        assert_eq!(mcp.upstreams.len(), 1);
+        assert_eq!(mcp.upstreams[&project_root].len(), 1);
+        assert_eq!(mcp.servers.len(), 3);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_mcp_endpoint_serves_custom_tools() {
+        use serde_json::json;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_root = temp_dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(project_root.join("agentd")).unwrap();
+        std::fs::write(
+            project_root.join("agentd/config.toml"),
+            r#"project_name = "demo"
+scripts_dir = "agentd/scripts"
+
+[[mcp.custom_tools]]
+name = "where_am_i"
+description = "Print the working directory"
+command = "pwd"
+input_schema = { type = "object" }
+"#,
+        )
+        .unwrap();
+        let other_dir = tempfile::TempDir::new().unwrap();
+
+        let router = app(AppState::new(Registry::new(0, 0)));
+        let uri = format!("/mcp?project={}", project_root.display());
+
+        let list = json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list" });
+        let response = post_mcp(&router, &uri, list.clone()).await;
+        assert!(tool_names(&response).contains(&"where_am_i".to_string()));
+        let response = post_mcp(&router, "/mcp", list).await;
+        assert!(!tool_names(&response).contains(&"where_am_i".to_string()));
+
+        // The tool runs in the project that configured it
+        let call = json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": {
+                "name": "where_am_i",
+                "arguments": { "project_path": other_dir.path().to_str().unwrap() }
+            }
+        });
+        let response = post_mcp(&router, &uri, call).await;
+        let text = response["result"]["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains(&format!("## stdout\n{}", project_root.display())));
+    }
 }
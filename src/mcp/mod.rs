@@ -8,12 +8,17 @@
 //! - `create_spec` - Create specs/*.md with requirements and scenarios
 //! - `create_tasks` - Create tasks.md with layered task structure
 //! - `validate_change` - Validate all proposal files
+//!
+//! Tools from external MCP servers listed under `[[mcp.upstream]]` are proxied
+//! as `<prefix>__<tool>` (see [`upstream`]).
 
+pub mod audit;
 pub mod config;
 pub mod http_server;
 pub mod registry;
 pub mod server;
 pub mod tools;
+pub mod upstream;
 
 pub use config::{ensure_codex_mcp_config, ensure_gemini_mcp_config};
 pub use http_server::start_server;
@@ -5,10 +5,12 @@
 //! - `tools/list` - Return available tool definitions
 //! - `tools/call` - Execute a tool and return result
 
+use crate::models::{McpCustomToolConfig, McpUpstreamConfig};
 use crate::Result;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
 
 use super::tools::ToolRegistry;
 
@@ -78,6 +80,23 @@ impl McpServer {
         })
     }
 
+    /// Create an MCP server serving a prepared tool registry
+    pub fn with_registry(tool_registry: ToolRegistry) -> Self {
+        Self { tool_registry }
+    }
+
+    /// Connect the upstream MCP servers configured for the project and expose
+    /// their tools alongside the native ones
+    pub async fn connect_upstreams(&mut self, configs: &[McpUpstreamConfig], project_root: &Path) {
+        self.tool_registry.connect_upstreams(configs, project_root).await;
+    }
+
+    /// Expose the project's custom command tools
+    pub fn register_custom_tools(&mut self, configs: &[McpCustomToolConfig], project_root: &Path) {
+        self.tool_registry
+            .register_custom_tools(configs, project_root);
+    }
+
     /// Run the MCP server, reading from stdin and writing to stdout
     pub async fn run(&self) -> Result<()> {
         let stdin = std::io::stdin();
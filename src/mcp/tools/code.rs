@@ -0,0 +1,158 @@
+//! search_code and get_symbol MCP Tools
+//!
+//! Read-only code lookups so challenge and review agents can verify claims
+//! about the codebase. Scoped to the project root and .gitignore-aware.
+
+use super::{get_optional_string, get_required_string, ToolDefinition};
+use crate::services::code_service::{
+    get_symbol, search_code, SearchCodeInput, SearchMode, DEFAULT_MAX_RESULTS, SYMBOL_KINDS,
+};
+use crate::Result;
+use serde_json::{json, Value};
+use std::path::Path;
+
+/// Get the tool definition for search_code
+pub fn search_definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "search_code".to_string(),
+        description: "Search the project source by regex (per line) or by symbol name. Respects .gitignore".to_string(),
+        input_schema: json!({
+            "type": "object",
+            "required": ["project_path", "query"],
+            "properties": {
+                "project_path": {
+                    "type": "string",
+                    "description": "Project root path (use $PWD for current directory)"
+                },
+                "query": {
+                    "type": "string",
+                    "description": "Regex (mode=regex) or symbol name substring (mode=symbol)"
+                },
+                "mode": {
+                    "enum": ["regex", "symbol"],
+                    "default": "regex",
+                    "description": "How to interpret the query"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Directory or file to search, relative to the project root"
+                },
+                "glob": {
+                    "type": "string",
+                    "description": "Only search files matching this glob (e.g., '*.rs')"
+                },
+                "case_sensitive": {
+                    "type": "boolean",
+                    "default": false,
+                    "description": "Match case exactly"
+                },
+                "max_results": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": 500,
+                    "default": DEFAULT_MAX_RESULTS,
+                    "description": "Maximum number of matches to return"
+                }
+            }
+        }),
+    }
+}
+
+/// Execute the search_code tool
+pub fn execute_search(args: &Value, project_root: &Path) -> Result<String> {
+    let query = get_required_string(args, "query")?;
+    let mode: SearchMode = get_optional_string(args, "mode")
+        .as_deref()
+        .unwrap_or("regex")
+        .parse()?;
+
+    let input = SearchCodeInput {
+        query,
+        mode,
+        path: get_optional_string(args, "path"),
+        glob: get_optional_string(args, "glob"),
+        case_sensitive: args
+            .get("case_sensitive")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        max_results: args
+            .get("max_results")
+            .and_then(|v| v.as_u64())
+            .map(|n| n as usize)
+            .unwrap_or(DEFAULT_MAX_RESULTS),
+    };
+
+    search_code(input, project_root)
+}
+
+/// Get the tool definition for get_symbol
+pub fn get_symbol_definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "get_symbol".to_string(),
+        description: "Find where a function, type or constant is defined, with file, line and signature".to_string(),
+        input_schema: json!({
+            "type": "object",
+            "required": ["project_path", "name"],
+            "properties": {
+                "project_path": {
+                    "type": "string",
+                    "description": "Project root path (use $PWD for current directory)"
+                },
+                "name": {
+                    "type": "string",
+                    "description": "Exact symbol name (e.g., 'validate_token', 'SessionStore')"
+                },
+                "kind": {
+                    "enum": SYMBOL_KINDS,
+                    "description": "Only return symbols of this kind"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Directory or file to search, relative to the project root"
+                }
+            }
+        }),
+    }
+}
+
+/// Execute the get_symbol tool
+pub fn execute_get_symbol(args: &Value, project_root: &Path) -> Result<String> {
+    let name = get_required_string(args, "name")?;
+    let kind = get_optional_string(args, "kind");
+    let path = get_optional_string(args, "path");
+    get_symbol(&name, kind.as_deref(), path.as_deref(), project_root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_search_and_get_symbol() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path();
+        std::fs::create_dir_all(project_root.join("src")).unwrap();
+        std::fs::write(
+            project_root.join("src/lib.rs"),
+            "pub fn parse_config(path: &str) -> Config {\n    todo!()\n}\n",
+        )
+        .unwrap();
+
+        let args = json!({ "query": "parse_config", "glob": "*.rs" });
+        let result = execute_search(&args, project_root).unwrap();
+        assert!(result.contains("src/lib.rs:1:"));
+
+        let args = json!({ "query": "parse", "mode": "symbol" });
+        let result = execute_search(&args, project_root).unwrap();
+        assert!(result.contains("function parse_config"));
+
+        let args = json!({ "name": "parse_config" });
+        let result = execute_get_symbol(&args, project_root).unwrap();
+        assert!(result.contains("## src/lib.rs:1"));
+        assert!(result.contains("parse_config(path: &str) -> Config"));
+
+        let args = json!({ "query": "x", "mode": "fuzzy" });
+        assert!(execute_search(&args, project_root).is_err());
+    }
+}
@@ -0,0 +1,499 @@
+//! Project-defined command tools (`[[mcp.custom_tools]]`)
+//!
+//! Each configured tool becomes an MCP tool whose arguments are validated
+//! against its JSON Schema and substituted into a command template. The
+//! command runs in the project root without a shell, with a timeout.
+
+use super::ToolDefinition;
+use crate::models::McpCustomToolConfig;
+use crate::Result;
+use jsonschema::Validator;
+use regex::Regex;
+use serde_json::{json, Value};
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::process::Command;
+
+/// Maximum bytes of stdout/stderr returned to the agent per stream
+const MAX_OUTPUT_BYTES: usize = 64 * 1024;
+
+fn placeholder_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\{\{\s*([A-Za-z0-9_-]+)\s*\}\}").unwrap())
+}
+
+/// A configured custom tool with its compiled argument schema
+pub struct CustomTool {
+    config: McpCustomToolConfig,
+    validator: Validator,
+    definition: ToolDefinition,
+}
+
+impl CustomTool {
+    /// Check a tool config and compile its argument schema
+    pub fn new(config: McpCustomToolConfig) -> Result<Self> {
+        if config.name.is_empty()
+            || !config
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            anyhow::bail!(
+                "Invalid custom tool name '{}' (use letters, digits, '_' or '-')",
+                config.name
+            );
+        }
+        let words = split_command(&config.command)?;
+        let Some(program) = words.first() else {
+            anyhow::bail!("Custom tool '{}' has an empty command", config.name);
+        };
+        if placeholder_regex().is_match(program) {
+            anyhow::bail!(
+                "Custom tool '{}' cannot take its program from a placeholder",
+                config.name
+            );
+        }
+        if config.input_schema.get("type").and_then(|t| t.as_str()) != Some("object") {
+            anyhow::bail!(
+                "Custom tool '{}' input_schema must have type = \"object\"",
+                config.name
+            );
+        }
+        let validator = Validator::new(&config.input_schema).map_err(|e| {
+            anyhow::anyhow!("Invalid input_schema for custom tool '{}': {}", config.name, e)
+        })?;
+
+        let definition = ToolDefinition {
+            name: config.name.clone(),
+            description: config.description.clone(),
+            input_schema: exposed_schema(&config.input_schema),
+        };
+
+        Ok(Self {
+            config,
+            validator,
+            definition,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    pub fn definition(&self) -> &ToolDefinition {
+        &self.definition
+    }
+
+    /// Validate the arguments, run the command and return its output
+    ///
+    /// A non-zero exit status is reported as an error carrying the output.
+    pub async fn execute(&self, args: &Value, project_root: &Path) -> Result<String> {
+        let mut args = args.clone();
+        if let Some(map) = args.as_object_mut() {
+            map.remove("project_path");
+        }
+
+        let errors: Vec<String> = self
+            .validator
+            .iter_errors(&args)
+            .map(|error| {
+                let path = error.instance_path.to_string();
+                if path.is_empty() {
+                    error.to_string()
+                } else {
+                    format!("{}: {}", path, error)
+                }
+            })
+            .collect();
+        if !errors.is_empty() {
+            anyhow::bail!(
+                "Invalid arguments for '{}':\n- {}",
+                self.config.name,
+                errors.join("\n- ")
+            );
+        }
+
+        let argv = render_command(&split_command(&self.config.command)?, &args)
+            .map_err(|e| anyhow::anyhow!("Invalid arguments for '{}': {}", self.config.name, e))?;
+        let (program, rest) = argv
+            .split_first()
+            .ok_or_else(|| anyhow::anyhow!("Custom tool '{}' has an empty command", self.config.name))?;
+
+        // Relative program paths (e.g. scripts/check.sh) are resolved against the project
+        let program = if program.contains('/') && Path::new(program).is_relative() {
+            project_root.join(program).to_string_lossy().to_string()
+        } else {
+            program.clone()
+        };
+
+        let child = Command::new(&program)
+            .args(rest)
+            .current_dir(project_root)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| anyhow::anyhow!("Failed to run '{}': {}", program, e))?;
+
+        let timeout = Duration::from_secs(self.config.timeout_secs);
+        let output = tokio::time::timeout(timeout, child.wait_with_output())
+            .await
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "Custom tool '{}' timed out after {}s",
+                    self.config.name,
+                    self.config.timeout_secs
+                )
+            })??;
+
+        let exit_code = output
+            .status
+            .code()
+            .map(|c| c.to_string())
+            .unwrap_or_else(|| "terminated by signal".to_string());
+        let report = format!(
+            "Command: {}\nExit code: {}\n\n## stdout\n{}\n## stderr\n{}",
+            argv.join(" "),
+            exit_code,
+            truncate_output(&output.stdout),
+            truncate_output(&output.stderr)
+        );
+
+        if !output.status.success() {
+            anyhow::bail!("Custom tool '{}' failed\n{}", self.config.name, report);
+        }
+        Ok(report)
+    }
+}
+
+/// Build the schema advertised to clients: the configured schema plus the
+/// `project_path` argument every agentd tool takes
+fn exposed_schema(schema: &Value) -> Value {
+    let mut exposed = schema.clone();
+    exposed["properties"]["project_path"] = json!({
+        "type": "string",
+        "description": "Project root path (use $PWD for current directory)"
+    });
+    let mut required = vec![json!("project_path")];
+    if let Some(existing) = schema.get("required").and_then(|r| r.as_array()) {
+        required.extend(existing.iter().cloned());
+    }
+    exposed["required"] = Value::Array(required);
+    exposed
+}
+
+/// Split a command template into words, honouring single/double quotes and
+/// backslash escapes like a POSIX shell (no expansion is performed)
+pub fn split_command(command: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = command.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => anyhow::bail!("Unterminated single quote in command: {}", command),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\')) => current.push(c),
+                            Some(c) => {
+                                current.push('\\');
+                                current.push(c);
+                            }
+                            None => anyhow::bail!("Unterminated double quote in command: {}", command),
+                        },
+                        Some(c) => current.push(c),
+                        None => anyhow::bail!("Unterminated double quote in command: {}", command),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                if let Some(c) = chars.next() {
+                    current.push(c);
+                }
+            }
+            c => {
+                in_word = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(current);
+    }
+    Ok(words)
+}
+
+/// Substitute `{{arg}}` placeholders into the command words
+///
+/// A word that is exactly one placeholder is replaced by the value as a single
+/// argument, expanded to several arguments for arrays, or dropped when the
+/// argument was not provided. Placeholders inside a larger word are replaced
+/// inline (missing values become empty).
+///
+/// Values may not turn a word into an option: a rendered word starting with
+/// `-` is rejected unless the template word itself starts with `-`.
+fn render_command(words: &[String], args: &Value) -> Result<Vec<String>> {
+    let placeholder = placeholder_regex();
+    let mut argv = Vec::new();
+    for word in words {
+        if !placeholder.is_match(word) {
+            argv.push(word.clone());
+            continue;
+        }
+
+        let mut rendered = Vec::new();
+        match placeholder.captures(word) {
+            Some(caps) if caps.get(0).map(|m| m.as_str()) == Some(word.as_str()) => {
+                match args.get(&caps[1]) {
+                    None | Some(Value::Null) => {}
+                    Some(Value::Array(items)) => rendered.extend(items.iter().map(value_to_arg)),
+                    Some(value) => rendered.push(value_to_arg(value)),
+                }
+            }
+            _ => rendered.push(
+                placeholder
+                    .replace_all(word, |caps: &regex::Captures| {
+                        args.get(&caps[1])
+                            .filter(|v| !v.is_null())
+                            .map(value_to_arg)
+                            .unwrap_or_default()
+                    })
+                    .into_owned(),
+            ),
+        }
+
+        if !word.starts_with('-') {
+            if let Some(option) = rendered.iter().find(|arg| arg.starts_with('-')) {
+                anyhow::bail!("value '{}' would be passed as an option", option);
+            }
+        }
+        argv.extend(rendered);
+    }
+    Ok(argv)
+}
+
+fn value_to_arg(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn truncate_output(bytes: &[u8]) -> String {
+    let text = String::from_utf8_lossy(bytes);
+    if text.len() <= MAX_OUTPUT_BYTES {
+        return text.into_owned();
+    }
+    let mut end = MAX_OUTPUT_BYTES;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!(
+        "{}\n... (truncated, {} bytes total)",
+        &text[..end],
+        text.len()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn tool(command: &str, input_schema: Value) -> CustomTool {
+        CustomTool::new(McpCustomToolConfig {
+            name: "test_tool".to_string(),
+            description: "Test tool".to_string(),
+            command: command.to_string(),
+            input_schema,
+            stages: Vec::new(),
+            timeout_secs: 5,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_split_and_render_command() {
+        let words = split_command(r#"grep -n "a b" 'c d' e\ f {{pattern}} --file={{file}}"#).unwrap();
+        assert_eq!(
+            words,
+            vec!["grep", "-n", "a b", "c d", "e f", "{{pattern}}", "--file={{file}}"]
+        );
+        assert!(split_command("echo 'oops").is_err());
+
+        let argv = render_command(
+            &split_command("cmd {{files}} {{missing}} {{name}} --n={{count}}").unwrap(),
+            &json!({ "files": ["a.rs", "b.rs"], "name": "x; rm -rf /", "count": -3 }),
+        )
+        .unwrap();
+        assert_eq!(argv, vec!["cmd", "a.rs", "b.rs", "x; rm -rf /", "--n=-3"]);
+
+        // Values cannot inject options
+        let words = split_command("rm {{name}} {{files}} {{name}}.bak").unwrap();
+        assert!(render_command(&words, &json!({ "name": "-rf" })).is_err());
+        assert!(render_command(&words, &json!({ "files": ["a", "--force"] })).is_err());
+        assert!(render_command(&words, &json!({ "name": "a" })).is_ok());
+    }
+
+    #[test]
+    fn test_schema_and_name_validation() {
+        let t = tool("echo", json!({ "type": "object", "required": ["env"] }));
+        assert_eq!(
+            t.definition().input_schema["required"],
+            json!(["project_path", "env"])
+        );
+
+        let bad_name = McpCustomToolConfig {
+            name: "bad name".to_string(),
+            ..t.config.clone()
+        };
+        assert!(CustomTool::new(bad_name).is_err());
+        let bad_schema = McpCustomToolConfig {
+            input_schema: json!({ "type": "string" }),
+            ..t.config.clone()
+        };
+        assert!(CustomTool::new(bad_schema).is_err());
+        for command in ["{{program}} --version", "./{{script}}.sh"] {
+            let placeholder_program = McpCustomToolConfig {
+                command: command.to_string(),
+                ..t.config.clone()
+            };
+            assert!(CustomTool::new(placeholder_program).is_err());
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_execute_custom_tool() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path();
+        std::fs::write(project_root.join("marker.txt"), "here").unwrap();
+
+        let schema = json!({
+            "type": "object",
+            "required": ["env"],
+            "properties": { "env": { "type": "string", "enum": ["dev", "staging"] } }
+        });
+        let t = tool("sh -c 'echo env=$0; cat marker.txt; echo oops >&2' {{env}}", schema);
+
+        let output = t
+            .execute(&json!({ "project_path": "/ignored", "env": "dev" }), project_root)
+            .await
+            .unwrap();
+        assert!(output.contains("Exit code: 0"));
+        assert!(output.contains("env=dev"));
+        assert!(output.contains("here"));
+        assert!(output.contains("## stderr\noops"));
+
+        let err = t
+            .execute(&json!({ "env": "prod" }), project_root)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid arguments"));
+        assert!(t.execute(&json!({}), project_root).await.is_err());
+
+        let failing = tool("sh -c 'echo broken; exit 3'", json!({ "type": "object" }));
+        let err = failing.execute(&json!({}), project_root).await.unwrap_err();
+        assert!(err.to_string().contains("Exit code: 3"));
+        assert!(err.to_string().contains("broken"));
+
+        let mut slow = tool("sleep 5", json!({ "type": "object" }));
+        slow.config.timeout_secs = 1;
+        let err = slow.execute(&json!({}), project_root).await.unwrap_err();
+        assert!(err.to_string().contains("timed out"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_registry_custom_tools() {
+        use crate::mcp::audit::{audit_log_path, AuditEntry};
+        use crate::mcp::tools::ToolRegistry;
+
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path();
+        std::fs::create_dir_all(project_root.join("agentd")).unwrap();
+
+        let review_only = McpCustomToolConfig {
+            stages: vec!["review".to_string()],
+            ..tool("echo {{msg}}", json!({ "type": "object" })).config.clone()
+        };
+        let shadowing = McpCustomToolConfig {
+            name: "read_file".to_string(),
+            ..review_only.clone()
+        };
+
+        let mut plan = ToolRegistry::new_for_stage("plan");
+        plan.register_custom_tools(std::slice::from_ref(&review_only), project_root);
+        assert!(plan.call_tool("test_tool", &json!({})).await.is_err());
+
+        let mut review = ToolRegistry::new_for_stage("review");
+        review.register_custom_tools(&[review_only, shadowing], project_root);
+        let names: Vec<Value> = review.list_tools().iter().map(|t| t["name"].clone()).collect();
+        assert_eq!(names.iter().filter(|n| *n == "test_tool").count(), 1);
+        assert_eq!(names.iter().filter(|n| *n == "read_file").count(), 1);
+
+        let project_path = project_root.to_string_lossy();
+        let output = review
+            .call_tool("test_tool", &json!({ "project_path": project_path, "msg": "hi" }))
+            .await
+            .unwrap();
+        assert!(output.contains("## stdout\nhi"));
+
+        let log = std::fs::read_to_string(audit_log_path(project_root)).unwrap();
+        let entry: AuditEntry = serde_json::from_str(log.lines().last().unwrap()).unwrap();
+        assert_eq!(entry.tool, "test_tool");
+        assert_eq!(entry.source, "custom");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_custom_tool_runs_in_its_project() {
+        use crate::mcp::audit::audit_log_path;
+        use crate::mcp::tools::ToolRegistry;
+
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path();
+        std::fs::create_dir_all(project_root.join("agentd")).unwrap();
+        std::fs::write(project_root.join("marker.txt"), "mine").unwrap();
+        let foreign_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(foreign_dir.path().join("agentd")).unwrap();
+        std::fs::write(foreign_dir.path().join("marker.txt"), "foreign").unwrap();
+
+        let cat = tool("cat marker.txt", json!({ "type": "object" }));
+        let mut registry = ToolRegistry::new_for_stage("implement");
+        registry.register_custom_tools(&[cat.config], project_root);
+
+        let foreign_path = foreign_dir.path().to_string_lossy();
+        let output = registry
+            .call_tool("test_tool", &json!({ "project_path": foreign_path }))
+            .await
+            .unwrap();
+        assert!(output.contains("## stdout\nmine"));
+        assert!(audit_log_path(project_root).exists());
+        assert!(!audit_log_path(foreign_dir.path()).exists());
+    }
+}
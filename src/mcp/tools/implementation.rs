@@ -4,13 +4,13 @@
 //! the implementation and review workflow stages.
 
 use super::{get_optional_string, get_required_array, get_required_string, ToolDefinition};
+use crate::services::diff_service::{self, FileChange};
 use crate::services::implementation_service::{
-    CreateReviewInput, ReviewIssue, ReviewVerdict, Severity, TestResults,
+    self, CreateReviewInput, ReviewIssue, ReviewVerdict, Severity, TestResults,
 };
 use crate::Result;
 use serde_json::{json, Value};
 use std::path::Path;
-use std::process::Command;
 
 /// Validate change_id to prevent directory traversal attacks
 fn validate_change_id(change_id: &str) -> Result<()> {
@@ -32,44 +32,9 @@ fn validate_change_id(change_id: &str) -> Result<()> {
     Ok(())
 }
 
-/// Check if current directory is a git repository
-fn is_git_repo() -> bool {
-    Command::new("git")
-        .args(&["rev-parse", "--git-dir"])
-        .output()
-        .map(|output| output.status.success())
-        .unwrap_or(false)
-}
-
-/// Get current git branch name
-fn get_current_branch() -> Result<String> {
-    let output = Command::new("git")
-        .args(&["rev-parse", "--abbrev-ref", "HEAD"])
-        .output()?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Failed to get current branch: {}", stderr);
-    }
-
-    Ok(String::from_utf8(output.stdout)?.trim().to_string())
-}
-
-/// Run a git command and return output
-fn run_git_command(args: &[&str]) -> Result<String> {
-    let output = Command::new("git").args(args).output()?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Ok(format!("⚠️ Git command failed: {}", stderr.trim()));
-    }
-
-    Ok(String::from_utf8(output.stdout)?.trim().to_string())
-}
-
-/// Validate that current branch matches expected pattern and return (branch, is_match)
-fn validate_branch(change_id: &str) -> Result<(String, bool)> {
-    let current_branch = get_current_branch()?;
+/// Current branch and whether it matches the `agentd/<change-id>` convention
+fn validate_branch(change_id: &str, project_root: &Path) -> Result<(String, bool)> {
+    let current_branch = diff_service::current_branch(project_root)?;
     let expected_branch = format!("agentd/{}", change_id);
     let is_match = current_branch == expected_branch;
     Ok((current_branch, is_match))
@@ -201,8 +166,7 @@ pub fn read_implementation_summary_definition() -> ToolDefinition {
                 },
                 "base_branch": {
                     "type": "string",
-                    "description": "Base branch to compare against (default: 'main')",
-                    "default": "main"
+                    "description": "Base branch or commit to compare against (default: the baseline commit recorded when implementation started, else 'main')"
                 }
             }
         }),
@@ -210,21 +174,19 @@ pub fn read_implementation_summary_definition() -> ToolDefinition {
 }
 
 /// Execute the read_implementation_summary tool
-pub fn execute_read_implementation_summary(args: &Value, _project_root: &Path) -> Result<String> {
+pub fn execute_read_implementation_summary(args: &Value, project_root: &Path) -> Result<String> {
     let change_id = get_required_string(args, "change_id")?;
     validate_change_id(&change_id)?;
 
-    let base_branch = get_optional_string(args, "base_branch").unwrap_or_else(|| "main".to_string());
-
-    if !is_git_repo() {
-        anyhow::bail!("Not in a git repository");
-    }
+    let base_branch = get_optional_string(args, "base_branch");
+    let base = diff_service::resolve_base(&change_id, base_branch.as_deref(), project_root);
+    let changed = diff_service::changed_files(project_root, &base)?;
 
     let mut output = String::new();
     output.push_str(&format!("# Implementation Summary for: {}\n\n", change_id));
 
     // Branch validation
-    match validate_branch(&change_id) {
+    match validate_branch(&change_id, project_root) {
         Ok((current_branch, is_match)) => {
             output.push_str(&format!("**Current Branch**: `{}`\n", current_branch));
             if !is_match {
@@ -241,48 +203,65 @@ pub fn execute_read_implementation_summary(args: &Value, _project_root: &Path) -
     }
 
     // Commits ahead of base
-    let commits_ahead = run_git_command(&[
-        "rev-list",
-        "--count",
-        &format!("{}..HEAD", base_branch),
-    ])?;
-    output.push_str(&format!("**Commits ahead of {}**: {}\n\n", base_branch, commits_ahead));
+    let commits = diff_service::commits_since(project_root, &base)?;
+    output.push_str(&format!("**Commits ahead of {}**: {}\n\n", base, commits.len()));
 
     // Changed files (name-status)
     output.push_str("## Changed Files\n\n");
-    let name_status = run_git_command(&["diff", "--name-status", &base_branch])?;
-    if name_status.is_empty() {
+    if changed.is_empty() {
         output.push_str("*No changes detected*\n\n");
     } else {
         output.push_str("```\n");
-        output.push_str(&name_status);
-        output.push_str("\n```\n\n");
+        for file in &changed {
+            let line = match &file.change {
+                FileChange::Added => format!("A\t{}", file.path),
+                FileChange::Modified => format!("M\t{}", file.path),
+                FileChange::Deleted => format!("D\t{}", file.path),
+                FileChange::Renamed { from } => format!("R\t{}\t{}", from, file.path),
+            };
+            output.push_str(&line);
+            output.push('\n');
+        }
+        output.push_str("```\n\n");
     }
 
     // Diff statistics
     output.push_str("## Diff Statistics\n\n");
-    let diff_stat = run_git_command(&["diff", "--stat", &base_branch])?;
-    if diff_stat.is_empty() {
+    if changed.is_empty() {
         output.push_str("*No changes*\n\n");
     } else {
+        let additions: usize = changed.iter().map(|f| f.additions).sum();
+        let deletions: usize = changed.iter().map(|f| f.deletions).sum();
         output.push_str("```\n");
-        output.push_str(&diff_stat);
-        output.push_str("\n```\n\n");
+        for file in &changed {
+            if file.binary {
+                output.push_str(&format!("{} | Bin\n", file.path));
+            } else {
+                output.push_str(&format!(
+                    "{} | +{} -{}\n",
+                    file.path, file.additions, file.deletions
+                ));
+            }
+        }
+        output.push_str(&format!(
+            "{} files changed, {} insertions(+), {} deletions(-)\n",
+            changed.len(),
+            additions,
+            deletions
+        ));
+        output.push_str("```\n\n");
     }
 
     // Commit log
     output.push_str("## Commit Log\n\n");
-    let commit_log = run_git_command(&[
-        "log",
-        "--oneline",
-        &format!("{}..HEAD", base_branch),
-    ])?;
-    if commit_log.is_empty() {
+    if commits.is_empty() {
         output.push_str("*No commits*\n\n");
     } else {
         output.push_str("```\n");
-        output.push_str(&commit_log);
-        output.push_str("\n```\n\n");
+        for commit in &commits {
+            output.push_str(&format!("{} {}\n", commit.id, commit.summary));
+        }
+        output.push_str("```\n\n");
     }
 
     output.push_str("---\n\n");
@@ -314,8 +293,7 @@ pub fn list_changed_files_definition() -> ToolDefinition {
                 },
                 "base_branch": {
                     "type": "string",
-                    "description": "Base branch to compare against (default: 'main')",
-                    "default": "main"
+                    "description": "Base branch or commit to compare against (default: the baseline commit recorded when implementation started, else 'main')"
                 },
                 "filter": {
                     "type": "string",
@@ -327,116 +305,17 @@ pub fn list_changed_files_definition() -> ToolDefinition {
 }
 
 /// Execute the list_changed_files tool
-pub fn execute_list_changed_files(args: &Value, _project_root: &Path) -> Result<String> {
+pub fn execute_list_changed_files(args: &Value, project_root: &Path) -> Result<String> {
     let change_id = get_required_string(args, "change_id")?;
-    validate_change_id(&change_id)?;
-
-    let base_branch = get_optional_string(args, "base_branch").unwrap_or_else(|| "main".to_string());
+    let base_branch = get_optional_string(args, "base_branch");
     let filter = get_optional_string(args, "filter");
 
-    if !is_git_repo() {
-        anyhow::bail!("Not in a git repository");
-    }
-
-    let mut output = String::new();
-    output.push_str(&format!("# Changed Files for: {}\n\n", change_id));
-
-    if let Some(ref f) = filter {
-        output.push_str(&format!("**Filter**: `{}`\n\n", f));
-    }
-
-    // Get numstat output
-    let numstat = run_git_command(&["diff", "--numstat", &base_branch])?;
-
-    if numstat.is_empty() || numstat.starts_with("⚠️") {
-        output.push_str("*No changes detected*\n");
-        return Ok(output);
-    }
-
-    // Parse numstat output
-    #[derive(Debug)]
-    struct FileStat {
-        added: String,
-        removed: String,
-        path: String,
-    }
-
-    let mut files: Vec<FileStat> = Vec::new();
-    let mut total_added = 0;
-    let mut total_removed = 0;
-
-    for line in numstat.lines() {
-        let parts: Vec<&str> = line.split('\t').collect();
-        if parts.len() != 3 {
-            continue;
-        }
-
-        let path = parts[2].to_string();
-
-        // Apply filter if specified
-        if let Some(ref f) = filter {
-            if !path.contains(f) {
-                continue;
-            }
-        }
-
-        let added_str = parts[0].to_string();
-        let removed_str = parts[1].to_string();
-
-        // Parse numbers (handle binary files marked with '-')
-        if added_str != "-" {
-            if let Ok(n) = added_str.parse::<usize>() {
-                total_added += n;
-            }
-        }
-        if removed_str != "-" {
-            if let Ok(n) = removed_str.parse::<usize>() {
-                total_removed += n;
-            }
-        }
-
-        files.push(FileStat {
-            added: added_str,
-            removed: removed_str,
-            path,
-        });
-    }
-
-    if files.is_empty() {
-        output.push_str("*No matching files found*\n");
-        return Ok(output);
-    }
-
-    // Format as markdown table
-    output.push_str("| File | Status | +Lines | -Lines |\n");
-    output.push_str("|------|--------|--------|--------|\n");
-
-    for file in &files {
-        let status = if file.added == "-" && file.removed == "-" {
-            "Binary"
-        } else if file.removed == "0" {
-            "Added"
-        } else if file.added == "0" {
-            "Deleted"
-        } else {
-            "Modified"
-        };
-
-        output.push_str(&format!(
-            "| {} | {} | {} | {} |\n",
-            file.path, status, file.added, file.removed
-        ));
-    }
-
-    output.push_str("\n");
-    output.push_str(&format!(
-        "**Totals**: {} files, +{} lines, -{} lines\n",
-        files.len(),
-        total_added,
-        total_removed
-    ));
-
-    Ok(output)
+    implementation_service::list_changed_files(
+        &change_id,
+        base_branch.as_deref(),
+        filter.as_deref(),
+        project_root,
+    )
 }
 
 // ============================================================================
@@ -743,21 +622,46 @@ mod tests {
     }
 
     #[test]
-    fn test_is_git_repo() {
-        // This test will pass or fail depending on whether we're in a git repo
-        // Just verify it doesn't panic
-        let _ = is_git_repo();
-    }
+    fn test_implementation_summary_against_baseline() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(args)
+                .current_dir(project_root)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?} failed", args);
+        };
+        git(&["init", "-q", "-b", "main"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "Test"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "init"]);
+        git(&["checkout", "-q", "-b", "agentd/test-change"]);
 
-    #[test]
-    fn test_git_helpers_in_git_repo() {
-        // Only run if we're in a git repo
-        if is_git_repo() {
-            let branch = get_current_branch();
-            assert!(branch.is_ok());
-
-            let status = run_git_command(&["status", "--short"]);
-            assert!(status.is_ok());
-        }
+        let change_dir = project_root.join("agentd/changes/test-change");
+        std::fs::create_dir_all(&change_dir).unwrap();
+        let mut state = crate::state::StateManager::load(&change_dir).unwrap();
+        state.set_baseline_commit(diff_service::head_commit(project_root).unwrap());
+        state.save().unwrap();
+
+        std::fs::write(project_root.join("a.rs"), "fn a() {}\n").unwrap();
+        git(&["add", "a.rs"]);
+        git(&["commit", "-q", "-m", "Add a"]);
+        std::fs::write(project_root.join("b.rs"), "fn b() {}\n").unwrap();
+
+        let args = json!({ "change_id": "test-change" });
+        let result = execute_read_implementation_summary(&args, project_root).unwrap();
+        assert!(result.contains("**Current Branch**: `agentd/test-change`"));
+        assert!(!result.contains("Warning"));
+        assert!(result.contains("A\ta.rs"));
+        assert!(result.contains("A\tb.rs"));
+        assert!(result.contains("Add a"));
+
+        let args = json!({ "change_id": "test-change", "filter": "b.rs" });
+        let result = execute_list_changed_files(&args, project_root).unwrap();
+        assert!(result.contains("| b.rs | Added | 1 | 0 |"));
+        assert!(!result.contains("a.rs"));
     }
 }
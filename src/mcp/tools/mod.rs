@@ -4,6 +4,8 @@
 //! markdown files, eliminating format errors from free-form LLM output.
 
 pub mod clarifications;
+pub mod code;
+pub mod custom;
 pub mod implementation;
 pub mod knowledge;
 pub mod mermaid;
@@ -14,13 +16,27 @@ pub mod task;
 pub mod tasks;
 pub mod validate;
 
+use super::audit::{self, AuditEntry};
+use super::upstream::{self, UpstreamServer};
+use crate::models::{McpCustomToolConfig, McpUpstreamConfig};
+use custom::CustomTool;
 use crate::Result;
 use serde_json::{json, Value};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
 
 /// Registry of available MCP tools
 pub struct ToolRegistry {
     tools: Vec<ToolDefinition>,
+    /// Workflow stage the registry was filtered for (None = all tools)
+    stage: Option<String>,
+    /// Connected upstream servers whose tools are proxied
+    upstreams: Vec<Arc<UpstreamServer>>,
+    /// Project-defined command tools
+    custom_tools: Vec<CustomTool>,
+    /// Project the upstream servers were started for
+    project_root: Option<PathBuf>,
 }
 
 /// Tool definition for MCP protocol
@@ -47,14 +63,88 @@ impl ToolRegistry {
             "archive" => Self::archive_tools(),
             _ => Self::all_tools_vec(),
         };
-        Self { tools }
+        Self {
+            tools,
+            stage: Some(stage.to_string()),
+            upstreams: Vec::new(),
+            custom_tools: Vec::new(),
+            project_root: None,
+        }
     }
 
-    /// All tools (23 total, including get_task)
+    /// All tools, including get_task
     fn all_tools() -> Self {
         Self {
             tools: Self::all_tools_vec(),
+            stage: None,
+            upstreams: Vec::new(),
+            custom_tools: Vec::new(),
+            project_root: None,
+        }
+    }
+
+    /// Connect the configured upstream MCP servers enabled for this stage and
+    /// merge their namespaced tools into the registry
+    pub async fn connect_upstreams(&mut self, configs: &[McpUpstreamConfig], project_root: &Path) {
+        let servers: Vec<_> = upstream::connect_all(configs, self.stage.as_deref(), project_root)
+            .await
+            .into_iter()
+            .map(Arc::new)
+            .collect();
+        self.attach_upstreams(&servers, project_root);
+    }
+
+    /// Expose the tools of already connected upstream servers enabled for
+    /// this stage (servers are shared between registries of one project)
+    pub fn attach_upstreams(&mut self, servers: &[Arc<UpstreamServer>], project_root: &Path) {
+        for server in servers {
+            if !server.enabled_for_stage(self.stage.as_deref()) {
+                continue;
+            }
+            for tool in server.tools() {
+                if self.tools.iter().any(|t| t.name == tool.name) {
+                    eprintln!(
+                        "⚠️  Upstream tool '{}' from '{}' conflicts with an existing tool, skipping",
+                        tool.name,
+                        server.name()
+                    );
+                    continue;
+                }
+                self.tools.push(tool.clone());
+            }
+            self.upstreams.push(Arc::clone(server));
         }
+        self.project_root = Some(project_root.to_path_buf());
+    }
+
+    /// Register the configured custom command tools enabled for this stage,
+    /// run in `project_root`
+    ///
+    /// Invalid definitions and names that clash with existing tools are
+    /// skipped with a warning.
+    pub fn register_custom_tools(&mut self, configs: &[McpCustomToolConfig], project_root: &Path) {
+        for config in configs {
+            if !config.enabled_for_stage(self.stage.as_deref()) {
+                continue;
+            }
+            let tool = match CustomTool::new(config.clone()) {
+                Ok(tool) => tool,
+                Err(e) => {
+                    eprintln!("⚠️  Skipping custom tool '{}': {}", config.name, e);
+                    continue;
+                }
+            };
+            if self.tools.iter().any(|t| t.name == tool.name()) {
+                eprintln!(
+                    "⚠️  Custom tool '{}' conflicts with an existing tool, skipping",
+                    tool.name()
+                );
+                continue;
+            }
+            self.tools.push(tool.definition().clone());
+            self.custom_tools.push(tool);
+        }
+        self.project_root = Some(project_root.to_path_buf());
     }
 
     fn all_tools_vec() -> Vec<ToolDefinition> {
@@ -65,11 +155,16 @@ impl ToolRegistry {
             clarifications::definition(),
             proposal::definition(),
             proposal::append_review_definition(),
+            proposal::patch_definition(),
             spec::definition(),
+            spec::patch_definition(),
             tasks::definition(),
+            tasks::update_status_definition(),
             validate::definition(),
             read::definition(),
             read::list_specs_definition(),
+            code::search_definition(),
+            code::get_symbol_definition(),
             knowledge::read_definition(),
             knowledge::list_definition(),
             knowledge::write_definition(),
@@ -86,42 +181,47 @@ impl ToolRegistry {
         tools
     }
 
-    /// Plan stage tools (22 tools: all core + Mermaid)
+    /// Plan stage tools (all core + Mermaid)
     /// Used by: Gemini for proposal generation
     fn plan_tools() -> Vec<ToolDefinition> {
         Self::all_tools_vec()
     }
 
-    /// Challenge stage tools (5 tools)
+    /// Challenge stage tools (7 tools)
     /// Used by: Codex for challenging proposals
     fn challenge_tools() -> Vec<ToolDefinition> {
         vec![
             read::definition(),
             read::list_specs_definition(),
+            code::search_definition(),
+            code::get_symbol_definition(),
             knowledge::read_definition(),
             knowledge::list_definition(),
             validate::definition(),
         ]
     }
 
-    /// Implement stage tools (4 tools)
+    /// Implement stage tools (5 tools)
     /// Used by: Claude for code implementation
     fn implement_tools() -> Vec<ToolDefinition> {
         vec![
             implementation::read_all_requirements_definition(),
             implementation::read_implementation_summary_definition(),
             implementation::list_changed_files_definition(),
+            tasks::update_status_definition(),
             read::definition(),
         ]
     }
 
-    /// Review stage tools (4 tools)
+    /// Review stage tools (6 tools)
     /// Used by: Codex for code review
     fn review_tools() -> Vec<ToolDefinition> {
         vec![
             validate::definition(),
             proposal::append_review_definition(),
             read::definition(),
+            code::search_definition(),
+            code::get_symbol_definition(),
             implementation::create_review_definition(),
         ]
     }
@@ -159,9 +259,70 @@ impl ToolRegistry {
 
     /// Call a tool by name with the given arguments
     ///
+    /// Proxied upstream tools are forwarded as-is and custom tools run their
+    /// command; every call is recorded in the project's audit log. Proxied
+    /// and custom calls are logged in the project they were configured for,
+    /// never in a `project_path` the caller passes.
+    pub async fn call_tool(
+        &self,
+        name: &str,
+        arguments: &Value,
+    ) -> Result<String> {
+        let started = Instant::now();
+
+        let upstream = self
+            .upstreams
+            .iter()
+            .find_map(|server| server.resolve(name).map(|tool| (server, tool)))
+            .filter(|_| self.tools.iter().any(|t| t.name == name));
+        let custom = self.custom_tools.iter().find(|tool| tool.name() == name);
+        let is_native = upstream.is_none() && custom.is_none();
+        let (source, result) = match (upstream, custom) {
+            (Some((server, tool)), _) => (server.name(), server.call_tool(tool, arguments).await),
+            (None, Some(tool)) => ("custom", self.call_custom(tool, arguments).await),
+            (None, None) => ("native", self.call_native(name, arguments).await),
+        };
+
+        let audit_root = if is_native {
+            resolve_project_path(arguments)
+                .ok()
+                .or_else(|| self.project_root.clone())
+        } else {
+            self.project_root
+                .clone()
+                .or_else(|| resolve_project_path(arguments).ok())
+        };
+        if let Some(root) = audit_root {
+            let mut entry = AuditEntry::new(
+                name,
+                source,
+                self.stage.as_deref(),
+                started.elapsed().as_millis() as u64,
+            );
+            if let Err(e) = &result {
+                entry = entry.with_error(&e.to_string());
+            }
+            audit::record(&root, &entry);
+        }
+
+        result
+    }
+
+    /// Run a custom command tool in the project root
+    async fn call_custom(&self, tool: &CustomTool, arguments: &Value) -> Result<String> {
+        // A project's tools run in that project, whatever project_path says
+        let project_root = match &self.project_root {
+            Some(root) => root.clone(),
+            None => resolve_project_path(arguments)?,
+        };
+        tool.execute(arguments, &project_root).await
+    }
+
+    /// Call a built-in agentd tool
+    ///
     /// The project_path is extracted from the arguments for most tools.
     /// Mermaid tools don't require a project context.
-    pub async fn call_tool(
+    async fn call_native(
         &self,
         name: &str,
         arguments: &Value,
@@ -179,11 +340,16 @@ impl ToolRegistry {
             "create_clarifications" => clarifications::execute(arguments, &project_root),
             "create_proposal" => proposal::execute(arguments, &project_root),
             "append_review" => proposal::execute_append_review(arguments, &project_root),
+            "patch_proposal" => proposal::execute_patch(arguments, &project_root),
             "create_spec" => spec::execute(arguments, &project_root),
+            "patch_spec" => spec::execute_patch(arguments, &project_root),
             "create_tasks" => tasks::execute(arguments, &project_root),
+            "update_task_status" => tasks::execute_update_status(arguments, &project_root),
             "validate_change" => validate::execute(arguments, &project_root).await,
             "read_file" => read::execute(arguments, &project_root),
             "list_specs" => read::execute_list_specs(arguments, &project_root),
+            "search_code" => code::execute_search(arguments, &project_root),
+            "get_symbol" => code::execute_get_symbol(arguments, &project_root),
             "read_knowledge" => knowledge::execute_read(arguments, &project_root),
             "list_knowledge" => knowledge::execute_list(arguments, &project_root),
             "write_knowledge" => knowledge::execute_write(arguments, &project_root),
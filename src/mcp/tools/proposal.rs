@@ -1,10 +1,14 @@
 //! create_proposal MCP Tool
 //!
-//! Creates a validated proposal.md file with enforced structure.
+//! Creates a validated proposal.md file with enforced structure, and patches
+//! individual sections of an existing proposal.
 
 use super::{get_optional_string, get_required_array, get_required_object, get_required_string, ToolDefinition};
 use crate::models::spec_rules::SpecFormatRules;
-use crate::services::proposal_service::{create_proposal, AffectedSpec, CreateProposalInput, ImpactData};
+use crate::services::proposal_service::{
+    create_proposal, patch_proposal, AffectedSpec, CreateProposalInput, ImpactData,
+    PatchProposalInput, ProposalSection,
+};
 use crate::Result;
 use serde_json::{json, Value};
 use std::path::Path;
@@ -91,6 +95,11 @@ pub fn definition() -> ToolDefinition {
                             "description": "Description of breaking changes if any"
                         }
                     }
+                },
+                "depends_on_changes": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "IDs of existing changes that must be merged before this one"
                 }
             }
         }),
@@ -163,6 +172,16 @@ pub fn execute(args: &Value, project_root: &Path) -> Result<String> {
         .filter_map(|v| v.as_str().map(|s| s.to_string()))
         .collect();
 
+    let depends_on_changes: Vec<String> = args
+        .get("depends_on_changes")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
     // Note: SpecFormatRules is used by the service layer indirectly
     let _prd_rules = SpecFormatRules::prd_defaults();
 
@@ -180,6 +199,7 @@ pub fn execute(args: &Value, project_root: &Path) -> Result<String> {
             affected_code,
             breaking_changes,
         },
+        depends_on_changes,
     };
 
     create_proposal(input, project_root)
@@ -280,6 +300,66 @@ pub fn execute_append_review(args: &Value, project_root: &Path) -> Result<String
     ))
 }
 
+/// Get the tool definition for patch_proposal
+pub fn patch_definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "patch_proposal".to_string(),
+        description: "Add, modify, remove or rename a single ## section of proposal.md without regenerating the whole file".to_string(),
+        input_schema: json!({
+            "type": "object",
+            "required": ["project_path", "change_id", "operation"],
+            "properties": {
+                "project_path": {
+                    "type": "string",
+                    "description": "Project root path (use $PWD for current directory)"
+                },
+                "change_id": {
+                    "type": "string",
+                    "pattern": "^[a-z0-9-]+$",
+                    "description": "Unique identifier for the change (lowercase, hyphens allowed)"
+                },
+                "operation": {
+                    "enum": ["add", "modify", "remove", "rename"],
+                    "description": "add/modify take 'section' and 'content'; remove/rename take 'id' (and 'new_name')"
+                },
+                "section": {
+                    "type": "string",
+                    "description": "Section heading to add or modify (e.g., 'What Changes')"
+                },
+                "content": {
+                    "type": "string",
+                    "description": "New section body in markdown (without the heading)"
+                },
+                "id": {
+                    "type": "string",
+                    "description": "Section heading to remove or rename"
+                },
+                "new_name": {
+                    "type": "string",
+                    "description": "New section heading (rename only)"
+                },
+                "reason": {
+                    "type": "string",
+                    "description": "Why the section is removed (remove only)"
+                }
+            }
+        }),
+    }
+}
+
+/// Execute the patch_proposal tool
+pub fn execute_patch(args: &Value, project_root: &Path) -> Result<String> {
+    let change_id = get_required_string(args, "change_id")?;
+    let patch = super::spec::section_delta(args, || {
+        Ok(ProposalSection {
+            name: get_required_string(args, "section")?,
+            content: get_required_string(args, "content")?,
+        })
+    })?;
+
+    patch_proposal(PatchProposalInput { change_id, patch }, project_root)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,9 +1,17 @@
-//! create_spec MCP Tool
+//! create_spec and patch_spec MCP Tools
 //!
-//! Creates a validated spec file with requirements and acceptance criteria.
+//! Creates a validated spec file with requirements and acceptance criteria,
+//! and applies section-level edits to an existing spec.
 
-use super::{get_optional_string, get_required_array, get_required_string, ToolDefinition};
-use crate::services::spec_service::{create_spec, CreateSpecInput, RequirementData, ScenarioData};
+use super::{
+    get_optional_string, get_required_array, get_required_object, get_required_string,
+    ToolDefinition,
+};
+use crate::models::SectionDelta;
+use crate::services::spec_service::{
+    create_spec, patch_spec, CreateSpecInput, PatchSpecInput, RequirementData, ScenarioData,
+    SpecPatch,
+};
 use crate::Result;
 use serde_json::{json, Value};
 use std::path::Path;
@@ -168,6 +176,138 @@ pub fn execute(args: &Value, project_root: &Path) -> Result<String> {
     create_spec(input, project_root)
 }
 
+/// Get the tool definition for patch_spec
+pub fn patch_definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "patch_spec".to_string(),
+        description: "Add, modify, remove or rename a single requirement or scenario in an existing spec without regenerating the whole file".to_string(),
+        input_schema: json!({
+            "type": "object",
+            "required": ["project_path", "change_id", "spec_id", "target", "operation"],
+            "properties": {
+                "project_path": {
+                    "type": "string",
+                    "description": "Project root path (use $PWD for current directory)"
+                },
+                "change_id": {
+                    "type": "string",
+                    "description": "The change ID this spec belongs to"
+                },
+                "spec_id": {
+                    "type": "string",
+                    "pattern": "^[a-z0-9-]+$",
+                    "description": "ID of the spec to patch"
+                },
+                "target": {
+                    "enum": ["requirement", "scenario"],
+                    "description": "Kind of section to edit"
+                },
+                "operation": {
+                    "enum": ["add", "modify", "remove", "rename"],
+                    "description": "add/modify take a full requirement or scenario; remove/rename take 'id' (and 'new_name')"
+                },
+                "id": {
+                    "type": "string",
+                    "description": "Requirement ID or scenario name to remove or rename"
+                },
+                "new_name": {
+                    "type": "string",
+                    "description": "New title (rename only)"
+                },
+                "reason": {
+                    "type": "string",
+                    "description": "Why the section is removed (remove only)"
+                },
+                "requirement": {
+                    "type": "object",
+                    "required": ["id", "title", "description"],
+                    "properties": {
+                        "id": { "type": "string", "pattern": "^R\\d+$" },
+                        "title": { "type": "string" },
+                        "description": { "type": "string" },
+                        "priority": { "enum": ["high", "medium", "low"], "default": "medium" }
+                    },
+                    "description": "Full requirement (target=requirement, operation=add|modify)"
+                },
+                "scenario": {
+                    "type": "object",
+                    "required": ["name", "when", "then"],
+                    "properties": {
+                        "name": { "type": "string" },
+                        "given": { "type": "string" },
+                        "when": { "type": "string" },
+                        "then": { "type": "string" }
+                    },
+                    "description": "Full scenario (target=scenario, operation=add|modify)"
+                }
+            }
+        }),
+    }
+}
+
+/// Execute the patch_spec tool
+pub fn execute_patch(args: &Value, project_root: &Path) -> Result<String> {
+    let change_id = get_required_string(args, "change_id")?;
+    let spec_id = get_required_string(args, "spec_id")?;
+    let target = get_required_string(args, "target")?;
+
+    let patch = match target.as_str() {
+        "requirement" => SpecPatch::Requirement(section_delta(args, || {
+            let r = get_required_object(args, "requirement")?;
+            Ok(RequirementData {
+                id: get_required_string(&r, "id")?,
+                title: get_required_string(&r, "title")?,
+                description: get_required_string(&r, "description")?,
+                priority: get_optional_string(&r, "priority").unwrap_or_else(|| "medium".to_string()),
+            })
+        })?),
+        "scenario" => SpecPatch::Scenario(section_delta(args, || {
+            let s = get_required_object(args, "scenario")?;
+            Ok(ScenarioData {
+                name: get_required_string(&s, "name")?,
+                given: get_optional_string(&s, "given"),
+                when: get_required_string(&s, "when")?,
+                then: get_required_string(&s, "then")?,
+            })
+        })?),
+        other => anyhow::bail!("target must be 'requirement' or 'scenario', got '{}'", other),
+    };
+
+    patch_spec(
+        PatchSpecInput {
+            change_id,
+            spec_id,
+            patch,
+        },
+        project_root,
+    )
+}
+
+/// Build a section delta from the `operation`, `id`, `new_name` and `reason`
+/// arguments; `item` extracts the full section for add/modify
+pub(super) fn section_delta<T>(
+    args: &Value,
+    item: impl FnOnce() -> Result<T>,
+) -> Result<SectionDelta<T>> {
+    let operation = get_required_string(args, "operation")?;
+    match operation.as_str() {
+        "add" => Ok(SectionDelta::Added(item()?)),
+        "modify" => Ok(SectionDelta::Modified(item()?)),
+        "remove" => Ok(SectionDelta::Removed {
+            key: get_required_string(args, "id")?,
+            reason: get_optional_string(args, "reason"),
+        }),
+        "rename" => Ok(SectionDelta::Renamed {
+            from: get_required_string(args, "id")?,
+            to: get_required_string(args, "new_name")?,
+        }),
+        other => anyhow::bail!(
+            "operation must be 'add', 'modify', 'remove' or 'rename', got '{}'",
+            other
+        ),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -233,4 +373,52 @@ mod tests {
         assert!(content.contains("**THEN**"));
         assert!(content.contains("```mermaid"));
     }
+
+    #[test]
+    fn test_patch_spec() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path();
+        std::fs::create_dir_all(project_root.join("agentd/changes/test-change")).unwrap();
+
+        let args = json!({
+            "change_id": "test-change",
+            "spec_id": "auth",
+            "title": "Authentication",
+            "overview": "This specification covers user authentication, including login and logout flows.",
+            "requirements": [
+                { "id": "R1", "title": "Login", "description": "Users can log in" }
+            ],
+            "scenarios": [
+                { "name": "Valid login", "when": "User logs in", "then": "Session created" }
+            ]
+        });
+        execute(&args, project_root).unwrap();
+
+        let args = json!({
+            "change_id": "test-change",
+            "spec_id": "auth",
+            "target": "scenario",
+            "operation": "add",
+            "scenario": { "name": "Logout", "when": "User logs out", "then": "Session destroyed" }
+        });
+        let result = execute_patch(&args, project_root).unwrap();
+        assert!(result.contains("added scenario 'Logout'"));
+
+        let args = json!({
+            "change_id": "test-change",
+            "spec_id": "auth",
+            "target": "requirement",
+            "operation": "rename",
+            "id": "R1",
+            "new_name": "Sign In"
+        });
+        execute_patch(&args, project_root).unwrap();
+
+        let content = std::fs::read_to_string(
+            project_root.join("agentd/changes/test-change/specs/auth.md"),
+        )
+        .unwrap();
+        assert!(content.contains("### R1 - Sign In"));
+        assert!(content.contains("### Scenario: Logout"));
+    }
 }
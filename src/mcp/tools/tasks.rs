@@ -1,9 +1,13 @@
-//! create_tasks MCP Tool
+//! create_tasks / update_task_status MCP Tools
 //!
-//! Creates a validated tasks.md file with layered task structure.
+//! Creates a validated tasks.md file with layered task structure and keeps
+//! task statuses in it up to date during implementation.
 
 use super::{get_required_array, get_required_string, ToolDefinition};
-use crate::services::tasks_service::{create_tasks, CreateTasksInput, FileActionData, TaskData};
+use crate::models::TaskStatus;
+use crate::services::tasks_service::{
+    create_tasks, update_task_status, CreateTasksInput, FileActionData, TaskData,
+};
 use crate::Result;
 use serde_json::{json, Value};
 use std::path::Path;
@@ -125,6 +129,51 @@ pub fn execute(args: &Value, project_root: &Path) -> Result<String> {
     create_tasks(input, project_root)
 }
 
+/// Get the tool definition for update_task_status
+pub fn update_status_definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "update_task_status".to_string(),
+        description: "Update the status of one or more tasks in tasks.md (pending, in_progress, completed, blocked)"
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "required": ["project_path", "change_id", "task_ids", "status"],
+            "properties": {
+                "project_path": {
+                    "type": "string",
+                    "description": "Project root path (use $PWD for current directory)"
+                },
+                "change_id": {
+                    "type": "string",
+                    "description": "The change ID the tasks belong to"
+                },
+                "task_ids": {
+                    "type": "array",
+                    "minItems": 1,
+                    "items": { "type": "string" },
+                    "description": "Task IDs to update (e.g., ['1.1', '1.2'])"
+                },
+                "status": {
+                    "enum": ["pending", "in_progress", "completed", "blocked"],
+                    "description": "New task status"
+                }
+            }
+        }),
+    }
+}
+
+/// Execute the update_task_status tool
+pub fn execute_update_status(args: &Value, project_root: &Path) -> Result<String> {
+    let change_id = get_required_string(args, "change_id")?;
+    let status: TaskStatus = get_required_string(args, "status")?.parse()?;
+    let task_ids: Vec<String> = get_required_array(args, "task_ids")?
+        .iter()
+        .filter_map(|v| v.as_str().map(String::from))
+        .collect();
+
+    update_task_status(&change_id, &task_ids, status, project_root)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,530 @@
+//! Upstream MCP servers
+//!
+//! Connects to external MCP servers configured under `[[mcp.upstream]]`
+//! (launched over stdio, or reached over HTTP) and proxies their tools
+//! through the agentd tool registry as `<prefix>__<tool>`.
+
+use super::tools::ToolDefinition;
+use crate::models::McpUpstreamConfig;
+use crate::Result;
+use http_body_util::{BodyExt, Full};
+use hyper::body::Bytes;
+use hyper::header::{ACCEPT, CONTENT_TYPE, HOST};
+use hyper::{Request, Uri};
+use hyper_util::rt::TokioIo;
+use serde_json::{json, Value};
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio::sync::Mutex;
+
+/// Separator between the namespace prefix and the upstream tool name
+pub const NAMESPACE_SEPARATOR: &str = "__";
+
+/// MCP protocol version sent in `initialize`
+const PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// Session header used by streamable HTTP servers
+const SESSION_HEADER: &str = "mcp-session-id";
+
+/// Name of a proxied tool as exposed to clients
+pub fn namespaced(prefix: &str, tool: &str) -> String {
+    format!("{}{}{}", prefix, NAMESPACE_SEPARATOR, tool)
+}
+
+/// A connected upstream server and the tools it exposes
+pub struct UpstreamServer {
+    config: McpUpstreamConfig,
+    transport: Mutex<Transport>,
+    next_id: AtomicU64,
+    tools: Vec<ToolDefinition>,
+}
+
+enum Transport {
+    Stdio(Box<StdioTransport>),
+    Http(HttpTransport),
+}
+
+struct StdioTransport {
+    // Held so the child is killed when the server is dropped
+    _child: Child,
+    stdin: ChildStdin,
+    stdout: Lines<BufReader<ChildStdout>>,
+}
+
+struct HttpTransport {
+    uri: Uri,
+    session_id: Option<String>,
+}
+
+impl UpstreamServer {
+    /// Launch or connect to a server, run the MCP handshake and list its tools
+    pub async fn connect(config: McpUpstreamConfig, project_root: &Path) -> Result<Self> {
+        let transport = match (&config.command, &config.url) {
+            (Some(command), None) => {
+                let mut child = Command::new(command)
+                    .args(&config.args)
+                    .envs(&config.env)
+                    .current_dir(project_root)
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::inherit())
+                    .kill_on_drop(true)
+                    .spawn()
+                    .map_err(|e| anyhow::anyhow!("Failed to launch '{}': {}", command, e))?;
+                let stdin = child.stdin.take().expect("stdin is piped");
+                let stdout = child.stdout.take().expect("stdout is piped");
+                Transport::Stdio(Box::new(StdioTransport {
+                    _child: child,
+                    stdin,
+                    stdout: BufReader::new(stdout).lines(),
+                }))
+            }
+            (None, Some(url)) => {
+                let uri: Uri = url
+                    .parse()
+                    .map_err(|e| anyhow::anyhow!("Invalid url '{}': {}", url, e))?;
+                if uri.scheme_str() != Some("http") {
+                    anyhow::bail!(
+                        "Unsupported url '{}': only http:// endpoints are supported",
+                        url
+                    );
+                }
+                Transport::Http(HttpTransport {
+                    uri,
+                    session_id: None,
+                })
+            }
+            _ => anyhow::bail!(
+                "Upstream '{}' must set exactly one of 'command' or 'url'",
+                config.name
+            ),
+        };
+
+        let mut server = Self {
+            config,
+            transport: Mutex::new(transport),
+            next_id: AtomicU64::new(1),
+            tools: Vec::new(),
+        };
+
+        server
+            .request(
+                "initialize",
+                json!({
+                    "protocolVersion": PROTOCOL_VERSION,
+                    "capabilities": {},
+                    "clientInfo": {
+                        "name": "agentd",
+                        "version": env!("CARGO_PKG_VERSION")
+                    }
+                }),
+            )
+            .await?;
+        server.notify("notifications/initialized").await?;
+
+        let listed = server.request("tools/list", json!({})).await?;
+        server.tools = listed
+            .get("tools")
+            .and_then(|t| t.as_array())
+            .map(|tools| {
+                tools
+                    .iter()
+                    .filter_map(|tool| server.expose(tool))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(server)
+    }
+
+    /// Server name from config
+    pub fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    /// Whether the server's tools are exposed for a stage (None = all tools)
+    pub fn enabled_for_stage(&self, stage: Option<&str>) -> bool {
+        self.config.enabled_for_stage(stage)
+    }
+
+    /// Proxied tool definitions (namespaced)
+    pub fn tools(&self) -> &[ToolDefinition] {
+        &self.tools
+    }
+
+    /// Upstream tool name for an exposed tool name, if this server owns it
+    pub fn resolve<'a>(&self, name: &'a str) -> Option<&'a str> {
+        let tool = name
+            .strip_prefix(self.config.prefix())?
+            .strip_prefix(NAMESPACE_SEPARATOR)?;
+        self.tools.iter().any(|t| t.name == name).then_some(tool)
+    }
+
+    /// Call an upstream tool and return its text content
+    pub async fn call_tool(&self, tool: &str, arguments: &Value) -> Result<String> {
+        let result = self
+            .request(
+                "tools/call",
+                json!({ "name": tool, "arguments": arguments }),
+            )
+            .await?;
+
+        let text = result
+            .get("content")
+            .and_then(|c| c.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .map(|item| match item.get("type").and_then(|t| t.as_str()) {
+                        Some("text") => item
+                            .get("text")
+                            .and_then(|t| t.as_str())
+                            .unwrap_or_default()
+                            .to_string(),
+                        Some(other) => format!(
+                            "[{} content: {}]",
+                            other,
+                            item.get("mimeType").and_then(|m| m.as_str()).unwrap_or("unknown")
+                        ),
+                        None => item.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .unwrap_or_default();
+
+        if result.get("isError").and_then(|e| e.as_bool()).unwrap_or(false) {
+            anyhow::bail!("{}", text);
+        }
+        Ok(text)
+    }
+
+    /// Build the exposed definition for an upstream tool
+    fn expose(&self, tool: &Value) -> Option<ToolDefinition> {
+        let name = tool.get("name")?.as_str()?;
+        let description = tool
+            .get("description")
+            .and_then(|d| d.as_str())
+            .unwrap_or_default();
+        Some(ToolDefinition {
+            name: namespaced(self.config.prefix(), name),
+            description: format!("[{}] {}", self.config.name, description)
+                .trim_end()
+                .to_string(),
+            input_schema: tool
+                .get("inputSchema")
+                .cloned()
+                .unwrap_or_else(|| json!({ "type": "object" })),
+        })
+    }
+
+    /// Send a JSON-RPC request and wait for its result
+    async fn request(&self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let message = json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params
+        });
+
+        let timeout = Duration::from_secs(self.config.timeout_secs);
+        let response = tokio::time::timeout(timeout, async {
+            let mut transport = self.transport.lock().await;
+            match &mut *transport {
+                Transport::Stdio(stdio) => stdio.request(&message, id).await,
+                Transport::Http(http) => http.post(&message, Some(id)).await,
+            }
+        })
+        .await
+        .map_err(|_| {
+            anyhow::anyhow!(
+                "Upstream '{}' timed out after {}s ({})",
+                self.config.name,
+                self.config.timeout_secs,
+                method
+            )
+        })??;
+
+        let response = response.ok_or_else(|| {
+            anyhow::anyhow!("Upstream '{}' sent no response to {}", self.config.name, method)
+        })?;
+        if let Some(error) = response.get("error") {
+            anyhow::bail!(
+                "Upstream '{}' error: {}",
+                self.config.name,
+                error
+                    .get("message")
+                    .and_then(|m| m.as_str())
+                    .unwrap_or("unknown error")
+            );
+        }
+        Ok(response.get("result").cloned().unwrap_or(Value::Null))
+    }
+
+    /// Send a JSON-RPC notification
+    async fn notify(&self, method: &str) -> Result<()> {
+        let message = json!({ "jsonrpc": "2.0", "method": method });
+        let mut transport = self.transport.lock().await;
+        match &mut *transport {
+            Transport::Stdio(stdio) => stdio.send(&message).await,
+            Transport::Http(http) => http.post(&message, None).await.map(|_| ()),
+        }
+    }
+}
+
+impl StdioTransport {
+    async fn send(&mut self, message: &Value) -> Result<()> {
+        let mut line = serde_json::to_string(message)?;
+        line.push('\n');
+        self.stdin.write_all(line.as_bytes()).await?;
+        self.stdin.flush().await?;
+        Ok(())
+    }
+
+    /// Send a request and read lines until the matching response arrives,
+    /// skipping server notifications and log output
+    async fn request(&mut self, message: &Value, id: u64) -> Result<Option<Value>> {
+        self.send(message).await?;
+        while let Some(line) = self.stdout.next_line().await? {
+            let Ok(value) = serde_json::from_str::<Value>(&line) else {
+                continue;
+            };
+            if value.get("id").and_then(|i| i.as_u64()) == Some(id) {
+                return Ok(Some(value));
+            }
+        }
+        anyhow::bail!("Upstream server closed its stdout")
+    }
+}
+
+impl HttpTransport {
+    /// POST a message; returns the matching response for requests
+    async fn post(&mut self, message: &Value, id: Option<u64>) -> Result<Option<Value>> {
+        let host = self
+            .uri
+            .host()
+            .ok_or_else(|| anyhow::anyhow!("Missing host in url '{}'", self.uri))?;
+        let port = self.uri.port_u16().unwrap_or(80);
+        let authority = self
+            .uri
+            .authority()
+            .map(|a| a.to_string())
+            .unwrap_or_else(|| host.to_string());
+        let path = self
+            .uri
+            .path_and_query()
+            .map(|p| p.as_str().to_string())
+            .unwrap_or_else(|| "/".to_string());
+
+        let stream = tokio::net::TcpStream::connect((host, port)).await?;
+        let (mut sender, connection) =
+            hyper::client::conn::http1::handshake(TokioIo::new(stream)).await?;
+        tokio::spawn(async move {
+            let _ = connection.await;
+        });
+
+        let mut builder = Request::post(path)
+            .header(HOST, authority)
+            .header(CONTENT_TYPE, "application/json")
+            .header(ACCEPT, "application/json, text/event-stream");
+        if let Some(session_id) = &self.session_id {
+            builder = builder.header(SESSION_HEADER, session_id);
+        }
+        let request = builder.body(Full::new(Bytes::from(serde_json::to_vec(message)?)))?;
+
+        let response = sender.send_request(request).await?;
+        let status = response.status();
+        if let Some(session_id) = response
+            .headers()
+            .get(SESSION_HEADER)
+            .and_then(|v| v.to_str().ok())
+        {
+            self.session_id = Some(session_id.to_string());
+        }
+        let is_event_stream = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("text/event-stream"));
+        let body = response.into_body().collect().await?.to_bytes();
+
+        if !status.is_success() {
+            anyhow::bail!(
+                "HTTP {}: {}",
+                status,
+                String::from_utf8_lossy(&body).trim()
+            );
+        }
+        let Some(id) = id else {
+            return Ok(None);
+        };
+
+        let body = String::from_utf8_lossy(&body);
+        if is_event_stream {
+            return Ok(parse_event_stream(&body, id));
+        }
+        if body.trim().is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&body)?))
+    }
+}
+
+/// Find the JSON-RPC response with the given id in a server-sent event stream
+fn parse_event_stream(body: &str, id: u64) -> Option<Value> {
+    body.split("\n\n")
+        .filter_map(|event| {
+            let data: Vec<&str> = event
+                .lines()
+                .filter_map(|line| line.strip_prefix("data:"))
+                .map(str::trim_start)
+                .collect();
+            serde_json::from_str::<Value>(&data.join("\n")).ok()
+        })
+        .find(|value| value.get("id").and_then(|i| i.as_u64()) == Some(id))
+}
+
+/// Connect to all upstream servers enabled for a stage
+///
+/// Servers that fail to start are reported on stderr and skipped, so a broken
+/// upstream never takes the native tools down with it.
+pub async fn connect_all(
+    configs: &[McpUpstreamConfig],
+    stage: Option<&str>,
+    project_root: &Path,
+) -> Vec<UpstreamServer> {
+    let mut servers: Vec<UpstreamServer> = Vec::new();
+    for config in configs.iter().filter(|c| c.enabled_for_stage(stage)) {
+        if servers.iter().any(|s| s.config.prefix() == config.prefix()) {
+            eprintln!(
+                "⚠️  Skipping upstream MCP server '{}': prefix '{}' is already used",
+                config.name,
+                config.prefix()
+            );
+            continue;
+        }
+        match UpstreamServer::connect(config.clone(), project_root).await {
+            Ok(server) => servers.push(server),
+            Err(e) => eprintln!(
+                "⚠️  Failed to connect upstream MCP server '{}': {}",
+                config.name, e
+            ),
+        }
+    }
+    servers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config(name: &str) -> McpUpstreamConfig {
+        McpUpstreamConfig {
+            name: name.to_string(),
+            prefix: None,
+            command: None,
+            args: Vec::new(),
+            env: HashMap::new(),
+            url: None,
+            stages: Vec::new(),
+            timeout_secs: 5,
+        }
+    }
+
+    #[test]
+    fn test_parse_event_stream() {
+        let body = "event: message\ndata: {\"jsonrpc\":\"2.0\",\"method\":\"notifications/progress\"}\n\nevent: message\ndata: {\"jsonrpc\":\"2.0\",\"id\":3,\"result\":{}}\n\n";
+        let value = parse_event_stream(body, 3).unwrap();
+        assert_eq!(value["id"], 3);
+        assert!(parse_event_stream(body, 4).is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stdio_upstream() {
+        // A scripted server: answers initialize (id 1), skips the initialized
+        // notification, answers tools/list (id 2) and one tools/call (id 3)
+        let script = r#"
+read line; echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2024-11-05","capabilities":{}}}'
+read line
+read line; echo '{"jsonrpc":"2.0","id":2,"result":{"tools":[{"name":"query","description":"Run a query","inputSchema":{"type":"object"}}]}}'
+read line; echo '{"jsonrpc":"2.0","method":"notifications/message"}'; echo '{"jsonrpc":"2.0","id":3,"result":{"content":[{"type":"text","text":"3 rows"}]}}'
+"#;
+        let mut cfg = config("db");
+        cfg.command = Some("sh".to_string());
+        cfg.args = vec!["-c".to_string(), script.to_string()];
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let server = UpstreamServer::connect(cfg, temp_dir.path()).await.unwrap();
+
+        assert_eq!(server.tools().len(), 1);
+        assert_eq!(server.tools()[0].name, "db__query");
+        assert_eq!(server.tools()[0].description, "[db] Run a query");
+        assert_eq!(server.resolve("db__query"), Some("query"));
+        assert_eq!(server.resolve("db__missing"), None);
+        assert_eq!(server.resolve("other__query"), None);
+
+        let result = server
+            .call_tool("query", &json!({ "sql": "select 1" }))
+            .await
+            .unwrap();
+        assert_eq!(result, "3 rows");
+    }
+
+    #[tokio::test]
+    async fn test_http_upstream() {
+        use axum::{routing::post, Json, Router};
+
+        async fn handle(Json(request): Json<Value>) -> axum::response::Response {
+            use axum::response::IntoResponse;
+            let id = request.get("id").cloned();
+            let result = match request["method"].as_str() {
+                Some("initialize") => json!({ "protocolVersion": PROTOCOL_VERSION }),
+                Some("tools/list") => json!({ "tools": [{ "name": "search", "inputSchema": { "type": "object" } }] }),
+                Some("tools/call") => json!({
+                    "content": [{ "type": "text", "text": format!("found {}", request["params"]["arguments"]["q"]) }],
+                    "isError": false
+                }),
+                _ => return axum::http::StatusCode::ACCEPTED.into_response(),
+            };
+            Json(json!({ "jsonrpc": "2.0", "id": id, "result": result })).into_response()
+        }
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, Router::new().route("/mcp", post(handle)))
+                .await
+                .unwrap();
+        });
+
+        let mut cfg = config("docs");
+        cfg.prefix = Some("d".to_string());
+        cfg.url = Some(format!("http://{}/mcp", addr));
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let server = UpstreamServer::connect(cfg, temp_dir.path()).await.unwrap();
+        assert_eq!(server.tools()[0].name, "d__search");
+
+        let result = server.call_tool("search", &json!({ "q": "auth" })).await.unwrap();
+        assert_eq!(result, "found \"auth\"");
+    }
+
+    #[tokio::test]
+    async fn test_connect_all_skips_failures() {
+        let mut broken = config("broken");
+        broken.command = Some("agentd-nonexistent-mcp-server".to_string());
+        let mut review_only = config("review-only");
+        review_only.stages = vec!["review".to_string()];
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let servers = connect_all(&[broken, review_only], Some("implement"), temp_dir.path()).await;
+        assert!(servers.is_empty());
+    }
+}
@@ -1,5 +1,6 @@
 use super::{Challenge, RequirementDelta, ValidationRules, Verification};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 /// Phase of a change
@@ -589,6 +590,11 @@ pub struct WorkflowConfig {
     /// Enable spec-by-spec sequential implementation (default: true)
     #[serde(default = "default_sequential_implementation")]
     pub sequential_implementation: bool,
+
+    /// Scope drift findings (unplanned, untouched or mismatched files) allowed
+    /// before merge-change is blocked
+    #[serde(default = "default_scope_drift_threshold")]
+    pub scope_drift_threshold: u32,
 }
 
 fn default_format_iterations() -> u32 { 2 }
@@ -598,6 +604,7 @@ fn default_archive_iterations() -> u32 { 1 }
 fn default_script_retries() -> u32 { 3 }
 fn default_retry_delay_secs() -> u64 { 5 }
 fn default_sequential_implementation() -> bool { true }
+fn default_scope_drift_threshold() -> u32 { 5 }
 
 impl Default for WorkflowConfig {
     fn default() -> Self {
@@ -609,10 +616,158 @@ impl Default for WorkflowConfig {
             script_retries: default_script_retries(),
             retry_delay_secs: default_retry_delay_secs(),
             sequential_implementation: default_sequential_implementation(),
+            scope_drift_threshold: default_scope_drift_threshold(),
         }
     }
 }
 
+/// Code fillback analysis settings (`[fillback]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FillbackConfig {
+    /// Max source files analyzed per run (0 = no limit)
+    #[serde(default = "default_fillback_max_files")]
+    pub max_files: usize,
+
+    /// Files larger than this many bytes are skipped
+    #[serde(default = "default_fillback_max_file_size")]
+    pub max_file_size: u64,
+
+    /// Parser threads (0 = one per available CPU)
+    #[serde(default)]
+    pub jobs: usize,
+
+    /// Reuse parse results for unchanged files from .agentd/cache
+    #[serde(default = "default_fillback_cache")]
+    pub cache: bool,
+}
+
+fn default_fillback_max_files() -> usize { 500 }
+fn default_fillback_max_file_size() -> u64 { 100_000 }
+fn default_fillback_cache() -> bool { true }
+
+impl Default for FillbackConfig {
+    fn default() -> Self {
+        Self {
+            max_files: default_fillback_max_files(),
+            max_file_size: default_fillback_max_file_size(),
+            jobs: 0,
+            cache: default_fillback_cache(),
+        }
+    }
+}
+
+/// An external MCP server whose tools are proxied through agentd
+///
+/// Exactly one of `command` (stdio) or `url` (HTTP) must be set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpUpstreamConfig {
+    /// Server name, used as the default tool namespace
+    pub name: String,
+
+    /// Tool name prefix (default: `name`); tools are exposed as `<prefix>__<tool>`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+
+    /// Command to launch a stdio server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+
+    /// Arguments for `command`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+
+    /// Extra environment variables for `command`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+
+    /// Endpoint of an HTTP server (e.g., "http://127.0.0.1:8811/mcp")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// Workflow stages that get this server's tools (empty = all stages)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stages: Vec<String>,
+
+    /// Timeout for each request to the server
+    #[serde(default = "default_upstream_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_upstream_timeout_secs() -> u64 { 30 }
+
+impl McpUpstreamConfig {
+    /// Namespace prefix for proxied tool names
+    pub fn prefix(&self) -> &str {
+        self.prefix.as_deref().unwrap_or(&self.name)
+    }
+
+    /// Check whether this server's tools are exposed for a stage (None = all tools)
+    pub fn enabled_for_stage(&self, stage: Option<&str>) -> bool {
+        stage_allowed(&self.stages, stage)
+    }
+}
+
+/// A project-specific MCP tool backed by a shell command
+///
+/// `command` is split into arguments like a shell would, then `{{arg}}`
+/// placeholders are replaced with tool argument values. The command is run
+/// directly (no shell), so values cannot inject extra commands.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpCustomToolConfig {
+    /// Tool name exposed to agents
+    pub name: String,
+
+    /// Tool description exposed to agents
+    pub description: String,
+
+    /// Command template, e.g. "scripts/check-migrations.sh --env {{env}}"
+    pub command: String,
+
+    /// JSON Schema for the tool arguments
+    #[serde(default = "default_custom_tool_schema")]
+    pub input_schema: serde_json::Value,
+
+    /// Workflow stages that get this tool (empty = all stages)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stages: Vec<String>,
+
+    /// Maximum run time before the command is killed
+    #[serde(default = "default_custom_tool_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_custom_tool_schema() -> serde_json::Value {
+    serde_json::json!({ "type": "object", "properties": {} })
+}
+fn default_custom_tool_timeout_secs() -> u64 { 60 }
+
+impl McpCustomToolConfig {
+    /// Check whether this tool is exposed for a stage (None = all tools)
+    pub fn enabled_for_stage(&self, stage: Option<&str>) -> bool {
+        stage_allowed(&self.stages, stage)
+    }
+}
+
+/// Per-stage allow-list check shared by upstream servers and custom tools
+fn stage_allowed(stages: &[String], stage: Option<&str>) -> bool {
+    match stage {
+        Some(stage) if !stages.is_empty() => stages.iter().any(|s| s == stage),
+        _ => true,
+    }
+}
+
+/// MCP server settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct McpConfig {
+    /// External MCP servers proxied through agentd (`[[mcp.upstream]]`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub upstream: Vec<McpUpstreamConfig>,
+
+    /// Project-specific command tools (`[[mcp.custom_tools]]`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_tools: Vec<McpCustomToolConfig>,
+}
+
 /// Agentd configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentdConfig {
@@ -645,6 +800,14 @@ pub struct AgentdConfig {
     #[serde(default)]
     pub validation: ValidationRules,
 
+    /// MCP server settings
+    #[serde(default)]
+    pub mcp: McpConfig,
+
+    /// Code fillback analysis settings
+    #[serde(default)]
+    pub fillback: FillbackConfig,
+
     // Legacy fields for backward compatibility (kept for TOML deserialization)
     #[serde(skip_serializing, default)]
     #[allow(dead_code)]
@@ -667,6 +830,8 @@ impl Default for AgentdConfig {
             claude: ClaudeConfig::default(),
             scripts_dir: PathBuf::from("agentd/scripts"),
             validation: ValidationRules::default(),
+            mcp: McpConfig::default(),
+            fillback: FillbackConfig::default(),
             gemini_command: None,
             claude_command: None,
             codex_command: None,
@@ -747,4 +912,92 @@ mod config_tests {
         assert!(!config.scripts_dir.is_absolute());
         assert_eq!(config.scripts_dir, PathBuf::from("agentd/scripts"));
     }
+
+    #[test]
+    fn test_mcp_upstream_config() {
+        let toml_str = r#"
+project_name = "test"
+scripts_dir = "agentd/scripts"
+
+[[mcp.upstream]]
+name = "db"
+command = "db-inspector"
+args = ["--stdio"]
+stages = ["challenge", "review"]
+
+[[mcp.upstream]]
+name = "docs"
+prefix = "d"
+url = "http://127.0.0.1:8811/mcp"
+"#;
+        let config: AgentdConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.mcp.upstream.len(), 2);
+
+        let db = &config.mcp.upstream[0];
+        assert_eq!(db.prefix(), "db");
+        assert_eq!(db.timeout_secs, 30);
+        assert!(db.enabled_for_stage(Some("review")));
+        assert!(!db.enabled_for_stage(Some("implement")));
+        assert!(db.enabled_for_stage(None));
+
+        let docs = &config.mcp.upstream[1];
+        assert_eq!(docs.prefix(), "d");
+        assert!(docs.enabled_for_stage(Some("implement")));
+    }
+
+    #[test]
+    fn test_mcp_custom_tools_config() {
+        let toml_str = r#"
+project_name = "test"
+scripts_dir = "agentd/scripts"
+
+[[mcp.custom_tools]]
+name = "check_migrations"
+description = "Check pending database migrations"
+command = "scripts/check-migrations.sh --env {{env}}"
+stages = ["review"]
+input_schema = { type = "object", required = ["env"], properties = { env = { type = "string", enum = ["dev", "staging"] } } }
+
+[[mcp.custom_tools]]
+name = "feature_flags"
+description = "List feature flags"
+command = "flags list"
+"#;
+        let config: AgentdConfig = toml::from_str(toml_str).unwrap();
+        let tools = &config.mcp.custom_tools;
+        assert_eq!(tools.len(), 2);
+        assert_eq!(tools[0].input_schema["required"][0], "env");
+        assert_eq!(tools[0].timeout_secs, 60);
+        assert!(!tools[0].enabled_for_stage(Some("plan")));
+        assert_eq!(tools[1].input_schema["type"], "object");
+        assert!(tools[1].enabled_for_stage(Some("plan")));
+
+        // Round-trips through TOML
+        let saved = toml::to_string_pretty(&config).unwrap();
+        let reloaded: AgentdConfig = toml::from_str(&saved).unwrap();
+        assert_eq!(reloaded.mcp.custom_tools[0].input_schema, tools[0].input_schema);
+    }
+
+    #[test]
+    fn test_fillback_config() {
+        let config: AgentdConfig = toml::from_str(
+            r#"
+project_name = "test"
+scripts_dir = "agentd/scripts"
+
+[fillback]
+max_files = 0
+jobs = 4
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.fillback.max_files, 0);
+        assert_eq!(config.fillback.jobs, 4);
+        assert_eq!(config.fillback.max_file_size, 100_000);
+        assert!(config.fillback.cache);
+
+        let defaults = AgentdConfig::default().fillback;
+        assert_eq!(defaults.max_files, 500);
+        assert_eq!(defaults.jobs, 0);
+    }
 }
@@ -64,6 +64,10 @@ pub struct ProposalFrontmatter {
     #[serde(default)]
     pub dependencies: Option<Dependencies>,
 
+    /// Other changes that must progress before this one
+    #[serde(default)]
+    pub depends_on_changes: Vec<ChangeDependency>,
+
     /// Risk assessment
     #[serde(default)]
     pub risks: Vec<Risk>,
@@ -126,6 +130,45 @@ pub struct Dependencies {
     pub internal: Vec<String>,
 }
 
+/// Another change this change depends on
+///
+/// Written either as a bare change ID or as `{ id, phase }`. `phase` is the
+/// phase the prerequisite must reach before this change is implemented
+/// (default `archived`, i.e. merged); merging always requires it archived.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "RawChangeDependency")]
+pub struct ChangeDependency {
+    pub id: String,
+    pub phase: StatePhase,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawChangeDependency {
+    Id(String),
+    Detailed {
+        id: String,
+        #[serde(default = "default_dependency_phase")]
+        phase: StatePhase,
+    },
+}
+
+fn default_dependency_phase() -> StatePhase {
+    StatePhase::Archived
+}
+
+impl From<RawChangeDependency> for ChangeDependency {
+    fn from(raw: RawChangeDependency) -> Self {
+        match raw {
+            RawChangeDependency::Id(id) => Self {
+                id,
+                phase: default_dependency_phase(),
+            },
+            RawChangeDependency::Detailed { id, phase } => Self { id, phase },
+        }
+    }
+}
+
 /// External dependency
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExternalDependency {
@@ -418,6 +461,16 @@ pub struct State {
     #[serde(default)]
     pub session_id: Option<String>,
 
+    /// Commit HEAD pointed to when implementation started; diffs for the
+    /// change are taken against it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baseline_commit: Option<String>,
+
+    /// Checksums of the main specs (relative to agentd/specs) the change's
+    /// deltas were written against; an empty hash means the spec did not exist
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub main_spec_checksums: HashMap<String, String>,
+
     /// File checksums
     #[serde(default)]
     pub checksums: HashMap<String, ChecksumEntry>,
@@ -513,6 +566,42 @@ impl StatePhase {
         matches!(self, StatePhase::Archived | StatePhase::Rejected)
     }
 
+    /// Phase name as written in STATE.yaml
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatePhase::Clarifying => "clarifying",
+            StatePhase::Drafting => "drafting",
+            StatePhase::SpecsGenerated => "specs_generated",
+            StatePhase::TasksGenerated => "tasks_generated",
+            StatePhase::Planned => "planned",
+            StatePhase::Implementing => "implementing",
+            StatePhase::Testing => "testing",
+            StatePhase::CodeReviewing => "code_reviewing",
+            StatePhase::Implemented => "implemented",
+            StatePhase::Merging => "merging",
+            StatePhase::Archived => "archived",
+            StatePhase::Rejected => "rejected",
+        }
+    }
+
+    /// How far along the workflow this phase is (Rejected ranks lowest)
+    pub fn progress(&self) -> u8 {
+        match self {
+            StatePhase::Rejected => 0,
+            StatePhase::Clarifying => 1,
+            StatePhase::Drafting => 2,
+            StatePhase::SpecsGenerated => 3,
+            StatePhase::TasksGenerated => 4,
+            StatePhase::Planned => 5,
+            StatePhase::Implementing => 6,
+            StatePhase::Testing => 7,
+            StatePhase::CodeReviewing => 8,
+            StatePhase::Implemented => 9,
+            StatePhase::Merging => 10,
+            StatePhase::Archived => 11,
+        }
+    }
+
     /// Get the workflow name for this phase
     pub fn workflow(&self) -> &'static str {
         if self.is_plan_phase() {
@@ -532,21 +621,7 @@ impl Serialize for StatePhase {
     where
         S: serde::Serializer,
     {
-        let s = match self {
-            StatePhase::Clarifying => "clarifying",
-            StatePhase::Drafting => "drafting",
-            StatePhase::SpecsGenerated => "specs_generated",
-            StatePhase::TasksGenerated => "tasks_generated",
-            StatePhase::Planned => "planned",
-            StatePhase::Implementing => "implementing",
-            StatePhase::Testing => "testing",
-            StatePhase::CodeReviewing => "code_reviewing",
-            StatePhase::Implemented => "implemented",
-            StatePhase::Merging => "merging",
-            StatePhase::Archived => "archived",
-            StatePhase::Rejected => "rejected",
-        };
-        serializer.serialize_str(s)
+        serializer.serialize_str(self.as_str())
     }
 }
 
@@ -710,6 +785,18 @@ pub enum TaskAction {
     Rename,
 }
 
+impl TaskAction {
+    /// Action value as written in tasks.md YAML blocks
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskAction::Create => "CREATE",
+            TaskAction::Modify => "MODIFY",
+            TaskAction::Delete => "DELETE",
+            TaskAction::Rename => "RENAME",
+        }
+    }
+}
+
 /// Task status
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -721,6 +808,55 @@ pub enum TaskStatus {
     Blocked,
 }
 
+impl TaskStatus {
+    /// Status value as written in tasks.md YAML blocks
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskStatus::Pending => "pending",
+            TaskStatus::InProgress => "in_progress",
+            TaskStatus::Completed => "completed",
+            TaskStatus::Blocked => "blocked",
+        }
+    }
+
+    /// Markdown checkbox marker for checkbox-style task lists
+    pub fn checkbox(&self) -> &'static str {
+        match self {
+            TaskStatus::Pending => "[ ]",
+            TaskStatus::InProgress => "[>]",
+            TaskStatus::Completed => "[x]",
+            TaskStatus::Blocked => "[!]",
+        }
+    }
+
+    /// Parse the character inside a markdown checkbox (`x`, `>`, `!`, ` `)
+    pub fn from_checkbox(marker: char) -> Self {
+        match marker {
+            'x' | 'X' => TaskStatus::Completed,
+            '>' => TaskStatus::InProgress,
+            '!' => TaskStatus::Blocked,
+            _ => TaskStatus::Pending,
+        }
+    }
+}
+
+impl std::str::FromStr for TaskStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim() {
+            "pending" => Ok(TaskStatus::Pending),
+            "in_progress" => Ok(TaskStatus::InProgress),
+            "completed" => Ok(TaskStatus::Completed),
+            "blocked" => Ok(TaskStatus::Blocked),
+            other => anyhow::bail!(
+                "Invalid task status '{}': expected pending, in_progress, completed or blocked",
+                other
+            ),
+        }
+    }
+}
+
 /// Inline requirement block (in specs/*.md)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RequirementBlock {
@@ -805,6 +941,7 @@ impl Default for ProposalFrontmatter {
             impact: None,
             affected_specs: Vec::new(),
             dependencies: None,
+            depends_on_changes: Vec::new(),
             risks: Vec::new(),
         }
     }
@@ -871,6 +1008,8 @@ impl Default for State {
             iteration: 1,
             last_action: None,
             session_id: None,
+            baseline_commit: None,
+            main_spec_checksums: HashMap::new(),
             checksums: HashMap::new(),
             validations: Vec::new(),
             telemetry: None,
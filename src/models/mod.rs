@@ -19,12 +19,14 @@ pub use archive_review::{
 pub use challenge::{Challenge, ChallengeIssue, ChallengeVerdict, IssueSeverity};
 pub use change::{
     AgentdConfig, Change, ChangePhase, ClaudeConfig, ClaudeModelConfig, CodexConfig,
-    CodexModelConfig, Complexity, GeminiConfig, GeminiModelConfig,
+    CodexModelConfig, Complexity, FillbackConfig, GeminiConfig, GeminiModelConfig, McpConfig,
+    McpCustomToolConfig, McpUpstreamConfig,
 };
 pub use delta_metrics::{decide_merging_strategy, DeltaMetrics, MergingStrategy, StrategyDecision};
 pub use frontmatter::{
     // Document frontmatter types
-    ChallengeFrontmatter, ChallengeVerdictType, ChecksumEntry, Dependencies, DesignElements,
+    ChallengeFrontmatter, ChallengeVerdictType, ChangeDependency, ChecksumEntry, Dependencies,
+    DesignElements,
     ExternalDependency, ImpactAssessment, ImpactScope, IssuesSummary, LayerBreakdown, LayerInfo,
     PriorityBreakdown, ProposalFrontmatter, ProposalStatus, RequirementsSummary, Risk,
     RiskSeverity, SpecFrontmatter, SpecReference, State, StatePhase, TasksFrontmatter,
@@ -33,7 +35,7 @@ pub use frontmatter::{
     IssueBlock, IssueLocation, IssueSeverity as FrontmatterIssueSeverity, RequirementBlock,
     RequirementPriority, RequirementStatus, TaskAction, TaskBlock, TaskStatus,
 };
-pub use requirement::{Requirement, RequirementDelta};
+pub use requirement::{Requirement, RequirementDelta, SectionDelta};
 pub use review::{IssueCategory, ReviewIssue, ReviewVerdict};
 pub use scenario::Scenario;
 pub use spec_generation::{SourceFile, SpecGenerationRequest};
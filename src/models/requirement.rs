@@ -93,3 +93,33 @@ impl RequirementDelta {
         }
     }
 }
+
+/// A change to a single section of a document (requirement, scenario or
+/// proposal section), following the same add/modify/remove/rename variants as
+/// `RequirementDelta`
+#[derive(Debug, Clone, PartialEq)]
+pub enum SectionDelta<T> {
+    /// New section added
+    Added(T),
+
+    /// Existing section replaced (identified by the key inside `T`)
+    Modified(T),
+
+    /// Section removed
+    Removed { key: String, reason: Option<String> },
+
+    /// Section heading renamed, body untouched
+    Renamed { from: String, to: String },
+}
+
+impl<T> SectionDelta<T> {
+    /// Operation name used in tool output ("added", "modified", ...)
+    pub fn operation(&self) -> &'static str {
+        match self {
+            Self::Added(_) => "added",
+            Self::Modified(_) => "modified",
+            Self::Removed { .. } => "removed",
+            Self::Renamed { .. } => "renamed",
+        }
+    }
+}
@@ -118,7 +118,7 @@ impl TaskGraph {
     /// Supports two formats:
     /// 1. YAML blocks (```yaml...```) - generated by MCP tool
     /// 2. Markdown checkbox format (- [ ] ...) - generated by Gemini
-    fn parse_tasks(content: &str) -> Result<Vec<TaskBlock>> {
+    pub fn parse_tasks(content: &str) -> Result<Vec<TaskBlock>> {
         let mut tasks = Vec::new();
 
         // Try YAML blocks first (```yaml...```)
@@ -161,8 +161,9 @@ impl TaskGraph {
 
         let mut tasks = Vec::new();
 
-        // Match task lines: - [ ] 1.1 Task title or - [x] 1.1 Task title
-        let task_re = Regex::new(r"^- \[[x ]\] (\d+\.\d+) (.+)$").unwrap();
+        // Match task lines: - [ ] 1.1 Task title, - [x] 1.1 Task title,
+        // - [>] (in progress) or - [!] (blocked)
+        let task_re = Regex::new(r"^- \[([xX >!])\] (\d+\.\d+) (.+)$").unwrap();
         let file_re = Regex::new(r"File: `([^`]+)`\s*\((\w+)\)").unwrap();
         let spec_re = Regex::new(r"Spec: `([^`]+)`").unwrap();
         let depends_re = Regex::new(r"Depends?: (.+)$").unwrap();
@@ -174,7 +175,9 @@ impl TaskGraph {
             let line = lines[i].trim();
 
             if let Some(caps) = task_re.captures(line) {
-                let id = caps.get(1).unwrap().as_str().to_string();
+                let marker = caps.get(1).unwrap().as_str().chars().next().unwrap_or(' ');
+                let status = TaskStatus::from_checkbox(marker);
+                let id = caps.get(2).unwrap().as_str().to_string();
                 let mut file = String::new();
                 let mut action = TaskAction::Modify;
                 let mut spec_ref = None;
@@ -229,7 +232,7 @@ impl TaskGraph {
                     tasks.push(TaskBlock {
                         id,
                         action,
-                        status: TaskStatus::default(),
+                        status,
                         file,
                         spec_ref,
                         depends_on,
@@ -1,59 +1,48 @@
 use super::cli_mapper::{LlmArg, LlmProvider};
 use super::prompts;
 use super::{ModelSelector, ScriptRunner, UsageMetrics};
+use crate::mcp::Registry;
 use crate::models::{AgentdConfig, Complexity};
 use anyhow::Result;
-use serde_json::json;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Claude orchestrator for implementation tasks
 pub struct ClaudeOrchestrator<'a> {
     model_selector: ModelSelector<'a>,
     runner: ScriptRunner,
+    project_root: PathBuf,
 }
 
 impl<'a> ClaudeOrchestrator<'a> {
-    pub fn new(config: &'a AgentdConfig, _project_root: impl Into<PathBuf>) -> Self {
+    pub fn new(config: &'a AgentdConfig, project_root: impl Into<PathBuf>) -> Self {
         let model_selector = ModelSelector::new(config);
         let runner = ScriptRunner::new();
 
         Self {
             model_selector,
             runner,
+            project_root: project_root.into(),
         }
     }
 
     /// Generate temporary MCP configuration file for Claude
     ///
-    /// Creates a JSON file in /tmp with filtered MCP tools for the implement stage.
-    /// If the file already exists, it will be reused.
+    /// Creates a JSON file in /tmp with filtered MCP tools for the given stage.
+    /// Uses the running agentd HTTP server when there is one, scoped to this
+    /// project and stage; otherwise launches `agentd mcp-server` over stdio.
     /// Returns the path to the temporary config file.
     fn generate_tmp_mcp_config(&self, change_id: &str, stage: &str) -> Result<PathBuf> {
-        let tmp_config_path = PathBuf::from(format!(
-            "/tmp/agentd-{}-{}.mcp.json",
-            stage, change_id
-        ));
+        let tmp_config_path = tmp_mcp_config_path(&self.project_root, change_id, stage);
 
-        // Reuse existing config if present
-        if tmp_config_path.exists() {
-            eprintln!(
-                "[agentd] Reusing existing MCP config: {}",
-                tmp_config_path.display()
-            );
-            return Ok(tmp_config_path);
-        }
-
-        let config_json = json!({
-            "mcpServers": {
-                "agentd": {
-                    "command": "agentd",
-                    "args": ["mcp-server", "--tools", stage],
-                    "env": {}
-                }
-            }
-        });
+        let http_port = Registry::load()
+            .ok()
+            .filter(|registry| registry.is_server_running())
+            .map(|registry| registry.server.port);
+        let config_json = mcp_config_json(&self.project_root, stage, http_port);
 
         let config_str = serde_json::to_string_pretty(&config_json)?;
         fs::write(&tmp_config_path, config_str)?;
@@ -71,11 +60,8 @@ impl<'a> ClaudeOrchestrator<'a> {
     ///
     /// Removes the temporary config file created for this change.
     /// This is optional since /tmp is automatically cleaned by the OS.
-    pub fn cleanup_tmp_mcp_config(change_id: &str, stage: &str) -> Result<()> {
-        let tmp_config_path = PathBuf::from(format!(
-            "/tmp/agentd-{}-{}.mcp.json",
-            stage, change_id
-        ));
+    pub fn cleanup_tmp_mcp_config(&self, change_id: &str, stage: &str) -> Result<()> {
+        let tmp_config_path = tmp_mcp_config_path(&self.project_root, change_id, stage);
 
         if tmp_config_path.exists() {
             fs::remove_file(&tmp_config_path)?;
@@ -213,6 +199,51 @@ impl<'a> ClaudeOrchestrator<'a> {
     }
 }
 
+/// Temporary MCP config path, unique per project, stage and change
+fn tmp_mcp_config_path(project_root: &Path, change_id: &str, stage: &str) -> PathBuf {
+    let digest = Sha256::digest(project_root.to_string_lossy().as_bytes());
+    let project_hash: String = digest
+        .iter()
+        .take(6)
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    PathBuf::from(format!(
+        "/tmp/agentd-{}-{}-{}.mcp.json",
+        stage, change_id, project_hash
+    ))
+}
+
+/// MCP config for the agentd server, scoped to a project and stage
+///
+/// With an HTTP port, points at the running server and scopes it via
+/// headers. Otherwise launches the stdio server, which runs in the
+/// project directory and loads its upstreams and custom tools itself.
+fn mcp_config_json(project_root: &Path, stage: &str, http_port: Option<u16>) -> Value {
+    match http_port {
+        Some(port) => json!({
+            "mcpServers": {
+                "agentd": {
+                    "type": "http",
+                    "url": format!("http://localhost:{}/mcp", port),
+                    "headers": {
+                        "X-Agentd-Cwd": project_root.display().to_string(),
+                        "X-Agentd-Stage": stage
+                    }
+                }
+            }
+        }),
+        None => json!({
+            "mcpServers": {
+                "agentd": {
+                    "command": "agentd",
+                    "args": ["mcp-server", "--tools", stage],
+                    "env": {}
+                }
+            }
+        }),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -223,4 +254,36 @@ mod tests {
         let _orchestrator = ClaudeOrchestrator::new(&config, "/tmp/test");
         // Orchestrator created successfully
     }
+
+    #[test]
+    fn test_mcp_config_launches_stdio_without_server() {
+        let config = mcp_config_json(Path::new("/work/app"), "implement", None);
+        let agentd = &config["mcpServers"]["agentd"];
+        assert_eq!(agentd["command"], "agentd");
+        assert_eq!(
+            agentd["args"],
+            json!(["mcp-server", "--tools", "implement"])
+        );
+    }
+
+    #[test]
+    fn test_mcp_config_is_scoped_to_project_and_stage() {
+        let config = mcp_config_json(Path::new("/work/app"), "implement", Some(4567));
+        let agentd = &config["mcpServers"]["agentd"];
+        assert_eq!(agentd["type"], "http");
+        assert_eq!(agentd["url"], "http://localhost:4567/mcp");
+        assert_eq!(agentd["headers"]["X-Agentd-Cwd"], "/work/app");
+        assert_eq!(agentd["headers"]["X-Agentd-Stage"], "implement");
+    }
+
+    #[test]
+    fn test_tmp_mcp_config_path_is_per_project() {
+        let app = tmp_mcp_config_path(Path::new("/work/app"), "add-auth", "implement");
+        let other = tmp_mcp_config_path(Path::new("/work/other"), "add-auth", "implement");
+        assert_ne!(app, other);
+        assert_eq!(
+            app,
+            tmp_mcp_config_path(Path::new("/work/app"), "add-auth", "implement")
+        );
+    }
 }
@@ -2,6 +2,7 @@ use super::cli_mapper::{LlmArg, LlmProvider};
 use super::prompts;
 use super::{ModelSelector, ScriptRunner, SelectedModel, UsageMetrics};
 use crate::models::{AgentdConfig, Complexity};
+use crate::services::scope_drift_service;
 use anyhow::{Context, Result};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -286,7 +287,7 @@ impl<'a> CodexOrchestrator<'a> {
             self.run_verification_tools().await?;
 
         // Step 2: Generate prompt with pre-processing results
-        let prompt = prompts::codex_review_prompt(
+        let mut prompt = prompts::codex_review_prompt(
             change_id,
             iteration,
             &test_output,
@@ -295,6 +296,13 @@ impl<'a> CodexOrchestrator<'a> {
             &clippy_output,
         );
 
+        // Scope drift is best-effort context (skipped without git or tasks.md)
+        if let Ok(drift) =
+            scope_drift_service::check_scope_drift(change_id, None, None, &self.project_root)
+        {
+            prompt.push_str(&prompts::scope_drift_section(&drift.to_markdown()));
+        }
+
         let env = self.build_env(change_id);
         // Resume if iteration > 0 (Impl stage)
         let resume = iteration > 0;
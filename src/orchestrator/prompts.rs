@@ -553,6 +553,8 @@ pub fn codex_challenge_prompt(change_id: &str) -> String {
    - **Technical feasibility** - Is the design implementable? Any blockers?
    - **Clarity** - Are requirements specific and testable? Ambiguous language?
    - **Dependencies** - Are task dependencies correct? Missing prerequisites?
+   - **Codebase claims** - Do referenced files, functions and types exist as described?
+     Use `search_code` (regex or symbol search) and `get_symbol` to verify
 
 3. **Submit Review** using MCP tool:
    - Use: `append_review` MCP tool with your findings
@@ -673,6 +675,7 @@ Change ID: {change_id}
    - Best practices, performance, error handling
    - Requirement compliance (match proposal/specs)
    - Consistency with existing patterns
+   - Use `search_code` and `get_symbol` to check call sites and existing conventions
 
 6. **Write Review**: Create agentd/changes/{change_id}/REVIEW.md with comprehensive findings
 
@@ -705,6 +708,21 @@ Be thorough but fair. Include iteration number in REVIEW.md.
     )
 }
 
+/// Scope drift findings embedded in the code review prompt
+pub fn scope_drift_section(drift_report: &str) -> String {
+    format!(
+        r#"
+## Scope Drift (Embedded)
+agentd compared the files tasks.md planned to change with the actual diff:
+
+{drift_report}
+Report unplanned changes that are not justified by the specs, and planned files
+left untouched, as Requirement Compliance issues.
+"#,
+        drift_report = drift_report
+    )
+}
+
 /// Generate Codex verify prompt
 pub fn codex_verify_prompt(change_id: &str) -> String {
     format!(
@@ -7,11 +7,15 @@ pub mod proposal;
 pub mod requirement;
 pub mod review;
 pub mod scenario;
+pub mod spec_sections;
 pub mod xml;
 
 pub use archive_review::parse_archive_review_verdict;
 pub use challenge::{parse_challenge_verdict, ChallengeParser};
-pub use proposal::{parse_affected_specs, parse_latest_review, topological_sort_specs, ReviewBlock};
+pub use proposal::{
+    parse_affected_specs, parse_change_dependencies, parse_latest_review, topological_sort_specs,
+    ReviewBlock,
+};
 pub use frontmatter::{
     calculate_body_checksum, calculate_checksum, has_frontmatter, is_stale, normalize_content,
     parse_document, parse_frontmatter_value, split_frontmatter, ParsedDocument,
@@ -24,4 +28,8 @@ pub use markdown::extract_heading_section;
 pub use requirement::RequirementParser;
 pub use review::parse_review_verdict;
 pub use scenario::ScenarioParser;
+pub use spec_sections::{
+    parse_headings, parse_sections, parse_spec_outline, MarkdownHeading, MarkdownSection,
+    RequirementSection, ScenarioSection, SpecOutline,
+};
 pub use xml::{extract_xml_block, extract_xml_blocks, parse_xml_attributes, update_xml_blocks, wrap_in_xml, UpdateMode, XmlBlock};
@@ -1,3 +1,4 @@
+use crate::models::ChangeDependency;
 use crate::services::proposal_service::AffectedSpec;
 use crate::Result;
 use regex::Regex;
@@ -237,6 +238,28 @@ fn parse_affected_specs_from_markdown(content: &str) -> Result<Vec<AffectedSpec>
     }
 }
 
+/// Parse `depends_on_changes` from proposal.md frontmatter
+///
+/// ```yaml
+/// depends_on_changes:
+///   - add-user-model              # must be archived (merged)
+///   - id: add-session-store
+///     phase: implemented          # must be implemented before impl-change
+/// ```
+///
+/// Returns an empty list when the proposal has no frontmatter or no field.
+pub fn parse_change_dependencies(content: &str) -> Result<Vec<ChangeDependency>> {
+    if !crate::parser::frontmatter::has_frontmatter(content) {
+        return Ok(Vec::new());
+    }
+    let frontmatter = crate::parser::frontmatter::parse_frontmatter_value(content)?;
+    match frontmatter.get("depends_on_changes") {
+        None | Some(serde_yaml::Value::Null) => Ok(Vec::new()),
+        Some(value) => serde_yaml::from_value(value.clone())
+            .map_err(|e| anyhow::anyhow!("Invalid depends_on_changes: {}", e)),
+    }
+}
+
 /// Represents a review block from proposal.md
 #[derive(Debug, Clone, PartialEq)]
 pub struct ReviewBlock {
@@ -466,4 +489,21 @@ Test proposal
         assert!(err.contains("nonexistent"));
         assert!(err.contains("not in affected_specs"));
     }
+
+    #[test]
+    fn test_parse_change_dependencies() {
+        use crate::models::StatePhase;
+
+        let content = "---\nid: add-billing\ndepends_on_changes:\n  - add-user-model\n  - id: add-sessions\n    phase: implemented\n---\n\n# Change\n";
+        let deps = parse_change_dependencies(content).unwrap();
+        assert_eq!(deps.len(), 2);
+        assert_eq!(deps[0].id, "add-user-model");
+        assert_eq!(deps[0].phase, StatePhase::Archived);
+        assert_eq!(deps[1].id, "add-sessions");
+        assert_eq!(deps[1].phase, StatePhase::Implemented);
+
+        assert!(parse_change_dependencies("---\nid: x\n---\n").unwrap().is_empty());
+        assert!(parse_change_dependencies("# No frontmatter").unwrap().is_empty());
+        assert!(parse_change_dependencies("---\ndepends_on_changes:\n  - id: a\n    phase: done\n---\n").is_err());
+    }
 }
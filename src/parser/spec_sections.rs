@@ -0,0 +1,405 @@
+//! Spec Section Parser
+//!
+//! Locates headings, requirements and scenarios in a markdown document and
+//! records their byte ranges, so callers can patch, diff or merge a single
+//! section while leaving the rest of the file untouched.
+
+use regex::Regex;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+/// A markdown ATX heading (outside code fences)
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkdownHeading {
+    /// Heading level (1-6)
+    pub level: usize,
+    /// Heading text without the leading hashes
+    pub title: String,
+    /// Byte range of the heading line, including its newline
+    pub line: Range<usize>,
+}
+
+/// A heading-delimited section: the heading line plus its body
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkdownSection {
+    pub heading: MarkdownHeading,
+    /// Byte range from the heading line to the start of the next heading of
+    /// the same or higher level (or a closing XML tag / end of document)
+    pub range: Range<usize>,
+}
+
+impl MarkdownSection {
+    /// Section body (everything after the heading line)
+    pub fn body<'a>(&self, content: &'a str) -> &'a str {
+        &content[self.heading.line.end..self.range.end]
+    }
+}
+
+/// A requirement section in a spec
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequirementSection {
+    /// Requirement ID (e.g., "R1", "RT-R2"); None for "Requirement: Title" headings
+    pub id: Option<String>,
+    /// Requirement title
+    pub title: String,
+    pub section: MarkdownSection,
+}
+
+impl RequirementSection {
+    /// Stable key for matching: the ID if present, otherwise the title
+    pub fn key(&self) -> &str {
+        self.id.as_deref().unwrap_or(&self.title)
+    }
+
+    /// Check whether this requirement is identified by `key` (ID or title)
+    pub fn matches(&self, key: &str) -> bool {
+        let key = key.trim();
+        self.id.as_deref().is_some_and(|id| id.eq_ignore_ascii_case(key))
+            || self.title.eq_ignore_ascii_case(key)
+    }
+}
+
+/// A scenario section in a spec
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScenarioSection {
+    /// Scenario name (text after "Scenario:")
+    pub name: String,
+    /// Key of the requirement this scenario is nested under, if any
+    pub requirement: Option<String>,
+    pub section: MarkdownSection,
+}
+
+impl ScenarioSection {
+    /// Check whether this scenario has the given name (case-insensitive)
+    pub fn matches(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name.trim())
+    }
+}
+
+/// Requirements and scenarios found in a spec, with insertion points
+#[derive(Debug, Clone, Default)]
+pub struct SpecOutline {
+    pub requirements: Vec<RequirementSection>,
+    pub scenarios: Vec<ScenarioSection>,
+    /// Offset at the end of the Requirements section (where new requirements go)
+    pub requirements_end: Option<usize>,
+    /// Offset at the end of the Acceptance Criteria section (where new scenarios go)
+    pub scenarios_end: Option<usize>,
+    /// Offset of the closing XML tag (e.g., `</spec>`), or end of document
+    pub body_end: usize,
+}
+
+impl SpecOutline {
+    pub fn find_requirement(&self, key: &str) -> Option<&RequirementSection> {
+        self.requirements.iter().find(|r| r.matches(key))
+    }
+
+    pub fn find_scenario(&self, name: &str) -> Option<&ScenarioSection> {
+        self.scenarios.iter().find(|s| s.matches(name))
+    }
+}
+
+fn requirement_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"^((?:[A-Za-z][A-Za-z0-9]*-)*R\d+)\b\s*(?:[:\-–—.]\s*)?(.*)$").unwrap()
+    })
+}
+
+/// Split a requirement heading into (ID, title)
+///
+/// Supports `R1 - Title`, `RT-R1: Title` and `Requirement: Title` (no ID).
+pub fn parse_requirement_heading(title: &str) -> Option<(Option<String>, String)> {
+    let title = title.trim();
+    if let Some(caps) = requirement_regex().captures(title) {
+        let id = caps.get(1).map(|m| m.as_str().to_string());
+        let text = caps.get(2).map(|m| m.as_str().trim()).unwrap_or("");
+        return Some((id, text.to_string()));
+    }
+    title
+        .strip_prefix("Requirement:")
+        .map(|rest| (None, rest.trim().to_string()))
+}
+
+/// Extract the scenario name from a `Scenario: Name` heading
+pub fn parse_scenario_heading(title: &str) -> Option<String> {
+    title
+        .trim()
+        .strip_prefix("Scenario:")
+        .map(|rest| rest.trim().to_string())
+}
+
+/// Find all ATX headings outside fenced code blocks
+pub fn parse_headings(content: &str) -> Vec<MarkdownHeading> {
+    let mut headings = Vec::new();
+    let mut in_fence = false;
+    let mut offset = 0;
+
+    for line in content.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+
+        let trimmed = line.trim_end();
+        if trimmed.trim_start().starts_with("```") || trimmed.trim_start().starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence || !trimmed.starts_with('#') {
+            continue;
+        }
+
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        let rest = &trimmed[level..];
+        if level > 6 || !(rest.is_empty() || rest.starts_with(' ')) {
+            continue;
+        }
+
+        headings.push(MarkdownHeading {
+            level,
+            title: rest.trim().to_string(),
+            line: start..offset,
+        });
+    }
+
+    headings
+}
+
+/// Byte offsets of closing XML tag lines (e.g., `</spec>`) and opening
+/// wrapper tags (e.g., `<review status="...">`) outside code fences
+fn xml_boundaries(content: &str) -> Vec<usize> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| Regex::new(r"^</?[A-Za-z][\w-]*(\s[^>]*)?>\s*$").unwrap());
+
+    let mut boundaries = Vec::new();
+    let mut in_fence = false;
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        let trimmed = line.trim_end();
+        if trimmed.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if !in_fence && re.is_match(trimmed) {
+            boundaries.push(start);
+        }
+    }
+    boundaries
+}
+
+/// Offset of the closing tag of the document's XML wrapper (e.g., `</spec>`
+/// for `<spec>`), or the end of the document if it has no wrapper
+pub fn wrapper_end(content: &str) -> usize {
+    let boundaries = xml_boundaries(content);
+    let Some(open) = boundaries
+        .iter()
+        .copied()
+        .find(|&b| !content[b..].starts_with("</"))
+    else {
+        return content.len();
+    };
+    let name: String = content[open + 1..]
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == '-')
+        .collect();
+    let closing = format!("</{}>", name);
+    boundaries
+        .into_iter()
+        .find(|&b| b > open && content[b..].starts_with(&closing))
+        .unwrap_or(content.len())
+}
+
+/// Split a document into sections, one per heading
+pub fn parse_sections(content: &str) -> Vec<MarkdownSection> {
+    let headings = parse_headings(content);
+    let boundaries = xml_boundaries(content);
+
+    headings
+        .iter()
+        .enumerate()
+        .map(|(i, heading)| {
+            let next_heading = headings[i + 1..]
+                .iter()
+                .find(|h| h.level <= heading.level)
+                .map(|h| h.line.start);
+            let next_boundary = boundaries
+                .iter()
+                .copied()
+                .find(|&b| b >= heading.line.end);
+            let end = [next_heading, next_boundary]
+                .into_iter()
+                .flatten()
+                .min()
+                .unwrap_or(content.len());
+            MarkdownSection {
+                heading: heading.clone(),
+                range: heading.line.start..end,
+            }
+        })
+        .collect()
+}
+
+/// Parse requirements and scenarios of a spec document
+pub fn parse_spec_outline(content: &str) -> SpecOutline {
+    let sections = parse_sections(content);
+    let mut outline = SpecOutline {
+        body_end: wrapper_end(content),
+        ..Default::default()
+    };
+
+    let mut requirements_section: Option<&MarkdownSection> = None;
+
+    for section in &sections {
+        let heading = &section.heading;
+
+        if heading.level <= 2 {
+            let lower = heading.title.to_lowercase();
+            requirements_section = None;
+            if lower.contains("requirements") {
+                requirements_section = Some(section);
+                outline.requirements_end = Some(section.range.end);
+            } else if lower.contains("acceptance criteria") {
+                outline.scenarios_end = Some(section.range.end);
+            }
+            continue;
+        }
+
+        if let Some(name) = parse_scenario_heading(&heading.title) {
+            let requirement = outline
+                .requirements
+                .iter()
+                .rev()
+                .find(|r| r.section.range.contains(&section.range.start))
+                .map(|r| r.key().to_string());
+            outline.scenarios.push(ScenarioSection {
+                name,
+                requirement,
+                section: section.clone(),
+            });
+            continue;
+        }
+
+        let in_requirements = requirements_section
+            .is_some_and(|r| r.range.contains(&section.range.start));
+        if in_requirements {
+            if let Some((id, title)) = parse_requirement_heading(&heading.title) {
+                outline.requirements.push(RequirementSection {
+                    id,
+                    title,
+                    section: section.clone(),
+                });
+            }
+        }
+    }
+
+    outline
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r#"---
+id: auth
+---
+
+<spec>
+
+# Auth
+
+## Overview
+
+Overview text.
+
+## Requirements
+
+### R1 - Login
+
+```yaml
+id: R1
+priority: high
+```
+
+Users can log in.
+
+### R2 - Logout
+
+Users can log out.
+
+## Acceptance Criteria
+
+### Scenario: Valid login
+
+- **WHEN** valid credentials
+- **THEN** session created
+
+### Scenario: Logout
+
+- **WHEN** user logs out
+- **THEN** session destroyed
+
+</spec>
+"#;
+
+    #[test]
+    fn test_parse_headings_skips_code_fences() {
+        let content = "# Title\n\n```\n# not a heading\n```\n\n## Real\n";
+        let headings = parse_headings(content);
+        assert_eq!(headings.len(), 2);
+        assert_eq!(headings[1].title, "Real");
+        assert_eq!(headings[1].level, 2);
+    }
+
+    #[test]
+    fn test_parse_spec_outline() {
+        let outline = parse_spec_outline(SPEC);
+
+        assert_eq!(outline.requirements.len(), 2);
+        assert_eq!(outline.requirements[0].id.as_deref(), Some("R1"));
+        assert_eq!(outline.requirements[0].title, "Login");
+        assert!(outline.requirements[0]
+            .section
+            .body(SPEC)
+            .contains("Users can log in."));
+        assert!(!outline.requirements[0]
+            .section
+            .body(SPEC)
+            .contains("Logout"));
+
+        assert_eq!(outline.scenarios.len(), 2);
+        assert_eq!(outline.scenarios[1].name, "Logout");
+        assert!(!outline.scenarios[1].section.body(SPEC).contains("</spec>"));
+
+        let req_end = outline.requirements_end.unwrap();
+        assert!(SPEC[req_end..].starts_with("## Acceptance Criteria"));
+        assert!(SPEC[outline.body_end..].starts_with("</spec>"));
+        assert_eq!(outline.scenarios_end, Some(outline.body_end));
+    }
+
+    #[test]
+    fn test_parse_requirement_heading_variants() {
+        assert_eq!(
+            parse_requirement_heading("RT-R1: Structured Review"),
+            Some((Some("RT-R1".to_string()), "Structured Review".to_string()))
+        );
+        assert_eq!(
+            parse_requirement_heading("R12 - Title"),
+            Some((Some("R12".to_string()), "Title".to_string()))
+        );
+        assert_eq!(
+            parse_requirement_heading("Requirement: User Login"),
+            Some((None, "User Login".to_string()))
+        );
+        assert_eq!(parse_requirement_heading("Notes"), None);
+    }
+
+    #[test]
+    fn test_nested_scenarios() {
+        let content = "## Requirements\n\n### Requirement: Login\n\nText.\n\n#### Scenario: Ok\n\n- **WHEN** a\n- **THEN** b\n\n### Requirement: Logout\n\nText.\n";
+        let outline = parse_spec_outline(content);
+        assert_eq!(outline.requirements.len(), 2);
+        assert_eq!(outline.scenarios.len(), 1);
+        assert_eq!(outline.scenarios[0].requirement.as_deref(), Some("Login"));
+    }
+}
@@ -0,0 +1,422 @@
+//! Code service - Read-only code search for challenge and review stages
+//!
+//! Lets reviewers verify proposal claims against the codebase. All lookups
+//! are scoped to the project root and respect .gitignore.
+
+use crate::fillback::{AstAnalyzer, SupportedLanguage, Symbol, SymbolKind};
+use crate::Result;
+use ignore::overrides::OverrideBuilder;
+use ignore::WalkBuilder;
+use regex::RegexBuilder;
+use std::path::{Component, Path, PathBuf};
+
+/// Default number of matches returned by a search
+pub const DEFAULT_MAX_RESULTS: usize = 50;
+
+/// Upper bound on matches returned by a search
+const MAX_RESULTS_LIMIT: usize = 500;
+
+/// Files larger than this are skipped
+const MAX_FILE_SIZE: u64 = 1_000_000;
+
+/// Longest line excerpt included in regex results
+const MAX_LINE_LENGTH: usize = 200;
+
+/// How `search_code` interprets its query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchMode {
+    /// Regular expression matched against each line
+    Regex,
+    /// Substring matched against symbol names extracted by the AST analyzer
+    Symbol,
+}
+
+impl std::str::FromStr for SearchMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "regex" => Ok(Self::Regex),
+            "symbol" => Ok(Self::Symbol),
+            _ => anyhow::bail!("mode must be 'regex' or 'symbol', got '{}'", s),
+        }
+    }
+}
+
+/// Input structure for searching code
+#[derive(Debug, Clone)]
+pub struct SearchCodeInput {
+    pub query: String,
+    pub mode: SearchMode,
+    /// Sub-directory or file to search, relative to the project root
+    pub path: Option<String>,
+    /// Glob restricting which files are searched (e.g., "*.rs")
+    pub glob: Option<String>,
+    pub case_sensitive: bool,
+    pub max_results: usize,
+}
+
+/// A symbol found in the codebase
+#[derive(Debug, Clone)]
+pub struct SymbolMatch {
+    pub file: String,
+    pub symbol: Symbol,
+    /// Declared signature, or the declaration line when the analyzer has none
+    pub signature: String,
+}
+
+/// Search the codebase by regex or symbol name
+pub fn search_code(input: SearchCodeInput, project_root: &Path) -> Result<String> {
+    let scope = resolve_scope(project_root, input.path.as_deref())?;
+    let max_results = input.max_results.clamp(1, MAX_RESULTS_LIMIT);
+    let files = collect_files(project_root, &scope, input.glob.as_deref())?;
+
+    let mut lines = Vec::new();
+    let mut truncated = false;
+
+    match input.mode {
+        SearchMode::Regex => {
+            let regex = RegexBuilder::new(&input.query)
+                .case_insensitive(!input.case_sensitive)
+                .build()
+                .map_err(|e| anyhow::anyhow!("Invalid regex '{}': {}", input.query, e))?;
+
+            'files: for (relative, path) in &files {
+                let Ok(content) = std::fs::read_to_string(path) else {
+                    continue; // binary or non-UTF-8 file
+                };
+                for (index, line) in content.lines().enumerate() {
+                    if !regex.is_match(line) {
+                        continue;
+                    }
+                    if lines.len() == max_results {
+                        truncated = true;
+                        break 'files;
+                    }
+                    lines.push(format!("{}:{}: {}", relative, index + 1, excerpt(line)));
+                }
+            }
+        }
+        SearchMode::Symbol => {
+            let query = if input.case_sensitive {
+                input.query.clone()
+            } else {
+                input.query.to_lowercase()
+            };
+            let matches = find_symbols(&files, |symbol| {
+                if input.case_sensitive {
+                    symbol.name.contains(&query)
+                } else {
+                    symbol.name.to_lowercase().contains(&query)
+                }
+            })?;
+            truncated = matches.len() > max_results;
+            for m in matches.into_iter().take(max_results) {
+                lines.push(format!(
+                    "{}:{}: {} {}",
+                    m.file, m.symbol.line, m.symbol.kind, m.signature
+                ));
+            }
+        }
+    }
+
+    let mut output = format!(
+        "# Code search: `{}` ({} match{})\n\n",
+        input.query,
+        lines.len(),
+        if lines.len() == 1 { "" } else { "es" }
+    );
+    if lines.is_empty() {
+        output.push_str("No matches found.\n");
+        return Ok(output);
+    }
+    output.push_str("```\n");
+    for line in &lines {
+        output.push_str(line);
+        output.push('\n');
+    }
+    output.push_str("```\n");
+    if truncated {
+        output.push_str(&format!(
+            "\nResults truncated at {}. Narrow the query, path or glob to see more.\n",
+            max_results
+        ));
+    }
+    Ok(output)
+}
+
+/// Look up a symbol definition by exact name
+///
+/// Falls back to a case-insensitive match if there is no exact match.
+pub fn find_symbol(
+    name: &str,
+    kind: Option<&str>,
+    path: Option<&str>,
+    project_root: &Path,
+) -> Result<Vec<SymbolMatch>> {
+    let scope = resolve_scope(project_root, path)?;
+    let files: Vec<(String, PathBuf)> = collect_files(project_root, &scope, None)?
+        .into_iter()
+        .filter(|(_, p)| {
+            p.extension()
+                .and_then(|e| e.to_str())
+                .and_then(SupportedLanguage::from_extension)
+                .is_some()
+        })
+        .collect();
+
+    let kind_matches = |symbol: &Symbol| kind.is_none_or(|k| symbol.kind.to_string() == k);
+
+    let exact = find_symbols(&files, |s| s.name == name && kind_matches(s))?;
+    if !exact.is_empty() {
+        return Ok(exact);
+    }
+    find_symbols(&files, |s| s.name.eq_ignore_ascii_case(name) && kind_matches(s))
+}
+
+/// Render symbol lookup results for the get_symbol tool
+pub fn get_symbol(
+    name: &str,
+    kind: Option<&str>,
+    path: Option<&str>,
+    project_root: &Path,
+) -> Result<String> {
+    if let Some(kind) = kind {
+        if !SYMBOL_KINDS.contains(&kind) {
+            anyhow::bail!("kind must be one of: {}", SYMBOL_KINDS.join(", "));
+        }
+    }
+
+    let matches = find_symbol(name, kind, path, project_root)?;
+    if matches.is_empty() {
+        return Ok(format!("Symbol '{}' not found.\n", name));
+    }
+
+    let mut output = format!(
+        "# Symbol: {} ({} definition{})\n\n",
+        name,
+        matches.len(),
+        if matches.len() == 1 { "" } else { "s" }
+    );
+    for m in &matches {
+        output.push_str(&format!("## {}:{}\n\n", m.file, m.symbol.line));
+        output.push_str(&format!("- **Kind**: {}\n", m.symbol.kind));
+        output.push_str(&format!(
+            "- **Visibility**: {}\n",
+            if m.symbol.is_public { "public" } else { "private" }
+        ));
+        output.push_str(&format!("- **Signature**: `{}`\n", m.signature));
+        if let Some(doc) = &m.symbol.doc {
+            output.push_str(&format!("- **Doc**: {}\n", doc.trim()));
+        }
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+/// Symbol kinds accepted by get_symbol
+pub const SYMBOL_KINDS: &[&str] = &[
+    "function",
+    "struct",
+    "enum",
+    "interface",
+    "class",
+    "module",
+    "constant",
+    "type",
+];
+
+/// Resolve an optional relative path to a directory or file inside the project
+fn resolve_scope(project_root: &Path, path: Option<&str>) -> Result<PathBuf> {
+    let Some(path) = path.filter(|p| !p.trim().is_empty()) else {
+        return Ok(project_root.to_path_buf());
+    };
+
+    let relative = Path::new(path.trim());
+    if relative
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        anyhow::bail!(
+            "Invalid path '{}': must be relative to the project root without '..'",
+            path
+        );
+    }
+
+    let scope = project_root.join(relative);
+    if !scope.exists() {
+        anyhow::bail!("Path '{}' not found in project", path);
+    }
+
+    // Reject symlinks that escape the project root
+    let root = project_root.canonicalize()?;
+    if !scope.canonicalize()?.starts_with(&root) {
+        anyhow::bail!("Path '{}' is outside the project root", path);
+    }
+    Ok(scope)
+}
+
+/// Walk the scope respecting .gitignore, returning (relative path, path) pairs
+pub(crate) fn collect_files(
+    project_root: &Path,
+    scope: &Path,
+    glob: Option<&str>,
+) -> Result<Vec<(String, PathBuf)>> {
+    let mut builder = WalkBuilder::new(scope);
+    builder.standard_filters(true);
+    if let Some(glob) = glob {
+        let overrides = OverrideBuilder::new(scope).add(glob)?.build()?;
+        builder.overrides(overrides);
+    }
+
+    let mut files = Vec::new();
+    for entry in builder.build() {
+        let entry = entry?;
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        if entry.metadata().map(|m| m.len() > MAX_FILE_SIZE).unwrap_or(true) {
+            continue;
+        }
+        let relative = path
+            .strip_prefix(project_root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        files.push((relative, path.to_path_buf()));
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Parse supported files and collect symbols accepted by the filter
+fn find_symbols(
+    files: &[(String, PathBuf)],
+    filter: impl Fn(&Symbol) -> bool,
+) -> Result<Vec<SymbolMatch>> {
+    let mut analyzer = AstAnalyzer::new()?;
+    let mut matches = Vec::new();
+
+    for (relative, path) in files {
+        let supported = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .and_then(SupportedLanguage::from_extension)
+            .is_some();
+        if !supported {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        let Ok(module) = analyzer.parse_file(path, &content) else {
+            continue;
+        };
+
+        for symbol in module.symbols.into_iter().filter(|s| filter(s)) {
+            let signature = symbol.signature.clone().unwrap_or_else(|| {
+                content
+                    .lines()
+                    .nth(symbol.line.saturating_sub(1))
+                    .map(|l| excerpt(l.trim().trim_end_matches('{').trim_end()))
+                    .unwrap_or_else(|| symbol.name.clone())
+            });
+            matches.push(SymbolMatch {
+                file: relative.clone(),
+                symbol,
+                signature,
+            });
+        }
+    }
+
+    matches.sort_by_key(|m| (m.symbol.kind != SymbolKind::Function, m.file.clone(), m.symbol.line));
+    Ok(matches)
+}
+
+/// Trim a line for display
+fn excerpt(line: &str) -> String {
+    let line = line.trim_end();
+    if line.chars().count() <= MAX_LINE_LENGTH {
+        return line.to_string();
+    }
+    let cut: String = line.chars().take(MAX_LINE_LENGTH).collect();
+    format!("{}…", cut)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup_project() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("target")).unwrap();
+        std::fs::write(
+            root.join("src/auth.rs"),
+            "/// Validate a session token\npub fn validate_token(token: &str) -> bool {\n    !token.is_empty()\n}\n\npub struct SessionStore {\n    ttl: u64,\n}\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("target/generated.rs"), "pub fn validate_token() {}\n").unwrap();
+        std::fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        // The ignore crate only applies .gitignore inside a git repository
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        temp_dir
+    }
+
+    fn input(query: &str, mode: SearchMode) -> SearchCodeInput {
+        SearchCodeInput {
+            query: query.to_string(),
+            mode,
+            path: None,
+            glob: None,
+            case_sensitive: false,
+            max_results: DEFAULT_MAX_RESULTS,
+        }
+    }
+
+    #[test]
+    fn test_search_code_regex_respects_gitignore() {
+        let temp_dir = setup_project();
+        let result = search_code(input(r"fn validate_\w+", SearchMode::Regex), temp_dir.path()).unwrap();
+        assert!(result.contains("src/auth.rs:2: pub fn validate_token"));
+        assert!(!result.contains("target/generated.rs"));
+        assert!(result.contains("(1 match)"));
+    }
+
+    #[test]
+    fn test_search_code_symbol() {
+        let temp_dir = setup_project();
+        let result = search_code(input("session", SearchMode::Symbol), temp_dir.path()).unwrap();
+        assert!(result.contains("src/auth.rs:6: struct"));
+        assert!(result.contains("SessionStore"));
+    }
+
+    #[test]
+    fn test_get_symbol() {
+        let temp_dir = setup_project();
+        let matches = find_symbol("validate_token", None, None, temp_dir.path()).unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].file, "src/auth.rs");
+        assert_eq!(matches[0].symbol.line, 2);
+        assert!(matches[0].signature.contains("(token: &str) -> bool"));
+
+        let output = get_symbol("SessionStore", Some("struct"), None, temp_dir.path()).unwrap();
+        assert!(output.contains("src/auth.rs:6"));
+        assert!(output.contains("pub struct SessionStore"));
+    }
+
+    #[test]
+    fn test_scope_rejects_escape() {
+        let temp_dir = setup_project();
+        let mut escaped = input("fn", SearchMode::Regex);
+        escaped.path = Some("../".to_string());
+        assert!(search_code(escaped, temp_dir.path()).is_err());
+
+        let mut absolute = input("fn", SearchMode::Regex);
+        absolute.path = Some("/etc".to_string());
+        assert!(search_code(absolute, temp_dir.path()).is_err());
+    }
+}
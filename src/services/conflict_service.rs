@@ -0,0 +1,408 @@
+//! Cross-change conflict detection
+//!
+//! Active changes under `agentd/changes` are planned independently, so two of
+//! them can rewrite the same requirement or edit the same source file. This
+//! cross-references their affected specs, requirement edits and task files,
+//! reports each overlap with a severity, and suggests a merge order.
+
+use crate::models::{TaskAction, TaskGraph};
+use crate::parser::parse_affected_specs;
+use crate::services::spec_diff_service::{self, ItemChange, ItemKind};
+use crate::state::StateManager;
+use crate::Result;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// How serious an overlap between two changes is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictSeverity {
+    /// Both changes touch the same spec (different requirements)
+    Low,
+    /// Both changes modify the same source file
+    Medium,
+    /// Both changes rewrite the same requirement, add the same requirement
+    /// ID, or create/delete the same file
+    High,
+}
+
+impl ConflictSeverity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConflictSeverity::High => "HIGH",
+            ConflictSeverity::Medium => "MEDIUM",
+            ConflictSeverity::Low => "LOW",
+        }
+    }
+}
+
+/// What the two changes overlap on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum ConflictSubject {
+    Spec { spec: String },
+    Requirement { spec: String, requirement: String },
+    File { path: String },
+}
+
+impl ConflictSubject {
+    pub fn describe(&self) -> String {
+        match self {
+            ConflictSubject::Spec { spec } => format!("spec `{}`", spec),
+            ConflictSubject::Requirement { spec, requirement } => {
+                format!("requirement `{}` in spec `{}`", requirement, spec)
+            }
+            ConflictSubject::File { path } => format!("file `{}`", path),
+        }
+    }
+}
+
+/// An overlap between two active changes
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangeConflict {
+    pub severity: ConflictSeverity,
+    pub changes: [String; 2],
+    pub subject: ConflictSubject,
+    pub detail: String,
+}
+
+/// Conflicts among all active changes, with a suggested merge order
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConflictReport {
+    pub changes: Vec<String>,
+    pub conflicts: Vec<ChangeConflict>,
+    /// Conflicting changes, the one to merge first first
+    pub suggested_order: Vec<String>,
+}
+
+impl ConflictReport {
+    /// Conflicts involving one change
+    pub fn involving(&self, change_id: &str) -> Vec<&ChangeConflict> {
+        self.conflicts
+            .iter()
+            .filter(|c| c.changes.iter().any(|id| id == change_id))
+            .collect()
+    }
+
+    pub fn has_high(&self) -> bool {
+        self.conflicts
+            .iter()
+            .any(|c| c.severity == ConflictSeverity::High)
+    }
+
+    /// Render the report as markdown
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "# Change Conflicts\n\n{} active change(s), {} conflict(s).\n\n",
+            self.changes.len(),
+            self.conflicts.len()
+        );
+        if self.conflicts.is_empty() {
+            out.push_str("No overlaps between active changes.\n");
+            return out;
+        }
+        out.push_str("| Severity | Changes | Overlap | Detail |\n");
+        out.push_str("|----------|---------|---------|--------|\n");
+        for conflict in &self.conflicts {
+            out.push_str(&format!(
+                "| {} | {} ↔ {} | {} | {} |\n",
+                conflict.severity.label(),
+                conflict.changes[0],
+                conflict.changes[1],
+                conflict.subject.describe(),
+                conflict.detail
+            ));
+        }
+        if !self.suggested_order.is_empty() {
+            out.push_str(&format!(
+                "\n**Suggested merge order**: {}\n\nChanges further along merge first; rebase the others onto the updated specs afterwards.\n",
+                self.suggested_order.join(" → ")
+            ));
+        }
+        out
+    }
+}
+
+/// What one change touches
+#[derive(Debug, Default)]
+struct ChangeFootprint {
+    id: String,
+    phase_rank: u8,
+    specs: BTreeSet<String>,
+    /// (spec, requirement key) of existing requirements modified/renamed/removed
+    edited_requirements: BTreeSet<(String, String)>,
+    /// (spec, requirement key) of requirements the change adds
+    added_requirements: BTreeSet<(String, String)>,
+    /// Planned file -> action (first task wins)
+    files: BTreeMap<String, TaskAction>,
+}
+
+/// Detect conflicts among all active changes
+pub fn detect_conflicts(project_root: &Path) -> Result<ConflictReport> {
+    let changes_dir = project_root.join("agentd/changes");
+    let mut footprints = Vec::new();
+    if changes_dir.exists() {
+        let mut ids: Vec<String> = std::fs::read_dir(&changes_dir)?
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_dir())
+            .filter_map(|e| e.file_name().to_str().map(String::from))
+            .filter(|name| !name.starts_with('.'))
+            .collect();
+        ids.sort();
+        for id in ids {
+            footprints.push(footprint(&id, project_root)?);
+        }
+    }
+
+    let mut report = ConflictReport {
+        changes: footprints.iter().map(|f| f.id.clone()).collect(),
+        ..Default::default()
+    };
+    for (i, a) in footprints.iter().enumerate() {
+        for b in &footprints[i + 1..] {
+            compare(a, b, &mut report.conflicts);
+        }
+    }
+    report
+        .conflicts
+        .sort_by(|x, y| y.severity.cmp(&x.severity).then(x.changes.cmp(&y.changes)));
+    report.suggested_order = suggest_order(&footprints, &report.conflicts);
+    Ok(report)
+}
+
+fn footprint(change_id: &str, project_root: &Path) -> Result<ChangeFootprint> {
+    let change_dir = project_root.join("agentd/changes").join(change_id);
+    let mut footprint = ChangeFootprint {
+        id: change_id.to_string(),
+        phase_rank: phase_rank(&change_dir),
+        ..Default::default()
+    };
+
+    let proposal_path = change_dir.join("proposal.md");
+    if proposal_path.exists() {
+        let proposal = std::fs::read_to_string(&proposal_path)?;
+        for spec in parse_affected_specs(&proposal).unwrap_or_default() {
+            footprint.specs.insert(spec.id);
+        }
+    }
+
+    if change_dir.join("specs").exists() {
+        for diff in spec_diff_service::diff_change(change_id, None, project_root)? {
+            let spec = diff.spec.trim_end_matches(".md").to_string();
+            for item in diff
+                .items
+                .iter()
+                .filter(|i| i.kind == ItemKind::Requirement)
+            {
+                let key = (spec.clone(), item.key.clone());
+                if item.change == ItemChange::Added {
+                    footprint.added_requirements.insert(key);
+                } else {
+                    footprint.edited_requirements.insert(key);
+                }
+            }
+            footprint.specs.insert(spec);
+        }
+    }
+
+    let tasks_path = change_dir.join("tasks.md");
+    if tasks_path.exists() {
+        let tasks =
+            TaskGraph::parse_tasks(&std::fs::read_to_string(&tasks_path)?).unwrap_or_default();
+        for task in tasks {
+            let path = task.file.trim().trim_start_matches("./").to_string();
+            if !path.is_empty() {
+                footprint.files.entry(path).or_insert(task.action);
+            }
+        }
+    }
+
+    Ok(footprint)
+}
+
+fn compare(a: &ChangeFootprint, b: &ChangeFootprint, conflicts: &mut Vec<ChangeConflict>) {
+    let pair = [a.id.clone(), b.id.clone()];
+    let mut push = |severity, subject, detail: String| {
+        conflicts.push(ChangeConflict {
+            severity,
+            changes: pair.clone(),
+            subject,
+            detail,
+        })
+    };
+
+    let mut specs_with_requirement_conflicts = BTreeSet::new();
+    for key in a.edited_requirements.intersection(&b.edited_requirements) {
+        specs_with_requirement_conflicts.insert(key.0.clone());
+        push(
+            ConflictSeverity::High,
+            ConflictSubject::Requirement {
+                spec: key.0.clone(),
+                requirement: key.1.clone(),
+            },
+            "both changes rewrite this requirement".to_string(),
+        );
+    }
+    for key in a.added_requirements.intersection(&b.added_requirements) {
+        specs_with_requirement_conflicts.insert(key.0.clone());
+        push(
+            ConflictSeverity::High,
+            ConflictSubject::Requirement {
+                spec: key.0.clone(),
+                requirement: key.1.clone(),
+            },
+            "both changes add a requirement with this ID".to_string(),
+        );
+    }
+    for spec in a.specs.intersection(&b.specs) {
+        if !specs_with_requirement_conflicts.contains(spec) {
+            push(
+                ConflictSeverity::Low,
+                ConflictSubject::Spec { spec: spec.clone() },
+                "both changes update this spec (different requirements)".to_string(),
+            );
+        }
+    }
+
+    for (path, action_a) in &a.files {
+        let Some(action_b) = b.files.get(path) else {
+            continue;
+        };
+        let severity = match (action_a, action_b) {
+            (TaskAction::Modify, TaskAction::Modify) => ConflictSeverity::Medium,
+            _ => ConflictSeverity::High,
+        };
+        push(
+            severity,
+            ConflictSubject::File { path: path.clone() },
+            format!(
+                "{} plans {}, {} plans {}",
+                a.id,
+                action_a.as_str(),
+                b.id,
+                action_b.as_str()
+            ),
+        );
+    }
+}
+
+/// Order conflicting changes: further along in the workflow first, then by ID
+fn suggest_order(footprints: &[ChangeFootprint], conflicts: &[ChangeConflict]) -> Vec<String> {
+    let involved: BTreeSet<&str> = conflicts
+        .iter()
+        .flat_map(|c| c.changes.iter().map(String::as_str))
+        .collect();
+    let mut ordered: Vec<&ChangeFootprint> = footprints
+        .iter()
+        .filter(|f| involved.contains(f.id.as_str()))
+        .collect();
+    ordered.sort_by(|x, y| y.phase_rank.cmp(&x.phase_rank).then(x.id.cmp(&y.id)));
+    ordered.into_iter().map(|f| f.id.clone()).collect()
+}
+
+/// How far a change is in the workflow (higher is further)
+fn phase_rank(change_dir: &Path) -> u8 {
+    if !change_dir.join("STATE.yaml").exists() {
+        return 0;
+    }
+    StateManager::load(change_dir)
+        .map(|state| state.phase().progress())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::StatePhase;
+    use tempfile::TempDir;
+
+    const MAIN_SPEC: &str = "# Auth\n\n## Requirements\n\n### R1 - Login\n\nPassword login.\n\n### R2 - Logout\n\nLog out.\n";
+
+    fn write_change(root: &Path, id: &str, spec: &str, tasks: &[(&str, &str)]) {
+        let dir = root.join("agentd/changes").join(id);
+        std::fs::create_dir_all(dir.join("specs")).unwrap();
+        std::fs::write(dir.join("specs/auth.md"), spec).unwrap();
+        let mut content = String::from("# Tasks\n\n## 1. Layer\n\n");
+        for (i, (action, file)) in tasks.iter().enumerate() {
+            content.push_str(&format!(
+                "- [ ] 1.{} Task\n  - File: `{}` ({})\n  - Spec: `auth:R1`\n  - Do: Work.\n  - Depends: none\n\n",
+                i + 1,
+                file,
+                action
+            ));
+        }
+        std::fs::write(dir.join("tasks.md"), content).unwrap();
+    }
+
+    #[test]
+    fn test_detect_conflicts() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("agentd/specs")).unwrap();
+        std::fs::write(root.join("agentd/specs/auth.md"), MAIN_SPEC).unwrap();
+
+        write_change(
+            root,
+            "add-oauth",
+            "## Requirements\n\n### R1 - Login\n\nPassword or OAuth login.\n\n### R3 - OAuth\n\nGoogle.\n",
+            &[("MODIFY", "src/auth.rs"), ("CREATE", "src/oauth.rs")],
+        );
+        write_change(
+            root,
+            "add-mfa",
+            "## Requirements\n\n### R1 - Login\n\nPassword login with MFA.\n\n### R3 - MFA\n\nTOTP.\n",
+            &[("MODIFY", "src/auth.rs"), ("CREATE", "src/oauth.rs")],
+        );
+        write_change(
+            root,
+            "fix-logout",
+            "## Requirements\n\n### R2 - Logout\n\nLog out everywhere.\n",
+            &[("MODIFY", "src/session.rs")],
+        );
+        let mut state = StateManager::load(root.join("agentd/changes/add-mfa")).unwrap();
+        state.set_phase(StatePhase::Implementing);
+        state.save().unwrap();
+
+        let report = detect_conflicts(root).unwrap();
+        let between = |a: &str, b: &str| -> Vec<(ConflictSeverity, String)> {
+            report
+                .conflicts
+                .iter()
+                .filter(|c| {
+                    c.changes.contains(&a.to_string()) && c.changes.contains(&b.to_string())
+                })
+                .map(|c| (c.severity, c.subject.describe()))
+                .collect()
+        };
+
+        let oauth_mfa = between("add-oauth", "add-mfa");
+        assert!(oauth_mfa.contains(&(
+            ConflictSeverity::High,
+            "requirement `R1` in spec `auth`".to_string()
+        )));
+        assert!(oauth_mfa.contains(&(
+            ConflictSeverity::High,
+            "requirement `R3` in spec `auth`".to_string()
+        )));
+        assert!(oauth_mfa.contains(&(ConflictSeverity::Medium, "file `src/auth.rs`".to_string())));
+        assert!(oauth_mfa.contains(&(ConflictSeverity::High, "file `src/oauth.rs`".to_string())));
+        assert!(!oauth_mfa.iter().any(|(_, s)| s == "spec `auth`"));
+
+        assert_eq!(
+            between("add-oauth", "fix-logout"),
+            vec![(ConflictSeverity::Low, "spec `auth`".to_string())]
+        );
+
+        assert!(report.has_high());
+        assert_eq!(report.conflicts[0].severity, ConflictSeverity::High);
+        assert_eq!(
+            report.suggested_order,
+            vec!["add-mfa", "add-oauth", "fix-logout"]
+        );
+        assert_eq!(report.involving("fix-logout").len(), 2);
+        assert!(report
+            .to_markdown()
+            .contains("add-mfa → add-oauth → fix-logout"));
+    }
+}
@@ -0,0 +1,455 @@
+//! Inter-change dependencies
+//!
+//! A proposal can list prerequisite changes in `depends_on_changes`. This
+//! resolves each prerequisite's current phase (archived changes count as
+//! `archived`), gates impl-change and merge-change on them, and builds the
+//! dependency graph shown by `agentd list` and the dashboard.
+
+use crate::models::{ChangeDependency, StatePhase};
+use crate::parser::parse_change_dependencies;
+use crate::state::StateManager;
+use crate::Result;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Workflow step that requires prerequisites to be far enough along
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependencyGate {
+    /// impl-change: each prerequisite must reach its declared phase
+    Implement,
+    /// merge-change: each prerequisite must be archived
+    Merge,
+}
+
+impl DependencyGate {
+    fn command(&self) -> &'static str {
+        match self {
+            DependencyGate::Implement => "impl-change",
+            DependencyGate::Merge => "merge-change",
+        }
+    }
+
+    fn required_phase(&self, dependency: &ChangeDependency) -> StatePhase {
+        match self {
+            DependencyGate::Implement => dependency.phase.clone(),
+            DependencyGate::Merge => StatePhase::Archived,
+        }
+    }
+}
+
+/// A prerequisite and how far it has progressed
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyStatus {
+    pub id: String,
+    /// Phase required before impl-change
+    pub required: StatePhase,
+    /// Current phase, or None if the change does not exist
+    pub current: Option<StatePhase>,
+}
+
+impl DependencyStatus {
+    /// Whether the prerequisite has reached `phase`
+    pub fn reached(&self, phase: &StatePhase) -> bool {
+        self.current
+            .as_ref()
+            .is_some_and(|current| current.progress() >= phase.progress())
+    }
+
+    /// Whether the prerequisite satisfies the impl-change requirement
+    pub fn is_satisfied(&self) -> bool {
+        self.reached(&self.required)
+    }
+
+    fn describe(&self, required: &StatePhase) -> String {
+        match &self.current {
+            Some(current) => format!(
+                "{}: needs {}, currently {}",
+                self.id,
+                required.as_str(),
+                current.as_str()
+            ),
+            None => format!("{}: change not found", self.id),
+        }
+    }
+}
+
+/// Current phase of a change: from STATE.yaml for active changes, `archived`
+/// for changes under agentd/archive, None if it does not exist
+pub fn change_phase(change_id: &str, project_root: &Path) -> Option<StatePhase> {
+    let change_dir = project_root.join("agentd/changes").join(change_id);
+    if change_dir.is_dir() {
+        return Some(
+            StateManager::load(&change_dir)
+                .map(|state| state.phase().clone())
+                .unwrap_or_default(),
+        );
+    }
+
+    let archive_dir = project_root.join("agentd/archive");
+    let suffix = format!("-{}", change_id);
+    let archived = std::fs::read_dir(archive_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .any(|entry| {
+            entry.file_name().to_string_lossy().ends_with(&suffix)
+                && entry.path().join(change_id).is_dir()
+        });
+    archived.then_some(StatePhase::Archived)
+}
+
+/// Prerequisites declared in an active change's proposal
+pub fn dependencies_of(change_id: &str, project_root: &Path) -> Result<Vec<ChangeDependency>> {
+    let proposal_path = project_root
+        .join("agentd/changes")
+        .join(change_id)
+        .join("proposal.md");
+    if !proposal_path.exists() {
+        return Ok(Vec::new());
+    }
+    parse_change_dependencies(&std::fs::read_to_string(&proposal_path)?)
+}
+
+/// Resolve the current phase of each prerequisite of a change
+pub fn dependency_status(change_id: &str, project_root: &Path) -> Result<Vec<DependencyStatus>> {
+    Ok(dependencies_of(change_id, project_root)?
+        .into_iter()
+        .map(|dependency| DependencyStatus {
+            current: change_phase(&dependency.id, project_root),
+            id: dependency.id,
+            required: dependency.phase,
+        })
+        .collect())
+}
+
+/// Fail unless every prerequisite of `change_id` is far enough along for `gate`
+pub fn ensure_ready(change_id: &str, gate: DependencyGate, project_root: &Path) -> Result<()> {
+    let unmet: Vec<String> = dependencies_of(change_id, project_root)?
+        .iter()
+        .filter_map(|dependency| {
+            let status = DependencyStatus {
+                id: dependency.id.clone(),
+                required: dependency.phase.clone(),
+                current: change_phase(&dependency.id, project_root),
+            };
+            let required = gate.required_phase(dependency);
+            (!status.reached(&required)).then(|| status.describe(&required))
+        })
+        .collect();
+
+    if !unmet.is_empty() {
+        anyhow::bail!(
+            "Cannot run {} for '{}': prerequisite changes are not ready\n  - {}",
+            gate.command(),
+            change_id,
+            unmet.join("\n  - ")
+        );
+    }
+    Ok(())
+}
+
+/// Problems with a change's declared prerequisites: self references,
+/// unknown changes and cycles among active changes
+pub fn validate_dependencies(change_id: &str, project_root: &Path) -> Result<Vec<String>> {
+    let mut errors = Vec::new();
+    for dependency in dependencies_of(change_id, project_root)? {
+        if dependency.id == change_id {
+            errors.push(format!("'{}' depends on itself", change_id));
+        } else if change_phase(&dependency.id, project_root).is_none() {
+            errors.push(format!(
+                "depends_on_changes references unknown change '{}'",
+                dependency.id
+            ));
+        }
+    }
+
+    let graph = dependency_graph(project_root)?;
+    for cycle in graph.cycles() {
+        if cycle.iter().any(|id| id == change_id) {
+            errors.push(format!("Dependency cycle: {}", cycle.join(" → ")));
+        }
+    }
+    Ok(errors)
+}
+
+/// An active change in the dependency graph
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphNode {
+    pub id: String,
+    pub phase: StatePhase,
+    pub depends_on: Vec<DependencyStatus>,
+}
+
+impl GraphNode {
+    /// Prerequisites not yet far enough along for impl-change
+    pub fn blocked_by(&self) -> Vec<&str> {
+        self.depends_on
+            .iter()
+            .filter(|d| !d.is_satisfied())
+            .map(|d| d.id.as_str())
+            .collect()
+    }
+}
+
+/// Dependencies among active changes
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DependencyGraph {
+    pub changes: Vec<GraphNode>,
+}
+
+/// Build the dependency graph of all active changes
+pub fn dependency_graph(project_root: &Path) -> Result<DependencyGraph> {
+    let changes_dir = project_root.join("agentd/changes");
+    let mut ids: Vec<String> = std::fs::read_dir(&changes_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| e.file_name().to_str().map(String::from))
+        .filter(|name| !name.starts_with('.'))
+        .collect();
+    ids.sort();
+
+    let mut changes = Vec::new();
+    for id in ids {
+        changes.push(GraphNode {
+            phase: change_phase(&id, project_root).unwrap_or_default(),
+            depends_on: dependency_status(&id, project_root).unwrap_or_default(),
+            id,
+        });
+    }
+    Ok(DependencyGraph { changes })
+}
+
+impl DependencyGraph {
+    pub fn has_edges(&self) -> bool {
+        self.changes.iter().any(|c| !c.depends_on.is_empty())
+    }
+
+    pub fn get(&self, change_id: &str) -> Option<&GraphNode> {
+        self.changes.iter().find(|c| c.id == change_id)
+    }
+
+    /// Cycles among active changes, each listed from its smallest ID and
+    /// closed by repeating it
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        let edges: BTreeMap<&str, Vec<&str>> = self
+            .changes
+            .iter()
+            .map(|c| {
+                let deps = c
+                    .depends_on
+                    .iter()
+                    .map(|d| d.id.as_str())
+                    .filter(|id| self.get(id).is_some())
+                    .collect();
+                (c.id.as_str(), deps)
+            })
+            .collect();
+
+        let mut cycles = BTreeSet::new();
+        for start in edges.keys() {
+            let mut stack = vec![(*start, vec![*start])];
+            while let Some((node, path)) = stack.pop() {
+                for next in edges.get(node).into_iter().flatten() {
+                    if next == start {
+                        if path.iter().all(|id| id >= start) {
+                            let mut cycle: Vec<String> =
+                                path.iter().map(|s| s.to_string()).collect();
+                            cycle.push(start.to_string());
+                            cycles.insert(cycle);
+                        }
+                    } else if !path.contains(next) {
+                        let mut extended = path.clone();
+                        extended.push(next);
+                        stack.push((next, extended));
+                    }
+                }
+            }
+        }
+        cycles.into_iter().collect()
+    }
+
+    /// Render the graph as an indented tree, prerequisites first
+    ///
+    /// Only changes with dependencies (or dependents) appear. Prerequisites
+    /// that are already archived are listed inline on the dependent.
+    pub fn render_tree(&self) -> String {
+        let mut dependents: BTreeMap<&str, Vec<&GraphNode>> = BTreeMap::new();
+        for change in &self.changes {
+            for dep in &change.depends_on {
+                dependents.entry(dep.id.as_str()).or_default().push(change);
+            }
+        }
+
+        let mut out = String::new();
+        let roots = self.changes.iter().filter(|c| {
+            dependents.contains_key(c.id.as_str())
+                && !c.depends_on.iter().any(|d| self.get(&d.id).is_some())
+        });
+        for root in roots {
+            self.render_node(root, &dependents, "", None, &mut vec![], &mut out);
+        }
+        // Changes whose prerequisites are all outside the active set
+        for change in &self.changes {
+            if !change.depends_on.is_empty()
+                && !change.depends_on.iter().any(|d| self.get(&d.id).is_some())
+                && !dependents.contains_key(change.id.as_str())
+            {
+                self.render_node(change, &dependents, "", None, &mut vec![], &mut out);
+            }
+        }
+        out
+    }
+
+    fn render_node<'a>(
+        &'a self,
+        node: &'a GraphNode,
+        dependents: &BTreeMap<&str, Vec<&'a GraphNode>>,
+        prefix: &str,
+        last: Option<bool>,
+        path: &mut Vec<&'a str>,
+        out: &mut String,
+    ) {
+        let (branch, child_prefix) = match last {
+            None => (String::new(), String::new()),
+            Some(true) => (format!("{}└── ", prefix), format!("{}    ", prefix)),
+            Some(false) => (format!("{}├── ", prefix), format!("{}│   ", prefix)),
+        };
+
+        let mut line = format!("{}{} ({})", branch, node.id, node.phase.as_str());
+        let external: Vec<String> = node
+            .depends_on
+            .iter()
+            .filter(|d| self.get(&d.id).is_none())
+            .map(|d| match &d.current {
+                Some(phase) => format!("{} {}", d.id, phase.as_str()),
+                None => format!("{} missing", d.id),
+            })
+            .collect();
+        if !external.is_empty() {
+            line.push_str(&format!(" [after: {}]", external.join(", ")));
+        }
+        let blocked = node.blocked_by();
+        if !blocked.is_empty() {
+            line.push_str(&format!(" ⛔ waiting on {}", blocked.join(", ")));
+        }
+        out.push_str(&line);
+        out.push('\n');
+
+        if path.contains(&node.id.as_str()) {
+            return;
+        }
+        path.push(&node.id);
+        let children = dependents
+            .get(node.id.as_str())
+            .cloned()
+            .unwrap_or_default();
+        for (i, child) in children.iter().enumerate() {
+            self.render_node(
+                child,
+                dependents,
+                &child_prefix,
+                Some(i + 1 == children.len()),
+                path,
+                out,
+            );
+        }
+        path.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_change(root: &Path, id: &str, phase: StatePhase, depends: &str) {
+        let dir = root.join("agentd/changes").join(id);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("proposal.md"),
+            format!(
+                "---\nid: {}\ndepends_on_changes:\n{}---\n\n# Change\n",
+                id, depends
+            ),
+        )
+        .unwrap();
+        let mut state = StateManager::load(&dir).unwrap();
+        state.set_phase(phase);
+        state.save().unwrap();
+    }
+
+    #[test]
+    fn test_gates_and_graph() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("agentd/archive/20260101-add-users/add-users")).unwrap();
+        write_change(
+            root,
+            "add-sessions",
+            StatePhase::Implementing,
+            "  - add-users\n",
+        );
+        write_change(
+            root,
+            "add-billing",
+            StatePhase::Planned,
+            "  - id: add-sessions\n    phase: implemented\n  - add-users\n",
+        );
+
+        assert_eq!(change_phase("add-users", root), Some(StatePhase::Archived));
+        assert_eq!(change_phase("nope", root), None);
+
+        // add-users is archived, so add-sessions is clear for both gates
+        ensure_ready("add-sessions", DependencyGate::Implement, root).unwrap();
+        ensure_ready("add-sessions", DependencyGate::Merge, root).unwrap();
+
+        let err = ensure_ready("add-billing", DependencyGate::Implement, root)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("add-sessions: needs implemented, currently implementing"));
+        assert!(!err.contains("add-users"));
+
+        let mut state = StateManager::load(root.join("agentd/changes/add-sessions")).unwrap();
+        state.set_phase(StatePhase::Implemented);
+        state.save().unwrap();
+        ensure_ready("add-billing", DependencyGate::Implement, root).unwrap();
+        // Merging still needs the prerequisite merged first
+        let err = ensure_ready("add-billing", DependencyGate::Merge, root)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("add-sessions: needs archived, currently implemented"));
+
+        let graph = dependency_graph(root).unwrap();
+        assert!(graph.has_edges());
+        assert!(graph.cycles().is_empty());
+        assert_eq!(
+            graph.render_tree(),
+            "add-sessions (implemented) [after: add-users archived]\n└── add-billing (planned) [after: add-users archived]\n"
+        );
+        assert!(validate_dependencies("add-billing", root)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_validate_dependencies() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        write_change(root, "a", StatePhase::Planned, "  - b\n  - ghost\n");
+        write_change(root, "b", StatePhase::Planned, "  - a\n");
+
+        let errors = validate_dependencies("a", root).unwrap();
+        assert_eq!(
+            errors,
+            vec![
+                "depends_on_changes references unknown change 'ghost'".to_string(),
+                "Dependency cycle: a → b → a".to_string(),
+            ]
+        );
+        let graph = dependency_graph(root).unwrap();
+        assert_eq!(graph.get("a").unwrap().blocked_by(), vec!["b", "ghost"]);
+    }
+}
@@ -0,0 +1,299 @@
+//! Working-tree diff queries
+//!
+//! Lists the files a change touched relative to a base commit using git2,
+//! including untracked files and renames, so reviews, traceability and scope
+//! drift checks share one view of the diff. The base defaults to the baseline
+//! commit recorded in STATE.yaml when implementation started.
+
+use crate::state::StateManager;
+use crate::Result;
+use git2::{Delta, DiffFindOptions, DiffOptions, Oid, Patch, Repository};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Base ref used when no baseline commit has been recorded
+pub const DEFAULT_BASE: &str = "main";
+
+/// How a file changed relative to the base
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum FileChange {
+    Added,
+    Modified,
+    Deleted,
+    Renamed { from: String },
+}
+
+impl FileChange {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FileChange::Added => "added",
+            FileChange::Modified => "modified",
+            FileChange::Deleted => "deleted",
+            FileChange::Renamed { .. } => "renamed",
+        }
+    }
+}
+
+/// A file changed relative to the base
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChangedFile {
+    /// Path relative to the project root (the new path for renames)
+    pub path: String,
+    pub change: FileChange,
+    pub additions: usize,
+    pub deletions: usize,
+    pub binary: bool,
+}
+
+/// A commit made since the base
+#[derive(Debug, Clone, Serialize)]
+pub struct CommitSummary {
+    pub id: String,
+    pub summary: String,
+}
+
+/// Baseline commit recorded for a change, if any
+pub fn baseline_commit(change_id: &str, project_root: &Path) -> Option<String> {
+    let change_dir = project_root.join("agentd/changes").join(change_id);
+    if !change_dir.join("STATE.yaml").exists() {
+        return None;
+    }
+    StateManager::load(change_dir)
+        .ok()?
+        .baseline_commit()
+        .map(String::from)
+}
+
+/// Pick the base for a change's diffs: an explicit ref, else the recorded
+/// baseline commit, else `main`
+pub fn resolve_base(change_id: &str, explicit: Option<&str>, project_root: &Path) -> String {
+    explicit
+        .map(String::from)
+        .or_else(|| baseline_commit(change_id, project_root))
+        .unwrap_or_else(|| DEFAULT_BASE.to_string())
+}
+
+/// Full hash of the commit HEAD points to
+pub fn head_commit(project_root: &Path) -> Result<String> {
+    let (repo, _) = open_repo(project_root)?;
+    let head = repo
+        .head()
+        .map_err(|e| anyhow::anyhow!("Cannot read HEAD: {}", e.message()))?;
+    let id = head.peel_to_commit()?.id();
+    Ok(id.to_string())
+}
+
+/// Name of the checked-out branch ("HEAD" when detached)
+pub fn current_branch(project_root: &Path) -> Result<String> {
+    let (repo, _) = open_repo(project_root)?;
+    let head = repo
+        .head()
+        .map_err(|e| anyhow::anyhow!("Cannot read HEAD: {}", e.message()))?;
+    Ok(if head.is_branch() {
+        head.shorthand().unwrap_or("HEAD").to_string()
+    } else {
+        "HEAD".to_string()
+    })
+}
+
+/// Commits reachable from HEAD but not from `base`, newest first
+pub fn commits_since(project_root: &Path, base: &str) -> Result<Vec<CommitSummary>> {
+    let (repo, _) = open_repo(project_root)?;
+    let base = resolve_commit(&repo, base)?;
+    let mut walk = repo.revwalk()?;
+    walk.push_head()?;
+    walk.hide(base)?;
+
+    let mut commits = Vec::new();
+    for oid in walk {
+        let commit = repo.find_commit(oid?)?;
+        commits.push(CommitSummary {
+            id: commit.as_object().short_id()?.as_str().unwrap_or_default().to_string(),
+            summary: commit.summary().unwrap_or_default().to_string(),
+        });
+    }
+    Ok(commits)
+}
+
+/// Files changed between `base` and the working tree (staged or not),
+/// including untracked files, with renames detected
+pub fn changed_files(project_root: &Path, base: &str) -> Result<Vec<ChangedFile>> {
+    let (repo, prefix) = open_repo(project_root)?;
+    let base_tree = repo.find_commit(resolve_commit(&repo, base)?)?.tree()?;
+
+    let mut options = DiffOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true);
+    let mut diff = repo.diff_tree_to_workdir_with_index(Some(&base_tree), Some(&mut options))?;
+    diff.find_similar(Some(
+        DiffFindOptions::new().renames(true).for_untracked(true),
+    ))?;
+
+    let mut files = Vec::new();
+    for (index, delta) in diff.deltas().enumerate() {
+        let change = match delta.status() {
+            Delta::Added | Delta::Untracked | Delta::Copied => FileChange::Added,
+            Delta::Deleted => FileChange::Deleted,
+            Delta::Modified | Delta::Typechange => FileChange::Modified,
+            Delta::Renamed => FileChange::Renamed {
+                from: delta
+                    .old_file()
+                    .path()
+                    .and_then(|p| relative_to(p, &prefix))
+                    .unwrap_or_default(),
+            },
+            _ => continue,
+        };
+        let file = match change {
+            FileChange::Deleted => delta.old_file(),
+            _ => delta.new_file(),
+        };
+        let Some(path) = file.path().and_then(|p| relative_to(p, &prefix)) else {
+            continue;
+        };
+
+        let (additions, deletions, binary) = match Patch::from_diff(&diff, index)? {
+            Some(patch) if !patch.delta().flags().is_binary() => {
+                let (_, additions, deletions) = patch.line_stats()?;
+                (additions, deletions, false)
+            }
+            _ => (0, 0, true),
+        };
+
+        files.push(ChangedFile {
+            path,
+            change,
+            additions,
+            deletions,
+            binary,
+        });
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// Open the repository containing the project, returning it with the
+/// project's path relative to the repository work tree
+fn open_repo(project_root: &Path) -> Result<(Repository, PathBuf)> {
+    let repo = Repository::discover(project_root).map_err(|_| {
+        anyhow::anyhow!("Not in a git repository: {}", project_root.display())
+    })?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| anyhow::anyhow!("Bare repositories are not supported"))?
+        .canonicalize()?;
+    let prefix = project_root
+        .canonicalize()?
+        .strip_prefix(&workdir)
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    Ok((repo, prefix))
+}
+
+fn resolve_commit(repo: &Repository, base: &str) -> Result<Oid> {
+    let object = repo
+        .revparse_single(base)
+        .map_err(|_| anyhow::anyhow!("Unknown base '{}': not a branch, tag or commit", base))?;
+    Ok(object.peel_to_commit()?.id())
+}
+
+/// Path relative to the project, or None for files outside it
+fn relative_to(path: &Path, prefix: &Path) -> Option<String> {
+    let relative = path.strip_prefix(prefix).ok()?;
+    Some(relative.to_string_lossy().replace('\\', "/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(root: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(root)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    fn init_repo(root: &Path) {
+        git(root, &["init", "-q", "-b", "main"]);
+        git(root, &["config", "user.email", "test@example.com"]);
+        git(root, &["config", "user.name", "Test"]);
+    }
+
+    #[test]
+    fn test_changed_files_statuses() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        init_repo(root);
+        std::fs::write(root.join("keep.txt"), "keep\n").unwrap();
+        std::fs::write(root.join("gone.txt"), "gone\n").unwrap();
+        std::fs::write(root.join("old.txt"), "line one\nline two\nline three\n").unwrap();
+        git(root, &["add", "."]);
+        git(root, &["commit", "-q", "-m", "init"]);
+
+        std::fs::write(root.join("keep.txt"), "changed\nmore\n").unwrap();
+        std::fs::remove_file(root.join("gone.txt")).unwrap();
+        // Unstaged rename: old file deleted, new file untracked
+        std::fs::rename(root.join("old.txt"), root.join("new.txt")).unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/fresh.txt"), "new\n").unwrap();
+
+        let files = changed_files(root, "main").unwrap();
+        let find = |path: &str| files.iter().find(|f| f.path == path).cloned();
+        let keep = find("keep.txt").unwrap();
+        assert_eq!(keep.change, FileChange::Modified);
+        assert_eq!((keep.additions, keep.deletions), (2, 1));
+        assert_eq!(find("gone.txt").unwrap().change, FileChange::Deleted);
+        assert_eq!(find("src/fresh.txt").unwrap().change, FileChange::Added);
+        assert_eq!(
+            find("new.txt").unwrap().change,
+            FileChange::Renamed {
+                from: "old.txt".to_string()
+            }
+        );
+        assert_eq!(files.len(), 4);
+
+        assert!(changed_files(root, "no-such-branch").is_err());
+    }
+
+    #[test]
+    fn test_baseline_and_commits() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        init_repo(root);
+        git(root, &["commit", "-q", "--allow-empty", "-m", "init"]);
+        git(root, &["checkout", "-q", "-b", "agentd/add-auth"]);
+        let baseline = head_commit(root).unwrap();
+
+        let change_dir = root.join("agentd/changes/add-auth");
+        std::fs::create_dir_all(&change_dir).unwrap();
+        assert_eq!(resolve_base("add-auth", None, root), DEFAULT_BASE);
+        let mut state = StateManager::load(&change_dir).unwrap();
+        state.set_baseline_commit(baseline.clone());
+        state.save().unwrap();
+        assert_eq!(resolve_base("add-auth", None, root), baseline);
+        assert_eq!(resolve_base("add-auth", Some("main"), root), "main");
+
+        std::fs::write(root.join("login.rs"), "fn login() {}\n").unwrap();
+        git(root, &["add", "login.rs"]);
+        git(root, &["commit", "-q", "-m", "Add login"]);
+
+        assert_eq!(current_branch(root).unwrap(), "agentd/add-auth");
+        let commits = commits_since(root, &baseline).unwrap();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].summary, "Add login");
+
+        // Committed and uncommitted changes are both part of the diff
+        let files = changed_files(root, &baseline).unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["agentd/changes/add-auth/STATE.yaml", "login.rs"]);
+    }
+}
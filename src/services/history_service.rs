@@ -0,0 +1,295 @@
+//! History service - Versioned snapshots of change documents
+//!
+//! Before proposal.md, tasks.md or a spec file is overwritten, the previous
+//! content is copied to `agentd/changes/<id>/.history/<file>/<n>.md`, where
+//! `<file>` is the document path relative to the change directory without the
+//! `.md` extension (`proposal`, `tasks`, `specs/<spec-id>`).
+
+use crate::Result;
+use chrono::{DateTime, Utc};
+use similar::{ChangeTag, TextDiff};
+use std::path::{Path, PathBuf};
+
+/// Name of the history directory inside a change directory
+pub const HISTORY_DIR: &str = ".history";
+
+/// A single saved version of a document
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    pub version: u32,
+    pub path: PathBuf,
+    pub saved_at: DateTime<Utc>,
+}
+
+/// Line counts between two versions of a document
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiffStats {
+    pub added: usize,
+    pub removed: usize,
+}
+
+/// Resolve a user-facing file name ("proposal", "tasks" or a spec name) to a
+/// document key relative to the change directory.
+pub fn document_key(change_dir: &Path, file: &str) -> Result<String> {
+    let name = file.trim_end_matches(".md");
+    match name {
+        "proposal" | "tasks" => Ok(name.to_string()),
+        _ => {
+            let spec = name.trim_start_matches("specs/");
+            if spec.is_empty()
+                || !spec
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                anyhow::bail!(
+                    "Invalid file: '{}'. Use 'proposal', 'tasks', or a spec name.",
+                    file
+                );
+            }
+            let key = format!("specs/{}", spec);
+            if !document_path(change_dir, &key).exists()
+                && !history_dir(change_dir, &key).exists()
+            {
+                anyhow::bail!(
+                    "File not found: '{}'. Use 'proposal', 'tasks', or a spec name.",
+                    file
+                );
+            }
+            Ok(key)
+        }
+    }
+}
+
+/// Path of the live document for a key
+pub fn document_path(change_dir: &Path, key: &str) -> PathBuf {
+    change_dir.join(format!("{}.md", key))
+}
+
+/// Directory holding saved versions for a key
+pub fn history_dir(change_dir: &Path, key: &str) -> PathBuf {
+    change_dir.join(HISTORY_DIR).join(key)
+}
+
+/// Save the current content of a document before it is replaced
+///
+/// Does nothing if the document does not exist yet or if the new content is
+/// identical to what is on disk. Returns the version number that was written.
+pub fn snapshot_before_write(
+    change_dir: &Path,
+    key: &str,
+    new_content: &str,
+) -> Result<Option<u32>> {
+    let doc_path = document_path(change_dir, key);
+    if !doc_path.exists() {
+        return Ok(None);
+    }
+
+    let current = std::fs::read_to_string(&doc_path)?;
+    if current == new_content {
+        return Ok(None);
+    }
+
+    let dir = history_dir(change_dir, key);
+    std::fs::create_dir_all(&dir)?;
+
+    let version = latest_version(&dir)? + 1;
+    std::fs::write(dir.join(format!("{}.md", version)), current)?;
+
+    Ok(Some(version))
+}
+
+/// List saved versions of a document, oldest first
+pub fn list_versions(change_dir: &Path, key: &str) -> Result<Vec<HistoryEntry>> {
+    let dir = history_dir(change_dir, key);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = Vec::new();
+    for entry in std::fs::read_dir(&dir)? {
+        let path = entry?.path();
+        let Some(version) = parse_version(&path) else {
+            continue;
+        };
+        let saved_at = std::fs::metadata(&path)?
+            .modified()
+            .map(DateTime::<Utc>::from)
+            .unwrap_or_else(|_| Utc::now());
+        entries.push(HistoryEntry {
+            version,
+            path,
+            saved_at,
+        });
+    }
+
+    entries.sort_by_key(|e| e.version);
+    Ok(entries)
+}
+
+/// Read the content of a saved version
+pub fn read_version(change_dir: &Path, key: &str, version: u32) -> Result<String> {
+    let path = history_dir(change_dir, key).join(format!("{}.md", version));
+    if !path.exists() {
+        anyhow::bail!("Version {} of '{}' not found", version, key);
+    }
+    Ok(std::fs::read_to_string(path)?)
+}
+
+/// Count added and removed lines between two versions
+pub fn diff_stats(old: &str, new: &str) -> DiffStats {
+    let mut stats = DiffStats::default();
+    for change in TextDiff::from_lines(old, new).iter_all_changes() {
+        match change.tag() {
+            ChangeTag::Insert => stats.added += 1,
+            ChangeTag::Delete => stats.removed += 1,
+            ChangeTag::Equal => {}
+        }
+    }
+    stats
+}
+
+/// Render a unified diff between two versions
+pub fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str) -> String {
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(3)
+        .header(old_label, new_label)
+        .to_string()
+}
+
+/// Restore a document to a saved version
+///
+/// The current content is snapshotted first, so a restore can itself be undone.
+pub fn restore_version(
+    change_id: &str,
+    file: &str,
+    version: u32,
+    project_root: &Path,
+) -> Result<String> {
+    let change_dir = project_root.join("agentd/changes").join(change_id);
+    if !change_dir.exists() {
+        anyhow::bail!("Change '{}' not found.", change_id);
+    }
+
+    let key = document_key(&change_dir, file)?;
+    let content = read_version(&change_dir, &key, version)?;
+
+    let saved = snapshot_before_write(&change_dir, &key, &content)?;
+
+    let doc_path = document_path(&change_dir, &key);
+    if let Some(parent) = doc_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&doc_path, &content)?;
+
+    let mut message = format!(
+        "Restored {}.md of change '{}' to version {}",
+        key, change_id, version
+    );
+    if let Some(saved) = saved {
+        message.push_str(&format!(" (previous content saved as version {})", saved));
+    }
+    Ok(message)
+}
+
+/// Highest version number present in a history directory (0 if none)
+fn latest_version(dir: &Path) -> Result<u32> {
+    let mut latest = 0;
+    for entry in std::fs::read_dir(dir)? {
+        if let Some(version) = parse_version(&entry?.path()) {
+            latest = latest.max(version);
+        }
+    }
+    Ok(latest)
+}
+
+/// Parse `<n>.md` into a version number
+fn parse_version(path: &Path) -> Option<u32> {
+    if path.extension().and_then(|e| e.to_str()) != Some("md") {
+        return None;
+    }
+    path.file_stem()?.to_str()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_snapshot_before_write() {
+        let temp_dir = TempDir::new().unwrap();
+        let change_dir = temp_dir.path().join("agentd/changes/test-change");
+        std::fs::create_dir_all(change_dir.join("specs")).unwrap();
+
+        // No snapshot for a file that does not exist yet
+        assert_eq!(
+            snapshot_before_write(&change_dir, "specs/auth", "v1").unwrap(),
+            None
+        );
+
+        std::fs::write(change_dir.join("specs/auth.md"), "v1").unwrap();
+
+        // No snapshot when content is unchanged
+        assert_eq!(
+            snapshot_before_write(&change_dir, "specs/auth", "v1").unwrap(),
+            None
+        );
+
+        assert_eq!(
+            snapshot_before_write(&change_dir, "specs/auth", "v2").unwrap(),
+            Some(1)
+        );
+        std::fs::write(change_dir.join("specs/auth.md"), "v2").unwrap();
+        assert_eq!(
+            snapshot_before_write(&change_dir, "specs/auth", "v3").unwrap(),
+            Some(2)
+        );
+
+        let versions = list_versions(&change_dir, "specs/auth").unwrap();
+        assert_eq!(versions.len(), 2);
+        assert_eq!(read_version(&change_dir, "specs/auth", 1).unwrap(), "v1");
+        assert_eq!(read_version(&change_dir, "specs/auth", 2).unwrap(), "v2");
+    }
+
+    #[test]
+    fn test_restore_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path();
+        let change_dir = project_root.join("agentd/changes/test-change");
+        std::fs::create_dir_all(&change_dir).unwrap();
+
+        std::fs::write(change_dir.join("proposal.md"), "first\n").unwrap();
+        snapshot_before_write(&change_dir, "proposal", "second\n").unwrap();
+        std::fs::write(change_dir.join("proposal.md"), "second\n").unwrap();
+
+        let result = restore_version("test-change", "proposal", 1, project_root).unwrap();
+        assert!(result.contains("version 1"));
+        assert!(result.contains("saved as version 2"));
+
+        let content = std::fs::read_to_string(change_dir.join("proposal.md")).unwrap();
+        assert_eq!(content, "first\n");
+        assert_eq!(read_version(&change_dir, "proposal", 2).unwrap(), "second\n");
+    }
+
+    #[test]
+    fn test_document_key() {
+        let temp_dir = TempDir::new().unwrap();
+        let change_dir = temp_dir.path();
+        std::fs::create_dir_all(change_dir.join("specs")).unwrap();
+        std::fs::write(change_dir.join("specs/auth.md"), "spec").unwrap();
+
+        assert_eq!(document_key(change_dir, "proposal").unwrap(), "proposal");
+        assert_eq!(document_key(change_dir, "tasks.md").unwrap(), "tasks");
+        assert_eq!(document_key(change_dir, "auth").unwrap(), "specs/auth");
+        assert!(document_key(change_dir, "missing").is_err());
+        assert!(document_key(change_dir, "../etc").is_err());
+    }
+
+    #[test]
+    fn test_diff_stats() {
+        let stats = diff_stats("a\nb\nc\n", "a\nc\nd\ne\n");
+        assert_eq!(stats.added, 2);
+        assert_eq!(stats.removed, 1);
+    }
+}
@@ -3,9 +3,9 @@
 //! Provides functions to read requirements and list changed files during
 //! the implementation and review stages.
 
+use crate::services::{diff_service, scope_drift_service};
 use crate::Result;
 use std::path::Path;
-use std::process::Command;
 
 /// Validate change_id to prevent directory traversal attacks
 fn validate_change_id(change_id: &str) -> Result<()> {
@@ -27,27 +27,6 @@ fn validate_change_id(change_id: &str) -> Result<()> {
     Ok(())
 }
 
-/// Check if current directory is a git repository
-fn is_git_repo() -> bool {
-    Command::new("git")
-        .args(&["rev-parse", "--git-dir"])
-        .output()
-        .map(|output| output.status.success())
-        .unwrap_or(false)
-}
-
-/// Run a git command and return output
-fn run_git_command(args: &[&str]) -> Result<String> {
-    let output = Command::new("git").args(args).output()?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Ok(format!("⚠️ Git command failed: {}", stderr.trim()));
-    }
-
-    Ok(String::from_utf8(output.stdout)?.trim().to_string())
-}
-
 /// Read all requirement files (proposal, tasks, specs) for a change in one call
 pub fn read_all_requirements(change_id: &str, project_root: &Path) -> Result<String> {
     validate_change_id(change_id)?;
@@ -124,83 +103,37 @@ pub fn read_all_requirements(change_id: &str, project_root: &Path) -> Result<Str
 }
 
 /// List changed files with detailed statistics (additions/deletions)
+///
+/// Diffs the working tree, including untracked files, against `base_branch`
+/// or, when omitted, the baseline commit recorded when implementation began.
 pub fn list_changed_files(
     change_id: &str,
     base_branch: Option<&str>,
     filter: Option<&str>,
-    _project_root: &Path,
+    project_root: &Path,
 ) -> Result<String> {
     validate_change_id(change_id)?;
 
-    let base_branch = base_branch.unwrap_or("main");
-
-    if !is_git_repo() {
-        anyhow::bail!("Not in a git repository");
-    }
+    let base = diff_service::resolve_base(change_id, base_branch, project_root);
+    let changed = diff_service::changed_files(project_root, &base)?;
 
     let mut output = String::new();
     output.push_str(&format!("# Changed Files for: {}\n\n", change_id));
+    output.push_str(&format!("**Base**: `{}`\n\n", base));
 
     if let Some(f) = filter {
         output.push_str(&format!("**Filter**: `{}`\n\n", f));
     }
 
-    // Get numstat output
-    let numstat = run_git_command(&["diff", "--numstat", base_branch])?;
-
-    if numstat.is_empty() || numstat.starts_with("⚠️") {
+    if changed.is_empty() {
         output.push_str("*No changes detected*\n");
         return Ok(output);
     }
 
-    // Parse numstat output
-    #[derive(Debug)]
-    struct FileStat {
-        added: String,
-        removed: String,
-        path: String,
-    }
-
-    let mut files: Vec<FileStat> = Vec::new();
-    let mut total_added = 0;
-    let mut total_removed = 0;
-
-    for line in numstat.lines() {
-        let parts: Vec<&str> = line.split('\t').collect();
-        if parts.len() != 3 {
-            continue;
-        }
-
-        let path = parts[2].to_string();
-
-        // Apply filter if specified
-        if let Some(f) = filter {
-            if !path.contains(f) {
-                continue;
-            }
-        }
-
-        let added_str = parts[0].to_string();
-        let removed_str = parts[1].to_string();
-
-        // Parse numbers (handle binary files marked with '-')
-        if added_str != "-" {
-            if let Ok(n) = added_str.parse::<usize>() {
-                total_added += n;
-            }
-        }
-        if removed_str != "-" {
-            if let Ok(n) = removed_str.parse::<usize>() {
-                total_removed += n;
-            }
-        }
-
-        files.push(FileStat {
-            added: added_str,
-            removed: removed_str,
-            path,
-        });
-    }
+    let files: Vec<_> = changed
+        .iter()
+        .filter(|file| filter.is_none_or(|f| file.path.contains(f)))
+        .collect();
 
     if files.is_empty() {
         output.push_str("*No matching files found*\n");
@@ -211,20 +144,28 @@ pub fn list_changed_files(
     output.push_str("| File | Status | +Lines | -Lines |\n");
     output.push_str("|------|--------|--------|--------|\n");
 
+    let mut total_added = 0;
+    let mut total_removed = 0;
     for file in &files {
-        let status = if file.added == "-" && file.removed == "-" {
-            "Binary"
-        } else if file.removed == "0" {
-            "Added"
-        } else if file.added == "0" {
-            "Deleted"
-        } else {
-            "Modified"
+        let path = match &file.change {
+            diff_service::FileChange::Renamed { from } => format!("{} → {}", from, file.path),
+            _ => file.path.clone(),
         };
-
+        if file.binary {
+            output.push_str(&format!("| {} | Binary | - | - |\n", path));
+            continue;
+        }
+        let status = match file.change {
+            diff_service::FileChange::Added => "Added",
+            diff_service::FileChange::Modified => "Modified",
+            diff_service::FileChange::Deleted => "Deleted",
+            diff_service::FileChange::Renamed { .. } => "Renamed",
+        };
+        total_added += file.additions;
+        total_removed += file.deletions;
         output.push_str(&format!(
             "| {} | {} | {} | {} |\n",
-            file.path, status, file.added, file.removed
+            path, status, file.additions, file.deletions
         ));
     }
 
@@ -419,6 +360,15 @@ pub fn create_review(input: CreateReviewInput, project_root: &Path) -> Result<St
         }
     }
 
+    // Scope drift computed by agentd (omitted without git or tasks.md)
+    if let Ok(drift) =
+        scope_drift_service::check_scope_drift(&input.change_id, None, None, project_root)
+    {
+        content.push_str("## Scope Drift\n");
+        content.push_str(&drift.to_markdown());
+        content.push('\n');
+    }
+
     // Verdict
     content.push_str("## Verdict\n");
     content.push_str(&format!("{}\n", input.verdict));
@@ -539,9 +489,38 @@ mod tests {
     }
 
     #[test]
-    fn test_is_git_repo() {
-        // This test will pass or fail depending on whether we're in a git repo
-        // Just verify it doesn't panic
-        let _ = is_git_repo();
+    fn test_list_changed_files_uses_baseline() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(args)
+                .current_dir(root)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?} failed", args);
+        };
+        git(&["init", "-q", "-b", "main"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "Test"]);
+        std::fs::write(root.join("lib.rs"), "fn a() {}\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "init"]);
+
+        let change_dir = root.join("agentd/changes/add-auth");
+        std::fs::create_dir_all(&change_dir).unwrap();
+        let mut state = crate::state::StateManager::load(&change_dir).unwrap();
+        state.set_baseline_commit(diff_service::head_commit(root).unwrap());
+        state.save().unwrap();
+
+        std::fs::write(root.join("lib.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+        std::fs::write(root.join("auth.rs"), "fn login() {}\n").unwrap();
+
+        let result = list_changed_files("add-auth", None, Some(".rs"), root).unwrap();
+        assert!(result.contains("| auth.rs | Added | 1 | 0 |"));
+        assert!(result.contains("| lib.rs | Modified | 1 | 0 |"));
+        assert!(!result.contains("STATE.yaml"));
+        assert!(result.contains("**Totals**: 2 files, +2 lines, -0 lines"));
     }
 }
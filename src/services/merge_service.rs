@@ -0,0 +1,605 @@
+//! Structural spec merge
+//!
+//! Merges a change's spec back into the main spec without an LLM. Both
+//! documents are parsed into requirements, scenarios and top-level sections
+//! keyed by ID (or title/name), and the differences are applied to the main
+//! spec as added/modified/removed/renamed deltas. Everything outside the
+//! touched sections is kept byte-for-byte.
+//!
+//! Requirements the change spec does not mention are kept. To remove one, list
+//! it under a `## Removed Requirements` section of the change spec, either as
+//! a requirement heading or as a bullet (`- R3: reason`).
+//!
+//! Anything the merge cannot resolve on its own is reported as a conflict so
+//! the caller can fall back to an LLM merge for that file.
+
+use crate::models::{SectionDelta, ValidationRules};
+use crate::parser::spec_sections::parse_requirement_heading;
+use crate::parser::{parse_sections, parse_spec_outline, MarkdownSection};
+use crate::services::spec_service::{
+    insert_block, replace_range, update_spec_frontmatter, validate_candidate,
+};
+use crate::Result;
+use std::collections::HashSet;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// What a merge operation applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeTarget {
+    /// The whole spec (new file)
+    Spec,
+    Requirement,
+    Scenario,
+    /// A top-level section such as Overview or Data Model
+    Section,
+}
+
+impl MergeTarget {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MergeTarget::Spec => "spec",
+            MergeTarget::Requirement => "requirement",
+            MergeTarget::Scenario => "scenario",
+            MergeTarget::Section => "section",
+        }
+    }
+}
+
+/// A single delta applied to the main spec
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeOperation {
+    pub target: MergeTarget,
+    /// Delta keyed by requirement ID, scenario name or section title
+    pub delta: SectionDelta<String>,
+}
+
+impl MergeOperation {
+    fn new(target: MergeTarget, delta: SectionDelta<String>) -> Self {
+        Self { target, delta }
+    }
+
+    /// One-line description, e.g. "modified requirement 'R2'"
+    pub fn describe(&self) -> String {
+        let target = self.target.label();
+        match &self.delta {
+            SectionDelta::Added(key) | SectionDelta::Modified(key) => {
+                format!("{} {} '{}'", self.delta.operation(), target, key)
+            }
+            SectionDelta::Removed { key, reason } => match reason {
+                Some(reason) => format!("removed {} '{}' ({})", target, key, reason),
+                None => format!("removed {} '{}'", target, key),
+            },
+            SectionDelta::Renamed { from, to } => {
+                format!("renamed {} '{}' -> '{}'", target, from, to)
+            }
+        }
+    }
+}
+
+/// Result of merging one change spec into its main spec
+#[derive(Debug, Clone, Default)]
+pub struct SpecMerge {
+    pub operations: Vec<MergeOperation>,
+    /// Problems the structural merge cannot resolve
+    pub conflicts: Vec<String>,
+    /// Merged content (only meaningful when there are no conflicts)
+    pub content: String,
+}
+
+impl SpecMerge {
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+
+    pub fn is_noop(&self) -> bool {
+        self.operations.is_empty()
+    }
+}
+
+/// A change spec paired with its main spec and merge result
+#[derive(Debug, Clone)]
+pub struct FileMerge {
+    /// Path relative to the specs directories
+    pub relative_path: PathBuf,
+    pub main_path: PathBuf,
+    /// Main spec content before the merge (None for new specs)
+    pub original: Option<String>,
+    pub merge: SpecMerge,
+}
+
+/// Compute the structural merge of every spec in a change
+///
+/// Nothing is written; callers apply `merge.content` for clean merges.
+pub fn plan_change_merge(change_id: &str, project_root: &Path) -> Result<Vec<FileMerge>> {
+    let agentd_dir = project_root.join("agentd");
+    let specs_dir = agentd_dir.join("changes").join(change_id).join("specs");
+    if !specs_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut delta_files: Vec<PathBuf> = WalkDir::new(&specs_dir)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .map(|e| e.into_path())
+        .filter(|p| p.is_file() && p.extension().and_then(|s| s.to_str()) == Some("md"))
+        .filter(|p| {
+            !p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with('_'))
+        })
+        .collect();
+    delta_files.sort();
+
+    let mut merges = Vec::new();
+    for delta_path in delta_files {
+        let relative_path = delta_path.strip_prefix(&specs_dir)?.to_path_buf();
+        let main_path = agentd_dir.join("specs").join(&relative_path);
+        let original = if main_path.exists() {
+            Some(std::fs::read_to_string(&main_path)?)
+        } else {
+            None
+        };
+        let delta = std::fs::read_to_string(&delta_path)?;
+        let merge = merge_spec(original.as_deref(), &delta);
+        merges.push(FileMerge {
+            relative_path,
+            main_path,
+            original,
+            merge,
+        });
+    }
+    Ok(merges)
+}
+
+/// Merge a change spec into a main spec (None when the spec is new)
+pub fn merge_spec(main: Option<&str>, delta: &str) -> SpecMerge {
+    let mut merge = SpecMerge::default();
+
+    let removed_ranges = removed_sections(delta);
+    let removals = removal_keys(delta, &removed_ranges);
+    let in_removed = |range: &Range<usize>| removed_ranges.iter().any(|r| r.contains(&range.start));
+
+    let Some(main) = main else {
+        if !removals.is_empty() {
+            merge
+                .conflicts
+                .push("spec does not exist yet but the change removes requirements".to_string());
+        }
+        let content = match removed_ranges.first() {
+            Some(_) => strip_ranges(delta, &removed_ranges),
+            None => delta.to_string(),
+        };
+        merge.operations.push(MergeOperation::new(
+            MergeTarget::Spec,
+            SectionDelta::Added("new spec".to_string()),
+        ));
+        merge.content = content;
+        return merge;
+    };
+
+    let delta_outline = parse_spec_outline(delta);
+    let delta_requirements: Vec<_> = delta_outline
+        .requirements
+        .iter()
+        .filter(|r| !in_removed(&r.section.range))
+        .collect();
+
+    check_duplicates(
+        "change spec",
+        delta_requirements.iter().map(|r| r.key()),
+        &mut merge.conflicts,
+    );
+    check_duplicates(
+        "main spec",
+        parse_spec_outline(main)
+            .requirements
+            .iter()
+            .map(|r| r.key()),
+        &mut merge.conflicts,
+    );
+    for (key, _) in &removals {
+        if delta_requirements.iter().any(|r| r.matches(key)) {
+            merge.conflicts.push(format!(
+                "requirement '{}' is both removed and redefined by the change",
+                key
+            ));
+        }
+    }
+    if !merge.conflicts.is_empty() {
+        return merge;
+    }
+
+    let mut content = main.to_string();
+
+    // Requirements (with their nested scenarios)
+    for requirement in &delta_requirements {
+        let text = section_text(delta, &requirement.section);
+        let outline = parse_spec_outline(&content);
+        let lookup = requirement.id.as_deref().unwrap_or(&requirement.title);
+        match outline.find_requirement(lookup) {
+            None => {
+                let Some(at) = outline.requirements_end else {
+                    merge.conflicts.push(format!(
+                        "main spec has no Requirements section to add '{}' to",
+                        requirement.key()
+                    ));
+                    continue;
+                };
+                content = insert_block(&content, at, &text);
+                merge.operations.push(MergeOperation::new(
+                    MergeTarget::Requirement,
+                    SectionDelta::Added(requirement.key().to_string()),
+                ));
+            }
+            Some(existing) => {
+                if same_text(&section_text(&content, &existing.section), &text) {
+                    continue;
+                }
+                if requirement.id.is_some() && existing.title != requirement.title {
+                    merge.operations.push(MergeOperation::new(
+                        MergeTarget::Requirement,
+                        SectionDelta::Renamed {
+                            from: existing.title.clone(),
+                            to: requirement.title.clone(),
+                        },
+                    ));
+                }
+                if !same_text(
+                    existing.section.body(&content),
+                    requirement.section.body(delta),
+                ) {
+                    merge.operations.push(MergeOperation::new(
+                        MergeTarget::Requirement,
+                        SectionDelta::Modified(requirement.key().to_string()),
+                    ));
+                }
+                content = replace_range(&content, existing.section.range.clone(), &text);
+            }
+        }
+    }
+
+    // Top-level scenarios (nested ones moved with their requirement)
+    for scenario in delta_outline
+        .scenarios
+        .iter()
+        .filter(|s| s.requirement.is_none() && !in_removed(&s.section.range))
+    {
+        let text = section_text(delta, &scenario.section);
+        let outline = parse_spec_outline(&content);
+        match outline.find_scenario(&scenario.name) {
+            None => {
+                content = match outline.scenarios_end {
+                    Some(at) => insert_block(&content, at, &text),
+                    None => insert_block(
+                        &content,
+                        outline.body_end,
+                        &format!("## Acceptance Criteria\n\n{}", text),
+                    ),
+                };
+                merge.operations.push(MergeOperation::new(
+                    MergeTarget::Scenario,
+                    SectionDelta::Added(scenario.name.clone()),
+                ));
+            }
+            Some(existing) => {
+                if same_text(&section_text(&content, &existing.section), &text) {
+                    continue;
+                }
+                content = replace_range(&content, existing.section.range.clone(), &text);
+                merge.operations.push(MergeOperation::new(
+                    MergeTarget::Scenario,
+                    SectionDelta::Modified(scenario.name.clone()),
+                ));
+            }
+        }
+    }
+
+    // Other top-level sections (Overview, Flow, Data Model, ...)
+    for section in parse_sections(delta)
+        .iter()
+        .filter(|s| s.heading.level == 2 && is_prose_section(&s.heading.title))
+    {
+        let text = section_text(delta, section);
+        let existing = parse_sections(&content).into_iter().find(|s| {
+            s.heading.level == 2 && s.heading.title.eq_ignore_ascii_case(&section.heading.title)
+        });
+        match existing {
+            None => {
+                let at = parse_spec_outline(&content).body_end;
+                content = insert_block(&content, at, &text);
+                merge.operations.push(MergeOperation::new(
+                    MergeTarget::Section,
+                    SectionDelta::Added(section.heading.title.clone()),
+                ));
+            }
+            Some(existing) => {
+                if same_text(&section_text(&content, &existing), &text) {
+                    continue;
+                }
+                content = replace_range(&content, existing.range.clone(), &text);
+                merge.operations.push(MergeOperation::new(
+                    MergeTarget::Section,
+                    SectionDelta::Modified(section.heading.title.clone()),
+                ));
+            }
+        }
+    }
+
+    // Explicit removals
+    for (key, reason) in &removals {
+        let outline = parse_spec_outline(&content);
+        // Requirements already gone (e.g. the merge is re-run) are skipped
+        if let Some(existing) = outline.find_requirement(key) {
+            content = replace_range(&content, existing.section.range.clone(), "");
+            merge.operations.push(MergeOperation::new(
+                MergeTarget::Requirement,
+                SectionDelta::Removed {
+                    key: existing.key().to_string(),
+                    reason: reason.clone(),
+                },
+            ));
+        }
+    }
+
+    if !merge.conflicts.is_empty() || merge.operations.is_empty() {
+        merge.content = main.to_string();
+        return merge;
+    }
+
+    // Blocks appended at the end of the document leave a trailing blank line
+    if main.ends_with('\n') && content.ends_with("\n\n") {
+        content = format!("{}\n", content.trim_end());
+    }
+    let content = update_spec_frontmatter(&content);
+
+    // Only hold the result to the spec rules if the main spec met them
+    if validate_candidate(main, ValidationRules::for_spec()).is_ok() {
+        if let Err(e) = validate_candidate(&content, ValidationRules::for_spec()) {
+            merge.conflicts.push(e.to_string());
+        }
+    }
+    merge.content = content;
+    merge
+}
+
+/// Section text normalised to end with a single blank line
+fn section_text(content: &str, section: &MarkdownSection) -> String {
+    format!("{}\n\n", content[section.range.clone()].trim_end())
+}
+
+fn same_text(a: &str, b: &str) -> bool {
+    a.trim() == b.trim()
+}
+
+/// Level-2 sections merged as whole blocks (requirements, scenarios and
+/// removal lists are handled structurally)
+fn is_prose_section(title: &str) -> bool {
+    let lower = title.to_lowercase();
+    !lower.contains("requirements")
+        && !lower.contains("acceptance criteria")
+        && !lower.contains("removed")
+}
+
+/// Ranges of "Removed ..." sections in the change spec
+pub(crate) fn removed_sections(delta: &str) -> Vec<Range<usize>> {
+    parse_sections(delta)
+        .into_iter()
+        .filter(|s| s.heading.level <= 2 && s.heading.title.to_lowercase().contains("removed"))
+        .map(|s| s.range)
+        .collect()
+}
+
+/// Requirement keys (with optional reason) listed in removed sections
+pub(crate) fn removal_keys(delta: &str, ranges: &[Range<usize>]) -> Vec<(String, Option<String>)> {
+    let mut keys = Vec::new();
+    for range in ranges {
+        let mut in_fence = false;
+        for line in delta[range.clone()].lines().skip(1) {
+            let trimmed = line.trim();
+            if trimmed.starts_with("```") {
+                in_fence = !in_fence;
+                continue;
+            }
+            if in_fence {
+                continue;
+            }
+            let (entry, is_heading) = if let Some(heading) = trimmed.strip_prefix('#') {
+                (heading.trim_start_matches('#').trim(), true)
+            } else if let Some(item) = trimmed
+                .strip_prefix("- ")
+                .or_else(|| trimmed.strip_prefix("* "))
+            {
+                (item.trim(), false)
+            } else {
+                continue;
+            };
+            let entry = entry.trim_matches('`');
+            let Some((id, rest)) = parse_requirement_heading(entry) else {
+                // Bare titles: "- Legacy login: reason"
+                let (title, reason) = match entry.split_once(':') {
+                    Some((title, reason)) => (title.trim(), Some(reason.trim().to_string())),
+                    None => (entry, None),
+                };
+                if !title.is_empty() {
+                    keys.push((title.to_string(), reason.filter(|r| !r.is_empty())));
+                }
+                continue;
+            };
+            // A heading's trailing text is the requirement title, a bullet's is the reason
+            let rest = rest.trim();
+            let reason = (!rest.is_empty() && !is_heading).then(|| rest.to_string());
+            match id {
+                Some(id) => keys.push((id, reason)),
+                None if !rest.is_empty() => keys.push((rest.to_string(), None)),
+                None => {}
+            }
+        }
+    }
+    keys
+}
+
+fn check_duplicates<'a>(
+    label: &str,
+    keys: impl Iterator<Item = &'a str>,
+    conflicts: &mut Vec<String>,
+) {
+    let mut seen = HashSet::new();
+    for key in keys {
+        if !seen.insert(key.to_lowercase()) {
+            conflicts.push(format!(
+                "{} defines requirement '{}' more than once",
+                label, key
+            ));
+        }
+    }
+}
+
+fn strip_ranges(content: &str, ranges: &[Range<usize>]) -> String {
+    let mut out = content.to_string();
+    let mut ranges = ranges.to_vec();
+    ranges.sort_by_key(|r| std::cmp::Reverse(r.start));
+    for range in ranges {
+        out = replace_range(&out, range, "");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAIN: &str = r#"# Auth
+
+## Overview
+
+Users authenticate with passwords.
+
+## Requirements
+
+### R1 - Login
+
+Users can log in with a password.
+
+### R2 - Logout
+
+Users can log out.
+
+### R3 - Remember me
+
+Sessions last 30 days.
+
+## Acceptance Criteria
+
+### Scenario: Successful login
+
+- **WHEN** valid credentials are submitted
+- **THEN** a session is created
+"#;
+
+    #[test]
+    fn test_merge_applies_deltas() {
+        let delta = r#"# Auth
+
+## Overview
+
+Users authenticate with passwords or OAuth.
+
+## Requirements
+
+### R1 - Sign in
+
+Users can log in with a password.
+
+### R2 - Logout
+
+Users can log out from every device.
+
+### R4 - OAuth login
+
+Users can log in with Google.
+
+## Acceptance Criteria
+
+### Scenario: OAuth login
+
+- **WHEN** Google returns a valid token
+- **THEN** a session is created
+
+## Removed Requirements
+
+- R3: sessions now expire with the OAuth token
+"#;
+        let merge = merge_spec(Some(MAIN), delta);
+        assert!(merge.is_clean(), "{:?}", merge.conflicts);
+
+        let ops: Vec<String> = merge.operations.iter().map(|o| o.describe()).collect();
+        assert_eq!(
+            ops,
+            vec![
+                "renamed requirement 'Login' -> 'Sign in'",
+                "modified requirement 'R2'",
+                "added requirement 'R4'",
+                "added scenario 'OAuth login'",
+                "modified section 'Overview'",
+                "removed requirement 'R3' (sessions now expire with the OAuth token)",
+            ]
+        );
+
+        let outline = parse_spec_outline(&merge.content);
+        let keys: Vec<&str> = outline.requirements.iter().map(|r| r.key()).collect();
+        assert_eq!(keys, vec!["R1", "R2", "R4"]);
+        assert!(merge.content.contains("### R1 - Sign in"));
+        assert!(merge.content.contains("every device"));
+        assert!(merge.content.contains("### Scenario: Successful login"));
+        assert!(merge.content.contains("### Scenario: OAuth login"));
+        assert!(!merge.content.contains("Removed Requirements"));
+        assert!(!merge.content.contains("30 days"));
+
+        // Merging again is a no-op
+        let again = merge_spec(Some(&merge.content), delta);
+        assert!(again.is_noop(), "{:?}", again.operations);
+    }
+
+    #[test]
+    fn test_merge_partial_delta_keeps_unmentioned() {
+        let delta = "## Requirements\n\n### R2 - Logout\n\nUsers can log out.\n";
+        let merge = merge_spec(Some(MAIN), delta);
+        assert!(merge.is_clean());
+        assert!(merge.is_noop());
+        assert_eq!(merge.content, MAIN);
+    }
+
+    #[test]
+    fn test_merge_conflicts() {
+        let delta = "## Requirements\n\n### R5 - New\n\nText.\n\n### R5 - Again\n\nText.\n";
+        let merge = merge_spec(Some(MAIN), delta);
+        assert!(merge.conflicts[0].contains("more than once"));
+
+        let delta =
+            "## Requirements\n\n### R1 - Login\n\nChanged.\n\n## Removed Requirements\n\n- R1\n";
+        let merge = merge_spec(Some(MAIN), delta);
+        assert!(merge.conflicts[0].contains("both removed and redefined"));
+
+        // Removing a requirement that is already gone is not a conflict
+        let delta = "## Removed Requirements\n\n### R9 - Ghost\n";
+        let merge = merge_spec(Some(MAIN), delta);
+        assert!(merge.is_clean() && merge.is_noop());
+
+        let merge = merge_spec(
+            Some("# Notes\n\nFree text.\n"),
+            "## Requirements\n\n### R1 - A\n\nText.\n",
+        );
+        assert!(merge.conflicts[0].contains("no Requirements section"));
+    }
+
+    #[test]
+    fn test_merge_new_spec() {
+        let merge = merge_spec(None, MAIN);
+        assert!(merge.is_clean());
+        assert_eq!(merge.operations[0].target, MergeTarget::Spec);
+        assert_eq!(merge.content, MAIN);
+    }
+}
@@ -5,16 +5,28 @@
 //! consistency and avoid code duplication.
 
 pub mod clarifications_service;
+pub mod code_service;
+pub mod conflict_service;
+pub mod dependency_service;
+pub mod diff_service;
 pub mod file_service;
+pub mod history_service;
 pub mod implementation_service;
 pub mod knowledge_service;
+pub mod merge_service;
 pub mod proposal_service;
+pub mod scope_drift_service;
+pub mod spec_diff_service;
+pub mod spec_drift_service;
+pub mod spec_history_service;
 pub mod spec_service;
 pub mod tasks_service;
+pub mod trace_service;
 
 // Re-export commonly used types
 pub use clarifications_service::{create_clarifications, CreateClarificationsInput, QuestionAnswer};
 pub use file_service::{list_specs, read_file};
+pub use history_service::{list_versions, restore_version};
 pub use implementation_service::{list_changed_files, read_all_requirements};
 pub use knowledge_service::{
     list_knowledge, read_knowledge, write_knowledge, write_main_spec, WriteKnowledgeInput,
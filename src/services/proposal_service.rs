@@ -1,5 +1,11 @@
 //! Proposal service - Business logic for proposal creation and management
 
+use crate::models::spec_rules::SpecFormatRules;
+use crate::models::{SectionDelta, ValidationRules};
+use crate::parser::spec_sections::wrapper_end;
+use crate::parser::{parse_sections, MarkdownSection};
+use crate::services::{dependency_service, history_service};
+use crate::services::spec_service::{insert_block, replace_range, validate_candidate};
 use crate::Result;
 use chrono::Utc;
 use std::path::Path;
@@ -25,6 +31,8 @@ pub struct CreateProposalInput {
     pub why: String,
     pub what_changes: Vec<String>,
     pub impact: ImpactData,
+    /// Changes that must be merged before this one
+    pub depends_on_changes: Vec<String>,
 }
 
 /// Impact data for a proposal
@@ -64,6 +72,16 @@ pub fn create_proposal(input: CreateProposalInput, project_root: &Path) -> Resul
         anyhow::bail!("impact.scope must be 'patch', 'minor', or 'major'");
     }
 
+    // Validate prerequisite changes
+    for dependency in &input.depends_on_changes {
+        if *dependency == input.change_id {
+            anyhow::bail!("depends_on_changes cannot reference the change itself");
+        }
+        if dependency_service::change_phase(dependency, project_root).is_none() {
+            anyhow::bail!("depends_on_changes references unknown change '{}'", dependency);
+        }
+    }
+
     // Create change directory
     let change_dir = project_root.join("agentd/changes").join(&input.change_id);
     std::fs::create_dir_all(&change_dir)?;
@@ -107,6 +125,13 @@ pub fn create_proposal(input: CreateProposalInput, project_root: &Path) -> Resul
         }
     }
 
+    if !input.depends_on_changes.is_empty() {
+        content.push_str("depends_on_changes:\n");
+        for dependency in &input.depends_on_changes {
+            content.push_str(&format!("  - {}\n", dependency));
+        }
+    }
+
     content.push_str("---\n\n");
 
     // Wrap proposal content in XML
@@ -171,6 +196,7 @@ pub fn create_proposal(input: CreateProposalInput, project_root: &Path) -> Resul
 
     // Write the file
     let proposal_path = change_dir.join("proposal.md");
+    history_service::snapshot_before_write(&change_dir, "proposal", &content)?;
     std::fs::write(&proposal_path, &content)?;
 
     // Create specs directory
@@ -251,10 +277,127 @@ pub fn append_review(
         )?
     };
 
+    if let Some(change_dir) = proposal_path.parent() {
+        history_service::snapshot_before_write(change_dir, "proposal", &updated)?;
+    }
     std::fs::write(proposal_path, updated)?;
     Ok(())
 }
 
+/// A proposal section (H2 heading and its body)
+#[derive(Debug, Clone)]
+pub struct ProposalSection {
+    pub name: String,
+    pub content: String,
+}
+
+/// Input structure for patching a proposal
+#[derive(Debug, Clone)]
+pub struct PatchProposalInput {
+    pub change_id: String,
+    pub patch: SectionDelta<ProposalSection>,
+}
+
+/// Add, replace, remove or rename a single `##` section of proposal.md
+///
+/// Review blocks and other sections are left untouched. Required sections
+/// (Summary, Why, What Changes, Impact) cannot be removed or renamed.
+pub fn patch_proposal(input: PatchProposalInput, project_root: &Path) -> Result<String> {
+    let change_dir = project_root.join("agentd/changes").join(&input.change_id);
+    let proposal_path = change_dir.join("proposal.md");
+    if !proposal_path.exists() {
+        anyhow::bail!(
+            "proposal.md not found for change '{}'. Use create_proposal first.",
+            input.change_id
+        );
+    }
+
+    let content = std::fs::read_to_string(&proposal_path)?;
+    let patched = apply_proposal_patch(&content, &input.patch)?;
+    let warnings = validate_candidate(&patched, ValidationRules::for_prd())?;
+
+    history_service::snapshot_before_write(&change_dir, "proposal", &patched)?;
+    std::fs::write(&proposal_path, &patched)?;
+
+    let name = match &input.patch {
+        SectionDelta::Added(s) | SectionDelta::Modified(s) => s.name.clone(),
+        SectionDelta::Removed { key, .. } => key.clone(),
+        SectionDelta::Renamed { from, to } => format!("{}' -> '{}", from, to),
+    };
+    let mut message = format!(
+        "Patched proposal.md for change '{}': {} section '{}'",
+        input.change_id,
+        input.patch.operation(),
+        name
+    );
+    for warning in warnings {
+        message.push_str(&format!("\n⚠ {}", warning));
+    }
+    Ok(message)
+}
+
+/// Apply a section patch to proposal content
+pub fn apply_proposal_patch(content: &str, patch: &SectionDelta<ProposalSection>) -> Result<String> {
+    let body_end = wrapper_end(content);
+    let sections: Vec<MarkdownSection> = parse_sections(content)
+        .into_iter()
+        .filter(|s| s.heading.level == 2 && s.range.start < body_end)
+        .collect();
+    let find = |name: &str| {
+        sections
+            .iter()
+            .find(|s| s.heading.title.eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| {
+                let known: Vec<&str> = sections.iter().map(|s| s.heading.title.as_str()).collect();
+                anyhow::anyhow!(
+                    "Section '{}' not found. Existing sections: {}",
+                    name,
+                    known.join(", ")
+                )
+            })
+    };
+    let ensure_optional = |name: &str| {
+        let required = SpecFormatRules::prd_defaults().required_headings;
+        if required.iter().any(|h| h.eq_ignore_ascii_case(name.trim())) {
+            anyhow::bail!("Section '{}' is required and cannot be removed or renamed", name);
+        }
+        Ok(())
+    };
+    let render_body = |text: &str| format!("\n{}\n\n", text.trim());
+
+    match patch {
+        SectionDelta::Added(section) => {
+            if find(&section.name).is_ok() {
+                anyhow::bail!("Section '{}' already exists", section.name);
+            }
+            let block = format!("## {}\n{}", section.name.trim(), render_body(&section.content));
+            Ok(insert_block(content, body_end, &block))
+        }
+        SectionDelta::Modified(section) => {
+            let existing = find(&section.name)?;
+            let range = existing.heading.line.end..existing.range.end;
+            Ok(replace_range(content, range, &render_body(&section.content)))
+        }
+        SectionDelta::Removed { key, .. } => {
+            ensure_optional(key)?;
+            let existing = find(key)?;
+            Ok(replace_range(content, existing.range.clone(), ""))
+        }
+        SectionDelta::Renamed { from, to } => {
+            ensure_optional(from)?;
+            let existing = find(from)?;
+            if find(to).is_ok() {
+                anyhow::bail!("Section '{}' already exists", to);
+            }
+            Ok(replace_range(
+                content,
+                existing.heading.line.clone(),
+                &format!("## {}\n", to.trim()),
+            ))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -287,6 +430,7 @@ mod tests {
                 affected_code: vec!["src/services/".to_string()],
                 breaking_changes: None,
             },
+            depends_on_changes: vec![],
         };
 
         let result = create_proposal(input, project_root).unwrap();
@@ -322,9 +466,114 @@ mod tests {
                 affected_code: vec![],
                 breaking_changes: None,
             },
+            depends_on_changes: vec![],
         };
 
         let result = create_proposal(input, project_root);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_create_proposal_depends_on_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path();
+        std::fs::create_dir_all(project_root.join("agentd/changes/add-users")).unwrap();
+
+        let input = |depends: &str| CreateProposalInput {
+            change_id: "add-billing".to_string(),
+            summary: "Add billing on top of user accounts".to_string(),
+            why: "Billing needs user accounts to exist, so it is split into its own change".to_string(),
+            what_changes: vec!["Add invoices".to_string()],
+            impact: ImpactData {
+                scope: "minor".to_string(),
+                affected_files: 2,
+                new_files: 1,
+                affected_specs: vec![],
+                affected_code: vec![],
+                breaking_changes: None,
+            },
+            depends_on_changes: vec![depends.to_string()],
+        };
+
+        let err = create_proposal(input("add-ghosts"), project_root).unwrap_err();
+        assert!(err.to_string().contains("unknown change 'add-ghosts'"));
+
+        create_proposal(input("add-users"), project_root).unwrap();
+        let content =
+            std::fs::read_to_string(project_root.join("agentd/changes/add-billing/proposal.md"))
+                .unwrap();
+        let dependencies = crate::parser::parse_change_dependencies(&content).unwrap();
+        assert_eq!(dependencies.len(), 1);
+        assert_eq!(dependencies[0].id, "add-users");
+    }
+
+    #[test]
+    fn test_patch_proposal() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path();
+
+        let input = CreateProposalInput {
+            change_id: "test-change".to_string(),
+            summary: "This is a test change with sufficient length".to_string(),
+            why: "This change is needed because we want to test the service functionality and ensure it works correctly".to_string(),
+            what_changes: vec!["Add new feature X".to_string()],
+            impact: ImpactData {
+                scope: "minor".to_string(),
+                affected_files: 1,
+                new_files: 0,
+                affected_specs: vec![],
+                affected_code: vec![],
+                breaking_changes: None,
+            },
+            depends_on_changes: vec![],
+        };
+        create_proposal(input, project_root).unwrap();
+        let proposal_path = project_root.join("agentd/changes/test-change/proposal.md");
+
+        let patch = |patch| PatchProposalInput {
+            change_id: "test-change".to_string(),
+            patch,
+        };
+
+        patch_proposal(
+            patch(SectionDelta::Modified(ProposalSection {
+                name: "What Changes".to_string(),
+                content: "- Add new feature X\n- Add feature Y".to_string(),
+            })),
+            project_root,
+        )
+        .unwrap();
+        patch_proposal(
+            patch(SectionDelta::Added(ProposalSection {
+                name: "Risks".to_string(),
+                content: "Low risk.".to_string(),
+            })),
+            project_root,
+        )
+        .unwrap();
+
+        let content = std::fs::read_to_string(&proposal_path).unwrap();
+        assert!(content.contains("## What Changes\n\n- Add new feature X\n- Add feature Y\n\n## Impact"));
+        assert!(content.contains("## Risks\n\nLow risk.\n\n</proposal>"));
+
+        let result = patch_proposal(
+            patch(SectionDelta::Removed {
+                key: "Why".to_string(),
+                reason: None,
+            }),
+            project_root,
+        );
+        assert!(result.is_err());
+
+        patch_proposal(
+            patch(SectionDelta::Renamed {
+                from: "Risks".to_string(),
+                to: "Risks and Mitigations".to_string(),
+            }),
+            project_root,
+        )
+        .unwrap();
+        let content = std::fs::read_to_string(&proposal_path).unwrap();
+        assert!(content.contains("## Risks and Mitigations\n\nLow risk."));
+    }
 }
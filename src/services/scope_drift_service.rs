@@ -0,0 +1,303 @@
+//! Scope drift detection
+//!
+//! Compares the files tasks.md planned to CREATE/MODIFY/DELETE/RENAME with the
+//! files the diff actually touched: unplanned changes, planned files left
+//! untouched, and files changed in a different way than planned.
+
+use crate::models::{TaskAction, TaskBlock, TaskGraph};
+use crate::services::diff_service::{self, ChangedFile, FileChange};
+use crate::Result;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+/// Scope drift between tasks.md and the diff
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScopeDriftReport {
+    pub change_id: String,
+    pub diff_base: String,
+    /// Changed files no task mentions
+    pub unplanned: Vec<ChangedFile>,
+    /// Planned files the diff does not touch
+    pub untouched: Vec<PlannedFile>,
+    /// Files changed in a different way than their tasks said
+    pub action_mismatches: Vec<ActionMismatch>,
+}
+
+/// A file named by one or more tasks
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedFile {
+    pub path: String,
+    pub action: TaskAction,
+    pub tasks: Vec<String>,
+}
+
+/// A planned file whose actual change contradicts the planned action
+#[derive(Debug, Clone, Serialize)]
+pub struct ActionMismatch {
+    pub path: String,
+    pub planned: TaskAction,
+    pub actual: FileChange,
+    pub tasks: Vec<String>,
+}
+
+impl ScopeDriftReport {
+    /// Total number of drift findings
+    pub fn drift_count(&self) -> usize {
+        self.unplanned.len() + self.untouched.len() + self.action_mismatches.len()
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.drift_count() == 0
+    }
+
+    /// Whether the drift exceeds the configured threshold
+    pub fn exceeds(&self, threshold: u32) -> bool {
+        self.drift_count() > threshold as usize
+    }
+
+    /// Render the findings as markdown (used in review context and REVIEW.md)
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        if self.is_clean() {
+            out.push_str(&format!(
+                "No scope drift: the diff against `{}` matches tasks.md.\n",
+                self.diff_base
+            ));
+            return out;
+        }
+
+        out.push_str(&format!(
+            "{} finding(s) comparing tasks.md with the diff against `{}`.\n\n",
+            self.drift_count(),
+            self.diff_base
+        ));
+        if !self.unplanned.is_empty() {
+            out.push_str("**Unplanned changes** (no task mentions these files):\n");
+            for file in &self.unplanned {
+                out.push_str(&format!("- `{}` ({})\n", file.path, file.change.label()));
+            }
+            out.push('\n');
+        }
+        if !self.untouched.is_empty() {
+            out.push_str("**Planned but untouched**:\n");
+            for file in &self.untouched {
+                out.push_str(&format!(
+                    "- `{}` ({}, task {})\n",
+                    file.path,
+                    file.action.as_str(),
+                    file.tasks.join(", ")
+                ));
+            }
+            out.push('\n');
+        }
+        if !self.action_mismatches.is_empty() {
+            out.push_str("**Action mismatches**:\n");
+            for mismatch in &self.action_mismatches {
+                out.push_str(&format!(
+                    "- `{}`: planned {} (task {}), actually {}\n",
+                    mismatch.path,
+                    mismatch.planned.as_str(),
+                    mismatch.tasks.join(", "),
+                    mismatch.actual.label()
+                ));
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Check a change's tasks.md against the diff
+///
+/// `done_tasks` limits the planned-but-untouched check to tasks that should
+/// already be implemented (e.g. after each spec group); `None` checks all.
+pub fn check_scope_drift(
+    change_id: &str,
+    base: Option<&str>,
+    done_tasks: Option<&HashSet<String>>,
+    project_root: &Path,
+) -> Result<ScopeDriftReport> {
+    let tasks_path = project_root
+        .join("agentd/changes")
+        .join(change_id)
+        .join("tasks.md");
+    if !tasks_path.exists() {
+        anyhow::bail!("tasks.md not found for change '{}'", change_id);
+    }
+    let tasks = TaskGraph::parse_tasks(&std::fs::read_to_string(&tasks_path)?)?;
+
+    let base = diff_service::resolve_base(change_id, base, project_root);
+    let changed = diff_service::changed_files(project_root, &base)?;
+
+    let mut report = compare(&tasks, &changed, done_tasks);
+    report.change_id = change_id.to_string();
+    report.diff_base = base;
+    Ok(report)
+}
+
+/// Compare planned task files with changed files
+fn compare(
+    tasks: &[TaskBlock],
+    changed: &[ChangedFile],
+    done_tasks: Option<&HashSet<String>>,
+) -> ScopeDriftReport {
+    // Group tasks by file; the first task's action is the planned one
+    let mut planned: BTreeMap<String, PlannedFile> = BTreeMap::new();
+    for task in tasks {
+        let path = normalize(&task.file);
+        if path.is_empty() {
+            continue;
+        }
+        planned
+            .entry(path.clone())
+            .or_insert_with(|| PlannedFile {
+                path,
+                action: task.action.clone(),
+                tasks: Vec::new(),
+            })
+            .tasks
+            .push(task.id.clone());
+    }
+
+    let mut report = ScopeDriftReport::default();
+    let mut touched: HashSet<&str> = HashSet::new();
+
+    for file in changed {
+        // The change's own planning documents are never drift
+        if file.path.starts_with("agentd/") {
+            continue;
+        }
+        let renamed_from = match &file.change {
+            FileChange::Renamed { from } => planned.get(from.as_str()),
+            _ => None,
+        };
+        match (planned.get(file.path.as_str()), renamed_from) {
+            (Some(plan), _) => {
+                touched.insert(plan.path.as_str());
+                if !action_matches(&plan.action, &file.change) {
+                    report.action_mismatches.push(ActionMismatch {
+                        path: file.path.clone(),
+                        planned: plan.action.clone(),
+                        actual: file.change.clone(),
+                        tasks: plan.tasks.clone(),
+                    });
+                }
+            }
+            (None, Some(plan)) => {
+                touched.insert(plan.path.as_str());
+            }
+            (None, None) => report.unplanned.push(file.clone()),
+        }
+    }
+
+    for plan in planned.values() {
+        let due = done_tasks.is_none_or(|done| plan.tasks.iter().any(|t| done.contains(t)));
+        if due && !touched.contains(plan.path.as_str()) {
+            report.untouched.push(plan.clone());
+        }
+    }
+
+    report
+}
+
+/// Whether an actual change is consistent with the planned action
+fn action_matches(planned: &TaskAction, actual: &FileChange) -> bool {
+    matches!(
+        (planned, actual),
+        (TaskAction::Create, FileChange::Added)
+            | (TaskAction::Create, FileChange::Renamed { .. })
+            | (TaskAction::Modify, FileChange::Modified)
+            | (TaskAction::Modify, FileChange::Renamed { .. })
+            | (TaskAction::Delete, FileChange::Deleted)
+            | (TaskAction::Rename, _)
+    )
+}
+
+fn normalize(path: &str) -> String {
+    path.trim().trim_start_matches("./").replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TaskStatus;
+
+    fn task(id: &str, action: TaskAction, file: &str) -> TaskBlock {
+        TaskBlock {
+            id: id.to_string(),
+            action,
+            status: TaskStatus::Pending,
+            file: file.to_string(),
+            spec_ref: None,
+            depends_on: Vec::new(),
+            estimated_lines: None,
+        }
+    }
+
+    fn changed(path: &str, change: FileChange) -> ChangedFile {
+        ChangedFile {
+            path: path.to_string(),
+            change,
+            additions: 0,
+            deletions: 0,
+            binary: false,
+        }
+    }
+
+    #[test]
+    fn test_compare_finds_drift() {
+        let tasks = vec![
+            task("1.1", TaskAction::Create, "src/new.rs"),
+            task("1.2", TaskAction::Modify, "./src/lib.rs"),
+            task("2.1", TaskAction::Modify, "src/existing.rs"),
+            task("2.2", TaskAction::Delete, "src/old.rs"),
+            task("3.1", TaskAction::Modify, "src/later.rs"),
+        ];
+        let diff = vec![
+            changed("src/new.rs", FileChange::Added),
+            changed("src/lib.rs", FileChange::Modified),
+            changed("src/existing.rs", FileChange::Added),
+            changed("src/extra.rs", FileChange::Modified),
+            changed("agentd/changes/x/tasks.md", FileChange::Modified),
+        ];
+
+        let report = compare(&tasks, &diff, None);
+        assert_eq!(report.unplanned.len(), 1);
+        assert_eq!(report.unplanned[0].path, "src/extra.rs");
+        let untouched: Vec<&str> = report.untouched.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(untouched, vec!["src/later.rs", "src/old.rs"]);
+        assert_eq!(report.action_mismatches.len(), 1);
+        assert_eq!(report.action_mismatches[0].path, "src/existing.rs");
+        assert_eq!(report.drift_count(), 4);
+        assert!(report.exceeds(3));
+        assert!(!report.exceeds(4));
+
+        let markdown = report.to_markdown();
+        assert!(markdown.contains("- `src/extra.rs` (modified)"));
+        assert!(markdown.contains("planned MODIFY (task 2.1), actually added"));
+
+        // Only tasks already done count as untouched
+        let done: HashSet<String> = ["1.1", "1.2", "2.1", "2.2"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let report = compare(&tasks, &diff, Some(&done));
+        assert_eq!(report.untouched.len(), 1);
+        assert_eq!(report.untouched[0].path, "src/old.rs");
+    }
+
+    #[test]
+    fn test_compare_renames() {
+        let tasks = vec![task("1.1", TaskAction::Rename, "src/old_name.rs")];
+        let diff = vec![changed(
+            "src/new_name.rs",
+            FileChange::Renamed {
+                from: "src/old_name.rs".to_string(),
+            },
+        )];
+        let report = compare(&tasks, &diff, None);
+        assert!(report.is_clean());
+        assert!(report.to_markdown().contains("No scope drift"));
+    }
+}
@@ -0,0 +1,554 @@
+//! Semantic spec diff
+//!
+//! Compares a change's spec with the main spec requirement by requirement and
+//! scenario by scenario instead of line by line. Items are matched by ID (or
+//! title/name when there is none) and reported as added, removed, modified or
+//! renamed, with word-level changes inside each. Matching and removal markers
+//! follow the structural merge, so the diff shows what merge-change applies.
+
+use crate::models::DeltaMetrics;
+use crate::parser::{parse_sections, parse_spec_outline, RequirementSection, SpecOutline};
+use crate::services::merge_service::{removal_keys, removed_sections};
+use crate::Result;
+use serde::Serialize;
+use similar::{ChangeTag, TextDiff};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// Kind of item that changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemKind {
+    Requirement,
+    Scenario,
+}
+
+/// How an item changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemChange {
+    Added,
+    Removed,
+    Modified,
+    Renamed,
+}
+
+/// A run of words in a word-level diff
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WordChange {
+    /// "equal", "insert" or "delete"
+    pub op: &'static str,
+    pub text: String,
+}
+
+/// A requirement or scenario that differs between main and change spec
+#[derive(Debug, Clone, Serialize)]
+pub struct ItemDiff {
+    pub kind: ItemKind,
+    pub change: ItemChange,
+    /// Requirement ID (or title) / scenario name
+    pub key: String,
+    pub title: String,
+    /// Previous title for renamed requirements
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub renamed_from: Option<String>,
+    /// Reason given for a removal
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Word-level changes of the body (modified and renamed items only)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<WordChange>,
+}
+
+/// Semantic diff of one spec
+#[derive(Debug, Clone, Serialize)]
+pub struct SpecDiff {
+    /// Spec path relative to the specs directory
+    pub spec: String,
+    /// Whether the spec does not exist in agentd/specs yet
+    pub new_spec: bool,
+    pub items: Vec<ItemDiff>,
+    /// Top-level sections the change adds
+    pub new_sections: Vec<String>,
+    pub metrics: DeltaMetrics,
+}
+
+impl SpecDiff {
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty() && self.new_sections.is_empty() && !self.new_spec
+    }
+
+    fn count(&self, kind: ItemKind, change: ItemChange) -> usize {
+        self.items
+            .iter()
+            .filter(|i| i.kind == kind && i.change == change)
+            .count()
+    }
+}
+
+/// Diff every spec of a change (or only `spec`) against the main specs
+pub fn diff_change(
+    change_id: &str,
+    spec: Option<&str>,
+    project_root: &Path,
+) -> Result<Vec<SpecDiff>> {
+    let specs_dir = project_root
+        .join("agentd/changes")
+        .join(change_id)
+        .join("specs");
+    if !specs_dir.exists() {
+        anyhow::bail!("Change '{}' has no specs", change_id);
+    }
+
+    let mut files: Vec<PathBuf> = walkdir::WalkDir::new(&specs_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .map(|e| e.into_path())
+        .filter(|p| p.is_file() && p.extension().and_then(|s| s.to_str()) == Some("md"))
+        .filter(|p| {
+            !p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with('_'))
+        })
+        .collect();
+    files.sort();
+
+    let mut diffs = Vec::new();
+    for path in files {
+        let relative = path
+            .strip_prefix(&specs_dir)?
+            .to_string_lossy()
+            .replace('\\', "/");
+        if let Some(wanted) = spec {
+            let wanted = wanted.trim_end_matches(".md");
+            if relative.trim_end_matches(".md") != wanted {
+                continue;
+            }
+        }
+        let main_path = project_root.join("agentd/specs").join(&relative);
+        let main = if main_path.exists() {
+            Some(std::fs::read_to_string(&main_path)?)
+        } else {
+            None
+        };
+        let delta = std::fs::read_to_string(&path)?;
+        diffs.push(diff_spec(&relative, main.as_deref(), &delta));
+    }
+
+    if let (Some(wanted), true) = (spec, diffs.is_empty()) {
+        anyhow::bail!("Spec '{}' not found in change '{}'", wanted, change_id);
+    }
+    Ok(diffs)
+}
+
+/// Diff a change spec against its main spec (None when the spec is new)
+pub fn diff_spec(spec: &str, main: Option<&str>, delta: &str) -> SpecDiff {
+    let removed_ranges = removed_sections(delta);
+    let in_removed = |range: &Range<usize>| removed_ranges.iter().any(|r| r.contains(&range.start));
+    let delta_outline = parse_spec_outline(delta);
+    let main_content = main.unwrap_or("");
+    let main_outline = parse_spec_outline(main_content);
+
+    let mut items = Vec::new();
+
+    for requirement in delta_outline
+        .requirements
+        .iter()
+        .filter(|r| !in_removed(&r.section.range))
+    {
+        let lookup = requirement.id.as_deref().unwrap_or(&requirement.title);
+        let Some(existing) = main_outline.find_requirement(lookup) else {
+            items.push(item(
+                ItemKind::Requirement,
+                ItemChange::Added,
+                requirement.key(),
+                &requirement.title,
+            ));
+            continue;
+        };
+
+        let old = own_text(main_content, existing, &main_outline);
+        let new = own_text(delta, requirement, &delta_outline);
+        let renamed = requirement.id.is_some() && existing.title != requirement.title;
+        let modified = old.trim() != new.trim();
+        if renamed || modified {
+            let mut diff = item(
+                ItemKind::Requirement,
+                if renamed {
+                    ItemChange::Renamed
+                } else {
+                    ItemChange::Modified
+                },
+                requirement.key(),
+                &requirement.title,
+            );
+            if renamed {
+                diff.renamed_from = Some(existing.title.clone());
+            }
+            if modified {
+                diff.words = word_diff(&old, &new);
+            }
+            items.push(diff);
+        }
+
+        // Nested scenarios dropped from a requirement the change rewrites
+        for scenario in main_outline
+            .scenarios
+            .iter()
+            .filter(|s| s.requirement.as_deref() == Some(existing.key()))
+        {
+            if delta_outline.find_scenario(&scenario.name).is_none() {
+                items.push(item(
+                    ItemKind::Scenario,
+                    ItemChange::Removed,
+                    &scenario.name,
+                    &scenario.name,
+                ));
+            }
+        }
+    }
+
+    for scenario in delta_outline
+        .scenarios
+        .iter()
+        .filter(|s| !in_removed(&s.section.range))
+    {
+        match main_outline.find_scenario(&scenario.name) {
+            None => items.push(item(
+                ItemKind::Scenario,
+                ItemChange::Added,
+                &scenario.name,
+                &scenario.name,
+            )),
+            Some(existing) => {
+                let old = existing.section.body(main_content);
+                let new = scenario.section.body(delta);
+                if old.trim() != new.trim() {
+                    let mut diff = item(
+                        ItemKind::Scenario,
+                        ItemChange::Modified,
+                        &scenario.name,
+                        &scenario.name,
+                    );
+                    diff.words = word_diff(old, new);
+                    items.push(diff);
+                }
+            }
+        }
+    }
+
+    if main.is_some() {
+        for (key, reason) in removal_keys(delta, &removed_ranges) {
+            if let Some(existing) = main_outline.find_requirement(&key) {
+                let mut diff = item(
+                    ItemKind::Requirement,
+                    ItemChange::Removed,
+                    existing.key(),
+                    &existing.title,
+                );
+                diff.reason = reason;
+                items.push(diff);
+            }
+        }
+    }
+
+    let main_sections = top_level_sections(main_content);
+    let new_sections: Vec<String> = if main.is_some() {
+        top_level_sections(delta)
+            .into_iter()
+            .filter(|s| !s.to_lowercase().contains("removed"))
+            .filter(|s| !main_sections.iter().any(|m| m.eq_ignore_ascii_case(s)))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut diff = SpecDiff {
+        spec: spec.to_string(),
+        new_spec: main.is_none(),
+        items,
+        new_sections,
+        metrics: DeltaMetrics::new(),
+    };
+    diff.metrics = metrics(&diff, main_content, &main_outline, delta);
+    diff
+}
+
+/// Delta metrics derived from the semantic diff (drives the merge strategy)
+fn metrics(diff: &SpecDiff, main: &str, main_outline: &SpecOutline, delta: &str) -> DeltaMetrics {
+    let mut metrics = DeltaMetrics::new();
+    metrics.existing_spec_size = main.len();
+    metrics.delta_spec_size = delta.len();
+    metrics.existing_req_count = main_outline.requirements.len();
+
+    if diff.new_spec {
+        metrics.added_count = parse_spec_outline(delta).requirements.len();
+    } else {
+        metrics.added_count = diff.count(ItemKind::Requirement, ItemChange::Added);
+        metrics.modified_count = diff.count(ItemKind::Requirement, ItemChange::Modified);
+        metrics.removed_count = diff.count(ItemKind::Requirement, ItemChange::Removed);
+        metrics.renamed_count = diff.count(ItemKind::Requirement, ItemChange::Renamed);
+        metrics.affected_req_count =
+            metrics.modified_count + metrics.removed_count + metrics.renamed_count;
+        metrics.has_new_sections = !diff.new_sections.is_empty();
+        metrics.has_schema_changes =
+            delta.contains("## Data Schema") || delta.contains("## Database Schema");
+        metrics.has_api_changes = delta.contains("## API Endpoints") || delta.contains("### API:");
+    }
+
+    metrics.calculate_ratios();
+    metrics
+}
+
+fn item(kind: ItemKind, change: ItemChange, key: &str, title: &str) -> ItemDiff {
+    ItemDiff {
+        kind,
+        change,
+        key: key.to_string(),
+        title: title.to_string(),
+        renamed_from: None,
+        reason: None,
+        words: Vec::new(),
+    }
+}
+
+/// Requirement body without its nested scenarios (diffed separately)
+fn own_text(content: &str, requirement: &RequirementSection, outline: &SpecOutline) -> String {
+    let body_start = requirement.section.heading.line.end;
+    let mut text = String::new();
+    let mut at = body_start;
+    for scenario in outline
+        .scenarios
+        .iter()
+        .filter(|s| s.requirement.as_deref() == Some(requirement.key()))
+    {
+        let range = &scenario.section.range;
+        if range.start >= at {
+            text.push_str(&content[at..range.start]);
+            at = range.end;
+        }
+    }
+    text.push_str(&content[at..requirement.section.range.end.max(at)]);
+    text
+}
+
+fn top_level_sections(content: &str) -> Vec<String> {
+    parse_sections(content)
+        .into_iter()
+        .filter(|s| s.heading.level == 2)
+        .map(|s| s.heading.title)
+        .collect()
+}
+
+/// Word-level diff, with consecutive words of the same kind joined
+pub fn word_diff(old: &str, new: &str) -> Vec<WordChange> {
+    let mut words: Vec<WordChange> = Vec::new();
+    for change in TextDiff::from_words(old.trim(), new.trim()).iter_all_changes() {
+        let op = match change.tag() {
+            ChangeTag::Equal => "equal",
+            ChangeTag::Insert => "insert",
+            ChangeTag::Delete => "delete",
+        };
+        match words.last_mut() {
+            Some(last) if last.op == op => last.text.push_str(change.value()),
+            _ => words.push(WordChange {
+                op,
+                text: change.value().to_string(),
+            }),
+        }
+    }
+    words
+}
+
+/// Render diffs for the terminal, marking word changes `[-old-]{+new+}`
+pub fn render_text(diffs: &[SpecDiff], color: bool) -> String {
+    use colored::Colorize;
+
+    let mut out = String::new();
+    for diff in diffs {
+        let header = if diff.new_spec {
+            format!("{} (new spec)", diff.spec)
+        } else {
+            diff.spec.clone()
+        };
+        out.push_str(&format!("== {} ==\n", header));
+        if diff.is_empty() {
+            out.push_str("  no changes\n\n");
+            continue;
+        }
+        for section in &diff.new_sections {
+            out.push_str(&format!("  + section '{}'\n", section));
+        }
+        for item in &diff.items {
+            let kind = match item.kind {
+                ItemKind::Requirement => "requirement",
+                ItemKind::Scenario => "scenario",
+            };
+            let (sign, label) = match item.change {
+                ItemChange::Added => ("+", "added"),
+                ItemChange::Removed => ("-", "removed"),
+                ItemChange::Modified => ("~", "modified"),
+                ItemChange::Renamed => ("~", "renamed"),
+            };
+            let mut line = if item.key == item.title {
+                format!("  {} {} {} '{}'", sign, label, kind, item.key)
+            } else {
+                format!(
+                    "  {} {} {} {} '{}'",
+                    sign, label, kind, item.key, item.title
+                )
+            };
+            if let Some(from) = &item.renamed_from {
+                line.push_str(&format!(" (was '{}')", from));
+            }
+            if let Some(reason) = &item.reason {
+                line.push_str(&format!(": {}", reason));
+            }
+            out.push_str(&line);
+            out.push('\n');
+            if !item.words.is_empty() {
+                out.push_str("      ");
+                for word in &item.words {
+                    let text = word.text.replace('\n', "\n      ");
+                    let rendered = match (word.op, color) {
+                        ("insert", true) => format!("{{+{}+}}", text).green().to_string(),
+                        ("delete", true) => format!("[-{}-]", text).red().to_string(),
+                        ("insert", false) => format!("{{+{}+}}", text),
+                        ("delete", false) => format!("[-{}-]", text),
+                        _ => text,
+                    };
+                    out.push_str(&rendered);
+                }
+                out.push('\n');
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAIN: &str = r#"# Auth
+
+## Overview
+
+Auth.
+
+## Requirements
+
+### R1 - Login
+
+Users can log in with a password.
+
+#### Scenario: Password login
+
+- **WHEN** a password is given
+- **THEN** the user is logged in
+
+#### Scenario: Locked account
+
+- **WHEN** the account is locked
+- **THEN** login fails
+
+### R2 - Logout
+
+Users can log out.
+
+### R3 - Remember me
+
+Sessions last 30 days.
+"#;
+
+    #[test]
+    fn test_diff_spec_items() {
+        let delta = r#"# Auth
+
+## Overview
+
+Auth.
+
+## Requirements
+
+### R1 - Sign in
+
+Users can log in with a passkey or password.
+
+#### Scenario: Password login
+
+- **WHEN** a password is given
+- **THEN** the user is logged in
+
+### R2 - Logout
+
+Users can log out.
+
+### R4 - OAuth
+
+Users can log in with Google.
+
+## Data Model
+
+Sessions table.
+
+## Removed Requirements
+
+- R3: replaced by OAuth sessions
+"#;
+        let diff = diff_spec("auth.md", Some(MAIN), delta);
+        let summary: Vec<(ItemKind, ItemChange, &str)> = diff
+            .items
+            .iter()
+            .map(|i| (i.kind, i.change, i.key.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ItemKind::Requirement, ItemChange::Renamed, "R1"),
+                (ItemKind::Scenario, ItemChange::Removed, "Locked account"),
+                (ItemKind::Requirement, ItemChange::Added, "R4"),
+                (ItemKind::Requirement, ItemChange::Removed, "R3"),
+            ]
+        );
+
+        let renamed = &diff.items[0];
+        assert_eq!(renamed.renamed_from.as_deref(), Some("Login"));
+        let inserted: Vec<&str> = renamed
+            .words
+            .iter()
+            .filter(|w| w.op == "insert")
+            .map(|w| w.text.as_str())
+            .collect();
+        assert_eq!(inserted, vec!["passkey or "]);
+        assert_eq!(
+            diff.items[3].reason.as_deref(),
+            Some("replaced by OAuth sessions")
+        );
+        assert_eq!(diff.new_sections, vec!["Data Model"]);
+
+        assert_eq!(diff.metrics.added_count, 1);
+        assert_eq!(diff.metrics.renamed_count, 1);
+        assert_eq!(diff.metrics.removed_count, 1);
+        assert_eq!(diff.metrics.existing_req_count, 3);
+        assert!(diff.metrics.has_new_sections);
+
+        let text = render_text(&[diff], false);
+        assert!(text.contains("~ renamed requirement R1 'Sign in' (was 'Login')"));
+        assert!(text.contains("with a {+passkey or +}password."));
+        assert!(text.contains("- removed requirement R3 'Remember me': replaced by OAuth sessions"));
+    }
+
+    #[test]
+    fn test_diff_spec_new_and_unchanged() {
+        let diff = diff_spec("auth.md", None, MAIN);
+        assert!(diff.new_spec);
+        assert_eq!(diff.metrics.added_count, 3);
+        assert_eq!(diff.metrics.change_ratio, 1.0);
+
+        let diff = diff_spec("auth.md", Some(MAIN), MAIN);
+        assert!(diff.is_empty());
+        assert!(render_text(&[diff], false).contains("no changes"));
+    }
+}
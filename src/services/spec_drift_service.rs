@@ -0,0 +1,1076 @@
+//! Spec/code drift detection
+//!
+//! Re-runs the fillback AST analysis and compares the code with what the main
+//! specs reference: module names, symbols, HTTP endpoints, CLI commands, data
+//! models and file paths. Reports specified items the code no longer has,
+//! public surface no spec mentions, and items that look renamed.
+
+use crate::fillback::ast::{AnalysisContext, ModuleInfo, SupportedLanguage, SymbolKind};
+use crate::fillback::code::CodeStrategy;
+use crate::fillback::datamodel::DataModel;
+use crate::fillback::interfaces::InterfaceKind;
+use crate::fillback::scenarios::{common_root, is_test_file};
+use crate::fillback::Workspace;
+use crate::Result;
+use regex::Regex;
+use serde::Serialize;
+use similar::TextDiff;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use walkdir::WalkDir;
+
+/// Minimum name similarity for a removed item and a new one to count as a rename
+const RENAME_THRESHOLD: f32 = 0.6;
+
+/// Share of a module spec's symbols a code module must define to count as
+/// the renamed module
+const MODULE_OVERLAP_THRESHOLD: f32 = 0.5;
+
+/// Kind of item compared between specs and code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DriftKind {
+    Module,
+    Symbol,
+    Endpoint,
+    Command,
+    Entity,
+    File,
+}
+
+impl std::fmt::Display for DriftKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Module => write!(f, "module"),
+            Self::Symbol => write!(f, "symbol"),
+            Self::Endpoint => write!(f, "endpoint"),
+            Self::Command => write!(f, "command"),
+            Self::Entity => write!(f, "entity"),
+            Self::File => write!(f, "file"),
+        }
+    }
+}
+
+/// An item a spec names
+#[derive(Debug, Clone, Serialize)]
+pub struct SpecReference {
+    pub kind: DriftKind,
+    pub name: String,
+    /// Module whose spec lists the symbol
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module: Option<String>,
+    /// Spec file, relative to the project root
+    pub spec: String,
+    pub line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package: Option<String>,
+}
+
+/// Public code surface no spec mentions
+#[derive(Debug, Clone, Serialize)]
+pub struct CodeItem {
+    pub kind: DriftKind,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module: Option<String>,
+    /// Symbol kind, or the public symbol count of an unspecified module
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    pub path: String,
+    pub line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package: Option<String>,
+}
+
+/// A specified item that disappeared, matched to the code item replacing it
+#[derive(Debug, Clone, Serialize)]
+pub struct RenamedItem {
+    pub kind: DriftKind,
+    pub from: String,
+    pub to: String,
+    pub spec: String,
+    pub line: usize,
+    /// Where the new name is declared
+    pub path: String,
+    pub similarity: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package: Option<String>,
+}
+
+/// Drift between the main specs and the code
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SpecDriftReport {
+    pub specs_dir: String,
+    /// Workspace packages analyzed (empty for single-package trees)
+    pub packages: Vec<String>,
+    /// Specified items the code no longer has
+    pub removed: Vec<SpecReference>,
+    /// Public surface no spec mentions
+    pub unspecified: Vec<CodeItem>,
+    pub renamed: Vec<RenamedItem>,
+}
+
+impl SpecDriftReport {
+    /// Total number of drift findings
+    pub fn drift_count(&self) -> usize {
+        self.removed.len() + self.unspecified.len() + self.renamed.len()
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.drift_count() == 0
+    }
+
+    /// Append the findings of another (package) report
+    pub fn merge(&mut self, other: SpecDriftReport) {
+        self.removed.extend(other.removed);
+        self.unspecified.extend(other.unspecified);
+        self.renamed.extend(other.renamed);
+    }
+
+    /// Render the findings as markdown
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("# Spec Drift\n\n");
+        if self.is_clean() {
+            out.push_str(&format!(
+                "No drift: the specs in `{}` match the code.\n",
+                self.specs_dir
+            ));
+            return out;
+        }
+
+        out.push_str(&format!(
+            "{} finding(s) comparing the specs in `{}` with the code.\n",
+            self.drift_count(),
+            self.specs_dir
+        ));
+        if !self.removed.is_empty() {
+            out.push_str("\n## Removed from Code\n\nStill specified, no longer in the code:\n\n");
+            for reference in &self.removed {
+                let module = reference
+                    .module
+                    .as_deref()
+                    .map(|module| format!(" in module `{}`", module))
+                    .unwrap_or_default();
+                out.push_str(&format!(
+                    "- {}{} `{}`{} ({}:{})\n",
+                    package_prefix(&reference.package),
+                    reference.kind,
+                    reference.name,
+                    module,
+                    reference.spec,
+                    reference.line
+                ));
+            }
+        }
+        if !self.renamed.is_empty() {
+            out.push_str("\n## Renamed\n\nSpecified under the old name:\n\n");
+            for item in &self.renamed {
+                out.push_str(&format!(
+                    "- {}{} `{}` → `{}` ({}:{}, declared in `{}`)\n",
+                    package_prefix(&item.package),
+                    item.kind,
+                    item.from,
+                    item.to,
+                    item.spec,
+                    item.line,
+                    item.path
+                ));
+            }
+        }
+        if !self.unspecified.is_empty() {
+            out.push_str("\n## Unspecified\n\nPublic surface no spec mentions:\n\n");
+            for item in &self.unspecified {
+                let detail = item
+                    .detail
+                    .as_deref()
+                    .map(|detail| format!(" ({})", detail))
+                    .unwrap_or_default();
+                out.push_str(&format!(
+                    "- {}{} `{}`{} in `{}:{}`\n",
+                    package_prefix(&item.package),
+                    item.kind,
+                    item.name,
+                    detail,
+                    item.path,
+                    item.line
+                ));
+            }
+        }
+        out
+    }
+}
+
+fn package_prefix(package: &Option<String>) -> String {
+    package
+        .as_deref()
+        .map(|package| format!("[{}] ", package))
+        .unwrap_or_default()
+}
+
+/// What a set of spec documents references and mentions
+#[derive(Debug, Default)]
+pub struct SpecIndex {
+    /// Items the specs name explicitly, checked against the code
+    pub references: Vec<SpecReference>,
+    /// Identifier-like words anywhere in the specs
+    words: HashSet<String>,
+    /// Normalized `METHOD /path` endpoints anywhere in the specs
+    endpoints: HashSet<String>,
+    /// Lowercased spec text, for multi-word command names
+    text: String,
+}
+
+impl SpecIndex {
+    /// Index every markdown spec under `specs_dir`, skipping `exclude`
+    /// directories (other packages' spec folders)
+    pub fn load(specs_dir: &Path, project_root: &Path, exclude: &[PathBuf]) -> Result<Self> {
+        let mut index = Self::default();
+        if !specs_dir.is_dir() {
+            return Ok(index);
+        }
+
+        let mut files: Vec<PathBuf> = WalkDir::new(specs_dir)
+            .into_iter()
+            .filter_entry(|e| !exclude.iter().any(|dir| e.path() == dir))
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .map(|e| e.into_path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "md"))
+            .collect();
+        files.sort();
+
+        for path in files {
+            let content = fs::read_to_string(&path)?;
+            let spec = path
+                .strip_prefix(project_root)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            index.add(&spec, &content);
+        }
+        Ok(index)
+    }
+
+    /// Index one spec document
+    ///
+    /// Module specs are recognized by their `## Symbols` table; endpoints,
+    /// `function()` calls, file paths, CLI commands and persisted entities are
+    /// picked up from prose and ER diagrams anywhere.
+    pub fn add(&mut self, spec: &str, content: &str) {
+        let mut heading: Option<(String, usize)> = None;
+        let mut in_symbols = false;
+        // Some(is_er_diagram) inside a fenced block
+        let mut fence: Option<bool> = None;
+        let mut table = Vec::new();
+        let mut references = Vec::new();
+
+        for (index, line) in content.lines().enumerate() {
+            let number = index + 1;
+            let trimmed = line.trim();
+            self.words
+                .extend(word_re().find_iter(line).map(|m| m.as_str().to_string()));
+            for caps in endpoint_re().captures_iter(line) {
+                self.endpoints.insert(endpoint_key(&caps[1], &caps[2]));
+            }
+            self.text.push_str(&line.to_lowercase());
+            self.text.push('\n');
+
+            if trimmed.starts_with("```") {
+                fence = match fence {
+                    Some(_) => None,
+                    None => Some(false),
+                };
+                continue;
+            }
+            if let Some(is_erd) = fence {
+                if trimmed == "erDiagram" {
+                    fence = Some(true);
+                } else if is_erd {
+                    if let Some(caps) = erd_entity_re().captures(trimmed) {
+                        references.push((DriftKind::Entity, caps[1].to_string(), number));
+                    }
+                }
+                continue;
+            }
+
+            if let Some(name) = trimmed.strip_prefix("# Specification:") {
+                heading = Some((name.trim().to_string(), number));
+                continue;
+            }
+            if let Some(section) = trimmed.strip_prefix("## ") {
+                in_symbols = section.trim() == "Symbols";
+                continue;
+            }
+            if in_symbols && trimmed.starts_with('|') {
+                if let Some(name) = symbol_row(trimmed) {
+                    table.push((name, number));
+                }
+                continue;
+            }
+
+            for caps in endpoint_re().captures_iter(line) {
+                let name = format!("{} {}", &caps[1], trim_prose(&caps[2]));
+                references.push((DriftKind::Endpoint, name, number));
+            }
+            for caps in command_re().captures_iter(line) {
+                references.push((DriftKind::Command, caps[1].to_string(), number));
+            }
+            for caps in persist_re().captures_iter(line) {
+                references.push((DriftKind::Entity, caps[1].to_string(), number));
+            }
+            for caps in covers_re().captures_iter(line) {
+                references.push((DriftKind::Symbol, caps[1].to_string(), number));
+            }
+            for caps in code_span_re().captures_iter(line) {
+                let span = &caps[1];
+                if let Some(call) = call_re().captures(span) {
+                    references.push((DriftKind::Symbol, call[1].to_string(), number));
+                } else if let Some(path) = file_reference(span) {
+                    references.push((DriftKind::File, path, number));
+                }
+            }
+        }
+
+        let module = match heading {
+            Some((module, line)) if !table.is_empty() => {
+                self.push(DriftKind::Module, &module, None, spec, line);
+                Some(module)
+            }
+            _ => None,
+        };
+        for (name, line) in table {
+            self.push(DriftKind::Symbol, &name, module.clone(), spec, line);
+        }
+        for (kind, name, line) in references {
+            self.push(kind, &name, None, spec, line);
+        }
+    }
+
+    /// Record a reference once per spec
+    fn push(
+        &mut self,
+        kind: DriftKind,
+        name: &str,
+        module: Option<String>,
+        spec: &str,
+        line: usize,
+    ) {
+        let duplicate = self
+            .references
+            .iter()
+            .any(|r| r.kind == kind && r.name == name && r.module == module && r.spec == spec);
+        if !duplicate {
+            self.references.push(SpecReference {
+                kind,
+                name: name.to_string(),
+                module,
+                spec: spec.to_string(),
+                line,
+                package: None,
+            });
+        }
+    }
+
+    /// Modules the specs describe with a symbols table
+    fn modules(&self) -> HashSet<&str> {
+        self.references
+            .iter()
+            .filter(|r| r.kind == DriftKind::Module)
+            .map(|r| r.name.as_str())
+            .collect()
+    }
+
+    /// Whether a name appears anywhere in the specs
+    fn mentions(&self, name: &str) -> bool {
+        let last = name.rsplit(['.', ':']).next().unwrap_or(name);
+        self.words.contains(name) || self.words.contains(last)
+    }
+}
+
+/// Analyze the code under `source` and compare it with the specs
+///
+/// In a multi-package workspace every package is compared with its own spec
+/// folder (`specs_dir/<package>/`, falling back to `specs_dir` without other
+/// packages' folders); `package` restricts the check to one package.
+pub fn check_drift(
+    strategy: &CodeStrategy,
+    source: &Path,
+    package: Option<&str>,
+    specs_dir: &Path,
+    project_root: &Path,
+) -> Result<SpecDriftReport> {
+    let mut report = SpecDriftReport {
+        specs_dir: specs_dir
+            .strip_prefix(project_root)
+            .unwrap_or(specs_dir)
+            .to_string_lossy()
+            .replace('\\', "/"),
+        ..SpecDriftReport::default()
+    };
+
+    let workspace = Workspace::detect(source)?;
+    let packages = match package {
+        Some(name) => vec![workspace.require(name)?],
+        None if workspace.is_multi_package() => workspace.packages.iter().collect(),
+        None => {
+            let (context, _) = strategy.analyze_codebase(source)?;
+            let specs = SpecIndex::load(specs_dir, project_root, &[])?;
+            report.merge(detect_drift(&context, &specs, project_root, None));
+            return Ok(report);
+        }
+    };
+
+    let package_folders: Vec<PathBuf> = workspace
+        .packages
+        .iter()
+        .map(|package| specs_dir.join(package.slug()))
+        .collect();
+    for package in packages {
+        // Packages without supported sources have no surface to compare
+        let Ok((context, _)) = strategy.analyze_package(&workspace, package) else {
+            continue;
+        };
+        let package_specs = specs_dir.join(package.slug());
+        let specs = if package_specs.is_dir() {
+            SpecIndex::load(&package_specs, project_root, &[])?
+        } else {
+            SpecIndex::load(specs_dir, project_root, &package_folders)?
+        };
+        report.packages.push(package.name.clone());
+        report.merge(detect_drift(
+            &context,
+            &specs,
+            project_root,
+            Some(&package.name),
+        ));
+    }
+    Ok(report)
+}
+
+/// Compare one analyzed code tree with the specs
+pub fn detect_drift(
+    context: &AnalysisContext,
+    specs: &SpecIndex,
+    project_root: &Path,
+    package: Option<&str>,
+) -> SpecDriftReport {
+    let root = common_root(context);
+    let display = |path: &str| {
+        Path::new(path)
+            .strip_prefix(project_root)
+            .unwrap_or(Path::new(path))
+            .to_string_lossy()
+            .replace('\\', "/")
+    };
+    let package = package.map(str::to_string);
+    let model = DataModel::from_analysis(context);
+
+    // Everything the code declares, public or not
+    let module_names: HashSet<&str> = context.modules.iter().map(|m| m.name.as_str()).collect();
+    let symbols: HashSet<&str> = context
+        .modules
+        .iter()
+        .flat_map(|m| &m.symbols)
+        .map(|s| s.name.as_str())
+        .collect();
+    let mut endpoints = HashSet::new();
+    let mut commands = HashSet::new();
+    for interface in context.modules.iter().flat_map(|m| &m.interfaces) {
+        match interface.kind {
+            InterfaceKind::Http => {
+                endpoints.insert(endpoint_key(
+                    interface.method.as_deref().unwrap_or("ANY"),
+                    &interface.path,
+                ));
+            }
+            InterfaceKind::Cli => {
+                commands.insert(interface.path.as_str());
+            }
+        }
+    }
+    let entities: HashSet<String> = model
+        .entities
+        .iter()
+        .flat_map(|e| std::iter::once(&e.name).chain(&e.table))
+        .map(|name| name.to_lowercase())
+        .collect();
+    let mut paths: Vec<&str> = context.modules.iter().map(|m| m.path.as_str()).collect();
+    paths.extend(model.entities.iter().map(|e| e.path.as_str()));
+
+    let mut removed: Vec<SpecReference> = specs
+        .references
+        .iter()
+        .filter(|reference| {
+            let name = reference.name.as_str();
+            let exists = match reference.kind {
+                DriftKind::Module => module_names.contains(name),
+                DriftKind::Symbol => symbols.contains(name),
+                DriftKind::Endpoint => {
+                    let (method, path) = name.split_once(' ').unwrap_or(("ANY", name));
+                    endpoints.contains(&endpoint_key(method, path))
+                }
+                DriftKind::Command => commands.contains(name),
+                DriftKind::Entity => entities.contains(&name.to_lowercase()),
+                DriftKind::File => {
+                    paths.iter().any(|path| Path::new(path).ends_with(name))
+                        || project_root.join(name).exists()
+                        || root.join(name).exists()
+                }
+            };
+            !exists
+        })
+        .map(|reference| SpecReference {
+            package: package.clone(),
+            ..reference.clone()
+        })
+        .collect();
+
+    // Public surface outside test files
+    let specified_modules = specs.modules();
+    let surface: Vec<&ModuleInfo> = context
+        .modules
+        .iter()
+        .filter(|m| {
+            !is_test_file(
+                Path::new(&m.path)
+                    .strip_prefix(&root)
+                    .unwrap_or(Path::new(&m.path)),
+            )
+        })
+        .collect();
+    let mut unspecified = Vec::new();
+    for module in &surface {
+        let public: Vec<_> = module
+            .symbols
+            .iter()
+            .filter(|s| s.is_public && s.kind != SymbolKind::Module)
+            .collect();
+        let unmentioned: Vec<_> = public.iter().filter(|s| !specs.mentions(&s.name)).collect();
+        let module_mentioned =
+            specified_modules.contains(module.name.as_str()) || specs.mentions(&module.name);
+        if !public.is_empty() && unmentioned.len() == public.len() && !module_mentioned {
+            unspecified.push(CodeItem {
+                kind: DriftKind::Module,
+                name: module.name.clone(),
+                module: None,
+                detail: Some(format!("{} public symbols", public.len())),
+                path: display(&module.path),
+                line: 1,
+                package: package.clone(),
+            });
+            continue;
+        }
+        for symbol in unmentioned {
+            unspecified.push(CodeItem {
+                kind: DriftKind::Symbol,
+                name: symbol.name.clone(),
+                module: Some(module.name.clone()),
+                detail: Some(symbol.kind.to_string()),
+                path: display(&module.path),
+                line: symbol.line,
+                package: package.clone(),
+            });
+        }
+        for interface in &module.interfaces {
+            let (kind, name, specified) = match interface.kind {
+                InterfaceKind::Http => {
+                    let method = interface.method.as_deref().unwrap_or("ANY");
+                    let key = endpoint_key(method, &interface.path);
+                    (
+                        DriftKind::Endpoint,
+                        interface.title(),
+                        specs.endpoints.contains(&key),
+                    )
+                }
+                InterfaceKind::Cli => (
+                    DriftKind::Command,
+                    interface.path.clone(),
+                    specs.text.contains(&interface.path.to_lowercase()),
+                ),
+            };
+            if !specified {
+                unspecified.push(CodeItem {
+                    kind,
+                    name,
+                    module: Some(module.name.clone()),
+                    detail: interface.handler.clone(),
+                    path: display(&module.path),
+                    line: interface.line,
+                    package: package.clone(),
+                });
+            }
+        }
+    }
+    for entity in &model.entities {
+        let specified = std::iter::once(&entity.name)
+            .chain(&entity.table)
+            .any(|name| specs.mentions(name) || specs.mentions(&name.to_lowercase()));
+        if !specified {
+            unspecified.push(CodeItem {
+                kind: DriftKind::Entity,
+                name: entity.name.clone(),
+                module: None,
+                detail: entity.table.clone(),
+                path: display(&entity.path),
+                line: entity.line,
+                package: package.clone(),
+            });
+        }
+    }
+
+    let mut renamed = Vec::new();
+    pair_modules(
+        specs,
+        &surface,
+        &mut removed,
+        &mut unspecified,
+        &mut renamed,
+        &display,
+    );
+    pair_files(&paths, &mut removed, &mut renamed, &display);
+    pair_renames(&module_names, &mut removed, &mut unspecified, &mut renamed);
+
+    SpecDriftReport {
+        removed,
+        unspecified,
+        renamed,
+        ..SpecDriftReport::default()
+    }
+}
+
+/// Match removed module specs to the code module defining most of their symbols
+fn pair_modules(
+    specs: &SpecIndex,
+    surface: &[&ModuleInfo],
+    removed: &mut Vec<SpecReference>,
+    unspecified: &mut Vec<CodeItem>,
+    renamed: &mut Vec<RenamedItem>,
+    display: &dyn Fn(&str) -> String,
+) {
+    let specified = specs.modules();
+    removed.retain(|reference| {
+        if reference.kind != DriftKind::Module {
+            return true;
+        }
+        let table: Vec<&str> = specs
+            .references
+            .iter()
+            .filter(|r| r.kind == DriftKind::Symbol && r.module.as_ref() == Some(&reference.name))
+            .map(|r| r.name.as_str())
+            .collect();
+        let best = surface
+            .iter()
+            .filter(|m| !specified.contains(m.name.as_str()))
+            .map(|m| {
+                let defined = table
+                    .iter()
+                    .filter(|name| m.symbols.iter().any(|s| s.name == **name))
+                    .count();
+                (defined as f32 / table.len().max(1) as f32, m)
+            })
+            .max_by(|a, b| a.0.total_cmp(&b.0));
+        let Some((overlap, module)) =
+            best.filter(|(overlap, _)| *overlap >= MODULE_OVERLAP_THRESHOLD)
+        else {
+            return true;
+        };
+        unspecified.retain(|item| !(item.kind == DriftKind::Module && item.name == module.name));
+        renamed.push(RenamedItem {
+            kind: DriftKind::Module,
+            from: reference.name.clone(),
+            to: module.name.clone(),
+            spec: reference.spec.clone(),
+            line: reference.line,
+            path: display(&module.path),
+            similarity: overlap,
+            package: reference.package.clone(),
+        });
+        false
+    });
+}
+
+/// Match removed file paths to a single analyzed file with the same name
+fn pair_files(
+    paths: &[&str],
+    removed: &mut Vec<SpecReference>,
+    renamed: &mut Vec<RenamedItem>,
+    display: &dyn Fn(&str) -> String,
+) {
+    removed.retain(|reference| {
+        if reference.kind != DriftKind::File {
+            return true;
+        }
+        let file_name = Path::new(&reference.name).file_name();
+        let moved: HashSet<String> = paths
+            .iter()
+            .filter(|path| Path::new(path).file_name() == file_name)
+            .map(|path| display(path))
+            .collect();
+        if moved.len() != 1 {
+            return true;
+        }
+        let to = moved.into_iter().next().unwrap_or_default();
+        renamed.push(RenamedItem {
+            kind: DriftKind::File,
+            from: reference.name.clone(),
+            similarity: similarity(&reference.name, &to),
+            to: to.clone(),
+            spec: reference.spec.clone(),
+            line: reference.line,
+            path: to,
+            package: reference.package.clone(),
+        });
+        false
+    });
+}
+
+/// Pair removed symbols, endpoints, commands and entities with unspecified
+/// items of the same kind and a similar name, most similar first
+fn pair_renames(
+    module_names: &HashSet<&str>,
+    removed: &mut Vec<SpecReference>,
+    unspecified: &mut Vec<CodeItem>,
+    renamed: &mut Vec<RenamedItem>,
+) {
+    let mut candidates = Vec::new();
+    for (r, reference) in removed.iter().enumerate() {
+        for (u, item) in unspecified.iter().enumerate() {
+            if reference.kind != item.kind || reference.kind == DriftKind::Module {
+                continue;
+            }
+            // A symbol stays in its module unless the module itself is gone
+            if let (Some(from), Some(to)) = (&reference.module, &item.module) {
+                if from != to && module_names.contains(from.as_str()) {
+                    continue;
+                }
+            }
+            if reference.kind == DriftKind::Endpoint
+                && reference.name.split(' ').next() != item.name.split(' ').next()
+            {
+                continue;
+            }
+            let score = similarity(&reference.name, &item.name);
+            if score >= RENAME_THRESHOLD {
+                candidates.push((score, r, u));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let mut paired_removed = HashSet::new();
+    let mut paired_unspecified = HashSet::new();
+    for (score, r, u) in candidates {
+        if paired_removed.contains(&r) || paired_unspecified.contains(&u) {
+            continue;
+        }
+        paired_removed.insert(r);
+        paired_unspecified.insert(u);
+        let (reference, item) = (&removed[r], &unspecified[u]);
+        renamed.push(RenamedItem {
+            kind: reference.kind,
+            from: reference.name.clone(),
+            to: item.name.clone(),
+            spec: reference.spec.clone(),
+            line: reference.line,
+            path: format!("{}:{}", item.path, item.line),
+            similarity: score,
+            package: reference.package.clone(),
+        });
+    }
+
+    let mut index = 0;
+    removed.retain(|_| {
+        index += 1;
+        !paired_removed.contains(&(index - 1))
+    });
+    let mut index = 0;
+    unspecified.retain(|_| {
+        index += 1;
+        !paired_unspecified.contains(&(index - 1))
+    });
+}
+
+/// Case-insensitive name similarity in [0, 1]
+fn similarity(a: &str, b: &str) -> f32 {
+    TextDiff::from_chars(a.to_lowercase().as_str(), b.to_lowercase().as_str()).ratio()
+}
+
+/// `METHOD /path` with path parameters (`:id`, `{id}`, `<id>`, `[id]`) unified
+fn endpoint_key(method: &str, path: &str) -> String {
+    let path = trim_prose(path);
+    let segments: Vec<&str> = path
+        .trim_end_matches('/')
+        .split('/')
+        .map(|segment| {
+            if segment.starts_with([':', '{', '<', '[']) {
+                ":"
+            } else {
+                segment
+            }
+        })
+        .collect();
+    let path = segments.join("/");
+    format!(
+        "{} {}",
+        method.to_uppercase(),
+        if path.is_empty() { "/" } else { &path }
+    )
+}
+
+/// Strip sentence punctuation trailing a path written in prose
+fn trim_prose(path: &str) -> &str {
+    path.trim_end_matches(['.', ',', ';', ':'])
+}
+
+/// Symbol name from a `| name | kind | ... |` table row
+fn symbol_row(line: &str) -> Option<String> {
+    let name = line.strip_prefix('|')?.split('|').next()?.trim();
+    let is_header = name == "Name" || name.is_empty() || name.starts_with('-');
+    (!is_header).then(|| name.to_string())
+}
+
+/// Source or schema path named in a code span (`src/lib.rs`, `src/lib.rs:12`);
+/// bare file names are skipped since prose uses them for libraries (`highlight.js`)
+fn file_reference(span: &str) -> Option<String> {
+    let path = span.strip_prefix("./").unwrap_or(span);
+    let path = match path.rsplit_once(':') {
+        Some((path, line)) if line.chars().all(|c| c.is_ascii_digit()) => path,
+        _ => path,
+    };
+    let caps = file_re().captures(path)?;
+    let ext = &caps[1];
+    let known = SupportedLanguage::from_extension(ext).is_some() || matches!(ext, "sql" | "prisma");
+    known.then(|| path.to_string())
+}
+
+fn word_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"[A-Za-z_][A-Za-z0-9_]*").unwrap())
+}
+
+fn code_span_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"`([^`]+)`").unwrap())
+}
+
+fn call_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^(?:\w+(?:::|\.))*([A-Za-z_]\w*)\(\)$").unwrap())
+}
+
+fn endpoint_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"\b(GET|POST|PUT|PATCH|DELETE|HEAD|OPTIONS|ANY)\s+(/[^\s`|)\]'\x22]*)").unwrap()
+    })
+}
+
+fn command_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"(?i)\bprovides? the `([^`]+)` command").unwrap())
+}
+
+fn persist_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\bpersists? `([^`]+)` records").unwrap())
+}
+
+fn covers_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\bcovers `([A-Za-z_]\w*)`").unwrap())
+}
+
+fn erd_entity_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^([A-Za-z_][\w-]*)\s*\{$").unwrap())
+}
+
+fn file_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^/?(?:[\w.@-]+/)+[\w@-][\w.@-]*\.(\w+)$").unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fillback::DependencyGraph;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn analyze(root: &Path) -> AnalysisContext {
+        CodeStrategy::new().analyze_codebase(root).unwrap().0
+    }
+
+    #[test]
+    fn test_generated_specs_match_code_until_it_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(
+            root.join("src/users.rs"),
+            "pub struct User {}\n\npub fn create_user() {}\n\npub fn delete_user() {}\n",
+        )
+        .unwrap();
+        fs::write(root.join("src/orders.rs"), "pub fn place_order() {}\n").unwrap();
+
+        let strategy = CodeStrategy::new();
+        let context = analyze(root);
+        let graph = DependencyGraph::from_analysis(&context);
+        let specs_dir = root.join("agentd/specs");
+        strategy
+            .generate_specs(&context, &graph, &specs_dir, &HashMap::new())
+            .unwrap();
+
+        let specs = SpecIndex::load(&specs_dir, root, &[]).unwrap();
+        let report = detect_drift(&context, &specs, root, None);
+        assert!(report.is_clean(), "{}", report.to_markdown());
+
+        fs::write(
+            root.join("src/users.rs"),
+            "pub struct User {}\n\npub fn create_user() {}\n\npub fn remove_user() {}\n",
+        )
+        .unwrap();
+        fs::write(root.join("src/orders.rs"), "pub fn export_csv() {}\n").unwrap();
+
+        let report = detect_drift(&analyze(root), &specs, root, None);
+        assert_eq!(report.renamed.len(), 1);
+        assert_eq!(report.renamed[0].from, "delete_user");
+        assert_eq!(report.renamed[0].to, "remove_user");
+        assert_eq!(report.renamed[0].spec, "agentd/specs/users.md");
+
+        assert_eq!(report.removed.len(), 1);
+        assert_eq!(report.removed[0].kind, DriftKind::Symbol);
+        assert_eq!(report.removed[0].name, "place_order");
+        assert_eq!(report.removed[0].module.as_deref(), Some("orders"));
+
+        assert_eq!(report.unspecified.len(), 1);
+        assert_eq!(report.unspecified[0].name, "export_csv");
+        assert_eq!(report.unspecified[0].path, "src/orders.rs");
+
+        let markdown = report.to_markdown();
+        assert!(markdown.contains("3 finding(s)"));
+        assert!(markdown.contains("- symbol `delete_user` → `remove_user`"));
+    }
+
+    #[test]
+    fn test_renamed_module_matched_by_symbols() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src/core")).unwrap();
+        fs::write(
+            root.join("src/core/accounts.rs"),
+            "pub fn create_user() {}\n\npub fn delete_user() {}\n",
+        )
+        .unwrap();
+
+        let mut specs = SpecIndex::default();
+        specs.add(
+            "agentd/specs/users.md",
+            "# Specification: users\n\n## Symbols\n\n| Name | Kind | Visibility | Line |\n|------|------|------------|------|\n| create_user | function | public | 1 |\n| delete_user | function | public | 3 |\n\nSee `src/accounts.rs` for details.\n",
+        );
+
+        let report = detect_drift(&analyze(root), &specs, root, None);
+        assert!(report.removed.is_empty());
+        assert!(report.unspecified.is_empty());
+        assert_eq!(report.renamed.len(), 2);
+        assert_eq!(report.renamed[0].kind, DriftKind::Module);
+        assert_eq!(report.renamed[0].to, "accounts");
+        assert_eq!(report.renamed[0].similarity, 1.0);
+        assert_eq!(report.renamed[1].kind, DriftKind::File);
+        assert_eq!(report.renamed[1].from, "src/accounts.rs");
+        assert_eq!(report.renamed[1].to, "src/core/accounts.rs");
+    }
+
+    #[test]
+    fn test_check_drift_per_workspace_package() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        )
+        .unwrap();
+        for name in ["core", "cli"] {
+            let dir = root.join("crates").join(name);
+            fs::create_dir_all(dir.join("src")).unwrap();
+            fs::write(
+                dir.join("Cargo.toml"),
+                format!("[package]\nname = \"{}\"\n", name),
+            )
+            .unwrap();
+        }
+        fs::write(root.join("crates/core/src/lib.rs"), "pub fn load() {}\n").unwrap();
+        fs::write(
+            root.join("crates/cli/src/main.rs"),
+            "pub fn parse_args() {}\n",
+        )
+        .unwrap();
+
+        let specs_dir = root.join("agentd/specs");
+        fs::create_dir_all(specs_dir.join("core")).unwrap();
+        fs::write(
+            specs_dir.join("core/lib.md"),
+            "Calls `load()` on startup.\n",
+        )
+        .unwrap();
+
+        let strategy = CodeStrategy::new();
+        let report = check_drift(&strategy, root, None, &specs_dir, root).unwrap();
+        assert_eq!(report.specs_dir, "agentd/specs");
+        assert_eq!(report.packages, vec!["cli", "core"]);
+        assert!(report.removed.is_empty());
+        assert_eq!(report.unspecified.len(), 1);
+        assert_eq!(report.unspecified[0].kind, DriftKind::Module);
+        assert_eq!(report.unspecified[0].package.as_deref(), Some("cli"));
+        assert_eq!(report.unspecified[0].path, "crates/cli/src/main.rs");
+
+        let report = check_drift(&strategy, root, Some("core"), &specs_dir, root).unwrap();
+        assert!(report.is_clean());
+        assert!(check_drift(&strategy, root, Some("web"), &specs_dir, root).is_err());
+    }
+
+    #[test]
+    fn test_spec_index_references() {
+        let mut specs = SpecIndex::default();
+        specs.add(
+            "agentd/specs/api.md",
+            "# Specification: API\n\n\
+             The system SHALL handle `GET /users/{id}` requests with `get_user()`.\n\
+             The CLI SHALL provide the `sync` command.\n\
+             The system SHALL persist `Invoice` records.\n\
+             Uses `highlight.js` and `src/api/routes.rs:12`.\n\n\
+             ```mermaid\nerDiagram\n    users {\n        int id\n    }\n```\n\n\
+             ```\nPOST /ignored is only mentioned\n```\n",
+        );
+
+        let references: Vec<(DriftKind, &str)> = specs
+            .references
+            .iter()
+            .map(|r| (r.kind, r.name.as_str()))
+            .collect();
+        assert_eq!(
+            references,
+            vec![
+                (DriftKind::Endpoint, "GET /users/{id}"),
+                (DriftKind::Symbol, "get_user"),
+                (DriftKind::Command, "sync"),
+                (DriftKind::Entity, "Invoice"),
+                (DriftKind::File, "src/api/routes.rs"),
+                (DriftKind::Entity, "users"),
+            ]
+        );
+        assert_eq!(specs.references[4].line, 6);
+        assert!(specs.endpoints.contains("POST /ignored"));
+        assert!(specs.mentions("highlight"));
+    }
+
+    #[test]
+    fn test_endpoint_key_normalizes_parameters() {
+        assert_eq!(endpoint_key("get", "/users/:id"), "GET /users/:");
+        assert_eq!(endpoint_key("GET", "/users/{id}/"), "GET /users/:");
+        assert_eq!(endpoint_key("GET", "/users/<int:id>."), "GET /users/:");
+        assert_eq!(endpoint_key("ANY", "/"), "ANY /");
+    }
+}
@@ -0,0 +1,562 @@
+//! Spec history service - Snapshots of main specs per merged change
+//!
+//! Every merge-change records which files under `agentd/specs` it altered,
+//! with their content before and after the merge, in
+//! `agentd/spec-history/<change-id>/`:
+//!
+//! - `snapshot.yaml` - change ID, merge time, archive location, touched files
+//! - `before/<path>` - content before the merge (absent for new files)
+//! - `after/<path>` - content right after the merge (absent for deletions)
+//!
+//! The snapshots answer "which change altered this spec" and let a merge be
+//! reverted with `agentd unmerge`. `agentd/specs/CHANGELOG.md` is shared by
+//! all merges, so only the lines a merge added to it are recorded and removed
+//! again on unmerge.
+
+use crate::models::frontmatter::StatePhase;
+use crate::services::history_service::{self, DiffStats};
+use crate::state::StateManager;
+use crate::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use similar::{ChangeTag, TextDiff};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Directory holding merge snapshots, relative to the project root
+pub const SPEC_HISTORY_DIR: &str = "agentd/spec-history";
+
+const MANIFEST: &str = "snapshot.yaml";
+
+/// Changelog under agentd/specs that every merge adds an entry to
+const CHANGELOG: &str = "CHANGELOG.md";
+
+/// Record of the spec files one merged change altered
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeSnapshot {
+    pub change_id: String,
+    pub merged_at: DateTime<Utc>,
+    /// Archive directory of the change, relative to the project root
+    pub archive_path: String,
+    pub files: Vec<SnapshotFile>,
+    /// Sections (or blocks of lines) the merge added to CHANGELOG.md
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changelog_entry: Vec<String>,
+}
+
+/// A file under agentd/specs altered by a merge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotFile {
+    /// Path relative to agentd/specs
+    pub path: String,
+    pub existed_before: bool,
+    pub exists_after: bool,
+}
+
+impl SnapshotFile {
+    /// "added", "modified" or "deleted"
+    pub fn change(&self) -> &'static str {
+        match (self.existed_before, self.exists_after) {
+            (false, _) => "added",
+            (true, true) => "modified",
+            (true, false) => "deleted",
+        }
+    }
+}
+
+/// One merge that altered a given spec
+#[derive(Debug, Clone)]
+pub struct SpecHistoryEntry {
+    pub snapshot: MergeSnapshot,
+    pub file: SnapshotFile,
+    pub stats: DiffStats,
+}
+
+/// Result of reverting a merge
+#[derive(Debug, Clone)]
+pub struct UnmergeReport {
+    pub restored: Vec<SnapshotFile>,
+    /// Active change directory the change was moved back to
+    pub change_dir: PathBuf,
+    /// Whether the merge's CHANGELOG entry was found and removed (false when
+    /// it was edited since, or the merge added none)
+    pub changelog_reverted: bool,
+}
+
+fn snapshot_dir(change_id: &str, project_root: &Path) -> PathBuf {
+    project_root.join(SPEC_HISTORY_DIR).join(change_id)
+}
+
+/// Record the files a merge changed by comparing the pre-merge copy of
+/// agentd/specs (`before_dir`) with the current agentd/specs
+///
+/// Returns None when the merge did not alter any file.
+pub fn record_merge(
+    change_id: &str,
+    before_dir: &Path,
+    archive_path: &Path,
+    project_root: &Path,
+) -> Result<Option<MergeSnapshot>> {
+    let specs_dir = project_root.join("agentd/specs");
+    let paths: BTreeSet<String> = list_files(before_dir)
+        .into_iter()
+        .chain(list_files(&specs_dir))
+        .collect();
+
+    let dir = snapshot_dir(change_id, project_root);
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir)?;
+    }
+
+    let mut files = Vec::new();
+    let mut changelog_entry = Vec::new();
+    for path in paths {
+        let before = read_optional(&before_dir.join(&path))?;
+        let after = read_optional(&specs_dir.join(&path))?;
+        if before == after {
+            continue;
+        }
+        if path == CHANGELOG {
+            changelog_entry = changelog_additions(
+                before.as_deref().unwrap_or_default(),
+                after.as_deref().unwrap_or_default(),
+            );
+            continue;
+        }
+        if let Some(content) = &before {
+            write_file(&dir.join("before").join(&path), content)?;
+        }
+        if let Some(content) = &after {
+            write_file(&dir.join("after").join(&path), content)?;
+        }
+        files.push(SnapshotFile {
+            path,
+            existed_before: before.is_some(),
+            exists_after: after.is_some(),
+        });
+    }
+
+    if files.is_empty() && changelog_entry.is_empty() {
+        return Ok(None);
+    }
+
+    let snapshot = MergeSnapshot {
+        change_id: change_id.to_string(),
+        merged_at: Utc::now(),
+        archive_path: archive_path
+            .strip_prefix(project_root)
+            .unwrap_or(archive_path)
+            .to_string_lossy()
+            .replace('\\', "/"),
+        files,
+        changelog_entry,
+    };
+    std::fs::write(dir.join(MANIFEST), serde_yaml::to_string(&snapshot)?)?;
+    Ok(Some(snapshot))
+}
+
+/// Load the snapshot of a merged change, if one was recorded
+pub fn load_snapshot(change_id: &str, project_root: &Path) -> Result<Option<MergeSnapshot>> {
+    let path = snapshot_dir(change_id, project_root).join(MANIFEST);
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(serde_yaml::from_str(&std::fs::read_to_string(path)?)?))
+}
+
+/// All recorded merges, oldest first
+pub fn list_snapshots(project_root: &Path) -> Result<Vec<MergeSnapshot>> {
+    let dir = project_root.join(SPEC_HISTORY_DIR);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut snapshots = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path().join(MANIFEST);
+        if path.exists() {
+            snapshots.push(serde_yaml::from_str::<MergeSnapshot>(
+                &std::fs::read_to_string(path)?,
+            )?);
+        }
+    }
+    snapshots.sort_by_key(|s| s.merged_at);
+    Ok(snapshots)
+}
+
+/// Merges that altered a spec ("auth", "auth.md" or a nested path), oldest first
+pub fn spec_history(spec: &str, project_root: &Path) -> Result<Vec<SpecHistoryEntry>> {
+    let wanted = spec
+        .trim_start_matches("agentd/specs/")
+        .trim_end_matches(".md");
+
+    let mut entries = Vec::new();
+    for snapshot in list_snapshots(project_root)? {
+        let Some(file) = snapshot
+            .files
+            .iter()
+            .find(|f| f.path.trim_end_matches(".md") == wanted)
+            .cloned()
+        else {
+            continue;
+        };
+        let before = read_version(&snapshot.change_id, &file.path, false, project_root)?;
+        let after = read_version(&snapshot.change_id, &file.path, true, project_root)?;
+        let stats = history_service::diff_stats(
+            before.as_deref().unwrap_or(""),
+            after.as_deref().unwrap_or(""),
+        );
+        entries.push(SpecHistoryEntry {
+            snapshot,
+            file,
+            stats,
+        });
+    }
+    Ok(entries)
+}
+
+/// Content of a spec before (`after = false`) or after a change's merge
+pub fn read_version(
+    change_id: &str,
+    path: &str,
+    after: bool,
+    project_root: &Path,
+) -> Result<Option<String>> {
+    let side = if after { "after" } else { "before" };
+    read_optional(&snapshot_dir(change_id, project_root).join(side).join(path))
+}
+
+/// Revert a merged change: restore the spec files it altered and move the
+/// change from the archive back to agentd/changes
+///
+/// Refuses if a file was modified after the merge (a later change built on
+/// it) unless `force` is set.
+pub fn unmerge(change_id: &str, force: bool, project_root: &Path) -> Result<UnmergeReport> {
+    let snapshot = load_snapshot(change_id, project_root)?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "No merge snapshot for '{}'. Only changes merged with spec history can be unmerged.",
+            change_id
+        )
+    })?;
+
+    let change_dir = project_root.join("agentd/changes").join(change_id);
+    if change_dir.exists() {
+        anyhow::bail!("Change '{}' is already active in agentd/changes", change_id);
+    }
+    let archive_dir = project_root.join(&snapshot.archive_path);
+    let archived_change = archive_dir.join(change_id);
+    if !archived_change.exists() {
+        anyhow::bail!("Archived change not found at {}", archived_change.display());
+    }
+
+    let specs_dir = project_root.join("agentd/specs");
+    if !force {
+        let mut edited = Vec::new();
+        for file in &snapshot.files {
+            let current = read_optional(&specs_dir.join(&file.path))?;
+            let merged = read_version(change_id, &file.path, true, project_root)?;
+            if current != merged {
+                edited.push(file.path.clone());
+            }
+        }
+        if !edited.is_empty() {
+            anyhow::bail!(
+                "These specs changed after '{}' was merged: {}. Unmerge the later changes first or use --force.",
+                change_id,
+                edited.join(", ")
+            );
+        }
+    }
+
+    for file in &snapshot.files {
+        let target = specs_dir.join(&file.path);
+        match read_version(change_id, &file.path, false, project_root)? {
+            Some(content) => write_file(&target, &content)?,
+            None if target.exists() => std::fs::remove_file(&target)?,
+            None => {}
+        }
+    }
+    let changelog_reverted =
+        remove_changelog_entry(&specs_dir.join(CHANGELOG), &snapshot.changelog_entry)?;
+
+    std::fs::create_dir_all(project_root.join("agentd/changes"))?;
+    std::fs::rename(&archived_change, &change_dir)?;
+    if archive_dir.read_dir()?.next().is_none() {
+        std::fs::remove_dir(&archive_dir)?;
+    }
+
+    let mut state = StateManager::load(&change_dir)?;
+    state.set_phase(StatePhase::Implemented);
+    state.set_last_action("unmerge");
+    state.save()?;
+
+    std::fs::remove_dir_all(snapshot_dir(change_id, project_root))?;
+
+    Ok(UnmergeReport {
+        restored: snapshot.files,
+        change_dir,
+        changelog_reverted,
+    })
+}
+
+/// What a merge added to the changelog: its new `## ` sections, or the
+/// blocks of added lines when it added no section
+fn changelog_additions(before: &str, after: &str) -> Vec<String> {
+    let existing = changelog_sections(before);
+    let sections: Vec<String> = changelog_sections(after)
+        .into_iter()
+        .filter(|section| !existing.contains(section))
+        .collect();
+    if sections.is_empty() {
+        added_blocks(before, after)
+    } else {
+        sections
+    }
+}
+
+/// `## ` sections of a changelog (the title and preamble are skipped)
+fn changelog_sections(content: &str) -> Vec<String> {
+    let mut sections: Vec<String> = Vec::new();
+    for line in content.split_inclusive('\n') {
+        if line.starts_with("## ") {
+            sections.push(String::new());
+        }
+        if let Some(section) = sections.last_mut() {
+            section.push_str(line);
+        }
+    }
+    sections
+}
+
+/// Blocks of consecutive lines present in `after` but not in `before`
+fn added_blocks(before: &str, after: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut current = String::new();
+    for change in TextDiff::from_lines(before, after).iter_all_changes() {
+        if change.tag() == ChangeTag::Insert {
+            current.push_str(change.value());
+        } else if !current.is_empty() {
+            blocks.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        blocks.push(current);
+    }
+    blocks
+}
+
+/// Remove a merge's blocks from the changelog, deleting the file if nothing
+/// else is left
+///
+/// Returns false, leaving the file untouched, unless every block is found.
+fn remove_changelog_entry(path: &Path, entry: &[String]) -> Result<bool> {
+    if entry.is_empty() {
+        return Ok(false);
+    }
+    let Some(mut content) = read_optional(path)? else {
+        return Ok(false);
+    };
+    for block in entry {
+        if !content.contains(block.as_str()) {
+            return Ok(false);
+        }
+        content = content.replacen(block.as_str(), "", 1);
+    }
+    if content.trim().is_empty() {
+        std::fs::remove_file(path)?;
+    } else {
+        std::fs::write(path, content)?;
+    }
+    Ok(true)
+}
+
+/// Relative paths of all files under a directory
+fn list_files(dir: &Path) -> Vec<String> {
+    if !dir.exists() {
+        return Vec::new();
+    }
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            e.path()
+                .strip_prefix(dir)
+                .ok()
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+        })
+        .collect()
+}
+
+fn read_optional(path: &Path) -> Result<Option<String>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(std::fs::read_to_string(path)?))
+}
+
+fn write_file(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Simulate merge-change: back up specs, edit them, archive the change
+    fn merge(root: &Path, change_id: &str, edit: impl FnOnce(&Path)) -> MergeSnapshot {
+        let specs = root.join("agentd/specs");
+        let backup = root.join(".agentd-backup");
+        let _ = std::fs::remove_dir_all(&backup);
+        for file in list_files(&specs) {
+            write_file(
+                &backup.join(&file),
+                &std::fs::read_to_string(specs.join(&file)).unwrap(),
+            )
+            .unwrap();
+        }
+        std::fs::create_dir_all(&backup).unwrap();
+        edit(&specs);
+
+        let archive = root
+            .join("agentd/archive")
+            .join(format!("20260101-{}", change_id));
+        std::fs::create_dir_all(&archive).unwrap();
+        std::fs::rename(
+            root.join("agentd/changes").join(change_id),
+            archive.join(change_id),
+        )
+        .unwrap();
+        record_merge(change_id, &backup, &archive, root)
+            .unwrap()
+            .unwrap()
+    }
+
+    fn setup(root: &Path) {
+        std::fs::create_dir_all(root.join("agentd/specs")).unwrap();
+        std::fs::write(root.join("agentd/specs/auth.md"), "# Auth\n\nv1\n").unwrap();
+        for id in ["add-oauth", "add-mfa"] {
+            std::fs::create_dir_all(root.join("agentd/changes").join(id)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_history_and_unmerge() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        setup(root);
+
+        let snapshot = merge(root, "add-oauth", |specs| {
+            std::fs::write(specs.join("auth.md"), "# Auth\n\nv2\n").unwrap();
+            std::fs::write(specs.join("oauth.md"), "# OAuth\n").unwrap();
+        });
+        assert_eq!(snapshot.archive_path, "agentd/archive/20260101-add-oauth");
+        let changes: Vec<(&str, &str)> = snapshot
+            .files
+            .iter()
+            .map(|f| (f.path.as_str(), f.change()))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![("auth.md", "modified"), ("oauth.md", "added")]
+        );
+
+        merge(root, "add-mfa", |specs| {
+            std::fs::write(specs.join("auth.md"), "# Auth\n\nv3\n").unwrap();
+        });
+
+        let history = spec_history("auth", root).unwrap();
+        let ids: Vec<&str> = history
+            .iter()
+            .map(|e| e.snapshot.change_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["add-oauth", "add-mfa"]);
+        assert_eq!(
+            history[1].stats,
+            DiffStats {
+                added: 1,
+                removed: 1
+            }
+        );
+
+        // add-mfa built on add-oauth's edit of auth.md
+        let err = unmerge("add-oauth", false, root).unwrap_err();
+        assert!(err.to_string().contains("auth.md"));
+
+        let report = unmerge("add-mfa", false, root).unwrap();
+        assert_eq!(report.restored.len(), 1);
+        assert_eq!(
+            std::fs::read_to_string(root.join("agentd/specs/auth.md")).unwrap(),
+            "# Auth\n\nv2\n"
+        );
+        assert!(root.join("agentd/changes/add-mfa").exists());
+        assert!(!root.join("agentd/archive/20260101-add-mfa").exists());
+        let state = StateManager::load(root.join("agentd/changes/add-mfa")).unwrap();
+        assert_eq!(*state.phase(), StatePhase::Implemented);
+
+        unmerge("add-oauth", false, root).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(root.join("agentd/specs/auth.md")).unwrap(),
+            "# Auth\n\nv1\n"
+        );
+        assert!(!root.join("agentd/specs/oauth.md").exists());
+        assert!(spec_history("auth", root).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_unmerge_removes_changelog_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        setup(root);
+
+        // add-oauth creates the changelog, add-mfa adds an entry on top
+        let snapshot = merge(root, "add-oauth", |specs| {
+            std::fs::write(specs.join("oauth.md"), "# OAuth\n").unwrap();
+            std::fs::write(
+                specs.join("CHANGELOG.md"),
+                "# CHANGELOG\n\n## 2026-01-01 (add-oauth)\n- OAuth login\n",
+            )
+            .unwrap();
+        });
+        assert!(snapshot.files.iter().all(|f| f.path != "CHANGELOG.md"));
+        assert_eq!(
+            snapshot.changelog_entry,
+            vec!["## 2026-01-01 (add-oauth)\n- OAuth login\n"]
+        );
+        merge(root, "add-mfa", |specs| {
+            std::fs::write(specs.join("mfa.md"), "# MFA\n").unwrap();
+            std::fs::write(
+                specs.join("CHANGELOG.md"),
+                "# CHANGELOG\n\n## 2026-01-02 (add-mfa)\n- MFA\n\n## 2026-01-01 (add-oauth)\n- OAuth login\n",
+            )
+            .unwrap();
+        });
+
+        // The changelog does not block unmerging an earlier change
+        let report = unmerge("add-oauth", false, root).unwrap();
+        assert!(report.changelog_reverted);
+        let changelog = root.join("agentd/specs/CHANGELOG.md");
+        assert_eq!(
+            std::fs::read_to_string(&changelog).unwrap(),
+            "# CHANGELOG\n\n## 2026-01-02 (add-mfa)\n- MFA\n\n"
+        );
+
+        // An entry edited since the merge is left alone
+        std::fs::write(
+            &changelog,
+            "# CHANGELOG\n\n## 2026-01-02 (add-mfa)\n- MFA, reworded\n",
+        )
+        .unwrap();
+        let report = unmerge("add-mfa", false, root).unwrap();
+        assert!(!report.changelog_reverted);
+        assert!(std::fs::read_to_string(&changelog)
+            .unwrap()
+            .contains("reworded"));
+    }
+}
@@ -1,9 +1,17 @@
 //! Spec service - Business logic for spec creation
 
 use crate::models::spec_rules::SpecFormatRules;
+use crate::models::{SectionDelta, ValidationRules};
+use crate::parser::{
+    parse_headings, parse_spec_outline, RequirementSection, ScenarioSection, SpecOutline,
+};
+use crate::services::history_service;
+use crate::validator::SpecFormatValidator;
 use crate::Result;
 use chrono::Utc;
 use serde_json::Value;
+use std::io::Write;
+use std::ops::Range;
 use std::path::Path;
 
 /// Requirement data
@@ -134,48 +142,16 @@ pub fn create_spec(input: CreateSpecInput, project_root: &Path) -> Result<String
     content.push_str("## Requirements\n\n");
 
     for req in &input.requirements {
-        content.push_str(&format!("### {} - {}\n\n", req.id, req.title));
-        content.push_str("```yaml\n");
-        content.push_str(&format!("id: {}\n", req.id));
-        content.push_str(&format!("priority: {}\n", req.priority));
-        content.push_str("status: draft\n");
-        content.push_str("```\n\n");
-        content.push_str(&format!("{}\n\n", req.description));
+        content.push_str(&render_requirement(req));
     }
 
     // Acceptance Criteria section - use central format rules
     let spec_rules = SpecFormatRules::spec_defaults();
 
-    // Find the "Acceptance Criteria" heading from required_headings
-    let ac_heading = spec_rules
-        .required_headings
-        .iter()
-        .find(|h| h.contains("Acceptance") || h.contains("Criteria"))
-        .map(|s| s.as_str())
-        .unwrap_or("Acceptance Criteria");
-
-    content.push_str(&format!("## {}\n\n", ac_heading));
+    content.push_str(&format!("## {}\n\n", acceptance_heading(&spec_rules)));
 
     for scenario in &input.scenarios {
-        // Use scenario heading format from rules: ### {prefix} {name}
-        let heading_hashes = "#".repeat(spec_rules.scenario_heading_level as usize);
-        content.push_str(&format!(
-            "{} {} {}\n\n",
-            heading_hashes, spec_rules.scenario_heading_prefix, scenario.name
-        ));
-
-        // Use WHEN/THEN keywords from rules
-        if let Some(given_text) = &scenario.given {
-            content.push_str(&format!("- **GIVEN** {}\n", given_text));
-        }
-        content.push_str(&format!(
-            "- **{}** {}\n",
-            spec_rules.when_keyword, scenario.when
-        ));
-        content.push_str(&format!(
-            "- **{}** {}\n\n",
-            spec_rules.then_keyword, scenario.then
-        ));
+        content.push_str(&render_scenario(scenario, &spec_rules));
     }
 
     // Flow diagram (optional)
@@ -202,6 +178,11 @@ pub fn create_spec(input: CreateSpecInput, project_root: &Path) -> Result<String
 
     // Write the file
     let spec_path = specs_dir.join(format!("{}.md", input.spec_id));
+    history_service::snapshot_before_write(
+        &change_dir,
+        &format!("specs/{}", input.spec_id),
+        &content,
+    )?;
     std::fs::write(&spec_path, &content)?;
 
     Ok(format!(
@@ -211,6 +192,339 @@ pub fn create_spec(input: CreateSpecInput, project_root: &Path) -> Result<String
     ))
 }
 
+/// Render a requirement section (`### R1 - Title` with its yaml block)
+pub(crate) fn render_requirement(req: &RequirementData) -> String {
+    render_requirement_with_status(req, "draft")
+}
+
+fn render_requirement_with_status(req: &RequirementData, status: &str) -> String {
+    let mut content = String::new();
+    content.push_str(&format!("### {} - {}\n\n", req.id, req.title));
+    content.push_str("```yaml\n");
+    content.push_str(&format!("id: {}\n", req.id));
+    content.push_str(&format!("priority: {}\n", req.priority));
+    content.push_str(&format!("status: {}\n", status));
+    content.push_str("```\n\n");
+    content.push_str(&format!("{}\n\n", req.description));
+    content
+}
+
+/// Render a scenario section using the heading and WHEN/THEN keywords from rules
+pub(crate) fn render_scenario(scenario: &ScenarioData, spec_rules: &SpecFormatRules) -> String {
+    let mut content = String::new();
+
+    // Use scenario heading format from rules: ### {prefix} {name}
+    content.push_str(&format!(
+        "{} {} {}\n\n",
+        scenario_hashes(spec_rules),
+        spec_rules.scenario_heading_prefix,
+        scenario.name
+    ));
+
+    // Use WHEN/THEN keywords from rules
+    if let Some(given_text) = &scenario.given {
+        content.push_str(&format!("- **GIVEN** {}\n", given_text));
+    }
+    content.push_str(&format!(
+        "- **{}** {}\n",
+        spec_rules.when_keyword, scenario.when
+    ));
+    content.push_str(&format!(
+        "- **{}** {}\n\n",
+        spec_rules.then_keyword, scenario.then
+    ));
+    content
+}
+
+fn scenario_hashes(spec_rules: &SpecFormatRules) -> String {
+    "#".repeat(spec_rules.scenario_heading_level as usize)
+}
+
+/// Find the "Acceptance Criteria" heading from required_headings
+pub(crate) fn acceptance_heading(spec_rules: &SpecFormatRules) -> &str {
+    spec_rules
+        .required_headings
+        .iter()
+        .find(|h| h.contains("Acceptance") || h.contains("Criteria"))
+        .map(|s| s.as_str())
+        .unwrap_or("Acceptance Criteria")
+}
+
+/// A section-level edit to a spec
+#[derive(Debug, Clone)]
+pub enum SpecPatch {
+    /// Edit a requirement, identified by its ID (or title for removal/rename)
+    Requirement(SectionDelta<RequirementData>),
+    /// Edit a scenario, identified by its name
+    Scenario(SectionDelta<ScenarioData>),
+}
+
+/// Input structure for patching a spec
+#[derive(Debug, Clone)]
+pub struct PatchSpecInput {
+    pub change_id: String,
+    pub spec_id: String,
+    pub patch: SpecPatch,
+}
+
+/// Apply a single requirement or scenario edit to an existing spec
+///
+/// Only the affected section is rewritten; the rest of the file is kept
+/// byte-for-byte. The patched spec is validated before it is written, and a
+/// history snapshot of the previous content is saved.
+pub fn patch_spec(input: PatchSpecInput, project_root: &Path) -> Result<String> {
+    let change_dir = project_root.join("agentd/changes").join(&input.change_id);
+    if !change_dir.exists() {
+        anyhow::bail!("Change '{}' not found.", input.change_id);
+    }
+
+    let spec_path = change_dir
+        .join("specs")
+        .join(format!("{}.md", input.spec_id));
+    if !spec_path.exists() {
+        anyhow::bail!(
+            "Spec '{}' not found in change '{}'. Use create_spec first.",
+            input.spec_id,
+            input.change_id
+        );
+    }
+
+    let content = std::fs::read_to_string(&spec_path)?;
+    let patched = apply_spec_patch(&content, &input.patch)?;
+    let patched = update_spec_frontmatter(&patched);
+
+    let warnings = validate_candidate(&patched, ValidationRules::for_spec())?;
+
+    history_service::snapshot_before_write(
+        &change_dir,
+        &format!("specs/{}", input.spec_id),
+        &patched,
+    )?;
+    std::fs::write(&spec_path, &patched)?;
+
+    let (target, key, operation) = match &input.patch {
+        SpecPatch::Requirement(delta) => ("requirement", delta_key(delta, |r| &r.id), delta.operation()),
+        SpecPatch::Scenario(delta) => ("scenario", delta_key(delta, |s| &s.name), delta.operation()),
+    };
+    let mut message = format!(
+        "Patched spec '{}' for change '{}': {} {} '{}'",
+        input.spec_id, input.change_id, operation, target, key
+    );
+    for warning in warnings {
+        message.push_str(&format!("\n⚠ {}", warning));
+    }
+    Ok(message)
+}
+
+/// Apply a spec patch to document content
+pub fn apply_spec_patch(content: &str, patch: &SpecPatch) -> Result<String> {
+    let outline = parse_spec_outline(content);
+
+    match patch {
+        SpecPatch::Requirement(delta) => match delta {
+            SectionDelta::Added(req) => {
+                if outline.find_requirement(&req.id).is_some() {
+                    anyhow::bail!("Requirement '{}' already exists", req.id);
+                }
+                let Some(at) = outline.requirements_end else {
+                    anyhow::bail!("Spec has no Requirements section");
+                };
+                Ok(insert_block(content, at, &render_requirement(req)))
+            }
+            SectionDelta::Modified(req) => {
+                let section = find_requirement(&outline, &req.id)?;
+                // Replace the heading, yaml block and description only: nested
+                // scenarios and the recorded status are kept
+                let start = section.section.range.start;
+                let end = parse_headings(section.section.body(content))
+                    .first()
+                    .map(|child| section.section.heading.line.end + child.line.start)
+                    .unwrap_or(section.section.range.end);
+                let status = requirement_status(&content[start..end]).unwrap_or("draft");
+                let block = render_requirement_with_status(req, status);
+                Ok(replace_range(content, start..end, &block))
+            }
+            SectionDelta::Removed { key, .. } => {
+                let section = find_requirement(&outline, key)?;
+                Ok(replace_range(content, section.section.range.clone(), ""))
+            }
+            SectionDelta::Renamed { from, to } => {
+                let section = find_requirement(&outline, from)?;
+                let heading = &section.section.heading;
+                let title = match (&section.id, heading.title.strip_suffix(section.title.as_str())) {
+                    (_, Some(prefix)) if !section.title.is_empty() => format!("{}{}", prefix, to),
+                    (Some(id), _) => format!("{} - {}", id, to),
+                    (None, _) => format!("Requirement: {}", to),
+                };
+                let line = format!("{} {}\n", "#".repeat(heading.level), title);
+                Ok(replace_range(content, heading.line.clone(), &line))
+            }
+        },
+        SpecPatch::Scenario(delta) => {
+            let spec_rules = SpecFormatRules::spec_defaults();
+            match delta {
+                SectionDelta::Added(scenario) => {
+                    if outline.find_scenario(&scenario.name).is_some() {
+                        anyhow::bail!("Scenario '{}' already exists", scenario.name);
+                    }
+                    let block = render_scenario(scenario, &spec_rules);
+                    match outline.scenarios_end {
+                        Some(at) => Ok(insert_block(content, at, &block)),
+                        None => {
+                            let block = format!("## {}\n\n{}", acceptance_heading(&spec_rules), block);
+                            Ok(insert_block(content, outline.body_end, &block))
+                        }
+                    }
+                }
+                SectionDelta::Modified(scenario) => {
+                    let section = find_scenario(&outline, &scenario.name)?;
+                    Ok(replace_range(
+                        content,
+                        section.section.range.clone(),
+                        &render_scenario(scenario, &spec_rules),
+                    ))
+                }
+                SectionDelta::Removed { key, .. } => {
+                    let section = find_scenario(&outline, key)?;
+                    Ok(replace_range(content, section.section.range.clone(), ""))
+                }
+                SectionDelta::Renamed { from, to } => {
+                    let section = find_scenario(&outline, from)?;
+                    let heading = &section.section.heading;
+                    let line = format!(
+                        "{} {} {}\n",
+                        "#".repeat(heading.level),
+                        spec_rules.scenario_heading_prefix,
+                        to
+                    );
+                    Ok(replace_range(content, heading.line.clone(), &line))
+                }
+            }
+        }
+    }
+}
+
+/// Status recorded in a requirement's yaml block, if any
+fn requirement_status(block: &str) -> Option<&str> {
+    let yaml = block.split("```yaml\n").nth(1)?.split("```").next()?;
+    yaml.lines()
+        .find_map(|line| line.strip_prefix("status:"))
+        .map(str::trim)
+        .filter(|status| !status.is_empty())
+}
+
+fn find_requirement<'a>(outline: &'a SpecOutline, key: &str) -> Result<&'a RequirementSection> {
+    outline.find_requirement(key).ok_or_else(|| {
+        let known: Vec<&str> = outline.requirements.iter().map(|r| r.key()).collect();
+        anyhow::anyhow!(
+            "Requirement '{}' not found. Existing requirements: {}",
+            key,
+            known.join(", ")
+        )
+    })
+}
+
+fn find_scenario<'a>(outline: &'a SpecOutline, name: &str) -> Result<&'a ScenarioSection> {
+    outline.find_scenario(name).ok_or_else(|| {
+        let known: Vec<&str> = outline.scenarios.iter().map(|s| s.name.as_str()).collect();
+        anyhow::anyhow!(
+            "Scenario '{}' not found. Existing scenarios: {}",
+            name,
+            known.join(", ")
+        )
+    })
+}
+
+fn delta_key<T>(delta: &SectionDelta<T>, key: impl Fn(&T) -> &String) -> String {
+    match delta {
+        SectionDelta::Added(item) | SectionDelta::Modified(item) => key(item).clone(),
+        SectionDelta::Removed { key, .. } => key.clone(),
+        SectionDelta::Renamed { from, to } => format!("{}' -> '{}", from, to),
+    }
+}
+
+/// Replace a byte range of the document
+pub(crate) fn replace_range(content: &str, range: Range<usize>, replacement: &str) -> String {
+    let mut out = String::with_capacity(content.len() + replacement.len());
+    out.push_str(&content[..range.start]);
+    out.push_str(replacement);
+    out.push_str(&content[range.end..]);
+    out
+}
+
+/// Insert a block at an offset, keeping a blank line before it
+pub(crate) fn insert_block(content: &str, at: usize, block: &str) -> String {
+    let before = &content[..at];
+    let mut out = before.to_string();
+    if !before.is_empty() && !before.ends_with("\n\n") {
+        out.push_str(if before.ends_with('\n') { "\n" } else { "\n\n" });
+    }
+    out.push_str(block);
+    out.push_str(&content[at..]);
+    out
+}
+
+/// Refresh `updated_at` and the requirements summary in the spec frontmatter
+pub(crate) fn update_spec_frontmatter(content: &str) -> String {
+    let Some(rest) = content.strip_prefix("---\n") else {
+        return content.to_string();
+    };
+    let Some(end) = rest.find("\n---\n") else {
+        return content.to_string();
+    };
+    let (frontmatter, body) = rest.split_at(end + 1);
+
+    let ids: Vec<String> = parse_spec_outline(content)
+        .requirements
+        .iter()
+        .filter_map(|r| r.id.clone())
+        .collect();
+
+    let mut out = String::from("---\n");
+    let mut lines = frontmatter.lines().peekable();
+    while let Some(line) = lines.next() {
+        if line.starts_with("updated_at:") {
+            out.push_str(&format!("updated_at: {}\n", Utc::now().to_rfc3339()));
+        } else if line == "requirements:" {
+            while lines.peek().is_some_and(|l| l.starts_with(' ')) {
+                lines.next();
+            }
+            out.push_str("requirements:\n");
+            out.push_str(&format!("  total: {}\n", ids.len()));
+            if !ids.is_empty() {
+                out.push_str("  ids:\n");
+                for id in &ids {
+                    out.push_str(&format!("    - {}\n", id));
+                }
+            }
+        } else {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out.push_str(body);
+    out
+}
+
+/// Validate candidate document content before it is written
+///
+/// Returns non-blocking findings as warnings; fails with all findings if any
+/// of them is high severity.
+pub(crate) fn validate_candidate(content: &str, rules: ValidationRules) -> Result<Vec<String>> {
+    let mut file = tempfile::Builder::new().suffix(".md").tempfile()?;
+    file.write_all(content.as_bytes())?;
+
+    let result = SpecFormatValidator::new(rules).validate(file.path());
+    let messages: Vec<String> = result.errors.iter().map(|e| e.message.clone()).collect();
+    if !result.is_valid() {
+        anyhow::bail!(
+            "Patch rejected, the resulting document is invalid:\n- {}",
+            messages.join("\n- ")
+        );
+    }
+    Ok(messages)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -309,4 +623,160 @@ mod tests {
         let result = create_spec(input, project_root);
         assert!(result.is_err());
     }
+
+    fn setup_spec(project_root: &Path) {
+        std::fs::create_dir_all(project_root.join("agentd/changes/test-change")).unwrap();
+        let input = CreateSpecInput {
+            change_id: "test-change".to_string(),
+            spec_id: "auth".to_string(),
+            title: "Authentication".to_string(),
+            overview: "This specification covers user authentication, including login and logout flows.".to_string(),
+            requirements: vec![RequirementData {
+                id: "R1".to_string(),
+                title: "Login".to_string(),
+                description: "Users can log in with email and password".to_string(),
+                priority: "high".to_string(),
+            }],
+            scenarios: vec![ScenarioData {
+                name: "Valid login".to_string(),
+                given: None,
+                when: "User submits valid credentials".to_string(),
+                then: "A session is created".to_string(),
+            }],
+            flow_diagram: None,
+            data_model: None,
+        };
+        create_spec(input, project_root).unwrap();
+    }
+
+    #[test]
+    fn test_patch_spec_requirement() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path();
+        setup_spec(project_root);
+        let spec_path = project_root.join("agentd/changes/test-change/specs/auth.md");
+        let original = std::fs::read_to_string(&spec_path).unwrap();
+
+        let patch = |patch| PatchSpecInput {
+            change_id: "test-change".to_string(),
+            spec_id: "auth".to_string(),
+            patch,
+        };
+
+        patch_spec(
+            patch(SpecPatch::Requirement(SectionDelta::Added(RequirementData {
+                id: "R2".to_string(),
+                title: "Logout".to_string(),
+                description: "Users can log out".to_string(),
+                priority: "medium".to_string(),
+            }))),
+            project_root,
+        )
+        .unwrap();
+        let content = std::fs::read_to_string(&spec_path).unwrap();
+        assert!(content.contains("### R2 - Logout\n"));
+        assert!(content.contains("  total: 2\n"));
+        assert!(content.contains("    - R2\n"));
+        assert!(content.find("### R2").unwrap() < content.find("## Acceptance Criteria").unwrap());
+
+        // Untouched sections are preserved byte-for-byte
+        let overview = &original[original.find("## Overview").unwrap()..original.find("## Requirements").unwrap()];
+        assert!(content.contains(overview));
+
+        patch_spec(
+            patch(SpecPatch::Requirement(SectionDelta::Renamed {
+                from: "R1".to_string(),
+                to: "Sign In".to_string(),
+            })),
+            project_root,
+        )
+        .unwrap();
+        patch_spec(
+            patch(SpecPatch::Requirement(SectionDelta::Removed {
+                key: "R2".to_string(),
+                reason: None,
+            })),
+            project_root,
+        )
+        .unwrap();
+        let content = std::fs::read_to_string(&spec_path).unwrap();
+        assert!(content.contains("### R1 - Sign In\n"));
+        assert!(!content.contains("R2"));
+        assert!(content.contains("Users can log in with email and password"));
+
+        // Each patch saved a history version
+        let change_dir = project_root.join("agentd/changes/test-change");
+        assert_eq!(history_service::list_versions(&change_dir, "specs/auth").unwrap().len(), 3);
+
+        let missing = patch_spec(
+            patch(SpecPatch::Requirement(SectionDelta::Removed {
+                key: "R9".to_string(),
+                reason: None,
+            })),
+            project_root,
+        );
+        assert!(missing.unwrap_err().to_string().contains("Existing requirements: R1"));
+    }
+
+    #[test]
+    fn test_patch_spec_scenario() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path();
+        setup_spec(project_root);
+        let spec_path = project_root.join("agentd/changes/test-change/specs/auth.md");
+
+        let patch = |patch| PatchSpecInput {
+            change_id: "test-change".to_string(),
+            spec_id: "auth".to_string(),
+            patch,
+        };
+
+        patch_spec(
+            patch(SpecPatch::Scenario(SectionDelta::Modified(ScenarioData {
+                name: "Valid login".to_string(),
+                given: Some("An active account".to_string()),
+                when: "User submits valid credentials".to_string(),
+                then: "A session is created and the user is redirected".to_string(),
+            }))),
+            project_root,
+        )
+        .unwrap();
+        let content = std::fs::read_to_string(&spec_path).unwrap();
+        assert!(content.contains("- **GIVEN** An active account\n"));
+        assert!(content.contains("redirected\n\n</spec>"));
+
+        // Removing the only scenario would make the spec invalid
+        let before = std::fs::read_to_string(&spec_path).unwrap();
+        let result = patch_spec(
+            patch(SpecPatch::Scenario(SectionDelta::Removed {
+                key: "Valid login".to_string(),
+                reason: None,
+            })),
+            project_root,
+        );
+        assert!(result.unwrap_err().to_string().contains("Patch rejected"));
+        assert_eq!(std::fs::read_to_string(&spec_path).unwrap(), before);
+    }
+
+    #[test]
+    fn test_modify_requirement_keeps_nested_scenarios() {
+        let content = "## Requirements\n\n### R1 - Login\n\n```yaml\nid: R1\npriority: high\nstatus: approved\n```\n\nUsers can log in\n\n#### Scenario: Valid login\n\n- **WHEN** User submits valid credentials\n- **THEN** A session is created\n\n### R2 - Logout\n\nUsers can log out\n";
+
+        let patched = apply_spec_patch(
+            content,
+            &SpecPatch::Requirement(SectionDelta::Modified(RequirementData {
+                id: "R1".to_string(),
+                title: "Login".to_string(),
+                description: "Users can log in with email and password".to_string(),
+                priority: "medium".to_string(),
+            })),
+        )
+        .unwrap();
+
+        assert!(patched.contains("priority: medium\nstatus: approved\n"));
+        let description = "Users can log in with email and password\n\n";
+        assert!(patched.contains(&format!("{}#### Scenario: Valid login\n", description)));
+        assert!(patched.contains("- **THEN** A session is created\n\n### R2 - Logout\n"));
+        assert!(!patched.contains("Users can log in\n"));
+    }
 }
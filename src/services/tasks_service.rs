@@ -1,8 +1,11 @@
 //! Tasks service - Business logic for tasks creation
 
+use crate::models::{TaskGraph, TaskStatus};
+use crate::services::history_service;
 use crate::Result;
 use chrono::Utc;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
 /// File action data
@@ -53,6 +56,54 @@ fn layer_display_name(layer: &str) -> &str {
     }
 }
 
+/// Layer key for a layer number (inverse of `layer_number`)
+fn layer_key(number: u32) -> &'static str {
+    match number {
+        1 => "data",
+        2 => "logic",
+        3 => "integration",
+        4 => "testing",
+        _ => "other",
+    }
+}
+
+/// Task progress for a single layer
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LayerProgress {
+    pub layer: String,
+    pub order: u32,
+    pub total: u32,
+    pub completed: u32,
+    pub in_progress: u32,
+    pub blocked: u32,
+    pub pending: u32,
+}
+
+impl LayerProgress {
+    /// Status label used in the tasks.md summary table
+    pub fn label(&self) -> String {
+        if self.total > 0 && self.completed == self.total {
+            "✅ Done".to_string()
+        } else if self.blocked > 0 {
+            "⛔ Blocked".to_string()
+        } else if self.completed > 0 || self.in_progress > 0 {
+            format!("🔄 In Progress ({}/{})", self.completed, self.total)
+        } else {
+            "🔲 Pending".to_string()
+        }
+    }
+
+    fn add(&mut self, status: &TaskStatus) {
+        self.total += 1;
+        match status {
+            TaskStatus::Pending => self.pending += 1,
+            TaskStatus::InProgress => self.in_progress += 1,
+            TaskStatus::Completed => self.completed += 1,
+            TaskStatus::Blocked => self.blocked += 1,
+        }
+    }
+}
+
 /// Create tasks file with validation
 pub fn create_tasks(input: CreateTasksInput, project_root: &Path) -> Result<String> {
     // Validate tasks
@@ -194,6 +245,7 @@ pub fn create_tasks(input: CreateTasksInput, project_root: &Path) -> Result<Stri
 
     // Write the file
     let tasks_path = change_dir.join("tasks.md");
+    history_service::snapshot_before_write(&change_dir, "tasks", &content)?;
     std::fs::write(&tasks_path, &content)?;
 
     Ok(format!(
@@ -204,6 +256,224 @@ pub fn create_tasks(input: CreateTasksInput, project_root: &Path) -> Result<Stri
     ))
 }
 
+/// Update the status of one or more tasks in tasks.md
+///
+/// Rewrites the `status:` field of each task's YAML block (or the checkbox of
+/// checkbox-style tasks), then refreshes the frontmatter summary counts and the
+/// per-layer status table so tasks.md reflects implementation progress.
+pub fn update_task_status(
+    change_id: &str,
+    task_ids: &[String],
+    status: TaskStatus,
+    project_root: &Path,
+) -> Result<String> {
+    if task_ids.is_empty() {
+        anyhow::bail!("At least one task_id is required");
+    }
+
+    let tasks_path = project_root
+        .join("agentd/changes")
+        .join(change_id)
+        .join("tasks.md");
+    if !tasks_path.exists() {
+        anyhow::bail!("tasks.md not found for change '{}'", change_id);
+    }
+
+    let mut content = std::fs::read_to_string(&tasks_path)?;
+    for task_id in task_ids {
+        content = apply_task_status(&content, task_id, &status)
+            .ok_or_else(|| anyhow::anyhow!("Task '{}' not found in tasks.md", task_id))?;
+    }
+
+    let progress = progress_by_layer(&content)?;
+    let content = sync_progress(&content, &progress);
+    std::fs::write(&tasks_path, &content)?;
+
+    let total: u32 = progress.iter().map(|p| p.total).sum();
+    let completed: u32 = progress.iter().map(|p| p.completed).sum();
+    Ok(format!(
+        "Updated task(s) {} to {} ({}/{} tasks completed)",
+        task_ids.join(", "),
+        status.as_str(),
+        completed,
+        total
+    ))
+}
+
+/// Compute task progress per layer from tasks.md
+pub fn task_progress(change_id: &str, project_root: &Path) -> Result<Vec<LayerProgress>> {
+    let tasks_path = project_root
+        .join("agentd/changes")
+        .join(change_id)
+        .join("tasks.md");
+    if !tasks_path.exists() {
+        anyhow::bail!("tasks.md not found for change '{}'", change_id);
+    }
+    progress_by_layer(&std::fs::read_to_string(&tasks_path)?)
+}
+
+/// Group task statuses by layer (derived from the task ID prefix)
+fn progress_by_layer(content: &str) -> Result<Vec<LayerProgress>> {
+    let tasks = TaskGraph::parse_tasks(content)?;
+
+    let mut layers: BTreeMap<u32, LayerProgress> = BTreeMap::new();
+    for task in &tasks {
+        let order = task
+            .id
+            .split('.')
+            .next()
+            .and_then(|n| n.parse::<u32>().ok())
+            .unwrap_or(5);
+        layers
+            .entry(order)
+            .or_insert_with(|| LayerProgress {
+                layer: layer_key(order).to_string(),
+                order,
+                ..Default::default()
+            })
+            .add(&task.status);
+    }
+
+    Ok(layers.into_values().collect())
+}
+
+/// Set the status of a single task, returning None if the task is not found
+fn apply_task_status(content: &str, task_id: &str, status: &TaskStatus) -> Option<String> {
+    let mut lines: Vec<String> = content.lines().map(String::from).collect();
+    let mut found = false;
+
+    // YAML block format (generated by create_tasks)
+    let mut i = 0;
+    while i < lines.len() {
+        if !lines[i].trim_start().starts_with("```yaml") {
+            i += 1;
+            continue;
+        }
+        let start = i + 1;
+        let Some(len) = lines[start..].iter().position(|l| l.trim_start().starts_with("```"))
+        else {
+            break;
+        };
+        let end = start + len;
+
+        let id_line = (start..end).find(|&j| {
+            lines[j]
+                .trim()
+                .strip_prefix("id:")
+                .map(|v| v.trim().trim_matches('"').trim_matches('\'') == task_id)
+                .unwrap_or(false)
+        });
+
+        if let Some(id_line) = id_line {
+            let indent: String = lines[id_line]
+                .chars()
+                .take_while(|c| c.is_whitespace())
+                .collect();
+            let status_line = format!("{}status: {}", indent, status.as_str());
+            match (start..end).find(|&j| lines[j].trim().starts_with("status:")) {
+                Some(j) => lines[j] = status_line,
+                None => lines.insert(id_line + 1, status_line),
+            }
+            found = true;
+            break;
+        }
+        i = end + 1;
+    }
+
+    // Checkbox format: - [ ] 1.1 Task title
+    let prefix = format!("] {} ", task_id);
+    for line in lines.iter_mut() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("- [") && trimmed.get(4..).is_some_and(|r| r.starts_with(&prefix))
+        {
+            let indent_len = line.len() - trimmed.len();
+            *line = format!(
+                "{}- {}{}",
+                &line[..indent_len],
+                status.checkbox(),
+                &trimmed[5..]
+            );
+            found = true;
+        }
+    }
+
+    if !found {
+        return None;
+    }
+
+    let mut updated = lines.join("\n");
+    if content.ends_with('\n') {
+        updated.push('\n');
+    }
+    Some(updated)
+}
+
+/// Refresh frontmatter summary counts and the layer status table
+fn sync_progress(content: &str, progress: &[LayerProgress]) -> String {
+    let count = |f: fn(&LayerProgress) -> u32| progress.iter().map(f).sum::<u32>();
+    let summary = [
+        ("total", count(|p| p.total)),
+        ("completed", count(|p| p.completed)),
+        ("in_progress", count(|p| p.in_progress)),
+        ("blocked", count(|p| p.blocked)),
+        ("pending", count(|p| p.pending)),
+    ];
+
+    let mut result = Vec::new();
+    let mut fence_count = 0;
+    let mut in_summary = false;
+
+    for (idx, line) in content.lines().enumerate() {
+        if line.trim() == "---" && (fence_count == 1 || (fence_count == 0 && idx == 0)) {
+            fence_count += 1;
+            in_summary = false;
+            result.push(line.to_string());
+            continue;
+        }
+
+        // Frontmatter: summary counts and updated_at
+        if fence_count == 1 {
+            if !line.starts_with(' ') {
+                in_summary = line.trim() == "summary:";
+                if line.starts_with("updated_at:") {
+                    result.push(format!("updated_at: {}", Utc::now().to_rfc3339()));
+                    continue;
+                }
+            } else if in_summary {
+                let key = line.trim().split(':').next().unwrap_or("");
+                if let Some((_, value)) = summary.iter().find(|(k, _)| *k == key) {
+                    result.push(format!("  {}: {}", key, value));
+                    continue;
+                }
+            }
+            result.push(line.to_string());
+            continue;
+        }
+
+        // Body: | Data Layer | 3 | 🔲 Pending |
+        if line.starts_with('|') {
+            let cells: Vec<&str> = line.trim_matches('|').split('|').map(str::trim).collect();
+            if cells.len() == 3 {
+                if let Some(layer) = progress
+                    .iter()
+                    .find(|p| layer_display_name(&p.layer) == cells[0])
+                {
+                    result.push(format!("| {} | {} | {} |", cells[0], cells[1], layer.label()));
+                    continue;
+                }
+            }
+        }
+
+        result.push(line.to_string());
+    }
+
+    let mut updated = result.join("\n");
+    if content.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -277,6 +547,81 @@ mod tests {
         assert!(content.contains("Task 2.1"));
     }
 
+    #[test]
+    fn test_update_task_status() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path();
+        std::fs::create_dir_all(project_root.join("agentd/changes/test-change")).unwrap();
+
+        let task = |layer: &str, number: u32| TaskData {
+            layer: layer.to_string(),
+            number,
+            title: format!("Task {} {}", layer, number),
+            file: FileActionData {
+                path: format!("src/{}_{}.rs", layer, number),
+                action: "CREATE".to_string(),
+            },
+            spec_ref: "spec:R1".to_string(),
+            description: "Do the thing".to_string(),
+            depends: vec![],
+        };
+        let input = CreateTasksInput {
+            change_id: "test-change".to_string(),
+            tasks: vec![task("data", 1), task("data", 2), task("logic", 1)],
+        };
+        create_tasks(input, project_root).unwrap();
+
+        let result = update_task_status(
+            "test-change",
+            &["1.1".to_string(), "1.2".to_string()],
+            TaskStatus::Completed,
+            project_root,
+        )
+        .unwrap();
+        assert!(result.contains("2/3 tasks completed"));
+
+        update_task_status(
+            "test-change",
+            &["2.1".to_string()],
+            TaskStatus::InProgress,
+            project_root,
+        )
+        .unwrap();
+
+        let content =
+            std::fs::read_to_string(project_root.join("agentd/changes/test-change/tasks.md"))
+                .unwrap();
+        assert!(content.contains("  completed: 2\n"));
+        assert!(content.contains("  in_progress: 1\n"));
+        assert!(content.contains("  pending: 0\n"));
+        assert!(content.contains("| Data Layer | 2 | ✅ Done |"));
+        assert!(content.contains("| Logic Layer | 1 | 🔄 In Progress (0/1) |"));
+
+        let progress = task_progress("test-change", project_root).unwrap();
+        assert_eq!(progress.len(), 2);
+        assert_eq!(progress[0].completed, 2);
+        assert_eq!(progress[1].in_progress, 1);
+
+        let missing = update_task_status(
+            "test-change",
+            &["9.9".to_string()],
+            TaskStatus::Completed,
+            project_root,
+        );
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn test_apply_task_status_checkbox() {
+        let content = "- [ ] 1.1 Add model\n  - File: `src/a.rs` (CREATE)\n- [ ] 1.2 Add view\n  - File: `src/b.rs` (CREATE)\n";
+        let updated = apply_task_status(content, "1.2", &TaskStatus::Blocked).unwrap();
+        assert!(updated.contains("- [ ] 1.1 Add model"));
+        assert!(updated.contains("- [!] 1.2 Add view"));
+
+        let tasks = TaskGraph::parse_tasks(&updated).unwrap();
+        assert_eq!(tasks[1].status, TaskStatus::Blocked);
+    }
+
     #[test]
     fn test_create_tasks_empty() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,606 @@
+//! Requirement traceability
+//!
+//! Joins a change's spec requirements with the tasks that reference them
+//! (`spec_ref: spec-id:R1`), the files those tasks touch that show up in the
+//! git diff, and the tests that mention the requirement ID.
+
+use crate::mcp::tools::mermaid;
+use crate::models::{TaskBlock, TaskGraph};
+use crate::parser::parse_spec_outline;
+use crate::services::code_service::collect_files;
+use crate::services::diff_service;
+use crate::Result;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// Traceability matrix for a change
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceMatrix {
+    pub change_id: String,
+    /// Base ref of the diff, None when the diff could not be computed
+    pub diff_base: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff_error: Option<String>,
+    pub changed_files: Vec<String>,
+    pub requirements: Vec<RequirementTrace>,
+    /// Tasks whose spec_ref does not resolve to a requirement
+    pub orphan_tasks: Vec<OrphanTask>,
+}
+
+/// One row of the matrix
+#[derive(Debug, Clone, Serialize)]
+pub struct RequirementTrace {
+    pub spec_id: String,
+    /// Requirement ID, or the title for requirements without one
+    pub requirement_id: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+    pub tasks: Vec<String>,
+    pub files: Vec<TracedFile>,
+    pub tests: Vec<TestReference>,
+    pub status: TraceStatus,
+}
+
+impl RequirementTrace {
+    /// Qualified reference as used in task spec_refs (e.g., "auth-spec:R1")
+    pub fn reference(&self) -> String {
+        format!("{}:{}", self.spec_id, self.requirement_id)
+    }
+}
+
+/// A file named by a task implementing the requirement
+#[derive(Debug, Clone, Serialize)]
+pub struct TracedFile {
+    pub path: String,
+    /// Whether the file appears in the git diff
+    pub changed: bool,
+}
+
+/// A test file line mentioning the requirement
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TestReference {
+    pub file: String,
+    pub line: usize,
+}
+
+/// Coverage status of a requirement
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceStatus {
+    /// Implemented by changed files and referenced by tests
+    Covered,
+    /// Implemented but no test references the requirement
+    Untested,
+    /// Tasks exist but none of their files changed
+    NotImplemented,
+    /// No task references the requirement
+    NoTasks,
+}
+
+impl TraceStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TraceStatus::Covered => "✅ Covered",
+            TraceStatus::Untested => "🧪 Untested",
+            TraceStatus::NotImplemented => "📝 Not implemented",
+            TraceStatus::NoTasks => "⚠️ No tasks",
+        }
+    }
+}
+
+/// A task that cannot be traced to a requirement
+#[derive(Debug, Clone, Serialize)]
+pub struct OrphanTask {
+    pub id: String,
+    pub file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spec_ref: Option<String>,
+    pub reason: String,
+}
+
+impl TraceMatrix {
+    /// Requirements not fully covered (no tasks, not implemented or untested)
+    pub fn uncovered(&self) -> Vec<&RequirementTrace> {
+        self.requirements
+            .iter()
+            .filter(|r| r.status != TraceStatus::Covered)
+            .collect()
+    }
+}
+
+/// Build the traceability matrix for a change
+///
+/// `base` is the git ref the working tree is diffed against (default: the
+/// change's baseline commit, else main).
+pub fn build_trace(
+    change_id: &str,
+    base: Option<&str>,
+    project_root: &Path,
+) -> Result<TraceMatrix> {
+    let change_dir = project_root.join("agentd/changes").join(change_id);
+    if !change_dir.exists() {
+        anyhow::bail!("Change '{}' not found", change_id);
+    }
+
+    let mut requirements = read_requirements(&change_dir.join("specs"))?;
+
+    let tasks_path = change_dir.join("tasks.md");
+    let tasks = if tasks_path.exists() {
+        TaskGraph::parse_tasks(&std::fs::read_to_string(&tasks_path)?).unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    let base = diff_service::resolve_base(change_id, base, project_root);
+    let (diff_base, diff_error, changed_files) =
+        match diff_service::changed_files(project_root, &base) {
+            Ok(files) => (
+                Some(base),
+                None,
+                files.into_iter().map(|f| f.path).collect(),
+            ),
+            Err(e) => (None, Some(e.to_string()), Vec::new()),
+        };
+    let changed: BTreeSet<&str> = changed_files.iter().map(String::as_str).collect();
+
+    // Link tasks to requirements
+    let mut orphan_tasks = Vec::new();
+    let mut task_files: Vec<Vec<String>> = vec![Vec::new(); requirements.len()];
+    for task in &tasks {
+        match resolve_task(task, &requirements) {
+            Ok(index) => {
+                requirements[index].tasks.push(task.id.clone());
+                if !task.file.is_empty() && !task_files[index].contains(&task.file) {
+                    task_files[index].push(task.file.clone());
+                }
+            }
+            Err(reason) => orphan_tasks.push(OrphanTask {
+                id: task.id.clone(),
+                file: task.file.clone(),
+                spec_ref: task.spec_ref.clone(),
+                reason,
+            }),
+        }
+    }
+    for (req, files) in requirements.iter_mut().zip(task_files) {
+        req.files = files
+            .into_iter()
+            .map(|path| TracedFile {
+                changed: changed.contains(path.as_str()),
+                path,
+            })
+            .collect();
+    }
+
+    find_test_references(project_root, &mut requirements, &changed)?;
+
+    let diff_available = diff_base.is_some();
+    for req in &mut requirements {
+        req.status = if req.tasks.is_empty() {
+            TraceStatus::NoTasks
+        } else if diff_available && !req.files.iter().any(|f| f.changed) {
+            TraceStatus::NotImplemented
+        } else if req.tests.is_empty() {
+            TraceStatus::Untested
+        } else {
+            TraceStatus::Covered
+        };
+    }
+
+    Ok(TraceMatrix {
+        change_id: change_id.to_string(),
+        diff_base,
+        diff_error,
+        changed_files,
+        requirements,
+        orphan_tasks,
+    })
+}
+
+/// Collect requirements from every spec in the change
+fn read_requirements(specs_dir: &Path) -> Result<Vec<RequirementTrace>> {
+    let mut spec_files: Vec<_> = if specs_dir.exists() {
+        std::fs::read_dir(specs_dir)?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "md"))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    spec_files.sort();
+
+    let priority_re = Regex::new(r"(?m)^\s*priority:\s*([A-Za-z]+)")?;
+    let mut requirements = Vec::new();
+    for path in spec_files {
+        let spec_id = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let content = std::fs::read_to_string(&path)?;
+        for section in parse_spec_outline(&content).requirements {
+            let priority = priority_re
+                .captures(section.section.body(&content))
+                .map(|c| c[1].to_lowercase());
+            requirements.push(RequirementTrace {
+                spec_id: spec_id.clone(),
+                requirement_id: section.key().to_string(),
+                title: section.title.clone(),
+                priority,
+                tasks: Vec::new(),
+                files: Vec::new(),
+                tests: Vec::new(),
+                status: TraceStatus::NoTasks,
+            });
+        }
+    }
+    Ok(requirements)
+}
+
+/// Find the requirement a task's spec_ref points to
+///
+/// Accepts `spec:R1`, `spec#R1` and `specs/spec.md:R1`; a bare `R1` resolves
+/// when exactly one spec has that requirement.
+fn resolve_task(
+    task: &TaskBlock,
+    requirements: &[RequirementTrace],
+) -> std::result::Result<usize, String> {
+    let spec_ref = match task.spec_ref.as_deref().map(str::trim) {
+        Some(r) if !r.is_empty() => r,
+        _ => return Err("no spec_ref".to_string()),
+    };
+
+    let (spec, requirement) = match spec_ref.rfind([':', '#']) {
+        Some(pos) => (Some(&spec_ref[..pos]), spec_ref[pos + 1..].trim()),
+        None => (None, spec_ref),
+    };
+    let spec = spec.map(|s| {
+        let s = s.trim();
+        let s = s.rsplit('/').next().unwrap_or(s);
+        s.strip_suffix(".md").unwrap_or(s)
+    });
+
+    let matches: Vec<usize> = requirements
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| spec.is_none_or(|s| r.spec_id == s))
+        .filter(|(_, r)| {
+            r.requirement_id.eq_ignore_ascii_case(requirement)
+                || r.title.eq_ignore_ascii_case(requirement)
+        })
+        .map(|(i, _)| i)
+        .collect();
+
+    match matches.as_slice() {
+        [index] => Ok(*index),
+        [] if spec.is_none() && requirements.iter().any(|r| r.spec_id == spec_ref) => Err(format!(
+            "spec_ref '{}' names a spec but no requirement",
+            spec_ref
+        )),
+        [] => Err(format!("spec_ref '{}' matches no requirement", spec_ref)),
+        _ => Err(format!("spec_ref '{}' is ambiguous", spec_ref)),
+    }
+}
+
+/// Whether a file holds tests, judged by its path or (for Rust) inline test modules
+fn is_test_file(path: &str, content: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    path.split('/')
+        .any(|dir| matches!(dir, "tests" | "test" | "__tests__" | "spec"))
+        || name.starts_with("test_")
+        || name.contains("_test.")
+        || name.contains(".test.")
+        || name.contains(".spec.")
+        || name.contains("Test.")
+        || (name.ends_with(".rs") && content.contains("#[test]"))
+}
+
+/// Record test lines mentioning each requirement
+///
+/// The qualified form (`spec-id:R1`) counts in any test file. A bare ID only
+/// counts in test files that the change touches, since short IDs like `R1`
+/// are common across unrelated specs.
+fn find_test_references(
+    project_root: &Path,
+    requirements: &mut [RequirementTrace],
+    changed: &BTreeSet<&str>,
+) -> Result<()> {
+    if requirements.is_empty() {
+        return Ok(());
+    }
+
+    let patterns: Vec<(Regex, Regex)> = requirements
+        .iter()
+        .map(|r| {
+            let id = regex::escape(&r.requirement_id);
+            let qualified = format!(r"\b{}[:#/]{}\b", regex::escape(&r.spec_id), id);
+            Ok((
+                Regex::new(&qualified)?,
+                Regex::new(&format!(r"\b{}\b", id))?,
+            ))
+        })
+        .collect::<Result<_>>()?;
+
+    for (relative, path) in collect_files(project_root, project_root, None)? {
+        if relative.starts_with("agentd/") {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        if !is_test_file(&relative, &content) {
+            continue;
+        }
+        let touched = changed.contains(relative.as_str())
+            || requirements
+                .iter()
+                .any(|r| r.files.iter().any(|f| f.path == relative));
+
+        for (req, (qualified, bare)) in requirements.iter_mut().zip(&patterns) {
+            for (index, line) in content.lines().enumerate() {
+                if qualified.is_match(line) || (touched && bare.is_match(line)) {
+                    req.tests.push(TestReference {
+                        file: relative.clone(),
+                        line: index + 1,
+                    });
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Render the matrix as markdown
+pub fn render_markdown(matrix: &TraceMatrix) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("# Traceability: {}\n\n", matrix.change_id));
+    match (&matrix.diff_base, &matrix.diff_error) {
+        (Some(base), _) => out.push_str(&format!(
+            "**Diff base**: `{}` ({} changed files)\n\n",
+            base,
+            matrix.changed_files.len()
+        )),
+        (None, Some(error)) => out.push_str(&format!(
+            "⚠️ **Warning**: git diff unavailable: {}\n\n",
+            error
+        )),
+        (None, None) => {}
+    }
+
+    out.push_str("| Requirement | Tasks | Files | Tests | Status |\n");
+    out.push_str("|-------------|-------|-------|-------|--------|\n");
+    for req in &matrix.requirements {
+        let files: Vec<String> = req
+            .files
+            .iter()
+            .map(|f| {
+                if f.changed {
+                    format!("`{}` (changed)", f.path)
+                } else {
+                    format!("`{}`", f.path)
+                }
+            })
+            .collect();
+        let tests: Vec<String> = req
+            .tests
+            .iter()
+            .map(|t| format!("`{}:{}`", t.file, t.line))
+            .collect();
+        out.push_str(&format!(
+            "| `{}` {} | {} | {} | {} | {} |\n",
+            req.reference(),
+            req.title,
+            or_dash(&req.tasks.join(", ")),
+            or_dash(&files.join(", ")),
+            or_dash(&tests.join(", ")),
+            req.status.label()
+        ));
+    }
+    if matrix.requirements.is_empty() {
+        out.push_str("| - | - | - | - | No requirements found |\n");
+    }
+
+    if !matrix.orphan_tasks.is_empty() {
+        out.push_str("\n## Orphan Tasks\n\n");
+        out.push_str("| Task | File | Reason |\n");
+        out.push_str("|------|------|--------|\n");
+        for task in &matrix.orphan_tasks {
+            out.push_str(&format!(
+                "| {} | {} | {} |\n",
+                task.id,
+                or_dash(&task.file),
+                task.reason
+            ));
+        }
+    }
+
+    let covered = matrix.requirements.len() - matrix.uncovered().len();
+    out.push_str(&format!(
+        "\n**Summary**: {}/{} requirements covered, {} orphan task(s)\n",
+        covered,
+        matrix.requirements.len(),
+        matrix.orphan_tasks.len()
+    ));
+    out
+}
+
+fn or_dash(value: &str) -> &str {
+    if value.is_empty() {
+        "-"
+    } else {
+        value
+    }
+}
+
+/// Render the matrix as a Mermaid requirement diagram
+///
+/// Task files become elements that satisfy requirements and test files
+/// become elements that verify them.
+pub fn render_mermaid(matrix: &TraceMatrix) -> Result<String> {
+    if matrix.requirements.is_empty() {
+        anyhow::bail!("Change '{}' has no requirements to trace", matrix.change_id);
+    }
+
+    let mut requirements = Vec::new();
+    let mut elements: Vec<serde_json::Value> = Vec::new();
+    let mut element_ids = BTreeSet::new();
+    let mut relationships = Vec::new();
+
+    let mut add_element = |path: &str, kind: &str, elements: &mut Vec<serde_json::Value>| {
+        let id = format!("{}_{}", kind, mermaid_id(path));
+        if element_ids.insert(id.clone()) {
+            elements.push(json!({ "id": id, "text": path, "type": "element", "docref": path }));
+        }
+        id
+    };
+
+    for req in &matrix.requirements {
+        let req_id = mermaid_id(&req.reference());
+        let risk = match req.priority.as_deref() {
+            Some("high") => "High",
+            Some("low") => "Low",
+            _ => "Medium",
+        };
+        requirements.push(json!({
+            "id": req_id,
+            "text": mermaid_text(&req.title),
+            "risk": risk,
+            "verification": "Test",
+            "type": "functionalRequirement"
+        }));
+        for file in &req.files {
+            let id = add_element(&file.path, "file", &mut elements);
+            relationships.push(json!({ "from": id, "to": req_id, "type": "satisfies" }));
+        }
+        let test_files: BTreeSet<&str> = req.tests.iter().map(|t| t.file.as_str()).collect();
+        for file in test_files {
+            let id = add_element(file, "test", &mut elements);
+            relationships.push(json!({ "from": id, "to": req_id, "type": "verifies" }));
+        }
+    }
+
+    mermaid::call_tool(
+        "generate_mermaid_requirement",
+        &json!({
+            "requirements": requirements,
+            "elements": elements,
+            "relationships": relationships
+        }),
+    )
+}
+
+fn mermaid_id(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn mermaid_text(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !matches!(c, '{' | '}' | '"' | '\n'))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn setup(project_root: &Path) {
+        let change_dir = project_root.join("agentd/changes/add-auth");
+        std::fs::create_dir_all(change_dir.join("specs")).unwrap();
+        std::fs::write(
+            change_dir.join("specs/auth-spec.md"),
+            "# Auth\n\n## Requirements\n\n### R1 - Login\n\n```yaml\nid: R1\npriority: high\nstatus: draft\n```\n\nUsers log in.\n\n### R2 - Logout\n\nUsers log out.\n\n### R3 - Audit\n\nLogins are audited.\n",
+        )
+        .unwrap();
+        std::fs::write(
+            change_dir.join("tasks.md"),
+            "# Tasks\n\n```yaml\nid: 1.1\naction: CREATE\nfile: src/login.rs\nspec_ref: auth-spec:R1\n```\n\n```yaml\nid: 1.2\naction: CREATE\nfile: src/logout.rs\nspec_ref: auth-spec:R2\n```\n\n```yaml\nid: 1.3\naction: MODIFY\nfile: src/misc.rs\nspec_ref: auth-spec:R9\n```\n\n```yaml\nid: 1.4\naction: MODIFY\nfile: README.md\n```\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(project_root.join("tests")).unwrap();
+        std::fs::write(
+            project_root.join("tests/login_test.rs"),
+            "// Verifies auth-spec:R1\n#[test]\nfn login() {}\n",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_build_trace_without_git() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path();
+        setup(project_root);
+
+        let matrix = build_trace("add-auth", Some("main"), project_root).unwrap();
+        assert!(matrix.diff_base.is_none());
+        assert_eq!(matrix.requirements.len(), 3);
+
+        let r1 = &matrix.requirements[0];
+        assert_eq!(r1.reference(), "auth-spec:R1");
+        assert_eq!(r1.priority.as_deref(), Some("high"));
+        assert_eq!(r1.tasks, vec!["1.1"]);
+        assert_eq!(
+            r1.tests,
+            vec![TestReference {
+                file: "tests/login_test.rs".to_string(),
+                line: 1
+            }]
+        );
+        assert_eq!(r1.status, TraceStatus::Covered);
+        assert_eq!(matrix.requirements[1].status, TraceStatus::Untested);
+        assert_eq!(matrix.requirements[2].status, TraceStatus::NoTasks);
+
+        let orphans: Vec<&str> = matrix.orphan_tasks.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(orphans, vec!["1.3", "1.4"]);
+        assert_eq!(matrix.uncovered().len(), 2);
+
+        let markdown = render_markdown(&matrix);
+        assert!(markdown.contains("| `auth-spec:R1` Login | 1.1 | `src/login.rs` | `tests/login_test.rs:1` | ✅ Covered |"));
+        assert!(markdown.contains("## Orphan Tasks"));
+        assert!(markdown.contains("1/3 requirements covered, 2 orphan task(s)"));
+
+        let diagram = render_mermaid(&matrix).unwrap();
+        assert!(diagram.contains("functionalRequirement auth_spec_R1"));
+        assert!(diagram.contains("file_src_login_rs - satisfies -> auth_spec_R1"));
+        assert!(diagram.contains("test_tests_login_test_rs - verifies -> auth_spec_R1"));
+    }
+
+    #[test]
+    fn test_build_trace_with_git_diff() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_root = temp_dir.path();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(project_root)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?} failed", args);
+        };
+        git(&["init", "-q", "-b", "main"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "Test"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "init"]);
+
+        setup(project_root);
+        std::fs::create_dir_all(project_root.join("src")).unwrap();
+        std::fs::write(project_root.join("src/login.rs"), "fn login() {}\n").unwrap();
+
+        let matrix = build_trace("add-auth", None, project_root).unwrap();
+        assert_eq!(matrix.diff_base.as_deref(), Some("main"));
+        assert!(matrix.changed_files.contains(&"src/login.rs".to_string()));
+        assert!(matrix.requirements[0].files[0].changed);
+        assert_eq!(matrix.requirements[0].status, TraceStatus::Covered);
+        assert_eq!(matrix.requirements[1].status, TraceStatus::NotImplemented);
+    }
+}
@@ -5,6 +5,7 @@ use crate::models::frontmatter::{
     ValidationResult as FrontmatterValidationResult,
 };
 use crate::parser::frontmatter::calculate_checksum;
+use crate::parser::parse_affected_specs;
 use anyhow::{Context, Result};
 use chrono::Utc;
 use std::collections::HashMap;
@@ -53,6 +54,8 @@ impl StateManager {
                 iteration: 1,
                 last_action: None,
                 session_id: None,
+                baseline_commit: None,
+                main_spec_checksums: HashMap::new(),
                 checksums: HashMap::new(),
                 validations: Vec::new(),
                 telemetry: None,
@@ -136,6 +139,17 @@ impl StateManager {
         self.state.session_id.as_deref()
     }
 
+    /// Record the commit implementation started from
+    pub fn set_baseline_commit(&mut self, commit: impl Into<String>) {
+        self.state.baseline_commit = Some(commit.into());
+        self.dirty = true;
+    }
+
+    /// Commit the change's diffs are taken against, if recorded
+    pub fn baseline_commit(&self) -> Option<&str> {
+        self.state.baseline_commit.as_deref()
+    }
+
     /// Update phase based on challenge verdict (for plan-change workflow)
     /// - APPROVED → Planned (ready for implementation)
     /// - NEEDS_REVISION → Drafting (stays for auto-reproposal)
@@ -242,6 +256,82 @@ impl StateManager {
         Ok(entry.hash != current_hash)
     }
 
+    /// Main specs directory (agentd/specs) next to the changes directory
+    fn main_specs_dir(&self) -> Option<PathBuf> {
+        Some(self.change_dir.parent()?.parent()?.join("specs"))
+    }
+
+    /// Main specs this change builds on: one per delta spec in specs/ plus
+    /// each affected spec listed in the proposal (paths relative to agentd/specs)
+    fn based_on_specs(&self) -> Vec<String> {
+        let mut specs = std::collections::BTreeSet::new();
+
+        let specs_dir = self.change_dir.join("specs");
+        if specs_dir.exists() {
+            for entry in walkdir::WalkDir::new(&specs_dir)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().extension().is_some_and(|ext| ext == "md"))
+            {
+                if let Ok(rel_path) = entry.path().strip_prefix(&specs_dir) {
+                    specs.insert(rel_path.to_string_lossy().replace('\\', "/"));
+                }
+            }
+        }
+
+        if let Ok(proposal) = std::fs::read_to_string(self.change_dir.join("proposal.md")) {
+            for spec in parse_affected_specs(&proposal).unwrap_or_default() {
+                specs.insert(format!("{}.md", spec.id));
+            }
+        }
+
+        specs.into_iter().collect()
+    }
+
+    /// Checksum of a main spec, or an empty string when it does not exist
+    fn main_spec_checksum(&self, spec: &str) -> Result<String> {
+        let Some(path) = self.main_specs_dir().map(|dir| dir.join(spec)) else {
+            return Ok(String::new());
+        };
+        if !path.exists() {
+            return Ok(String::new());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Ok(calculate_checksum(&content))
+    }
+
+    /// Record checksums of the main specs the change's deltas are written
+    /// against, replacing any earlier record
+    pub fn record_main_spec_checksums(&mut self) -> Result<()> {
+        let mut checksums = HashMap::new();
+        for spec in self.based_on_specs() {
+            let hash = self.main_spec_checksum(&spec)?;
+            checksums.insert(spec, hash);
+        }
+        self.state.main_spec_checksums = checksums;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Whether main-spec checksums were recorded at planning time
+    pub fn has_main_spec_checksums(&self) -> bool {
+        !self.state.main_spec_checksums.is_empty()
+    }
+
+    /// Main specs changed (created, edited or deleted) since their checksums
+    /// were recorded, sorted by path
+    pub fn drifted_main_specs(&self) -> Result<Vec<String>> {
+        let mut drifted = Vec::new();
+        for (spec, recorded) in &self.state.main_spec_checksums {
+            if self.main_spec_checksum(spec)? != *recorded {
+                drifted.push(spec.clone());
+            }
+        }
+        drifted.sort();
+        Ok(drifted)
+    }
+
     /// Get full staleness report for all tracked files
     pub fn check_staleness(&self) -> Result<StalenessReport> {
         let mut stale_files = Vec::new();
@@ -288,6 +378,7 @@ impl StateManager {
             stale_files,
             missing_checksums,
             up_to_date,
+            drifted_main_specs: self.drifted_main_specs()?,
         })
     }
 
@@ -477,6 +568,8 @@ pub struct StalenessReport {
     pub missing_checksums: Vec<String>,
     /// Files that are up to date
     pub up_to_date: Vec<String>,
+    /// Main specs (relative to agentd/specs) changed since planning
+    pub drifted_main_specs: Vec<String>,
 }
 
 impl StalenessReport {
@@ -490,9 +583,16 @@ impl StalenessReport {
         self.missing_checksums.is_empty()
     }
 
+    /// Check if main specs moved since the change was planned
+    pub fn has_main_spec_drift(&self) -> bool {
+        !self.drifted_main_specs.is_empty()
+    }
+
     /// Check if everything is up to date
     pub fn is_fresh(&self) -> bool {
-        self.stale_files.is_empty() && self.missing_checksums.is_empty()
+        self.stale_files.is_empty()
+            && self.missing_checksums.is_empty()
+            && self.drifted_main_specs.is_empty()
     }
 
     /// Total number of tracked files
@@ -616,6 +716,38 @@ mod tests {
         assert!(!report.is_fresh());
     }
 
+    #[test]
+    fn test_main_spec_drift() {
+        let temp_dir = TempDir::new().unwrap();
+        let agentd_dir = temp_dir.path().join("agentd");
+        let change_dir = agentd_dir.join("changes/add-oauth");
+        std::fs::create_dir_all(change_dir.join("specs")).unwrap();
+        std::fs::create_dir_all(agentd_dir.join("specs")).unwrap();
+        std::fs::write(change_dir.join("specs/auth.md"), "# Auth delta").unwrap();
+        std::fs::write(agentd_dir.join("specs/auth.md"), "# Auth").unwrap();
+        std::fs::write(agentd_dir.join("specs/billing.md"), "# Billing").unwrap();
+
+        let mut manager = StateManager::load(&change_dir).unwrap();
+        assert!(!manager.has_main_spec_checksums());
+        manager.record_main_spec_checksums().unwrap();
+        manager.save().unwrap();
+
+        let manager = StateManager::load(&change_dir).unwrap();
+        assert!(manager.has_main_spec_checksums());
+        assert!(!manager.check_staleness().unwrap().has_main_spec_drift());
+
+        // Unrelated specs do not count; the change's base spec does
+        std::fs::write(agentd_dir.join("specs/billing.md"), "# Billing v2").unwrap();
+        assert!(manager.drifted_main_specs().unwrap().is_empty());
+        std::fs::write(agentd_dir.join("specs/auth.md"), "# Auth v2").unwrap();
+        let report = manager.check_staleness().unwrap();
+        assert_eq!(report.drifted_main_specs, vec!["auth.md".to_string()]);
+        assert!(!report.is_fresh());
+
+        std::fs::remove_file(agentd_dir.join("specs/auth.md")).unwrap();
+        assert_eq!(manager.drifted_main_specs().unwrap(), vec!["auth.md"]);
+    }
+
     #[test]
     fn test_record_validation() {
         let (_temp, change_dir) = setup_test_change();
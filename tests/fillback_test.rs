@@ -360,21 +360,42 @@ fn test_spec_generation_with_clarifications() {
 }
 
 #[test]
-fn test_module_filter() {
+fn test_package_filter() {
     let temp_dir = TempDir::new().unwrap();
-    create_multi_language_project(temp_dir.path());
-
-    let strategy = CodeStrategy::with_config(CodeStrategyConfig {
-        module: Some("config".to_string()),
-        ..Default::default()
-    });
+    let root = temp_dir.path();
+    create_multi_language_project(root);
+    fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"app\"\n\n[workspace]\nmembers = [\"crates/*\"]\n",
+    )
+    .unwrap();
+    fs::create_dir_all(root.join("crates/core/src")).unwrap();
+    fs::write(
+        root.join("crates/core/Cargo.toml"),
+        "[package]\nname = \"core\"\n",
+    )
+    .unwrap();
+    fs::write(root.join("crates/core/src/lib.rs"), "pub fn core_fn() {}\n").unwrap();
 
-    let src_dir = temp_dir.path().join("src");
-    let (context, _) = strategy.analyze_codebase(&src_dir).unwrap();
+    let package = |name: &str| {
+        CodeStrategy::with_config(CodeStrategyConfig {
+            package: Some(name.to_string()),
+            ..Default::default()
+        })
+    };
 
-    // Should only include modules matching the filter
+    // Only the member crate is analyzed
+    let (context, _) = package("core").analyze_codebase(root).unwrap();
     assert_eq!(context.modules.len(), 1);
-    assert_eq!(context.modules[0].name, "config");
+    assert_eq!(context.modules[0].name, "lib");
+
+    // The root package excludes nested members
+    let (context, _) = package("app").analyze_codebase(root).unwrap();
+    assert!(context.modules.iter().all(|m| m.name != "lib"));
+    assert!(context.modules.iter().any(|m| m.name == "config"));
+
+    let err = package("missing").analyze_codebase(root).unwrap_err();
+    assert!(err.to_string().contains("Available packages: app, core"));
 }
 
 #[test]